@@ -1,27 +1,11 @@
 #[cfg(feature = "bench-pvm-interpreter")]
 pub fn create_specs(contract: &revive_integration::cases::Contract) -> revive_runner::Specs {
     use revive_runner::*;
-    use SpecsAction::*;
     Specs {
         differential: false,
         actions: vec![
-            Instantiate {
-                code: Code::Bytes(contract.pvm_runtime.to_vec()),
-                origin: TestAddress::Alice,
-                data: Default::default(),
-                value: Default::default(),
-                gas_limit: Default::default(),
-                storage_deposit_limit: Default::default(),
-                salt: Default::default(),
-            },
-            Call {
-                origin: TestAddress::Alice,
-                dest: TestAddress::Instantiated(0),
-                data: contract.calldata.to_vec(),
-                value: Default::default(),
-                gas_limit: Default::default(),
-                storage_deposit_limit: Default::default(),
-            },
+            SpecsAction::instantiate(Code::Bytes(contract.pvm_runtime.to_vec()), vec![]),
+            SpecsAction::call(TestAddress::Instantiated(0), contract.calldata.to_vec()),
         ],
         ..Default::default()
     }
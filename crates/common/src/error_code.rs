@@ -0,0 +1,343 @@
+//! The resolc diagnostic error codes.
+
+/// The resolc diagnostic error code.
+///
+/// Every code has a stable identifier like `R2001`, used as the `errorCode` of the
+/// standard JSON output messages, and a long explanation printed by `resolc --explain`.
+///
+/// Errors carry their code as a [`CodedError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ErrorCode {
+    /// The `type(T).runtimeCode` usage.
+    RuntimeCode,
+    /// The `CALLCODE` instruction.
+    CallCode,
+    /// The `PC` instruction.
+    Pc,
+    /// The `EXTCODECOPY` instruction.
+    ExtCodeCopy,
+    /// The `CODECOPY` instruction in the runtime code.
+    RuntimeCodeCopy,
+    /// The `BLOBHASH` and `BLOBBASEFEE` instructions.
+    Blob,
+    /// The `verbatim` instructions.
+    Verbatim,
+    /// The static heap usage exceeds the heap size.
+    HeapSize,
+    /// The estimated stack usage exceeds the stack size.
+    StackSize,
+    /// The stack usage of recursive functions can't be bounded.
+    Recursion,
+    /// The contract can't be linked.
+    Link,
+    /// The newyork IR is invalid after the optimization pipeline.
+    IrValidation,
+    /// The generated LLVM IR is invalid.
+    LlvmVerification,
+    /// The LLVM optimizer failed.
+    LlvmOptimization,
+    /// The PVM code generation failed.
+    Assembly,
+    /// The translation to newyork IR failed.
+    NewYorkTranslation,
+    /// The LLVM IR generation from newyork IR failed.
+    NewYorkCodegen,
+}
+
+impl ErrorCode {
+    /// All error codes.
+    pub const ALL: [Self; 17] = [
+        Self::RuntimeCode,
+        Self::CallCode,
+        Self::Pc,
+        Self::ExtCodeCopy,
+        Self::RuntimeCodeCopy,
+        Self::Blob,
+        Self::Verbatim,
+        Self::HeapSize,
+        Self::StackSize,
+        Self::Recursion,
+        Self::Link,
+        Self::IrValidation,
+        Self::LlvmVerification,
+        Self::LlvmOptimization,
+        Self::Assembly,
+        Self::NewYorkTranslation,
+        Self::NewYorkCodegen,
+    ];
+
+    /// Returns the stable identifier.
+    ///
+    /// The first digit is the category: `1` source code, `2` unsupported instructions,
+    /// `3` memory configuration, `4` linking and `9` internal compiler errors.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::RuntimeCode => "R1001",
+            Self::CallCode => "R2001",
+            Self::Pc => "R2002",
+            Self::ExtCodeCopy => "R2003",
+            Self::RuntimeCodeCopy => "R2004",
+            Self::Blob => "R2005",
+            Self::Verbatim => "R2006",
+            Self::HeapSize => "R3001",
+            Self::StackSize => "R3002",
+            Self::Recursion => "R3003",
+            Self::Link => "R4001",
+            Self::IrValidation => "R9001",
+            Self::LlvmVerification => "R9002",
+            Self::LlvmOptimization => "R9003",
+            Self::Assembly => "R9004",
+            Self::NewYorkTranslation => "R9005",
+            Self::NewYorkCodegen => "R9006",
+        }
+    }
+
+    /// Returns the one line summary.
+    pub const fn title(&self) -> &'static str {
+        match self {
+            Self::RuntimeCode => "`type(T).runtimeCode` is not supported",
+            Self::CallCode => "The `CALLCODE` instruction is not supported",
+            Self::Pc => "The `PC` instruction is not supported",
+            Self::ExtCodeCopy => "The `EXTCODECOPY` instruction is not supported",
+            Self::RuntimeCodeCopy => {
+                "The `CODECOPY` instruction is not supported in the runtime code"
+            }
+            Self::Blob => "The blob instructions are not supported",
+            Self::Verbatim => "The `verbatim` instructions are not supported",
+            Self::HeapSize => "The static heap usage exceeds the heap size",
+            Self::StackSize => "The estimated stack usage exceeds the stack size",
+            Self::Recursion => "The stack usage of recursive functions can't be bounded",
+            Self::Link => "The contract can't be linked",
+            Self::IrValidation => "Internal compiler error: invalid newyork IR",
+            Self::LlvmVerification => "Internal compiler error: invalid LLVM IR",
+            Self::LlvmOptimization => "Internal compiler error: the LLVM optimizer failed",
+            Self::Assembly => "Internal compiler error: the PVM code generation failed",
+            Self::NewYorkTranslation => {
+                "Internal compiler error: the newyork IR translation failed"
+            }
+            Self::NewYorkCodegen => {
+                "Internal compiler error: the newyork LLVM IR generation failed"
+            }
+        }
+    }
+
+    /// Returns the long explanation.
+    pub const fn explanation(&self) -> &'static str {
+        match self {
+            Self::RuntimeCode => {
+                r#"Deploy and runtime code are merged into a single PVM blob. There is no separate
+runtime code to access, hence `type(T).runtimeCode` can't be compiled.
+
+Contracts usually read the runtime code to deploy it with `CREATE2`, or to compute
+the address of a contract deployed with it. Use `new T{salt: salt}()` to deploy the
+contract, and `type(T).creationCode` for address computations."#
+            }
+            Self::CallCode => {
+                r#"The `CALLCODE` instruction is deprecated on the EVM and has no counterpart on PolkaVM.
+
+Use `DELEGATECALL` instead, which keeps the caller and the call value of the
+current call."#
+            }
+            Self::Pc => {
+                r#"The `PC` instruction returns the EVM program counter. PVM code is not EVM bytecode,
+hence there is no EVM program counter to return.
+
+The instruction is only available in inline assembly and legacy code. Remove it."#
+            }
+            Self::ExtCodeCopy => {
+                r#"The `EXTCODECOPY` instruction copies the EVM bytecode of another account. The code
+of PolkaVM contracts is a PVM blob, which is not meaningful to EVM contracts.
+
+Checking whether an account is a contract should use `EXTCODESIZE` or
+`EXTCODEHASH`. Both are supported."#
+            }
+            Self::RuntimeCodeCopy => {
+                r#"Deploy and runtime code are merged into a single PVM blob. The `CODECOPY` instruction
+is supported in the deploy code, where it copies the constructor arguments, but
+there is no code to copy in the runtime code.
+
+The instruction is usually found in inline assembly reading data appended to the
+contract code. Pass the data as constructor arguments and store it in immutables
+or storage instead."#
+            }
+            Self::Blob => {
+                r#"The `BLOBHASH` and `BLOBBASEFEE` instructions access the blob transactions introduced
+by EIP-4844. There are no blob transactions on Polkadot.
+
+Remove the code depending on blob transactions."#
+            }
+            Self::Verbatim => {
+                r#"The Yul `verbatim` builtins inject raw EVM bytecode, which can't be translated to PVM
+code. `resolc` only supports the `verbatim` instructions it emits itself.
+
+Express the injected bytecode in Yul or Solidity instead."#
+            }
+            Self::HeapSize => {
+                r#"The contract accesses heap memory at constant offsets past the configured heap size.
+These accesses trap whenever they are executed. The check is only done by the newyork
+pipeline.
+
+Increase the heap size to at least the reported size, with
+`settings.polkavm.memoryConfig.heapSize` or `--heap-size`. With the newyork pipeline,
+`settings.polkavm.memoryConfig: "auto"` sizes the heap automatically."#
+            }
+            Self::StackSize => {
+                r#"The deepest call chain of the contract is estimated to use more stack than the
+configured stack size. Contracts running out of stack trap. The estimate doesn't
+include values spilled by the register allocator, hence the stack usage is higher.
+
+Increase the stack size with `settings.polkavm.memoryConfig.stackSize` or
+`--stack-size`, or reduce the call depth."#
+            }
+            Self::Recursion => {
+                r#"The contract has recursive functions. Their stack usage depends on the recursion depth
+at runtime, hence it can't be estimated statically. Contracts running out of stack
+trap.
+
+Make sure the recursion depth is bounded and test the contract with the largest
+expected inputs. `revive-runner tune-memory` finds the stack size needed by a set of
+test calls."#
+            }
+            Self::Link => {
+                r#"The contract ELF object can't be linked into a PVM blob.
+
+Contracts referencing libraries which are not deployed yet can't be linked at compile
+time. Provide the library addresses with `--libraries` or
+`settings.libraries`, or link the emitted ELF objects with `resolc --link` later.
+Other linker failures are compiler bugs, please report them."#
+            }
+            Self::IrValidation => {
+                r#"The newyork IR failed validation after the optimization pipeline. This is a compiler
+bug, please report it with the source code of the contract.
+
+The stock Yul pipeline, which is used without `--newyork`, is not affected."#
+            }
+            Self::LlvmVerification => {
+                r#"The LLVM IR generated for the contract failed verification. This is a compiler bug,
+please report it with the source code of the contract and the compiler settings.
+
+Debug output directories (`--debug-output-dir`) capture the invalid LLVM IR."#
+            }
+            Self::LlvmOptimization => {
+                r#"The LLVM optimizer failed on the LLVM IR generated for the contract. This is a
+compiler bug, please report it with the source code of the contract and the compiler settings.
+
+Another optimization level (`-O`, `settings.optimizer.mode`) may avoid the failure."#
+            }
+            Self::Assembly => {
+                r#"The PVM code generation from the optimized LLVM IR failed. This is a compiler bug,
+please report it with the source code of the contract and the compiler settings.
+
+Debug output directories (`--debug-output-dir`) capture the optimized LLVM IR."#
+            }
+            Self::NewYorkTranslation => {
+                r#"The Yul code of the contract couldn't be translated to newyork IR. This is a compiler
+bug, please report it with the source code of the contract.
+
+The stock Yul pipeline, which is used without `--newyork`, is not affected."#
+            }
+            Self::NewYorkCodegen => {
+                r#"The LLVM IR generation from the newyork IR of the contract failed. This is a compiler
+bug, please report it with the source code of the contract.
+
+The stock Yul pipeline, which is used without `--newyork`, is not affected."#
+            }
+        }
+    }
+
+    /// Returns the error code with the `code` identifier, ignoring the case.
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|error_code| error_code.code().eq_ignore_ascii_case(code))
+    }
+}
+
+/// An error with a resolc [`ErrorCode`].
+///
+/// Errors are mostly passed around as `anyhow` errors and re-wrapped with more context on
+/// the way up. [`CodedError::wrap`] keeps the code when re-wrapping, and
+/// [`CodedError::code_of`] finds it when the error is converted into a standard JSON
+/// output message, also through a [`crate::LocatedError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodedError {
+    /// The error code.
+    pub error_code: ErrorCode,
+    /// The error message.
+    pub message: String,
+}
+
+impl CodedError {
+    /// A shortcut constructor.
+    pub fn new(error_code: ErrorCode, message: impl std::fmt::Display) -> Self {
+        Self {
+            error_code,
+            message: message.to_string(),
+        }
+    }
+
+    /// Returns the error code of the `error`.
+    pub fn code_of(error: &anyhow::Error) -> Option<ErrorCode> {
+        error
+            .chain()
+            .find_map(|error| error.downcast_ref::<Self>())
+            .map(|coded_error| coded_error.error_code)
+    }
+
+    /// Replaces the message of the `error` by the result of `message`, keeping its error code.
+    ///
+    /// A [`crate::LocatedError`] must be unwrapped with [`crate::LocatedError::map`] first
+    /// to keep its location.
+    pub fn wrap(error: anyhow::Error, message: impl FnOnce(&str) -> String) -> anyhow::Error {
+        match error.downcast::<Self>() {
+            Ok(coded_error) => Self::new(
+                coded_error.error_code,
+                message(coded_error.message.as_str()),
+            )
+            .into(),
+            Err(error) => anyhow::anyhow!(message(error.to_string().as_str())),
+        }
+    }
+}
+
+impl std::fmt::Display for CodedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for CodedError {}
+
+#[cfg(test)]
+mod tests {
+    use super::CodedError;
+    use super::ErrorCode;
+
+    #[test]
+    fn codes_are_unique() {
+        for (index, error_code) in ErrorCode::ALL.iter().enumerate() {
+            assert_eq!(ErrorCode::from_code(error_code.code()), Some(*error_code));
+            assert!(ErrorCode::ALL[index + 1..]
+                .iter()
+                .all(|other| other.code() != error_code.code()));
+        }
+    }
+
+    #[test]
+    fn wrap_keeps_the_code() {
+        let error = anyhow::Error::from(CodedError::new(
+            ErrorCode::Pc,
+            "The `PC` instruction is not supported",
+        ));
+        let error = CodedError::wrap(error, |message| format!("NewYork IR generator: {message}"));
+        assert_eq!(CodedError::code_of(&error), Some(ErrorCode::Pc));
+        assert_eq!(
+            error.to_string(),
+            "NewYork IR generator: The `PC` instruction is not supported"
+        );
+
+        let error = anyhow::anyhow!("The message mentions [R2002]");
+        let error = CodedError::wrap(error, |message| format!("Wrapped: {message}"));
+        assert_eq!(CodedError::code_of(&error), None);
+    }
+}
@@ -39,5 +39,8 @@ pub static EXTENSION_POLKAVM_ASSEMBLY: &str = "pvmasm";
 /// The PolkaVM bytecode file extension.
 pub static EXTENSION_POLKAVM_BINARY: &str = "pvm";
 
+/// The PolkaVM code size report file extension.
+pub static EXTENSION_SIZE_REPORT: &str = "size.json";
+
 /// The ELF shared object file extension.
 pub static EXTENSION_OBJECT: &str = "o";
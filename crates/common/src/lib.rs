@@ -4,10 +4,12 @@ pub(crate) mod base;
 pub(crate) mod bit_length;
 pub(crate) mod byte_length;
 pub(crate) mod contract_identifier;
+pub(crate) mod error_code;
 pub(crate) mod evm_version;
 pub(crate) mod exit_code;
 pub(crate) mod extension;
 pub(crate) mod keccak256;
+pub(crate) mod located_error;
 pub(crate) mod metadata;
 pub(crate) mod object;
 pub(crate) mod solidity_selector;
@@ -16,10 +18,13 @@ pub(crate) mod utils;
 pub use self::base::*;
 pub use self::bit_length::*;
 pub use self::byte_length::*;
+pub use self::error_code::CodedError;
+pub use self::error_code::ErrorCode;
 pub use self::evm_version::EVMVersion;
 pub use self::exit_code::*;
 pub use self::extension::*;
 pub use self::keccak256::*;
+pub use self::located_error::LocatedError;
 pub use self::metadata::*;
 pub use self::object::*;
 pub use self::solidity_selector::*;
@@ -0,0 +1,105 @@
+//! The compiler error located in the code it occurs in.
+
+/// An error located at the `location` of the code it occurs in.
+///
+/// The location type depends on the code, for example a Yul statement or a Solidity source
+/// code range. The located error is displayed as the inner `error`, which is also its source,
+/// so [`crate::CodedError::code_of`] finds the error code through it.
+#[derive(Debug)]
+pub struct LocatedError<L> {
+    /// The location of the code the error occurs in.
+    pub location: L,
+    /// The inner error.
+    pub error: anyhow::Error,
+}
+
+impl<L> LocatedError<L>
+where
+    L: std::fmt::Debug + Send + Sync + 'static,
+{
+    /// Locates the `error` at the `location`, unless it is located already.
+    ///
+    /// Errors are located at the innermost code they occur in.
+    pub fn locate(error: anyhow::Error, location: L) -> anyhow::Error {
+        if Self::location_of(&error).is_some() {
+            return error;
+        }
+        Self { location, error }.into()
+    }
+
+    /// Returns the location of the `error`.
+    pub fn location_of(error: &anyhow::Error) -> Option<&L> {
+        error
+            .chain()
+            .find_map(|error| error.downcast_ref::<Self>())
+            .map(|located_error| &located_error.location)
+    }
+
+    /// Replaces the inner error of the `error` by the result of `map`, keeping its location.
+    pub fn map(
+        error: anyhow::Error,
+        map: impl FnOnce(anyhow::Error) -> anyhow::Error,
+    ) -> anyhow::Error {
+        match error.downcast::<Self>() {
+            Ok(located_error) => Self {
+                location: located_error.location,
+                error: map(located_error.error),
+            }
+            .into(),
+            Err(error) => map(error),
+        }
+    }
+}
+
+impl<L> std::fmt::Display for LocatedError<L> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl<L> std::error::Error for LocatedError<L>
+where
+    L: std::fmt::Debug,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CodedError;
+    use crate::ErrorCode;
+
+    use super::LocatedError;
+
+    /// A line and column location.
+    type Location = (u32, u32);
+
+    #[test]
+    fn locates_the_innermost_code() {
+        let error = anyhow::Error::from(CodedError::new(
+            ErrorCode::ExtCodeCopy,
+            "The `EXTCODECOPY` instruction is not supported",
+        ));
+        let error = LocatedError::<Location>::locate(error, (12, 5));
+        let error = LocatedError::<Location>::locate(error, (3, 1));
+        let error = LocatedError::<Location>::map(error, |error| {
+            CodedError::wrap(error, |message| format!("LLVM IR generator: {message}"))
+        });
+
+        assert_eq!(
+            LocatedError::<Location>::location_of(&error),
+            Some(&(12, 5))
+        );
+        assert_eq!(CodedError::code_of(&error), Some(ErrorCode::ExtCodeCopy));
+        assert_eq!(
+            error.to_string(),
+            "LLVM IR generator: The `EXTCODECOPY` instruction is not supported"
+        );
+        assert_eq!(
+            LocatedError::<Location>::location_of(&anyhow::anyhow!("unlocated")),
+            None
+        );
+    }
+}
@@ -182,6 +182,7 @@ impl From<&str> for EvmLog {
 }
 
 /// Builder for running contracts in geth `evm`
+#[derive(Clone)]
 pub struct Evm {
     genesis_json: Option<String>,
     genesis_path: Option<PathBuf>,
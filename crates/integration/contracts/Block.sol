@@ -35,6 +35,76 @@ pragma solidity ^0.8;
 }
 */
 
+/* runner.json
+{
+    "differential": false,
+    "actions": [
+        {
+            "Instantiate": {
+                "code": {
+                    "Solidity": {
+                        "contract": "Block"
+                    }
+                }
+            }
+        },
+        {
+            "AdvanceBlocks": {
+                "count": 2,
+                "seconds": 12
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "data": "8381f58a"
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "output": "0000000000000000000000000000000000000000000000000000000000000003"
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "data": "b80777ea"
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "output": "0000000000000000000000000000000000000000000000000000000000000018"
+            }
+        },
+        {
+            "SetTimestamp": {
+                "seconds": 1700000000
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "data": "b80777ea"
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "output": "000000000000000000000000000000000000000000000000000000006553f100"
+            }
+        }
+    ]
+}
+*/
+
 contract Block {
     function timestamp() public view returns (uint ret) {
         ret = block.timestamp;
@@ -20,6 +20,90 @@ pragma solidity ^0.8;
 }
 */
 
+/* runner.json
+{
+    "differential": false,
+    "actions": [
+        {
+            "Instantiate": {
+                "code": {
+                    "Solidity": {
+                        "contract": "BlockHash"
+                    }
+                },
+                "data": "e8ec043305d4cfbb51936ae25b50e0a4352d8eaab03d0f66d8d543e65a9a9668"
+            }
+        },
+        {
+            "AdvanceBlocks": {
+                "count": 3,
+                "seconds": 12
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "known",
+                "args": ["0"]
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "values": ["true"]
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "known",
+                "args": ["1"]
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "values": ["true"]
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "known",
+                "args": ["3"]
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "values": ["true"]
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "known",
+                "args": ["4"]
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "values": ["false"]
+            }
+        }
+    ]
+}
+*/
+
 contract BlockHash {
     constructor(bytes32 expected) payable {
         assert(blockhash(0) == expected);
@@ -30,4 +114,8 @@ contract BlockHash {
             ) == 0
         );
     }
+
+    function known(uint256 number) public view returns (bool) {
+        return blockhash(number) != 0;
+    }
 }
@@ -1,6 +1,53 @@
 // SPDX-License-Identifier: MIT
 pragma solidity ^0.8;
 
+/* runner.json
+{
+    "differential": false,
+    "actions": [
+        {
+            "Instantiate": {
+                "code": {
+                    "Solidity": {
+                        "contract": "CustomErrorArgs"
+                    }
+                }
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "f",
+                "args": ["0", "5", "0"]
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": false,
+                "revert_reason": "E1(5)"
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "f(uint256,uint256,uint256)",
+                "args": ["1", "1", "2"]
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": false,
+                "revert_reason": "E2(1, 2, 0x0000000000000000000000000000000000000001)"
+            }
+        }
+    ]
+}
+*/
+
 /// Probe: custom error revert data must match EVM (selector + ABI-encoded args)
 /// for edge-case argument values. Targets newyork's custom-error outlining.
 contract CustomErrorArgs {
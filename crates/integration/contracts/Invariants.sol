@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: MIT
+
+pragma solidity ^0.8;
+
+/* runner.json
+{
+    "differential": true,
+    "actions": [
+        {
+            "Instantiate": {
+                "code": {
+                    "Solidity": {
+                        "contract": "Invariants"
+                    }
+                }
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "belowLimit"
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "values": [
+                    "true"
+                ]
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "increment"
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "increment"
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "increment"
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "belowLimit"
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "values": [
+                    "true"
+                ]
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "increment"
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "belowLimit"
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "values": [
+                    "false"
+                ]
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "reset"
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 0
+                },
+                "call": "belowLimit"
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "values": [
+                    "true"
+                ]
+            }
+        }
+    ]
+}
+*/
+
+contract Invariants {
+    uint256 public count;
+
+    function increment() external {
+        count += 1;
+    }
+
+    function reset() external {
+        count = 0;
+    }
+
+    function belowLimit() external view returns (bool) {
+        return count <= 3;
+    }
+}
@@ -52,6 +52,55 @@ import "contracts/Library.sol";
 }
 */
 
+/* runner.json
+{
+    "differential": false,
+    "actions": [
+        {
+            "Instantiate": {
+                "code": {
+                    "Solidity": {
+                        "contract": "L",
+                        "path": "contracts/Library.sol"
+                    }
+                }
+            }
+        },
+        {
+            "Instantiate": {
+                "code": {
+                    "SolidityLinked": {
+                        "contract": "Linked",
+                        "path": "contracts/Linked.sol",
+                        "libraries": {
+                            "contracts/Library.sol": {
+                                "L": {
+                                    "Instantiated": 0
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        {
+            "Call": {
+                "dest": {
+                    "Instantiated": 1
+                },
+                "call": "main"
+            }
+        },
+        {
+            "VerifyCall": {
+                "success": true,
+                "values": ["10"]
+            }
+        }
+    ]
+}
+*/
+
 contract Linked {
     function main() public returns (uint) {
         return L.f();
@@ -20,7 +20,9 @@ macro_rules! test_spec {
         fn $test_name() {
             let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("should always exist");
             let path = format!("{manifest_dir}/../integration/contracts/{}", $source_file);
-            Specs::from_comment($contract_name, &path).remove(0).run();
+            for specs in Specs::from_comment($contract_name, &path) {
+                specs.run();
+            }
         }
     };
 }
@@ -74,6 +76,7 @@ test_spec!(clz, "CountLeadingZeros", "CountLeadingZeros.sol");
 test_spec!(erc7201, "ERC7201", "ERC7201.sol");
 test_spec!(call_gas, "CallGas", "CallGas.sol");
 test_spec!(linker_symbol, "Linked", "Linked.sol");
+test_spec!(custom_error_args, "CustomErrorArgs", "CustomErrorArgs.sol");
 test_spec!(
     struct_delete_storage,
     "StructDeleteStorage",
@@ -96,6 +99,7 @@ test_spec!(
     "SubUnderflowZext",
     "SubUnderflowZext.sol"
 );
+test_spec!(invariants, "Invariants", "Invariants.sol");
 
 fn instantiate(path: &str, contract: &str) -> Vec<SpecsAction> {
     vec![Instantiate {
@@ -111,6 +115,8 @@ fn instantiate(path: &str, contract: &str) -> Vec<SpecsAction> {
         },
         data: vec![],
         salt: OptionalHex::default(),
+        constructor: None,
+        args: vec![],
     }]
 }
 
@@ -153,6 +159,8 @@ fn ulongrem_fuzz() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: Contract::ulongrem_big_mulmod(a, b, m).calldata,
+            call: None,
+            args: vec![],
         });
     }
 
@@ -185,6 +193,8 @@ fn bitwise_byte() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: input,
+            call: None,
+            args: vec![],
         })
     }
 
@@ -212,6 +222,8 @@ fn unsigned_division() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: Contract::division_arithmetics_div(n, d).calldata,
+            call: None,
+            args: vec![],
         })
     }
 
@@ -249,6 +261,8 @@ fn signed_division() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: Contract::division_arithmetics_sdiv(n, d).calldata,
+            call: None,
+            args: vec![],
         })
     }
 
@@ -278,6 +292,8 @@ fn unsigned_remainder() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: Contract::division_arithmetics_mod(n, d).calldata,
+            call: None,
+            args: vec![],
         })
     }
 
@@ -319,6 +335,8 @@ fn signed_remainder() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: Contract::division_arithmetics_smod(n, d).calldata,
+            call: None,
+            args: vec![],
         })
     }
 
@@ -347,6 +365,8 @@ fn div_self_zero_returns_zero() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: Contract::division_arithmetics_div_self(x).calldata,
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -369,6 +389,8 @@ fn sdiv_self_zero_returns_zero() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: Contract::division_arithmetics_sdiv_self(x).calldata,
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -385,6 +407,8 @@ fn mod_self_zero_returns_zero() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: Contract::division_arithmetics_mod_self(x).calldata,
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -414,6 +438,8 @@ fn param_mload_huge_offset_traps() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: Contract::param_mload_try_fetch(huge).calldata,
+            call: None,
+            args: vec![],
         });
         Specs {
             actions,
@@ -442,6 +468,8 @@ fn panic_code_high_bits_preserved() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::panic_code_bug_trigger().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -465,6 +493,8 @@ fn unaligned_mstore_forwarding() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::unaligned_mstore_bug().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -488,6 +518,8 @@ fn unaligned_mload_native_byte_order() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::unaligned_mload_native_bug().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -513,6 +545,8 @@ fn const_return_offset_overflow_traps() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::const_return_overflow_bug().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -543,6 +577,8 @@ fn linker_i32_boundary_constant_fold() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::linker_i32_boundary_fold_bug(a0, a2).calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -569,6 +605,8 @@ fn panic_pattern_intervening_mstore_preserved() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::panic_intervene_bug().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -592,6 +630,8 @@ fn fmp_revert_native_mode_corrupts_revert_data() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::fmp_revert_bug().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -634,6 +674,8 @@ fn fmp_load_range_proof_corrupts_non_bounded_value() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::fmp_range_proof_bug().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -653,6 +695,8 @@ fn fmp_native_store_truncates_non_bounded_value() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::fmp_native_store_bug().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -679,6 +723,8 @@ fn fmp_cross_object_value_id_collision_corrupts_fmp() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::fmp_cross_object_bug().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -693,6 +739,8 @@ fn fmp_propagation_misses_dynamic_offset_mstore() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::fmp_dyn_store_bug().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -717,6 +765,8 @@ fn keccak_fuse_preserves_scratch_observable_via_mload() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::keccak_fuse_bug_probe(seeds).calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -739,6 +789,8 @@ fn fmp_dyn_revert_native_mode_corrupts_revert_data() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::fmp_dyn_revert_bug().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -760,6 +812,8 @@ fn unaligned_mstore8_forwarding() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::unaligned_mstore8_bug().calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -788,6 +842,8 @@ fn copy_length_invalidates_tracked_overlap() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::copy_overlap_bug(U256::from(64u64)).calldata,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -818,6 +874,8 @@ fn instantiate_yul(path: &str, contract: &str) -> Vec<SpecsAction> {
         },
         data: vec![],
         salt: OptionalHex::default(),
+        constructor: None,
+        args: vec![],
     }]
 }
 
@@ -1018,6 +1076,8 @@ fn push_call(actions: &mut Vec<SpecsAction>, dest: TestAddress, data: Vec<u8>) {
         gas_limit: None,
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
 }
 
@@ -1333,11 +1393,15 @@ fn ext_code_hash() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::code_hash().calldata,
+        call: None,
+        args: vec![],
     });
     actions.push(VerifyCall(VerifyCallExpectation {
         success: true,
         output: OptionalHex::from(code_hash.as_bytes().to_vec()),
         gas_consumed: None,
+        values: None,
+        revert_reason: None,
     }));
 
     // code hash for a given contract address
@@ -1348,11 +1412,15 @@ fn ext_code_hash() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::ext_code_hash(Address::from(addr.to_fixed_bytes())).calldata,
+        call: None,
+        args: vec![],
     });
     actions.push(VerifyCall(VerifyCallExpectation {
         success: true,
         output: OptionalHex::from(code_hash.as_bytes().to_vec()),
         gas_consumed: None,
+        values: None,
+        revert_reason: None,
     }));
 
     // EOA returns fixed hash
@@ -1363,6 +1431,8 @@ fn ext_code_hash() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::ext_code_hash(Address::from(CHARLIE.to_fixed_bytes())).calldata,
+        call: None,
+        args: vec![],
     });
     actions.push(VerifyCall(VerifyCallExpectation {
         success: true,
@@ -1370,6 +1440,8 @@ fn ext_code_hash() {
             hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").to_vec(),
         ),
         gas_consumed: None,
+        values: None,
+        revert_reason: None,
     }));
 
     // non-existing account
@@ -1380,11 +1452,15 @@ fn ext_code_hash() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: Contract::ext_code_hash(Address::from([8u8; 20])).calldata,
+        call: None,
+        args: vec![],
     });
     actions.push(VerifyCall(VerifyCallExpectation {
         success: true,
         output: OptionalHex::from([0u8; 32].to_vec()),
         gas_consumed: None,
+        values: None,
+        revert_reason: None,
     }));
 
     Specs {
@@ -1425,6 +1501,8 @@ fn ext_code_size() {
                 },
                 data: vec![],
                 salt: OptionalHex::from([0; 32]),
+                constructor: None,
+                args: vec![],
             },
             // Alice is not a contract and returns a code size of 0
             Call {
@@ -1434,11 +1512,15 @@ fn ext_code_size() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data: Contract::ext_code_size(alice).calldata,
+                call: None,
+                args: vec![],
             },
             VerifyCall(VerifyCallExpectation {
                 success: true,
                 output: OptionalHex::from([0u8; 32].to_vec()),
                 gas_consumed: None,
+                values: None,
+                revert_reason: None,
             }),
             // Unknown address returns a code size of 0
             Call {
@@ -1448,11 +1530,15 @@ fn ext_code_size() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data: Contract::ext_code_size(Address::from([0xff; 20])).calldata,
+                call: None,
+                args: vec![],
             },
             VerifyCall(VerifyCallExpectation {
                 success: true,
                 output: OptionalHex::from([0u8; 32].to_vec()),
                 gas_consumed: None,
+                values: None,
+                revert_reason: None,
             }),
             // Own address via extcodesize returns own code size
             Call {
@@ -1462,11 +1548,15 @@ fn ext_code_size() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data: Contract::ext_code_size(own_address).calldata,
+                call: None,
+                args: vec![],
             },
             VerifyCall(VerifyCallExpectation {
                 success: true,
                 output: OptionalHex::from(own_code_size.to_be_bytes::<32>().to_vec()),
                 gas_consumed: None,
+                values: None,
+                revert_reason: None,
             }),
             // Own address via codesize returns own code size
             Call {
@@ -1476,11 +1566,15 @@ fn ext_code_size() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data: Contract::code_size().calldata,
+                call: None,
+                args: vec![],
             },
             VerifyCall(VerifyCallExpectation {
                 success: true,
                 output: OptionalHex::from(own_code_size.to_be_bytes::<32>().to_vec()),
                 gas_consumed: None,
+                values: None,
+                revert_reason: None,
             }),
             // Baseline address returns the baseline code size
             Call {
@@ -1490,11 +1584,15 @@ fn ext_code_size() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data: Contract::ext_code_size(baseline_address).calldata,
+                call: None,
+                args: vec![],
             },
             VerifyCall(VerifyCallExpectation {
                 success: true,
                 output: OptionalHex::from(baseline_code_size.to_be_bytes::<32>().to_vec()),
                 gas_consumed: None,
+                values: None,
+                revert_reason: None,
             }),
         ],
         ..Default::default()
@@ -1543,6 +1641,8 @@ fn ext_code_size_two_addresses() {
                 },
                 data: vec![],
                 salt: OptionalHex::from([0; 32]),
+                constructor: None,
+                args: vec![],
             },
             // extcodesize(own) + extcodesize(baseline) in a single call
             Call {
@@ -1552,11 +1652,15 @@ fn ext_code_size_two_addresses() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data: Contract::ext_code_size_sum(own_address, baseline_address).calldata,
+                call: None,
+                args: vec![],
             },
             VerifyCall(VerifyCallExpectation {
                 success: true,
                 output: OptionalHex::from(expected_sum.to_be_bytes::<32>().to_vec()),
                 gas_consumed: None,
+                values: None,
+                revert_reason: None,
             }),
         ],
         ..Default::default()
@@ -1584,6 +1688,8 @@ fn create2_salt() {
                 code: Code::Bytes(predictor.pvm_runtime),
                 data: predictor.calldata,
                 salt: OptionalHex::default(),
+                constructor: None,
+                args: vec![],
             },
         ],
         differential: false,
@@ -1616,6 +1722,8 @@ fn code_block_stops() {
                 code: Code::Bytes(code.to_vec()),
                 data: Default::default(),
                 salt: OptionalHex::default(),
+                constructor: None,
+                args: vec![],
             },
             Call {
                 origin: TestAddress::Alice,
@@ -1624,6 +1732,8 @@ fn code_block_stops() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data: Default::default(),
+                call: None,
+                args: vec![],
             },
             VerifyCall(Default::default()),
         ],
@@ -1673,6 +1783,8 @@ fn code_block_with_nested_object_stops() {
                 code: Code::Bytes(code.to_vec()),
                 data: Default::default(),
                 salt: OptionalHex::default(),
+                constructor: None,
+                args: vec![],
             },
             Call {
                 origin: TestAddress::Alice,
@@ -1681,6 +1793,8 @@ fn code_block_with_nested_object_stops() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data: Default::default(),
+                call: None,
+                args: vec![],
             },
             VerifyCall(Default::default()),
         ],
@@ -1739,6 +1853,8 @@ fn nested_function_forward_declared() {
                 code: Code::Bytes(code),
                 data: Default::default(),
                 salt: OptionalHex::default(),
+                constructor: None,
+                args: vec![],
             },
             Call {
                 origin: TestAddress::Alice,
@@ -1747,11 +1863,15 @@ fn nested_function_forward_declared() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data: Default::default(),
+                call: None,
+                args: vec![],
             },
             VerifyCall(VerifyCallExpectation {
                 success: true,
                 output: OptionalHex::from(expected_output.to_vec()),
                 gas_consumed: None,
+                values: None,
+                revert_reason: None,
             }),
         ],
         differential: false,
@@ -1816,6 +1936,8 @@ fn for_condition_call_argument_trimmed() {
                 code: Code::Bytes(code),
                 data: Default::default(),
                 salt: OptionalHex::default(),
+                constructor: None,
+                args: vec![],
             },
             Call {
                 origin: TestAddress::Alice,
@@ -1824,11 +1946,15 @@ fn for_condition_call_argument_trimmed() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data: Default::default(),
+                call: None,
+                args: vec![],
             },
             VerifyCall(VerifyCallExpectation {
                 success: true,
                 output: OptionalHex::from(expected_output.to_vec()),
                 gas_consumed: None,
+                values: None,
+                revert_reason: None,
             }),
         ],
         differential: false,
@@ -1865,6 +1991,8 @@ fn sbrk_bounds_checks() {
                 code: Code::Bytes(code.to_vec()),
                 data: Default::default(),
                 salt: OptionalHex::default(),
+                constructor: None,
+                args: vec![],
             },
             VerifyCall(VerifyCallExpectation {
                 success: false,
@@ -1913,6 +2041,8 @@ fn invalid_opcode_works() {
                 code: Code::Bytes(code.to_vec()),
                 data: Default::default(),
                 salt: OptionalHex::default(),
+                constructor: None,
+                args: vec![],
             },
             VerifyCall(VerifyCallExpectation {
                 success: false,
@@ -1963,6 +2093,8 @@ fn sdiv_narrow_masked() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: Contract::sdiv_narrow_bug_masked(a, b).calldata,
+            call: None,
+            args: vec![],
         });
     }
 
@@ -1999,6 +2131,8 @@ fn caller_origin_aliasing() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: calldata,
+            call: None,
+            args: vec![],
         });
     }
 
@@ -2028,6 +2162,8 @@ fn mload_huge_offset_traps() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             },
             VerifyCall(VerifyCallExpectation {
                 success: false,
@@ -2060,6 +2196,8 @@ fn mload_at_fmp_slot() {
             gas_limit: None,
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         }]);
         Specs {
             actions,
@@ -2087,6 +2225,8 @@ fn safe_truncate_int_to_xlen_works() {
             gas_limit: None,
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         },
         VerifyCall(VerifyCallExpectation {
             success: false,
@@ -2151,6 +2291,8 @@ fn fuzzy_dedup_switch_case_values_preserved() {
             gas_limit: None,
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2177,6 +2319,8 @@ fn sdiv_return_narrowing_drops_sign_bits() {
         gas_limit: None,
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2201,6 +2345,8 @@ fn sar_constant_shift_return_narrowing_drops_sign_bits() {
         gas_limit: None,
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2227,6 +2373,8 @@ fn fmp_slot_return_byte_order() {
         gas_limit: None,
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2252,6 +2400,8 @@ fn sdiv_compare_narrowing_wrong_branch() {
         gas_limit: None,
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2278,6 +2428,8 @@ fn for_init_block_variable_scope() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: w(n).to_vec(),
+            call: None,
+            args: vec![],
         });
         run_differential(actions);
     }
@@ -2306,6 +2458,8 @@ fn param_conditional_offset_narrowing_spurious_trap() {
         gas_limit: None,
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2331,6 +2485,8 @@ fn leave_edge_output_width_inferred_from_inputs() {
         gas_limit: None,
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2354,6 +2510,8 @@ fn fmp_unbounded_mload_compare_full_width() {
         gas_limit: None,
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2383,6 +2541,8 @@ fn calldatacopy_src_offset_zero_fill() {
             gas_limit: None,
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
         run_differential(actions);
     }
@@ -2407,6 +2567,8 @@ fn msize_parity() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             });
             run_differential(actions);
         }
@@ -2441,6 +2603,8 @@ fn custom_error_revert_args() {
                         b,
                     }
                     .abi_encode(),
+                    call: None,
+                    args: vec![],
                 });
             }
         }
@@ -2479,6 +2643,8 @@ fn multi_leave_return_narrowing() {
                 b,
             }
             .abi_encode(),
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2509,6 +2675,8 @@ fn encode_packed_hash() {
             e: Bytes::from(vec![1, 2, 3, 4, 5]),
         }
         .abi_encode(),
+        call: None,
+        args: vec![],
     });
     actions.push(Call {
         origin: TestAddress::Alice,
@@ -2521,6 +2689,8 @@ fn encode_packed_hash() {
             b: U256::from(1u64) << 255,
         }
         .abi_encode(),
+        call: None,
+        args: vec![],
     });
     actions.push(Call {
         origin: TestAddress::Alice,
@@ -2533,6 +2703,8 @@ fn encode_packed_hash() {
             n: 0x7f,
         }
         .abi_encode(),
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2557,6 +2729,8 @@ fn less_common_panics() {
             x: U256::from(1u64),
         }
         .abi_encode(),
+        call: None,
+        args: vec![],
     }); // ok
     actions.push(Call {
         origin: TestAddress::Alice,
@@ -2568,6 +2742,8 @@ fn less_common_panics() {
             x: U256::from(5u64),
         }
         .abi_encode(),
+        call: None,
+        args: vec![],
     }); // 0x21
     actions.push(Call {
         origin: TestAddress::Alice,
@@ -2576,6 +2752,8 @@ fn less_common_panics() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: uninitFpCall {}.abi_encode(),
+        call: None,
+        args: vec![],
     }); // 0x51
     actions.push(Call {
         origin: TestAddress::Alice,
@@ -2587,6 +2765,8 @@ fn less_common_panics() {
             n: U256::from(3u64),
         }
         .abi_encode(),
+        call: None,
+        args: vec![],
     }); // ok
     actions.push(Call {
         origin: TestAddress::Alice,
@@ -2595,6 +2775,8 @@ fn less_common_panics() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: overAllocCall { n: U256::MAX }.abi_encode(),
+        call: None,
+        args: vec![],
     }); // 0x41
     run_differential(actions);
 }
@@ -2621,6 +2803,8 @@ fn signextend_index_param_narrow() {
             gas_limit: None,
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
         run_differential(actions);
     }
@@ -2642,6 +2826,8 @@ fn scratch_copy_taint() {
         gas_limit: None,
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2660,6 +2846,8 @@ fn switch_wide_label_alias() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: v.to_be_bytes::<32>().to_vec(),
+        call: None,
+        args: vec![],
     });
     // also: calldata word = 2^64 + 5 -> masked x = 5 -> default (0xCC) on both
     actions.push(Call {
@@ -2669,6 +2857,8 @@ fn switch_wide_label_alias() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: (v + U256::from(5u64)).to_be_bytes::<32>().to_vec(),
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2697,6 +2887,8 @@ fn shift_param_narrow() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: d,
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2717,6 +2909,8 @@ fn msize_unaligned_store() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: d,
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2736,6 +2930,8 @@ fn msize_copy_ops() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: d,
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2755,6 +2951,8 @@ fn msize_read_ops() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: d,
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2777,6 +2975,8 @@ fn msize_in_for_condition() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: x.to_be_bytes::<32>().to_vec(),
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2798,6 +2998,8 @@ fn for_loop_fmp_realloc() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: U256::from(n).to_be_bytes::<32>().to_vec(),
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2820,6 +3022,8 @@ fn branch_calls_allocator_fmp() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: U256::from(cnt).to_be_bytes::<32>().to_vec(),
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2841,6 +3045,8 @@ fn switch_callvalue_no_match_fallthrough() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: U256::from(sel).to_be_bytes::<32>().to_vec(),
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2862,6 +3068,8 @@ fn switch_callvalue_cse_dangling() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: U256::from(sel).to_be_bytes::<32>().to_vec(),
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2886,6 +3094,8 @@ fn folded_guard_narrow_if_input_zext() {
             gas_limit: None,
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2905,6 +3115,8 @@ fn panic_outline_preserves_call() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: vec![],
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2924,6 +3136,8 @@ fn panic_outline_yield_rescue() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: U256::from(cond).to_be_bytes::<32>().to_vec(),
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -2946,6 +3160,8 @@ fn panic_outline_overwritten_selector() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: vec![],
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2963,6 +3179,8 @@ fn custom_error_duplicate_argument() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: vec![],
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -2983,6 +3201,8 @@ fn store_huge_const_offset_traps() {
         gas_limit: None,
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -3002,6 +3222,8 @@ fn return_past_heap_const_offset_traps() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: vec![],
+        call: None,
+        args: vec![],
     });
     actions.push(VerifyCall(VerifyCallExpectation {
         success: false,
@@ -3033,6 +3255,8 @@ fn callvalue_outline_keeps_revert_data() {
         gas_limit: None,
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -3057,6 +3281,8 @@ fn calldataload_oob() {
             gas_limit: None,
             storage_deposit_limit: None,
             data: off.to_be_bytes::<32>().to_vec(),
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -3074,6 +3300,8 @@ fn fmp_prop_copy_invalidation() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: vec![0u8; 32],
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -3091,6 +3319,8 @@ fn load_forward_copy_invalidation() {
         gas_limit: None,
         storage_deposit_limit: None,
         data: distinctive.to_be_bytes::<32>().to_vec(),
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -3169,6 +3399,8 @@ fn op_boundary_sweep() {
             gas_limit: None,
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
     }
 
@@ -3211,6 +3443,8 @@ fn narrow_width_sweep() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             });
         }
     }
@@ -3254,6 +3488,8 @@ fn memory_byteorder_sweep() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             });
         }
     }
@@ -3296,6 +3532,8 @@ fn escape_byteorder_sweep() {
                 gas_limit: None,
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             });
         }
     }
@@ -3337,6 +3575,8 @@ fn storage_mapping_sweep() {
             gas_limit: None,
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
         run_differential(actions);
     }
@@ -3507,6 +3747,8 @@ fn generative_expr_fuzz() {
             },
             data: vec![],
             salt: OptionalHex::default(),
+            constructor: None,
+            args: vec![],
         }];
 
         for vec4 in &input_vectors {
@@ -3521,6 +3763,8 @@ fn generative_expr_fuzz() {
                 gas_limit: Some(GAS_LIMIT),
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             });
         }
 
@@ -3562,6 +3806,8 @@ fn signed_compare_narrow_sign() {
             gas_limit: Some(GAS_LIMIT),
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
     }
     run_differential(actions);
@@ -3672,6 +3918,8 @@ fn generative_mem_fuzz() {
             },
             data: vec![],
             salt: OptionalHex::default(),
+            constructor: None,
+            args: vec![],
         }];
         for vec4 in &input_vectors {
             let mut data = Vec::with_capacity(128);
@@ -3685,6 +3933,8 @@ fn generative_mem_fuzz() {
                 gas_limit: Some(GAS_LIMIT),
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             });
         }
         let actions_cl = actions.clone();
@@ -3723,6 +3973,8 @@ fn mem27_repro() {
         gas_limit: Some(GAS_LIMIT),
         storage_deposit_limit: None,
         data: vec![0u8; 128],
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -3827,6 +4079,8 @@ fn generative_cf_fuzz() {
             },
             data: vec![],
             salt: OptionalHex::default(),
+            constructor: None,
+            args: vec![],
         }];
         for vec4 in &input_vectors {
             let mut data = Vec::with_capacity(128);
@@ -3840,6 +4094,8 @@ fn generative_cf_fuzz() {
                 gas_limit: Some(GAS_LIMIT),
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             });
         }
         let actions_cl = actions.clone();
@@ -3993,6 +4249,8 @@ fn generative_fn_fuzz() {
             },
             data: vec![],
             salt: OptionalHex::default(),
+            constructor: None,
+            args: vec![],
         }];
         for vec4 in &input_vectors {
             let mut data = Vec::with_capacity(128);
@@ -4006,6 +4264,8 @@ fn generative_fn_fuzz() {
                 gas_limit: Some(GAS_LIMIT),
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             });
         }
         let actions_cl = actions.clone();
@@ -4057,6 +4317,8 @@ fn fn_ssa_ice_repro() {
         gas_limit: Some(GAS_LIMIT),
         storage_deposit_limit: None,
         data: vec![0u8; 128],
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -4079,6 +4341,8 @@ fn store8_fmp_byteorder() {
         gas_limit: Some(GAS_LIMIT),
         storage_deposit_limit: None,
         data: vec![],
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -4111,6 +4375,8 @@ fn try_catch_catch_return_solc_unoptimized() {
         },
         data: vec![],
         salt: OptionalHex::default(),
+        constructor: None,
+        args: vec![],
     }];
     actions.push(Call {
         origin: TestAddress::Alice,
@@ -4119,6 +4385,8 @@ fn try_catch_catch_return_solc_unoptimized() {
         gas_limit: Some(GAS_LIMIT),
         storage_deposit_limit: None,
         data: keccak256(b"run()").0[..4].to_vec(),
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -4151,6 +4419,8 @@ fn tstore_tload_probe() {
             gas_limit: Some(GAS_LIMIT),
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
         run_differential(actions);
     }
@@ -4184,6 +4454,8 @@ fn fmp_big_value_load() {
             gas_limit: Some(GAS_LIMIT),
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
         run_differential(actions);
     }
@@ -4269,6 +4541,8 @@ fn generative_memdyn_fuzz() {
             },
             data: vec![],
             salt: OptionalHex::default(),
+            constructor: None,
+            args: vec![],
         }];
         for vec4 in &input_vectors {
             let mut data = Vec::with_capacity(128);
@@ -4282,6 +4556,8 @@ fn generative_memdyn_fuzz() {
                 gas_limit: Some(GAS_LIMIT),
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             });
         }
         let actions_cl = actions.clone();
@@ -4384,6 +4660,8 @@ fn generative_fnmem_fuzz() {
             },
             data: vec![],
             salt: OptionalHex::default(),
+            constructor: None,
+            args: vec![],
         }];
         for vec4 in &input_vectors {
             let mut data = Vec::with_capacity(128);
@@ -4397,6 +4675,8 @@ fn generative_fnmem_fuzz() {
                 gas_limit: Some(GAS_LIMIT),
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             });
         }
         let actions_cl = actions.clone();
@@ -4460,6 +4740,8 @@ fn self_call_probe() {
             gas_limit: Some(GAS_LIMIT),
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
         run_differential(actions);
     }
@@ -4530,6 +4812,8 @@ fn self_call_returndata() {
             gas_limit: Some(GAS_LIMIT),
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
         run_differential(actions);
     }
@@ -4567,6 +4851,8 @@ fn generative_expr_deep() {
             },
             data: vec![],
             salt: OptionalHex::default(),
+            constructor: None,
+            args: vec![],
         }];
         // 8 per-program random boundary-biased input vectors
         for _ in 0..8 {
@@ -4581,6 +4867,8 @@ fn generative_expr_deep() {
                 gas_limit: Some(GAS_LIMIT),
                 storage_deposit_limit: None,
                 data,
+                call: None,
+                args: vec![],
             });
         }
         let actions_cl = actions.clone();
@@ -4704,6 +4992,8 @@ fn generative_solidity_fuzz() {
                         gas_limit: Some(GAS_LIMIT),
                         storage_deposit_limit: None,
                         data,
+                        call: None,
+                        args: vec![],
                     });
                     Specs {
                         differential: true,
@@ -4764,6 +5054,8 @@ fn array_bounds_probe() {
             gas_limit: Some(GAS_LIMIT),
             storage_deposit_limit: None,
             data,
+            call: None,
+            args: vec![],
         });
         run_differential(actions);
     }
@@ -4791,6 +5083,8 @@ fn fmp_overlap_store_observed_traps() {
             gas_limit: Some(GAS_LIMIT),
             storage_deposit_limit: None,
             data: vec![],
+            call: None,
+            args: vec![],
         },
         VerifyCall(VerifyCallExpectation {
             success: false,
@@ -4832,6 +5126,8 @@ fn fmp_overlap_store_in_branch_traps() {
             gas_limit: Some(GAS_LIMIT),
             storage_deposit_limit: None,
             data: vec![0u8; 32],
+            call: None,
+            args: vec![],
         },
         VerifyCall(VerifyCallExpectation {
             success: true,
@@ -4844,6 +5140,8 @@ fn fmp_overlap_store_in_branch_traps() {
             gas_limit: Some(GAS_LIMIT),
             storage_deposit_limit: None,
             data: corrupting_word.to_vec(),
+            call: None,
+            args: vec![],
         },
         VerifyCall(VerifyCallExpectation {
             success: false,
@@ -4882,6 +5180,8 @@ fn fn_native_fmp_store_no_spurious_trap() {
         gas_limit: Some(GAS_LIMIT),
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -4904,6 +5204,8 @@ fn msize_observes_dead_store_expansion() {
         gas_limit: Some(GAS_LIMIT),
         storage_deposit_limit: None,
         data: vec![],
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -4934,6 +5236,8 @@ fn calldatacopy_dynamic_dest_fmp_corruption() {
         gas_limit: Some(GAS_LIMIT),
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -4956,6 +5260,8 @@ fn dead_store_read_by_overlapping_load() {
         gas_limit: Some(GAS_LIMIT),
         storage_deposit_limit: None,
         data: vec![],
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
@@ -4982,6 +5288,47 @@ fn calldatacopy_fmp_range_proof() {
         gas_limit: Some(GAS_LIMIT),
         storage_deposit_limit: None,
         data,
+        call: None,
+        args: vec![],
     });
     run_differential(actions);
 }
+
+#[test]
+fn invariant_shrinking() {
+    let failure = InvariantTest {
+        setup: Specs {
+            actions: instantiate("contracts/Invariants.sol", "Invariants"),
+            ..Default::default()
+        },
+        targets: vec![InvariantTarget {
+            contract: TestAddress::Instantiated(0),
+            functions: vec![],
+        }],
+        invariants: vec![Invariant::Call {
+            contract: TestAddress::Instantiated(0),
+            call: "belowLimit".to_string(),
+            args: vec![],
+        }],
+        depth: 32,
+        ..Default::default()
+    }
+    .run()
+    .unwrap_err();
+
+    // Four increments exceed the limit, any resets are shrunk away.
+    let calls = failure
+        .specs
+        .actions
+        .iter()
+        .filter_map(|action| match action {
+            Call { call, .. } => call.clone(),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        calls,
+        [vec!["increment()"; 4], vec!["belowLimit"]].concat(),
+        "{failure:?}"
+    );
+}
@@ -1,8 +1,11 @@
 //! The revive ELF object linker library.
 
-use std::{ffi::CString, fs, path::PathBuf, sync::Mutex};
+use std::{collections::BTreeSet, ffi::CString, fs, path::PathBuf, sync::Mutex};
 
 use lld_sys::LLDELFLink;
+use object::elf::EM_RISCV;
+use object::read::elf::{ElfFile64, FileHeader};
+use object::{LittleEndian, Object, ObjectSection, ObjectSymbol, SectionKind};
 use tempfile::TempDir;
 
 use revive_builtins::COMPILER_RT;
@@ -112,3 +115,208 @@ fn lld(arguments: Vec<String>) -> bool {
     let _lock = GUARD.lock().expect("ICE: linker mutex should not poison");
     unsafe { LLDELFLink(args.as_ptr(), args.len()) == 0 }
 }
+
+/// A reader of the little endian 64-bit RISC-V ELF objects emitted by the compiler.
+pub struct ElfObject<'a> {
+    file: ElfFile64<'a, LittleEndian>,
+}
+
+impl<'a> ElfObject<'a> {
+    /// Parses the ELF `object`, validating its header.
+    pub fn parse(object: &'a [u8]) -> anyhow::Result<Self> {
+        let file = ElfFile64::<LittleEndian>::parse(object)
+            .map_err(|error| anyhow::anyhow!("Invalid ELF object: {error}"))?;
+        let machine = file.elf_header().e_machine(file.endian());
+        if machine != EM_RISCV {
+            anyhow::bail!("Unsupported ELF machine {machine}, expected RISC-V");
+        }
+        Ok(Self { file })
+    }
+
+    /// The names of the symbols referenced but not defined by the object.
+    pub fn undefined_symbols(&self) -> anyhow::Result<BTreeSet<String>> {
+        let mut symbols = BTreeSet::new();
+        for symbol in self.file.symbols().filter(ObjectSymbol::is_undefined) {
+            let name = symbol
+                .name()
+                .map_err(|error| anyhow::anyhow!("Invalid ELF symbol name: {error}"))?;
+            if !name.is_empty() {
+                symbols.insert(name.to_owned());
+            }
+        }
+        Ok(symbols)
+    }
+
+    /// The `size` bytes of the data defined by the symbol `name`, if the object defines it.
+    ///
+    /// Symbols in sections without data, like `.bss`, are zero initialized.
+    pub fn symbol_data(&self, name: &str, size: usize) -> anyhow::Result<Option<Vec<u8>>> {
+        let Some(symbol) = self
+            .file
+            .symbols()
+            .find(|symbol| !symbol.is_undefined() && symbol.name() == Ok(name))
+        else {
+            return Ok(None);
+        };
+        let section = symbol
+            .section_index()
+            .and_then(|index| self.file.section_by_index(index).ok())
+            .ok_or_else(|| anyhow::anyhow!("The ELF symbol `{name}` has no section"))?;
+        if section.kind() == SectionKind::UninitializedData {
+            return Ok(Some(vec![0; size]));
+        }
+
+        let data = section
+            .data()
+            .map_err(|error| anyhow::anyhow!("Invalid ELF section data: {error}"))?;
+        usize::try_from(symbol.address())
+            .ok()
+            .and_then(|start| data.get(start..start.checked_add(size)?))
+            .map(|data| Some(data.to_vec()))
+            .ok_or_else(|| anyhow::anyhow!("The ELF symbol `{name}` is out of bounds"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ElfObject;
+
+    const UNDEFINED: &str = "contract.sol:Library";
+    const DEFINED: &str = "__immutable_data_size";
+    const SECTION_HEADERS: usize = 0x100;
+    const SYMBOL_TABLE: usize = SECTION_HEADERS + 64;
+
+    /// A relocatable RISC-V object defining `DEFINED` as `42` and referencing `UNDEFINED`.
+    fn object() -> Vec<u8> {
+        let mut object = vec![0; SECTION_HEADERS + 4 * 64];
+        let mut write = |offset: usize, bytes: &[u8]| {
+            object[offset..offset + bytes.len()].copy_from_slice(bytes)
+        };
+
+        // The file header.
+        write(0x00, b"\x7fELF\x02\x01\x01");
+        write(0x10, &1u16.to_le_bytes());
+        write(0x12, &243u16.to_le_bytes());
+        write(0x14, &1u32.to_le_bytes());
+        write(0x28, &(SECTION_HEADERS as u64).to_le_bytes());
+        write(0x34, &64u16.to_le_bytes());
+        write(0x3a, &64u16.to_le_bytes());
+        write(0x3c, &4u16.to_le_bytes());
+        write(0x3e, &2u16.to_le_bytes());
+
+        // The string table, data and symbol table.
+        let strings = format!("\0{UNDEFINED}\0{DEFINED}\0");
+        write(0x40, strings.as_bytes());
+        write(0x80, &42u32.to_le_bytes());
+        for (index, (name, section)) in [(1, 0u16), (2 + UNDEFINED.len(), 3)].iter().enumerate() {
+            let symbol = 0xa0 + (index + 1) * 24;
+            write(symbol, &(*name as u32).to_le_bytes());
+            write(symbol + 4, &[0x10]);
+            write(symbol + 6, &section.to_le_bytes());
+        }
+
+        // The section headers: null, symbol table, string table and data.
+        for (index, (kind, offset, size, link)) in [
+            (2u32, 0xa0u64, 72u64, 2u32),
+            (3, 0x40, strings.len() as u64, 0),
+            (1, 0x80, 4, 0),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let header = SECTION_HEADERS + (index + 1) * 64;
+            write(header + 0x04, &kind.to_le_bytes());
+            write(header + 0x18, &offset.to_le_bytes());
+            write(header + 0x20, &size.to_le_bytes());
+            write(header + 0x28, &link.to_le_bytes());
+            write(header + 0x2c, &1u32.to_le_bytes());
+            if *kind == 2 {
+                write(header + 0x38, &24u64.to_le_bytes());
+            }
+        }
+
+        object
+    }
+
+    /// Reads everything from the `object`, which must not panic.
+    fn read(object: &[u8]) -> anyhow::Result<()> {
+        let elf = ElfObject::parse(object)?;
+        elf.undefined_symbols()?;
+        elf.symbol_data(DEFINED, 4)?;
+        Ok(())
+    }
+
+    #[test]
+    fn reads_symbols() {
+        let object = object();
+        let elf = ElfObject::parse(&object).unwrap();
+        assert_eq!(
+            elf.undefined_symbols()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [UNDEFINED]
+        );
+        assert_eq!(
+            elf.symbol_data(DEFINED, 4).unwrap(),
+            Some(42u32.to_le_bytes().to_vec())
+        );
+        assert_eq!(elf.symbol_data(UNDEFINED, 4).unwrap(), None);
+        assert!(elf.symbol_data(DEFINED, 8).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        for (offset, byte) in [(0x00, 0), (0x04, 1), (0x05, 2), (0x12, 62)] {
+            let mut object = object();
+            object[offset] = byte;
+            assert!(ElfObject::parse(&object).is_err(), "offset {offset:#x}");
+        }
+    }
+
+    #[test]
+    fn truncated_objects() {
+        let object = object();
+        for length in 0..object.len() {
+            assert!(read(&object[..length]).is_err(), "length {length}");
+        }
+    }
+
+    #[test]
+    fn overflowing_section_headers() {
+        let fields = [
+            0x28,
+            SYMBOL_TABLE + 0x18,
+            SYMBOL_TABLE + 0x20,
+            SYMBOL_TABLE + 64 + 0x18,
+            SYMBOL_TABLE + 128 + 0x18,
+        ];
+        for field in fields {
+            for value in [u64::MAX, u64::MAX - 8, 1 << 63, u32::MAX as u64] {
+                let mut object = object();
+                object[field..field + 8].copy_from_slice(&value.to_le_bytes());
+                assert!(read(&object).is_err(), "field {field:#x} value {value:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn corrupted_objects() {
+        // A SplitMix64 generator, so failures can be reproduced.
+        let mut state = 0u64;
+        let mut next = || {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            let z = (state ^ (state >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            let z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^ (z >> 31)
+        };
+        for _ in 0..4096 {
+            let mut object = object();
+            for _ in 0..1 + next() % 4 {
+                let offset = next() as usize % object.len();
+                object[offset] = next() as u8;
+            }
+            let _ = read(&object);
+        }
+    }
+}
@@ -1,5 +1,8 @@
 //! The revive PVM blob linker library.
 
+use polkavm_common::program::{ProgramParts, BLOB_LEN_OFFSET, BLOB_LEN_SIZE, SECTION_END_OF_FILE};
+use polkavm_common::varint::{write_varint, MAX_VARINT_LENGTH};
+
 pub fn polkavm_linker<T: AsRef<[u8]>>(code: T, strip_binary: bool) -> anyhow::Result<Vec<u8>> {
     let mut config = polkavm_linker::Config::default();
     config.set_strip(strip_binary);
@@ -12,3 +15,73 @@ pub fn polkavm_linker<T: AsRef<[u8]>>(code: T, strip_binary: bool) -> anyhow::Re
     )
     .map_err(|reason| anyhow::anyhow!("polkavm linker failed: {}", reason))
 }
+
+/// Strips the debug information from the linked PVM `blob`.
+///
+/// The result is the blob the PolkaVM linker emits with `strip_binary`, which
+/// only omits the optional debug information sections following the code.
+pub fn polkavm_strip(blob: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let parse = |blob: &[u8]| {
+        ProgramParts::from_bytes(blob.to_vec().into())
+            .map_err(|error| anyhow::anyhow!("invalid PVM blob: {error}"))
+    };
+
+    let parts = parse(blob)?;
+    let debug_information = [
+        &parts.debug_strings,
+        &parts.debug_line_programs,
+        &parts.debug_line_program_ranges,
+    ]
+    .into_iter()
+    .filter(|section| !section.is_empty())
+    .map(|section| {
+        let length = write_varint(section.len() as u32, &mut [0; MAX_VARINT_LENGTH]);
+        1 + length + section.len()
+    })
+    .sum::<usize>();
+    let code_end = blob
+        .len()
+        .checked_sub(debug_information + 1)
+        .ok_or_else(|| anyhow::anyhow!("invalid PVM blob: the debug information overflows"))?;
+
+    let mut stripped = blob[..code_end].to_vec();
+    stripped.push(SECTION_END_OF_FILE);
+    let length = (stripped.len() as u64).to_le_bytes();
+    stripped[BLOB_LEN_OFFSET..BLOB_LEN_OFFSET + BLOB_LEN_SIZE].copy_from_slice(&length);
+
+    let stripped_parts = parse(&stripped)?;
+    if stripped_parts.code_and_jump_table[..] != parts.code_and_jump_table[..] {
+        anyhow::bail!("invalid PVM blob: unexpected sections after the debug information");
+    }
+    Ok(stripped)
+}
+
+#[cfg(test)]
+mod tests {
+    use polkavm_common::program::{
+        asm, InstructionSetKind, SECTION_OPT_DEBUG_LINE_PROGRAMS,
+        SECTION_OPT_DEBUG_LINE_PROGRAM_RANGES, SECTION_OPT_DEBUG_STRINGS,
+    };
+    use polkavm_common::writer::ProgramBlobBuilder;
+
+    use super::polkavm_strip;
+
+    #[test]
+    fn strips_debug_information() {
+        let mut builder = ProgramBlobBuilder::new(InstructionSetKind::ReviveV1);
+        builder.set_ro_data(vec![1; 16]);
+        builder.set_ro_data_size(16);
+        builder.add_import(b"seal_return");
+        builder.add_export_by_basic_block(0, b"call");
+        builder.set_code(&[asm::ecalli(0), asm::ret()], &[]);
+        let stripped = builder.to_vec().unwrap();
+
+        builder.add_custom_section(SECTION_OPT_DEBUG_STRINGS, vec![2; 300]);
+        builder.add_custom_section(SECTION_OPT_DEBUG_LINE_PROGRAMS, vec![3; 3]);
+        builder.add_custom_section(SECTION_OPT_DEBUG_LINE_PROGRAM_RANGES, vec![4; 70000]);
+        let unstripped = builder.to_vec().unwrap();
+
+        assert_eq!(polkavm_strip(&unstripped).unwrap(), stripped);
+        assert_eq!(polkavm_strip(&stripped).unwrap(), stripped);
+    }
+}
@@ -16,6 +16,14 @@ pub struct DebugConfig {
     pub output_directory: Option<PathBuf>,
     /// Whether debug info should be emitted.
     pub emit_debug_info: bool,
+    /// Whether the newyork IR should be kept in the build artifacts.
+    pub emit_newyork_ir: bool,
+    /// Whether the unoptimized LLVM IR should be kept in the build artifacts.
+    pub emit_llvm_ir: bool,
+    /// Whether the optimized LLVM IR should be kept in the build artifacts.
+    pub emit_llvm_ir_optimized: bool,
+    /// Whether the Yul source map should be kept in the build artifacts.
+    pub emit_source_map: bool,
     /// The YUL debug output file path.
     pub contract_path: Option<PathBuf>,
 }
@@ -26,6 +34,10 @@ impl DebugConfig {
         Self {
             output_directory,
             emit_debug_info,
+            emit_newyork_ir: false,
+            emit_llvm_ir: false,
+            emit_llvm_ir_optimized: false,
+            emit_source_map: false,
             contract_path: None,
         }
     }
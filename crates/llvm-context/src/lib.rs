@@ -15,6 +15,7 @@ pub use self::polkavm::context::address_space::AddressSpace as PolkaVMAddressSpa
 pub use self::polkavm::context::argument::Argument as PolkaVMArgument;
 pub use self::polkavm::context::attribute::Attribute as PolkaVMAttribute;
 pub use self::polkavm::context::attribute::MemoryEffect as PolkaVMMemoryEffect;
+pub use self::polkavm::context::attribute::OUTLINED_BY as PolkaVMOutlinedByAttribute;
 pub use self::polkavm::context::build::Build as PolkaVMBuild;
 pub use self::polkavm::context::code_type::CodeType as PolkaVMCodeType;
 pub use self::polkavm::context::debug_info::DebugInfo;
@@ -84,6 +85,7 @@ pub use self::polkavm::evm::storage as polkavm_evm_storage;
 pub use self::polkavm::hash as polkavm_hash;
 pub use self::polkavm::link as polkavm_link;
 pub use self::polkavm::r#const as polkavm_const;
+pub use self::polkavm::strip as polkavm_strip;
 pub use self::polkavm::DummyLLVMWritable as PolkaVMDummyLLVMWritable;
 pub use self::polkavm::WriteLLVM as PolkaVMWriteLLVM;
 pub use self::target_machine::target::Target as PolkaVMTarget;
@@ -3,6 +3,11 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+/// The string attribute naming the pass an outlined helper function was created for.
+///
+/// The attributed functions are reported in [`crate::PolkaVMBuild::outlined_functions`].
+pub const OUTLINED_BY: &str = "revive-outlined-by";
+
 /// The LLVM attribute.
 /// In order to check the real order in a new major version of LLVM, find the `Attributes.inc` file
 /// inside of the LLVM build directory. This order is actually generated during the building.
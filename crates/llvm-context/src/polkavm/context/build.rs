@@ -1,5 +1,7 @@
 //! The LLVM module build.
 
+use std::collections::BTreeMap;
+
 use revive_common::BYTE_LENGTH_WORD;
 use serde::Deserialize;
 use serde::Serialize;
@@ -15,6 +17,17 @@ pub struct Build {
     pub bytecode: Vec<u8>,
     /// The PolkaVM bytecode hash. Unlinked builds don't have a hash yet.
     pub bytecode_hash: Option<[u8; BYTE_LENGTH_WORD]>,
+    /// The immutables identifier-to-offset mapping in the immutable data.
+    pub immutables: BTreeMap<String, usize>,
+    /// The newyork IR, if requested.
+    pub newyork_ir: Option<String>,
+    /// The unoptimized LLVM IR, if requested.
+    pub llvm_ir: Option<String>,
+    /// The optimized LLVM IR, if requested.
+    pub llvm_ir_optimized: Option<String>,
+    /// The outlined helper functions left after the optimizations, with the pass
+    /// each was created for.
+    pub outlined_functions: BTreeMap<String, String>,
 }
 
 impl Build {
@@ -25,6 +38,11 @@ impl Build {
             metadata_hash,
             bytecode,
             bytecode_hash: None,
+            immutables: BTreeMap::new(),
+            newyork_ir: None,
+            llvm_ir: None,
+            llvm_ir_optimized: None,
+            outlined_functions: BTreeMap::new(),
         }
     }
 }
@@ -11,6 +11,8 @@ use inkwell::debug_info::DIScope;
 use inkwell::types::BasicType;
 use inkwell::values::BasicValue;
 use inkwell::values::InstructionOpcode;
+use revive_common::CodedError;
+use revive_common::ErrorCode;
 use revive_solc_json_interface::PolkaVMDefaultHeapMemorySize;
 use revive_solc_json_interface::PolkaVMDefaultStackMemorySize;
 use revive_solc_json_interface::SolcStandardJsonInputSettingsPolkaVMMemory;
@@ -339,22 +341,26 @@ impl<'ctx> Context<'ctx> {
 
         self.debug_config
             .dump_llvm_ir_unoptimized(contract_path, self.module())?;
+        let llvm_ir = self
+            .debug_config
+            .emit_llvm_ir
+            .then(|| self.module().print_to_string().to_string());
 
         self.verify().map_err(|error| {
-            anyhow::anyhow!(
-                "The contract `{}` unoptimized LLVM IR verification error: {}",
-                contract_path,
-                error
+            CodedError::new(
+                ErrorCode::LlvmVerification,
+                format!(
+                    "The contract `{contract_path}` unoptimized LLVM IR verification error: {error}"
+                ),
             )
         })?;
 
         self.optimizer
             .run(&target_machine, self.module())
             .map_err(|error| {
-                anyhow::anyhow!(
-                    "The contract `{}` optimizing error: {}",
-                    contract_path,
-                    error
+                CodedError::new(
+                    ErrorCode::LlvmOptimization,
+                    format!("The contract `{contract_path}` optimizing error: {error}"),
                 )
             })?;
 
@@ -366,22 +372,26 @@ impl<'ctx> Context<'ctx> {
 
         self.debug_config
             .dump_llvm_ir_optimized(contract_path, self.module())?;
+        let llvm_ir_optimized = self
+            .debug_config
+            .emit_llvm_ir_optimized
+            .then(|| self.module().print_to_string().to_string());
 
         self.verify().map_err(|error| {
-            anyhow::anyhow!(
-                "The contract `{}` optimized LLVM IR verification error: {}",
-                contract_path,
-                error
+            CodedError::new(
+                ErrorCode::LlvmVerification,
+                format!(
+                    "The contract `{contract_path}` optimized LLVM IR verification error: {error}"
+                ),
             )
         })?;
 
         let buffer = target_machine
             .write_to_memory_buffer(self.module())
             .map_err(|error| {
-                anyhow::anyhow!(
-                    "The contract `{}` assembly generating error: {}",
-                    contract_path,
-                    error
+                CodedError::new(
+                    ErrorCode::Assembly,
+                    format!("The contract `{contract_path}` assembly generating error: {error}"),
                 )
             })?;
 
@@ -389,12 +399,34 @@ impl<'ctx> Context<'ctx> {
 
         self.debug_config.dump_object(contract_path, &object)?;
 
-        crate::polkavm::build(
+        let mut build = crate::polkavm::build(
             &object,
             metadata_hash
                 .as_ref()
                 .map(|hash| hash.as_bytes().try_into().unwrap()),
-        )
+        )?;
+        build.immutables = self.solidity().immutables().to_owned();
+        build.llvm_ir = llvm_ir;
+        build.llvm_ir_optimized = llvm_ir_optimized;
+        build.outlined_functions = self.outlined_functions();
+        Ok(build)
+    }
+
+    /// Returns the functions with the [`attribute::OUTLINED_BY`] attribute, with its value.
+    fn outlined_functions(&self) -> BTreeMap<String, String> {
+        self.module()
+            .get_functions()
+            .filter_map(|function| {
+                let pass = function.get_string_attribute(
+                    inkwell::attributes::AttributeLoc::Function,
+                    attribute::OUTLINED_BY,
+                )?;
+                Some((
+                    function.get_name().to_string_lossy().into_owned(),
+                    pass.get_string_value().to_string_lossy().into_owned(),
+                ))
+            })
+            .collect()
     }
 
     /// Verifies the current LLVM IR module.
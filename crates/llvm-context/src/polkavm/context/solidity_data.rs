@@ -24,6 +24,11 @@ impl SolidityData {
         self.immutables.len() * BYTE_LENGTH_WORD
     }
 
+    /// Returns the immutables identifier-to-offset mapping.
+    pub fn immutables(&self) -> &BTreeMap<String, usize> {
+        &self.immutables
+    }
+
     /// Allocates memory for an immutable value in the auxiliary heap.
     /// If the identifier is already known, just returns its offset.
     pub fn allocate_immutable(&mut self, identifier: &str) -> usize {
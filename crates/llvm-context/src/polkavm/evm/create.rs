@@ -3,6 +3,8 @@
 use inkwell::values::BasicValue;
 use num::Zero;
 
+use revive_common::CodedError;
+use revive_common::ErrorCode;
 use revive_common::BIT_LENGTH_ETH_ADDRESS;
 
 use crate::polkavm::context::argument::Argument;
@@ -122,7 +124,11 @@ pub fn contract_hash<'ctx>(
                 .with_constant(num::BigUint::zero()));
         }
         CodeType::Runtime if context.yul().is_some() && identifier.ends_with("_deployed") => {
-            anyhow::bail!("type({identifier}).runtimeCode is not supported");
+            return Err(CodedError::new(
+                ErrorCode::RuntimeCode,
+                format!("type({identifier}).runtimeCode is not supported"),
+            )
+            .into());
         }
         _ => {}
     }
@@ -163,7 +169,11 @@ pub fn header_size<'ctx>(
                 .with_constant(num::BigUint::zero()));
         }
         CodeType::Runtime if context.yul().is_some() && identifier.ends_with("_deployed") => {
-            anyhow::bail!("type({identifier}).runtimeCode is not supported");
+            return Err(CodedError::new(
+                ErrorCode::RuntimeCode,
+                format!("type({identifier}).runtimeCode is not supported"),
+            )
+            .into());
         }
         _ => {}
     }
@@ -16,6 +16,7 @@ use revive_common::{
 };
 use revive_linker::elf::ElfLinker;
 use revive_linker::pvm::polkavm_linker;
+use revive_linker::pvm::polkavm_strip;
 
 use self::context::build::Build;
 use self::context::Context;
@@ -70,6 +71,11 @@ pub fn hash(bytecode_buffer: &[u8]) -> [u8; BYTE_LENGTH_WORD] {
         .expect("the bytecode hash should be word sized")
 }
 
+/// Strips the debug information from the linked PVM `bytecode`.
+pub fn strip(bytecode: &[u8]) -> anyhow::Result<Vec<u8>> {
+    polkavm_strip(bytecode)
+}
+
 /// Links the `bytecode` with `linker_symbols` and `factory_dependencies`.
 pub fn link(
     bytecode: &[u8],
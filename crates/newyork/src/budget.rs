@@ -0,0 +1,272 @@
+//! Static heap and stack memory budget estimation.
+//!
+//! PolkaVM contracts run with a heap and a stack of a fixed size, configured at
+//! compile time. Contracts exceeding either trap at runtime. This module estimates
+//! the memory a contract needs, so the compiler can warn about configurations
+//! which are too small:
+//!
+//! - **Heap**: the static high-water mark from the heap analysis, see
+//!   [`crate::HeapAnalysis::static_high_water`]. Memory allocated dynamically
+//!   past the free memory pointer is not included.
+//! - **Stack**: the deepest call chain of the call graph, see
+//!   [`crate::analyze_call_graph`], with a frame size estimated from the
+//!   function signatures. Local values spilled by the register allocator are
+//!   not included, hence the estimate is a lower bound. The stack usage of
+//!   recursive functions can't be bounded statically.
+//!
+//! The estimates also size the memory of contracts compiled with the automatic
+//! memory configuration, see [`MemoryBudget::recommended_heap_size`] and
+//! [`MemoryBudget::recommended_stack_size`].
+
+use std::collections::BTreeMap;
+
+use crate::heap_opt::HeapOptResults;
+use crate::inline::{analyze_call_graph, CallGraphAnalysis};
+use crate::ir::{Function, FunctionId, Object, Type};
+
+/// The stack frame size of every call in bytes: the return address and the callee-saved
+/// registers of the RISC-V calling convention, aligned to 16 bytes.
+pub const FRAME_OVERHEAD_BYTES: u64 = 112;
+
+/// The register size in bytes. Values passed on the stack occupy whole registers.
+const REGISTER_BYTES: u64 = 8;
+
+/// The granularity of the recommended memory sizes in bytes.
+pub const SIZE_GRANULARITY_BYTES: u64 = 4 * 1024;
+
+/// The factor applied to the stack estimate for the recommended stack size, covering the
+/// local values spilled by the register allocator.
+pub const STACK_SAFETY_FACTOR: u64 = 4;
+
+/// The estimated memory budget of a contract.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryBudget {
+    /// The static heap high-water mark in bytes.
+    pub heap_high_water: u64,
+    /// Whether the heap is accessed at dynamic offsets, which are not included in the
+    /// high-water mark.
+    pub heap_dynamic: bool,
+    /// The number of frames of the deepest call chain, including the entry frame.
+    pub max_call_depth: usize,
+    /// The estimated stack usage of the call chain using the most stack in bytes.
+    pub stack_estimate: u64,
+    /// The names of the recursive functions, whose stack usage can't be bounded.
+    pub recursive_functions: Vec<String>,
+}
+
+impl MemoryBudget {
+    /// Estimates the memory budget of the `object` tree with the heap analysis `heap_opt`.
+    pub fn new(object: &Object, heap_opt: &HeapOptResults) -> Self {
+        let mut budget = Self {
+            heap_high_water: heap_opt.static_high_water,
+            heap_dynamic: heap_opt.has_dynamic_accesses || heap_opt.has_dynamic_escapes,
+            ..Default::default()
+        };
+        budget.estimate_stack(object);
+        budget.recursive_functions.sort();
+        budget.recursive_functions.dedup();
+        budget
+    }
+
+    /// Returns the recommended heap size in bytes: the static high-water mark rounded up.
+    ///
+    /// Returns `None` if the heap is accessed at dynamic offsets, since the heap usage
+    /// can't be bounded statically then.
+    pub fn recommended_heap_size(&self) -> Option<u64> {
+        (!self.heap_dynamic).then(|| {
+            self.heap_high_water
+                .max(1)
+                .next_multiple_of(SIZE_GRANULARITY_BYTES)
+        })
+    }
+
+    /// Returns the recommended stack size in bytes: the stack estimate with a safety
+    /// margin, rounded up.
+    ///
+    /// Returns `None` if there are recursive functions, since the stack usage can't be
+    /// bounded statically then.
+    pub fn recommended_stack_size(&self) -> Option<u64> {
+        self.recursive_functions.is_empty().then(|| {
+            (self.stack_estimate * STACK_SAFETY_FACTOR).next_multiple_of(SIZE_GRANULARITY_BYTES)
+        })
+    }
+
+    /// Estimates the stack usage of the `object` and its subobjects.
+    fn estimate_stack(&mut self, object: &Object) {
+        let call_graph = analyze_call_graph(object);
+        let mut chains = BTreeMap::new();
+
+        let (depth, bytes) = call_graph
+            .top_level_calls
+            .iter()
+            .map(|function_id| deepest_chain(*function_id, object, &call_graph, &mut chains))
+            .max_by_key(|(_depth, bytes)| *bytes)
+            .unwrap_or_default();
+        self.max_call_depth = self.max_call_depth.max(depth + 1);
+        self.stack_estimate = self.stack_estimate.max(bytes + FRAME_OVERHEAD_BYTES);
+
+        self.recursive_functions.extend(
+            call_graph
+                .recursive_functions
+                .iter()
+                .filter_map(|function_id| object.functions.get(function_id))
+                .map(|function| function.name.to_owned()),
+        );
+
+        for subobject in object.subobjects.iter() {
+            self.estimate_stack(subobject);
+        }
+    }
+}
+
+/// Returns the number of frames and the estimated stack usage in bytes of the call chain
+/// starting at `function_id` using the most stack.
+///
+/// Calls between recursive functions are not followed, so every recursive function is counted
+/// once. `chains` memoizes the results by function.
+fn deepest_chain(
+    function_id: FunctionId,
+    object: &Object,
+    call_graph: &CallGraphAnalysis,
+    chains: &mut BTreeMap<FunctionId, (usize, u64)>,
+) -> (usize, u64) {
+    if let Some(chain) = chains.get(&function_id) {
+        return *chain;
+    }
+    let Some(function) = object.functions.get(&function_id) else {
+        return (0, 0);
+    };
+    let frame = frame_bytes(function);
+    chains.insert(function_id, (1, frame));
+
+    let is_recursive = call_graph.recursive_functions.contains(&function_id);
+    let (depth, bytes) = call_graph
+        .call_edges
+        .get(&function_id)
+        .into_iter()
+        .flatten()
+        .filter(|callee| !is_recursive || !call_graph.recursive_functions.contains(callee))
+        .map(|callee| deepest_chain(*callee, object, call_graph, chains))
+        .max_by_key(|(_depth, bytes)| *bytes)
+        .unwrap_or_default();
+
+    let chain = (depth + 1, bytes + frame);
+    chains.insert(function_id, chain);
+    chain
+}
+
+/// Returns the estimated stack frame size of the `function` in bytes: the frame overhead and
+/// the parameters and return values.
+fn frame_bytes(function: &Function) -> u64 {
+    function
+        .parameters
+        .iter()
+        .map(|(_id, value_type)| value_type)
+        .chain(function.returns.iter())
+        .map(type_bytes)
+        .sum::<u64>()
+        + FRAME_OVERHEAD_BYTES
+}
+
+/// Returns the stack size of a value of `value_type` in bytes.
+fn type_bytes(value_type: &Type) -> u64 {
+    match value_type {
+        Type::Int(width) => (width.bits() as u64)
+            .div_ceil(8)
+            .next_multiple_of(REGISTER_BYTES),
+        Type::Ptr(_) => REGISTER_BYTES,
+        Type::Void => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use revive_yul::lexer::Lexer;
+    use revive_yul::parser::statement::object::Object as YulObject;
+
+    use super::*;
+
+    /// Translates the Yul `source` and returns its memory budget.
+    fn budget(source: &str) -> MemoryBudget {
+        let mut lexer = Lexer::new(source.to_owned());
+        let object = YulObject::parse(&mut lexer, None).expect("Yul parsing");
+        crate::translate_yul_object(&object, false)
+            .expect("newyork translation")
+            .memory_budget
+    }
+
+    /// Translates the Yul `source` without optimizing it and returns its memory budget.
+    ///
+    /// The functions are neither inlined nor narrowed, so their frames are predictable.
+    fn unoptimized_budget(source: &str) -> MemoryBudget {
+        let mut lexer = Lexer::new(source.to_owned());
+        let object = YulObject::parse(&mut lexer, None).expect("Yul parsing");
+        let object = crate::from_yul::YulTranslator::new()
+            .translate_object(&object)
+            .expect("newyork translation");
+        MemoryBudget::new(&object, &object.analyze_heap())
+    }
+
+    #[test]
+    fn static_heap_high_water() {
+        let budget = budget(
+            r#"
+object "T" {
+    code {
+        mstore(0x40, memoryguard(0x200))
+        mstore(0x1000, calldataload(0))
+        return(0x1000, 0x20)
+    }
+}"#,
+        );
+        assert_eq!(budget.heap_high_water, 0x1020);
+        assert!(!budget.heap_dynamic);
+        assert_eq!(
+            budget.recommended_heap_size(),
+            Some(2 * SIZE_GRANULARITY_BYTES)
+        );
+    }
+
+    #[test]
+    fn call_chain_depth() {
+        let budget = unoptimized_budget(
+            r#"
+object "T" {
+    code {
+        function a(x) -> r { r := b(add(x, 1)) sstore(x, r) }
+        function b(x) -> r { r := c(add(x, 2)) sstore(x, r) }
+        function c(x) -> r { r := calldataload(x) sstore(r, x) }
+        sstore(0, a(calldataload(0)))
+        sstore(1, a(calldataload(1)))
+    }
+}"#,
+        );
+        assert!(budget.recursive_functions.is_empty());
+        // The entry frame and the frames of `a`, `b` and `c`, each with an i256 parameter and
+        // return value.
+        assert_eq!(budget.max_call_depth, 4);
+        assert_eq!(budget.stack_estimate, 4 * FRAME_OVERHEAD_BYTES + 3 * 2 * 32);
+        assert_eq!(
+            budget.recommended_stack_size(),
+            Some(SIZE_GRANULARITY_BYTES)
+        );
+    }
+
+    #[test]
+    fn recursive_functions() {
+        let budget = budget(
+            r#"
+object "T" {
+    code {
+        function f(x) -> r {
+            r := x
+            if x { r := add(f(sub(x, 1)), x) }
+        }
+        sstore(0, f(calldataload(0)))
+    }
+}"#,
+        );
+        assert_eq!(budget.recursive_functions, vec!["f".to_owned()]);
+        assert_eq!(budget.recommended_stack_size(), None);
+    }
+}
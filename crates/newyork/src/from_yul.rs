@@ -5,10 +5,13 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use num::BigUint;
+use revive_common::CodedError;
+use revive_common::ErrorCode;
 
 use revive_yul::lexer::token::lexeme::literal::boolean::Boolean as BooleanLiteral;
 use revive_yul::lexer::token::lexeme::literal::integer::Integer as IntegerLiteral;
 use revive_yul::lexer::token::lexeme::literal::Literal as LexicalLiteral;
+use revive_yul::lexer::token::location::Location;
 use revive_yul::parser::statement::assignment::Assignment;
 use revive_yul::parser::statement::block::Block as YulBlock;
 use revive_yul::parser::statement::expression::function_call::name::Name as FunctionName;
@@ -47,6 +50,66 @@ pub enum TranslationError {
     /// A Yul construct was encountered that this translator does not lower.
     #[error("Unsupported construct: {0}")]
     Unsupported(String),
+
+    /// A Yul construct which is not supported on PolkaVM, with the error code explaining it.
+    #[error("{0}")]
+    Coded(CodedError),
+
+    /// The IR failed validation after the optimization pipeline, which is a compiler bug.
+    #[error(
+        "ICE: IR validation failed for object `{object}` after optimization pipeline:\n{details}"
+    )]
+    InvalidIr {
+        /// The object name.
+        object: String,
+        /// The validation errors, one per line.
+        details: String,
+    },
+
+    /// An error translating the Yul statement at `location`.
+    #[error("{error}")]
+    Located {
+        /// The Yul statement location.
+        location: Location,
+        /// The translation error.
+        error: Box<TranslationError>,
+    },
+}
+
+impl TranslationError {
+    /// Returns the resolc error code of the error.
+    ///
+    /// Errors without a more specific code are compiler bugs.
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Coded(error) => error.error_code,
+            Self::InvalidIr { .. } => ErrorCode::IrValidation,
+            Self::Located { error, .. } => error.error_code(),
+            Self::UndefinedVariable(_)
+            | Self::UndefinedFunction(_)
+            | Self::InvalidLiteral(_)
+            | Self::Unsupported(_) => ErrorCode::NewYorkTranslation,
+        }
+    }
+
+    /// Returns the Yul location of the failing statement.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Self::Located { location, .. } => Some(*location),
+            _ => None,
+        }
+    }
+
+    /// Attaches the Yul statement `location`, unless the error already carries one.
+    fn located(self, location: Location) -> Self {
+        match self {
+            error @ Self::Located { .. } => error,
+            error => Self::Located {
+                location,
+                error: Box::new(error),
+            },
+        }
+    }
 }
 
 /// Translator from Yul AST to newyork IR.
@@ -111,6 +174,7 @@ impl YulTranslator {
             functions,
             subobjects,
             data: BTreeMap::new(),
+            location: Some(yul_object.code.location),
         })
     }
 
@@ -130,6 +194,7 @@ impl YulTranslator {
             if let YulStatement::FunctionDefinition(function_definition) = statement {
                 let id = self.next_function_id.fresh();
                 let mut function = Function::new(id, function_definition.identifier.clone());
+                function.location = Some(function_definition.location);
 
                 for _parameter in &function_definition.arguments {
                     let parameter_id = self.ssa.fresh_id();
@@ -173,7 +238,9 @@ impl YulTranslator {
         let mut ir_block = Block::new();
 
         for statement in &block.statements {
-            let ir_statements = self.translate_statement(statement)?;
+            let ir_statements = self
+                .translate_statement(statement)
+                .map_err(|error| error.located(statement.location()))?;
             for ir_statement in ir_statements {
                 ir_block.push(ir_statement);
             }
@@ -192,7 +259,9 @@ impl YulTranslator {
         let mut region = Region::new();
 
         for statement in &block.statements {
-            let ir_statements = self.translate_statement(statement)?;
+            let ir_statements = self
+                .translate_statement(statement)
+                .map_err(|error| error.located(statement.location()))?;
             for ir_statement in ir_statements {
                 region.push(ir_statement);
             }
@@ -485,7 +554,8 @@ impl YulTranslator {
         }
         arguments.reverse();
 
-        let expression = self.translate_builtin_or_call(&call.name, arguments, &mut statements)?;
+        let expression =
+            self.translate_builtin_or_call(&call.name, arguments, call.location, &mut statements)?;
         Ok((statements, expression))
     }
 
@@ -520,11 +590,12 @@ impl YulTranslator {
         }
     }
 
-    /// Translates a builtin function or user-defined call.
+    /// Translates a builtin function or user-defined call at the Yul `location`.
     fn translate_builtin_or_call(
         &mut self,
         name: &FunctionName,
         arguments: Vec<Value>,
+        location: Location,
         statements: &mut Vec<Statement>,
     ) -> std::result::Result<Expression, TranslationError> {
         match name {
@@ -720,6 +791,7 @@ impl YulTranslator {
                     ret_offset: arguments[5],
                     ret_length: arguments[6],
                     result: result_id,
+                    location: Some(location),
                 });
                 Ok(Expression::Var(result_id))
             }
@@ -735,6 +807,7 @@ impl YulTranslator {
                     ret_offset: arguments[5],
                     ret_length: arguments[6],
                     result: result_id,
+                    location: Some(location),
                 });
                 Ok(Expression::Var(result_id))
             }
@@ -750,6 +823,7 @@ impl YulTranslator {
                     ret_offset: arguments[4],
                     ret_length: arguments[5],
                     result: result_id,
+                    location: Some(location),
                 });
                 Ok(Expression::Var(result_id))
             }
@@ -765,6 +839,7 @@ impl YulTranslator {
                     ret_offset: arguments[4],
                     ret_length: arguments[5],
                     result: result_id,
+                    location: Some(location),
                 });
                 Ok(Expression::Var(result_id))
             }
@@ -855,6 +930,7 @@ impl YulTranslator {
                     destination: arguments[0],
                     offset: arguments[1],
                     length: arguments[2],
+                    location: Some(location),
                 });
                 Ok(Expression::Literal {
                     value: BigUint::from(0u32),
@@ -867,6 +943,7 @@ impl YulTranslator {
                     destination: arguments[1],
                     offset: arguments[2],
                     length: arguments[3],
+                    location: Some(location),
                 });
                 Ok(Expression::Literal {
                     value: BigUint::from(0u32),
@@ -929,7 +1006,10 @@ impl YulTranslator {
                 unreachable!("ICE: LinkerSymbol handled in translate_function_call")
             }
 
-            FunctionName::Pc => Err(TranslationError::Unsupported("pc".to_string())),
+            FunctionName::Pc => Err(TranslationError::Coded(CodedError::new(
+                ErrorCode::Pc,
+                "The `PC` instruction is not supported",
+            ))),
 
             FunctionName::Clz => Ok(unary_op(UnaryOperation::Clz, &arguments)),
 
@@ -937,9 +1017,10 @@ impl YulTranslator {
                 unreachable!("LoadImmutable/SetImmutable handled in translate_function_call")
             }
 
-            FunctionName::Verbatim { .. } => {
-                Err(TranslationError::Unsupported("verbatim".to_string()))
-            }
+            FunctionName::Verbatim { .. } => Err(TranslationError::Coded(CodedError::new(
+                ErrorCode::Verbatim,
+                "The `verbatim` instructions are not supported",
+            ))),
 
             FunctionName::UserDefined(name) => {
                 let function_id = self
@@ -1672,6 +1753,34 @@ object "Test" {
         );
     }
 
+    /// Translation errors carry the location of the innermost Yul statement they occur in.
+    #[test]
+    fn errors_are_located() {
+        let source = r#"
+object "Test" {
+    code {
+        function f() -> r {
+            r := pc()
+        }
+        sstore(0, f())
+    }
+}
+"#;
+        let mut lexer = Lexer::new(source.to_owned());
+        let yul_object = YulObject::parse(&mut lexer, None).expect("the Yul object should parse");
+
+        let error = YulTranslator::new()
+            .translate_object(&yul_object)
+            .expect_err("`pc` is not supported");
+
+        assert_eq!(error.location(), Some(Location::new(5, 13)));
+        assert!(
+            error.to_string().starts_with("The `PC` instruction"),
+            "{error}"
+        );
+        assert_eq!(error.error_code(), ErrorCode::Pc);
+    }
+
     /// `setimmutable(offset, "name", value)` must evaluate its offset argument for side effects,
     /// even though the IR `SetImmutable` is keyed by name and ignores the offset value.
     ///
@@ -1121,11 +1121,8 @@ mod tests {
         let mut functions = BTreeMap::new();
         functions.insert(FunctionId(0), function);
         Object {
-            name: "test".to_string(),
-            code: Block { statements: vec![] },
             functions,
-            subobjects: vec![],
-            data: BTreeMap::new(),
+            ..Object::new("test".to_string())
         }
     }
 
@@ -1260,11 +1257,8 @@ mod tests {
         functions.insert(FunctionId(0), validator_function(true));
         functions.insert(FunctionId(1), validator_function(false));
         let object = Object {
-            name: "test".to_string(),
-            code: Block { statements: vec![] },
             functions,
-            subobjects: vec![],
-            data: BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let noreturn = detect_noreturn_functions(&object);
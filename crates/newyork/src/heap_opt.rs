@@ -167,6 +167,9 @@ pub struct HeapAnalysis {
     /// call sites by [`Self::scan_fmp_corruption`], so corruption escaping a callee is visible
     /// to the caller's observation scan.
     fmp_corrupting_functions: BTreeSet<FunctionId>,
+    /// The largest statically known value stored to the free memory pointer slot, such as the
+    /// `memoryguard` reservation. Memory below it is reserved by the compiler.
+    max_static_free_pointer: u64,
 }
 
 /// Information about a value used as a memory offset.
@@ -257,6 +260,7 @@ impl HeapAnalysis {
             fmp_could_be_unbounded: false,
             value_expressions: BTreeMap::new(),
             fmp_corrupting_functions: BTreeSet::new(),
+            max_static_free_pointer: 0,
         }
     }
 
@@ -339,6 +343,12 @@ impl HeapAnalysis {
                 if is_fmp_store && !self.is_trusted_fmp_source(value.id.0) {
                     self.fmp_could_be_unbounded = true;
                 }
+                if is_fmp_store {
+                    if let Some(free_pointer) = self.extract_static_offset(value) {
+                        self.max_static_free_pointer =
+                            self.max_static_free_pointer.max(free_pointer);
+                    }
+                }
             }
 
             Statement::MStore8 { offset, .. } => {
@@ -1275,6 +1285,22 @@ impl HeapAnalysis {
         self.fmp_could_be_unbounded
     }
 
+    /// Returns the static heap high-water mark in bytes: the end of the highest word accessed at
+    /// a statically known offset, or the largest static free memory pointer value.
+    ///
+    /// Accesses at dynamic offsets, like allocations past the free memory pointer, are not
+    /// included.
+    pub fn static_high_water(&self) -> u64 {
+        self.memory_accesses
+            .keys()
+            .chain(self.tainted_regions.iter())
+            .chain(self.escaping_regions.iter())
+            .map(|&address| address.saturating_add(BYTE_LENGTH_WORD as u64))
+            .chain(std::iter::once(self.max_static_free_pointer))
+            .max()
+            .unwrap_or_default()
+    }
+
     /// Walks the (possibly transitive) `Let` chain for `value_id` and
     /// returns true iff its source expression matches a Solidity-allocator
     /// pattern that keeps the FMP < heap_size at runtime. Recognized
@@ -1432,6 +1458,8 @@ pub struct HeapOptResults {
     /// Whether the FMP word at 0x40 is tainted (byte/unaligned write). Forces
     /// big-endian emulation for the FMP slot; see `fmp_native_safe`.
     fmp_slot_tainted: bool,
+    /// The static heap high-water mark in bytes, see [`HeapAnalysis::static_high_water`].
+    pub static_high_water: u64,
 }
 
 impl Object {
@@ -1484,6 +1512,7 @@ impl HeapOptResults {
             variable_accessed_offsets: analysis.variable_accessed_offsets().clone(),
             fmp_could_be_unbounded: analysis.fmp_could_be_unbounded(),
             fmp_slot_tainted: analysis.tainted_regions().contains(&0x40),
+            static_high_water: analysis.static_high_water(),
         }
     }
 
@@ -1711,14 +1740,12 @@ mod tests {
     fn object_with_code(statements: Vec<Statement>, functions: Vec<crate::ir::Function>) -> Object {
         use crate::ir::Block;
         Object {
-            name: "T".to_string(),
             code: Block { statements },
             functions: functions
                 .into_iter()
                 .map(|function| (function.id, function))
                 .collect(),
-            subobjects: vec![],
-            data: std::collections::BTreeMap::new(),
+            ..Object::new("T".to_string())
         }
     }
 
@@ -1735,11 +1762,9 @@ mod tests {
             length: Value::int(ValueId(21)),
         });
         Object {
-            name: "T".to_string(),
             code: Block { statements },
             functions: std::collections::BTreeMap::new(),
-            subobjects: vec![],
-            data: std::collections::BTreeMap::new(),
+            ..Object::new("T".to_string())
         }
     }
 
@@ -2061,17 +2086,13 @@ mod tests {
 
         fn make_function(id: u32, offset: u64, offset_id: u32, value_id: u32) -> Function {
             Function {
-                id: FunctionId(id),
-                name: format!("store_{offset:#x}"),
                 parameters: vec![(ValueId(value_id), Type::default())],
-                returns: vec![],
-                return_values_initial: vec![],
-                return_values: vec![],
                 body: Block {
                     statements: store_at(offset, offset_id, value_id),
                 },
                 call_count: 1,
                 size_estimate: 15,
+                ..Function::new(FunctionId(id), format!("store_{offset:#x}"))
             }
         }
 
@@ -2080,11 +2101,8 @@ mod tests {
         functions.insert(FunctionId(1), make_function(1, 0xa0, 21, 20));
 
         let mut object = Object {
-            name: "test".to_string(),
-            code: Block { statements: vec![] },
             functions,
-            subobjects: vec![],
-            data: std::collections::BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let before = object.analyze_heap();
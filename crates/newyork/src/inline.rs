@@ -2112,6 +2112,7 @@ mod tests {
                 ret_offset: make_value(6),
                 ret_length: make_value(7),
                 result: call_result,
+                location: None,
             },
             Statement::Create {
                 kind: CreateKind::Create,
@@ -6,6 +6,7 @@
 
 use num::BigUint;
 use revive_common::BYTE_LENGTH_WORD;
+use revive_yul::lexer::token::location::Location;
 use std::collections::BTreeMap;
 
 /// Bit width for integer types.
@@ -667,6 +668,8 @@ pub enum Statement {
         ret_offset: Value,
         ret_length: Value,
         result: ValueId,
+        /// The Yul location of the call, which locates the error of an unsupported `kind`.
+        location: Option<Location>,
     },
 
     /// Deploy a new contract, dispatched by `kind`; `result` receives the address.
@@ -691,6 +694,8 @@ pub enum Statement {
         destination: Value,
         offset: Value,
         length: Value,
+        /// The Yul location of the copy, which locates the error in the runtime code.
+        location: Option<Location>,
     },
     /// Copy `address`'s code into memory.
     ExtCodeCopy {
@@ -698,6 +703,8 @@ pub enum Statement {
         destination: Value,
         offset: Value,
         length: Value,
+        /// The Yul location of the copy, which locates the unsupported instruction error.
+        location: Option<Location>,
     },
     /// Copy the last external call's return data into memory.
     ReturnDataCopy {
@@ -757,6 +764,8 @@ pub struct Function {
     pub call_count: usize,
     /// Instruction count estimate (for inlining decisions).
     pub size_estimate: usize,
+    /// Location of the Yul function definition, for error reporting.
+    pub location: Option<Location>,
 }
 
 impl Function {
@@ -772,6 +781,7 @@ impl Function {
             body: Block::new(),
             call_count: 0,
             size_estimate: 0,
+            location: None,
         }
     }
 }
@@ -789,6 +799,8 @@ pub struct Object {
     pub subobjects: Vec<Object>,
     /// Embedded data sections keyed by Yul `datasize`/`dataoffset` identifier.
     pub data: BTreeMap<String, Vec<u8>>,
+    /// Location of the Yul code block, for error reporting.
+    pub location: Option<Location>,
 }
 
 impl Object {
@@ -800,6 +812,7 @@ impl Object {
             functions: BTreeMap::new(),
             subobjects: Vec::new(),
             data: BTreeMap::new(),
+            location: None,
         }
     }
 
@@ -1130,6 +1143,7 @@ impl Statement {
                 destination,
                 offset,
                 length,
+                ..
             }
             | Statement::ReturnDataCopy {
                 destination,
@@ -1155,6 +1169,7 @@ impl Statement {
                 destination,
                 offset,
                 length,
+                ..
             } => {
                 visit(address.id);
                 visit(destination.id);
@@ -1321,6 +1336,7 @@ impl Statement {
                 destination,
                 offset,
                 length,
+                ..
             }
             | Statement::ReturnDataCopy {
                 destination,
@@ -1346,6 +1362,7 @@ impl Statement {
                 destination,
                 offset,
                 length,
+                ..
             } => {
                 visit(&mut address.id);
                 visit(&mut destination.id);
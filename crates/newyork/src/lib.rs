@@ -19,6 +19,7 @@
 //! 3. **Pure Expressions vs Effectful Statements** - Enables easier reasoning
 //! 4. **Semantic Annotations** - Storage/memory operations tagged with region info
 
+pub mod budget;
 pub mod from_yul;
 pub mod guard_narrow;
 pub mod heap_opt;
@@ -33,6 +34,7 @@ pub mod to_llvm;
 pub mod type_inference;
 pub mod validate;
 
+pub use budget::MemoryBudget;
 pub use from_yul::{TranslationError, YulTranslator};
 pub use heap_opt::{
     AccessPattern, HeapAnalysis, HeapAnalysisStats, HeapOptResults, MemorySlot, OffsetInfo,
@@ -71,6 +73,8 @@ pub struct TranslationResult {
     pub mem_opt: MemOptResults,
     /// Inlining results (which functions were inlined and removed).
     pub inline_results: InlineResults,
+    /// The estimated heap and stack memory budget.
+    pub memory_budget: MemoryBudget,
     /// The IR printed after the intra-object optimization passes, before the late passes.
     /// `Some` only when `capture_ir_snapshot` was requested.
     pub ir_snapshot: Option<String>,
@@ -119,18 +123,21 @@ pub fn translate_yul_object(
             .map(|error| format!("  - {error}"))
             .collect::<Vec<_>>()
             .join("\n");
-        panic!(
-            "ICE: IR validation failed for object `{}` after optimization pipeline:\n{}",
-            ir_object.name, details,
-        );
+        return Err(TranslationError::InvalidIr {
+            object: ir_object.name,
+            details,
+        });
     }
 
+    let memory_budget = MemoryBudget::new(&ir_object, &heap_opt);
+
     Ok(TranslationResult {
         object: ir_object,
         heap_opt,
         type_info,
         mem_opt: mem_opt_results,
         inline_results,
+        memory_budget,
         ir_snapshot,
     })
 }
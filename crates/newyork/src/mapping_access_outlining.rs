@@ -286,7 +286,6 @@ mod tests {
             yields: vec![],
         };
         let mut object = Object {
-            name: "test".to_string(),
             code: Block {
                 statements: vec![
                     literal(1, 0),
@@ -300,9 +299,7 @@ mod tests {
                     },
                 ],
             },
-            functions: BTreeMap::new(),
-            subobjects: vec![],
-            data: BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let statistics = outline_mapping_accesses_in_object(&mut object);
@@ -342,13 +339,10 @@ mod tests {
             yields: vec![],
         };
         let mut object = Object {
-            name: "test".to_string(),
             code: Block {
                 statements: vec![literal(1, 0), literal(2, 1), Statement::Block(inner)],
             },
-            functions: BTreeMap::new(),
-            subobjects: vec![],
-            data: BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let statistics = outline_mapping_accesses_in_object(&mut object);
@@ -1563,11 +1563,8 @@ mod tests {
         ];
 
         let mut object = Object {
-            name: "test".to_string(),
             code: Block { statements },
-            functions: BTreeMap::new(),
-            subobjects: vec![],
-            data: BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let statistics = optimizer.optimize_object(&mut object);
@@ -1631,11 +1628,8 @@ mod tests {
         ];
 
         let mut object = Object {
-            name: "test".to_string(),
             code: Block { statements },
-            functions: BTreeMap::new(),
-            subobjects: vec![],
-            data: BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let statistics = MemoryOptimizer::new().optimize_object(&mut object);
@@ -1694,11 +1688,8 @@ mod tests {
         ];
 
         let mut object = Object {
-            name: "test".to_string(),
             code: Block { statements },
-            functions: BTreeMap::new(),
-            subobjects: vec![],
-            data: BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let statistics = MemoryOptimizer::new().optimize_object(&mut object);
@@ -1772,11 +1763,8 @@ mod tests {
         ];
 
         let mut object = Object {
-            name: "test".to_string(),
             code: Block { statements },
-            functions: BTreeMap::new(),
-            subobjects: vec![],
-            data: BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let _statistics = optimizer.optimize_object(&mut object);
@@ -1857,11 +1845,8 @@ mod tests {
         ];
 
         let mut object = Object {
-            name: "test".to_string(),
             code: Block { statements },
-            functions: BTreeMap::new(),
-            subobjects: vec![],
-            data: BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let statistics = optimizer.optimize_object(&mut object);
@@ -1945,11 +1930,8 @@ mod tests {
         ];
 
         let mut object = Object {
-            name: "test".to_string(),
             code: Block { statements },
-            functions: BTreeMap::new(),
-            subobjects: vec![],
-            data: BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let statistics = optimizer.optimize_object(&mut object);
@@ -2000,11 +1982,9 @@ mod tests {
         ];
 
         let mut object = Object {
-            name: "test".to_string(),
             code: Block { statements },
             functions: std::collections::BTreeMap::new(),
-            subobjects: vec![],
-            data: std::collections::BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let mut fmp = FmpPropagation::new();
@@ -2067,11 +2047,9 @@ mod tests {
         ];
 
         let mut object = Object {
-            name: "test".to_string(),
             code: Block { statements },
             functions: std::collections::BTreeMap::new(),
-            subobjects: vec![],
-            data: std::collections::BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let mut fmp = FmpPropagation::new();
@@ -2140,11 +2118,9 @@ mod tests {
         ];
 
         let mut object = Object {
-            name: "test".to_string(),
             code: Block { statements },
             functions: std::collections::BTreeMap::new(),
-            subobjects: vec![],
-            data: std::collections::BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let mut fmp = FmpPropagation::new();
@@ -2217,11 +2193,9 @@ mod tests {
         ];
 
         let mut object = Object {
-            name: "test".to_string(),
             code: Block { statements },
             functions: std::collections::BTreeMap::new(),
-            subobjects: vec![],
-            data: std::collections::BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
 
         let mut fmp = FmpPropagation::new();
@@ -2284,14 +2258,12 @@ mod tests {
         statements.extend(middle);
         statements.push(fmp_observing_load(30));
         let mut object = Object {
-            name: "test".to_string(),
             code: Block { statements },
             functions: functions
                 .into_iter()
                 .map(|function| (function.id, function))
                 .collect(),
-            subobjects: vec![],
-            data: std::collections::BTreeMap::new(),
+            ..Object::new("test".to_string())
         };
         let mut fmp = FmpPropagation::new();
         fmp.propagate_object(&mut object);
@@ -727,6 +727,7 @@ impl<'a> Printer<'a> {
                 ret_offset,
                 ret_length,
                 result,
+                ..
             } => {
                 self.write_indent();
                 self.output.push_str("let ");
@@ -812,6 +813,7 @@ impl<'a> Printer<'a> {
                 destination,
                 offset,
                 length,
+                ..
             } => {
                 self.write_indent();
                 self.output.push_str("codecopy(");
@@ -829,6 +831,7 @@ impl<'a> Printer<'a> {
                 destination,
                 offset,
                 length,
+                ..
             } => {
                 self.write_indent();
                 self.output.push_str("extcodecopy(");
@@ -1523,8 +1526,6 @@ mod tests {
     #[test]
     fn test_print_function() {
         let function = Function {
-            id: FunctionId(0),
-            name: "add_one".to_string(),
             parameters: vec![(ValueId(0), Type::Int(BitWidth::I256))],
             returns: vec![Type::Int(BitWidth::I256)],
             return_values_initial: vec![ValueId(1)],
@@ -1541,6 +1542,7 @@ mod tests {
             },
             call_count: 1,
             size_estimate: 5,
+            ..Function::new(FunctionId(0), "add_one".to_string())
         };
         let output = print_function(&function);
         assert!(output.contains("function add_one"));
@@ -1557,37 +1559,19 @@ mod tests {
     #[test]
     fn test_subobject_function_names_are_scoped() {
         let parent_only = Function {
-            id: FunctionId(0),
-            name: "parent_only".to_string(),
-            parameters: vec![],
-            returns: vec![],
-            return_values_initial: vec![],
-            return_values: vec![],
-            body: Block { statements: vec![] },
             call_count: 1,
             size_estimate: 1,
+            ..Function::new(FunctionId(0), "parent_only".to_string())
         };
         let sub_collides = Function {
-            id: FunctionId(0),
-            name: "sub_collides".to_string(),
-            parameters: vec![],
-            returns: vec![],
-            return_values_initial: vec![],
-            return_values: vec![],
-            body: Block { statements: vec![] },
             call_count: 1,
             size_estimate: 1,
+            ..Function::new(FunctionId(0), "sub_collides".to_string())
         };
         let sub_unique = Function {
-            id: FunctionId(7),
-            name: "sub_unique".to_string(),
-            parameters: vec![],
-            returns: vec![],
-            return_values_initial: vec![],
-            return_values: vec![],
-            body: Block { statements: vec![] },
             call_count: 1,
             size_estimate: 1,
+            ..Function::new(FunctionId(7), "sub_unique".to_string())
         };
 
         let call = |id| {
@@ -1604,22 +1588,19 @@ mod tests {
         sub_functions.insert(sub_unique.id, sub_unique);
 
         let subobject = Object {
-            name: "Sub".to_string(),
             code: Block {
                 statements: vec![call(0), call(7)],
             },
             functions: sub_functions,
-            subobjects: Vec::new(),
-            data: BTreeMap::new(),
+            ..Object::new("Sub".to_string())
         };
         let object = Object {
-            name: "Parent".to_string(),
             code: Block {
                 statements: vec![call(0)],
             },
             functions: parent_functions,
             subobjects: vec![subobject],
-            data: BTreeMap::new(),
+            ..Object::new("Parent".to_string())
         };
 
         let output = print_object(&object);
@@ -1654,7 +1635,6 @@ mod tests {
     #[test]
     fn test_print_simple_object() {
         let object = Object {
-            name: "Test".to_string(),
             code: Block {
                 statements: vec![Statement::Let {
                     bindings: vec![ValueId(0)],
@@ -1664,9 +1644,7 @@ mod tests {
                     },
                 }],
             },
-            functions: BTreeMap::new(),
-            subobjects: Vec::new(),
-            data: BTreeMap::new(),
+            ..Object::new("Test".to_string())
         };
         let output = print_object(&object);
         assert!(output.contains("object \"Test\""));
@@ -2659,6 +2659,7 @@ impl Canonicalizer {
                 ret_offset,
                 ret_length,
                 result,
+                ..
             } => {
                 buffer.push(0x94);
                 buffer.push(callkind_tag(*kind));
@@ -2714,6 +2715,7 @@ impl Canonicalizer {
                 destination,
                 offset,
                 length,
+                ..
             } => {
                 buffer.push(0x97);
                 self.encode_value(destination, buffer);
@@ -2725,6 +2727,7 @@ impl Canonicalizer {
                 destination,
                 offset,
                 length,
+                ..
             } => {
                 buffer.push(0x98);
                 self.encode_value(address, buffer);
@@ -8,10 +8,13 @@ use std::collections::{BTreeMap, BTreeSet};
 use inkwell::types::BasicType;
 use inkwell::values::{AnyValue, BasicValue, BasicValueEnum, IntValue};
 use num::{ToPrimitive, Zero};
+use revive_common::CodedError;
+use revive_common::ErrorCode;
 use revive_llvm_context::{
     PolkaVMArgument, PolkaVMContext, PolkaVMFunctionDeployCode, PolkaVMFunctionRuntimeCode,
     PolkaVMMemoryEffect,
 };
+use revive_yul::lexer::token::location::Location;
 
 use crate::heap_opt::HeapOptResults;
 use crate::ir::{
@@ -37,11 +40,61 @@ pub enum CodegenError {
 
     #[error("{0}")]
     Unsupported(String),
+
+    /// A construct which is not supported on PolkaVM, with the error code explaining it.
+    #[error("{0}")]
+    Coded(CodedError),
+
+    #[error("{error}")]
+    Located {
+        location: Location,
+        error: Box<CodegenError>,
+    },
+}
+
+impl CodegenError {
+    /// Returns the resolc error code of the error.
+    ///
+    /// Errors without a more specific code are compiler bugs.
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Coded(error) => error.error_code,
+            Self::Located { error, .. } => error.error_code(),
+            Self::Llvm(_)
+            | Self::UndefinedValue(_)
+            | Self::UndefinedFunction(_)
+            | Self::TypeMismatch { .. }
+            | Self::Unsupported(_) => ErrorCode::NewYorkCodegen,
+        }
+    }
+
+    /// Returns the Yul location of the failing statement, function or object code.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Self::Located { location, .. } => Some(*location),
+            _ => None,
+        }
+    }
+
+    /// Attaches the Yul `location` of the failing statement, function or object code,
+    /// unless the error already carries one.
+    fn located(self, location: Option<Location>) -> Self {
+        match (self, location) {
+            (error @ Self::Located { .. }, _) | (error, None) => error,
+            (error, Some(location)) => Self::Located {
+                location,
+                error: Box::new(error),
+            },
+        }
+    }
 }
 
 impl From<anyhow::Error> for CodegenError {
     fn from(error: anyhow::Error) -> Self {
-        CodegenError::Llvm(error.to_string())
+        match error.downcast::<CodedError>() {
+            Ok(error) => CodegenError::Coded(error),
+            Err(error) => CodegenError::Llvm(error.to_string()),
+        }
     }
 }
 
@@ -69,6 +122,32 @@ fn add_noinline_minsize_attrs<'ctx>(
     );
 }
 
+/// Adds the internal helper function `name`, outlined for the newyork `pass`.
+///
+/// Helpers the code generator outlines on its own are attributed to `codegen`.
+/// The pass is kept as the [`revive_llvm_context::PolkaVMOutlinedByAttribute`]
+/// string attribute, which survives the LLVM optimizations.
+fn add_outlined_function<'ctx>(
+    context: &PolkaVMContext<'ctx>,
+    name: &str,
+    function_type: inkwell::types::FunctionType<'ctx>,
+    pass: &str,
+) -> inkwell::values::FunctionValue<'ctx> {
+    let function = context.module().add_function(
+        name,
+        function_type,
+        Some(inkwell::module::Linkage::Internal),
+    );
+    let outlined_by_attribute = context
+        .llvm()
+        .create_string_attribute(revive_llvm_context::PolkaVMOutlinedByAttribute, pass);
+    function.add_attribute(
+        inkwell::attributes::AttributeLoc::Function,
+        outlined_by_attribute,
+    );
+    function
+}
+
 /// Attaches the named `memory(...)` effect to an outlined helper.
 fn add_memory_effect_attribute<'ctx>(
     context: &PolkaVMContext<'ctx>,
@@ -1462,11 +1541,7 @@ impl<'ctx> LlvmCodegen<'ctx> {
         }
         let function_type = context.llvm().void_type().fn_type(&parameter_types, false);
 
-        let function = context.module().add_function(
-            &function_name,
-            function_type,
-            Some(inkwell::module::Linkage::Internal),
-        );
+        let function = add_outlined_function(context, &function_name, function_type, "codegen");
 
         let noinline_attribute = context
             .llvm()
@@ -1602,11 +1677,7 @@ impl<'ctx> LlvmCodegen<'ctx> {
         }
         let function_type = context.llvm().void_type().fn_type(&parameter_types, false);
 
-        let function = context.module().add_function(
-            &function_name,
-            function_type,
-            Some(inkwell::module::Linkage::Internal),
-        );
+        let function = add_outlined_function(context, &function_name, function_type, "codegen");
 
         let noinline_attribute = context
             .llvm()
@@ -1698,11 +1769,8 @@ impl<'ctx> LlvmCodegen<'ctx> {
             .llvm()
             .void_type()
             .fn_type(&[xlen_type.into(), word_type.into()], false);
-        let function = context.module().add_function(
-            "__revive_store_bswap",
-            function_type,
-            Some(inkwell::module::Linkage::Internal),
-        );
+        let function =
+            add_outlined_function(context, "__revive_store_bswap", function_type, "heap_opt");
 
         let noinline_attribute = context
             .llvm()
@@ -1762,10 +1830,11 @@ impl<'ctx> LlvmCodegen<'ctx> {
             .llvm()
             .void_type()
             .fn_type(&[xlen_type.into(), word_type.into()], false);
-        let function = context.module().add_function(
+        let function = add_outlined_function(
+            context,
             "__revive_store_bswap_checked",
             function_type,
-            Some(inkwell::module::Linkage::Internal),
+            "heap_opt",
         );
 
         let noinline_attribute = context
@@ -1853,10 +1922,11 @@ impl<'ctx> LlvmCodegen<'ctx> {
             .llvm()
             .void_type()
             .fn_type(&[xlen_type.into()], false);
-        let function = context.module().add_function(
+        let function = add_outlined_function(
+            context,
             "__revive_store_zero_checked",
             function_type,
-            Some(inkwell::module::Linkage::Internal),
+            "heap_opt",
         );
 
         let noinline_attribute = context
@@ -1953,10 +2023,11 @@ impl<'ctx> LlvmCodegen<'ctx> {
             .llvm()
             .void_type()
             .fn_type(&[xlen_type.into(), i64_type.into()], false);
-        let function = context.module().add_function(
+        let function = add_outlined_function(
+            context,
             "__revive_store_low_word_checked",
             function_type,
-            Some(inkwell::module::Linkage::Internal),
+            "heap_opt",
         );
 
         let noinline_attribute = context
@@ -2081,10 +2152,11 @@ impl<'ctx> LlvmCodegen<'ctx> {
             .llvm()
             .void_type()
             .fn_type(&[xlen_type.into(), i32_type.into()], false);
-        let function = context.module().add_function(
+        let function = add_outlined_function(
+            context,
             "__revive_store_high_word_checked",
             function_type,
-            Some(inkwell::module::Linkage::Internal),
+            "heap_opt",
         );
 
         let noinline_attribute = context
@@ -2199,11 +2271,8 @@ impl<'ctx> LlvmCodegen<'ctx> {
             .llvm()
             .void_type()
             .fn_type(&[xlen_type.into(), word_type.into()], false);
-        let function = context.module().add_function(
-            "__revive_return_word",
-            function_type,
-            Some(inkwell::module::Linkage::Internal),
-        );
+        let function =
+            add_outlined_function(context, "__revive_return_word", function_type, "heap_opt");
 
         let noinline_attribute = context
             .llvm()
@@ -2286,10 +2355,11 @@ impl<'ctx> LlvmCodegen<'ctx> {
         let xlen_type = context.xlen_type();
         let word_type = context.word_type();
         let function_type = word_type.fn_type(&[xlen_type.into()], false);
-        let function = context.module().add_function(
+        let function = add_outlined_function(
+            context,
             "__revive_load_bswap_checked",
             function_type,
-            Some(inkwell::module::Linkage::Internal),
+            "heap_opt",
         );
 
         add_noinline_minsize_attrs(context, function);
@@ -2364,11 +2434,8 @@ impl<'ctx> LlvmCodegen<'ctx> {
             &[xlen_type.into(), xlen_type.into(), xlen_type.into()],
             false,
         );
-        let function = context.module().add_function(
-            "__revive_exit_checked",
-            function_type,
-            Some(inkwell::module::Linkage::Internal),
-        );
+        let function =
+            add_outlined_function(context, "__revive_exit_checked", function_type, "heap_opt");
 
         let noinline_attribute = context
             .llvm()
@@ -2487,11 +2554,8 @@ impl<'ctx> LlvmCodegen<'ctx> {
         let word_type = context.word_type();
         let xlen_type = context.xlen_type();
         let function_type = word_type.fn_type(&[word_type.into()], false);
-        let function = context.module().add_function(
-            "__revive_sload_word",
-            function_type,
-            Some(inkwell::module::Linkage::Internal),
-        );
+        let function =
+            add_outlined_function(context, "__revive_sload_word", function_type, "codegen");
 
         add_noinline_minsize_attrs(context, function);
         add_memory_effect_attribute(context, function, PolkaVMMemoryEffect::ReadInaccessible);
@@ -2562,11 +2626,8 @@ impl<'ctx> LlvmCodegen<'ctx> {
             .llvm()
             .void_type()
             .fn_type(&[word_type.into(), word_type.into()], false);
-        let function = context.module().add_function(
-            "__revive_sstore_word",
-            function_type,
-            Some(inkwell::module::Linkage::Internal),
-        );
+        let function =
+            add_outlined_function(context, "__revive_sstore_word", function_type, "codegen");
 
         add_noinline_minsize_attrs(context, function);
         add_memory_effect_attribute(context, function, PolkaVMMemoryEffect::WriteInaccessible);
@@ -2631,10 +2692,11 @@ impl<'ctx> LlvmCodegen<'ctx> {
         let word_type = context.word_type();
         let xlen_type = context.xlen_type();
         let function_type = word_type.fn_type(&[word_type.into(), word_type.into()], false);
-        let function = context.module().add_function(
+        let function = add_outlined_function(
+            context,
             "__revive_mapping_sload",
             function_type,
-            Some(inkwell::module::Linkage::Internal),
+            "mapping_access_outlining",
         );
 
         let noinline_attribute = context
@@ -2742,10 +2804,11 @@ impl<'ctx> LlvmCodegen<'ctx> {
             &[word_type.into(), word_type.into(), word_type.into()],
             false,
         );
-        let function = context.module().add_function(
+        let function = add_outlined_function(
+            context,
             "__revive_mapping_sstore",
             function_type,
-            Some(inkwell::module::Linkage::Internal),
+            "mapping_access_outlining",
         );
 
         add_noinline_minsize_attrs(context, function);
@@ -2895,10 +2958,11 @@ impl<'ctx> LlvmCodegen<'ctx> {
 
         let void_type = context.llvm().void_type();
         let function_type = void_type.fn_type(&[], false);
-        let function = context.module().add_function(
+        let function = add_outlined_function(
+            context,
             "__revive_callvalue_check",
             function_type,
-            Some(inkwell::module::Linkage::Internal),
+            "codegen",
         );
 
         add_noinline_minsize_attrs(context, function);
@@ -3335,6 +3399,7 @@ impl<'ctx> LlvmCodegen<'ctx> {
                     destination,
                     offset,
                     length,
+                    ..
                 } => {
                     Self::mark_if_callvalue(destination.id.0, callvalue_ids, used);
                     Self::mark_if_callvalue(offset.id.0, callvalue_ids, used);
@@ -3345,6 +3410,7 @@ impl<'ctx> LlvmCodegen<'ctx> {
                     destination,
                     offset,
                     length,
+                    ..
                 } => {
                     Self::mark_if_callvalue(address.id.0, callvalue_ids, used);
                     Self::mark_if_callvalue(destination.id.0, callvalue_ids, used);
@@ -3679,7 +3745,8 @@ impl<'ctx> LlvmCodegen<'ctx> {
         }
 
         for function in object.functions.values() {
-            self.generate_function(function, context)?;
+            self.generate_function(function, context)
+                .map_err(|error| error.located(function.location))?;
         }
 
         let function_name = if is_runtime {
@@ -3693,7 +3760,8 @@ impl<'ctx> LlvmCodegen<'ctx> {
             .map_err(|error| CodegenError::Llvm(error.to_string()))?;
         context.set_basic_block(context.current_function().borrow().entry_block());
 
-        self.generate_block(&object.code, context)?;
+        self.generate_block(&object.code, context)
+            .map_err(|error| error.located(object.location))?;
 
         context
             .set_debug_location(0, 0, None)
@@ -5647,11 +5715,14 @@ impl<'ctx> LlvmCodegen<'ctx> {
                 ret_offset,
                 ret_length,
                 result,
+                location,
             } => {
                 if matches!(kind, CallKind::CallCode) {
-                    return Err(CodegenError::Unsupported(
-                        "The `CALLCODE` instruction is not supported".into(),
-                    ));
+                    return Err(CodegenError::Coded(CodedError::new(
+                        ErrorCode::CallCode,
+                        "The `CALLCODE` instruction is not supported",
+                    ))
+                    .located(*location));
                 }
 
                 let gas_value = self.translate_value(gas)?.into_int_value();
@@ -5856,14 +5927,17 @@ impl<'ctx> LlvmCodegen<'ctx> {
                 destination,
                 offset,
                 length,
+                location,
             } => {
                 if matches!(
                     context.code_type(),
                     Some(revive_llvm_context::PolkaVMCodeType::Runtime)
                 ) {
-                    return Err(CodegenError::Unsupported(
-                        "The `CODECOPY` instruction is not supported in the runtime code".into(),
-                    ));
+                    return Err(CodegenError::Coded(CodedError::new(
+                        ErrorCode::RuntimeCodeCopy,
+                        "The `CODECOPY` instruction is not supported in the runtime code",
+                    ))
+                    .located(*location));
                 }
                 let destination_value = self.translate_value(destination)?.into_int_value();
                 let destination_value = self.narrow_offset_for_pointer(
@@ -5894,10 +5968,12 @@ impl<'ctx> LlvmCodegen<'ctx> {
                 )?;
             }
 
-            Statement::ExtCodeCopy { .. } => {
-                return Err(CodegenError::Unsupported(
-                    "The `EXTCODECOPY` instruction is not supported".into(),
-                ));
+            Statement::ExtCodeCopy { location, .. } => {
+                return Err(CodegenError::Coded(CodedError::new(
+                    ErrorCode::ExtCodeCopy,
+                    "The `EXTCODECOPY` instruction is not supported",
+                ))
+                .located(*location));
             }
 
             Statement::ReturnDataCopy {
@@ -7196,11 +7272,7 @@ impl<'ctx> LlvmCodegen<'ctx> {
         let word_type = context.word_type();
         let function_type = word_type.fn_type(&[word_type.into()], false);
 
-        let wrapper_fn = context.module().add_function(
-            &wrapper_name,
-            function_type,
-            Some(inkwell::module::Linkage::Internal),
-        );
+        let wrapper_fn = add_outlined_function(context, &wrapper_name, function_type, "mem_opt");
 
         let noinline_attribute = context
             .llvm()
@@ -1201,6 +1201,7 @@ impl TypeInference {
                 destination,
                 offset,
                 length,
+                ..
             } => {
                 self.record_zero_filling_copy_uses(destination, offset, length);
             }
@@ -1601,6 +1602,7 @@ impl TypeInference {
                 destination,
                 offset,
                 length,
+                ..
             }
             | Statement::ExtCodeCopy {
                 destination,
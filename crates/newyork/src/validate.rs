@@ -527,6 +527,7 @@ impl Validator {
                 destination,
                 offset,
                 length,
+                ..
             } => {
                 self.use_value(destination, context);
                 self.use_value(offset, context);
@@ -538,6 +539,7 @@ impl Validator {
                 destination,
                 offset,
                 length,
+                ..
             } => {
                 self.use_value(address, context);
                 self.use_value(destination, context);
@@ -730,7 +732,6 @@ mod tests {
     #[test]
     fn test_valid_let() {
         let object = Object {
-            name: "Test".to_string(),
             code: Block {
                 statements: vec![
                     Statement::Let {
@@ -746,9 +747,7 @@ mod tests {
                     },
                 ],
             },
-            functions: BTreeMap::new(),
-            subobjects: Vec::new(),
-            data: BTreeMap::new(),
+            ..Object::new("Test".to_string())
         };
         assert!(validate_object(&object).is_ok());
     }
@@ -756,16 +755,13 @@ mod tests {
     #[test]
     fn test_use_before_def() {
         let object = Object {
-            name: "Test".to_string(),
             code: Block {
                 statements: vec![Statement::Let {
                     bindings: vec![ValueId(1)],
                     value: Expression::Var(ValueId(0)),
                 }],
             },
-            functions: BTreeMap::new(),
-            subobjects: Vec::new(),
-            data: BTreeMap::new(),
+            ..Object::new("Test".to_string())
         };
         let result = validate_object(&object);
         assert!(result.is_err());
@@ -778,7 +774,6 @@ mod tests {
     #[test]
     fn test_if_yield_mismatch() {
         let object = Object {
-            name: "Test".to_string(),
             code: Block {
                 statements: vec![
                     Statement::Let {
@@ -803,9 +798,7 @@ mod tests {
                     },
                 ],
             },
-            functions: BTreeMap::new(),
-            subobjects: Vec::new(),
-            data: BTreeMap::new(),
+            ..Object::new("Test".to_string())
         };
         let result = validate_object(&object);
         assert!(result.is_err());
@@ -821,8 +814,6 @@ mod tests {
         functions.insert(
             FunctionId(0),
             Function {
-                id: FunctionId(0),
-                name: "add_one".to_string(),
                 parameters: vec![(ValueId(0), Type::Int(BitWidth::I256))],
                 returns: vec![Type::Int(BitWidth::I256)],
                 return_values_initial: vec![ValueId(1)],
@@ -837,17 +828,13 @@ mod tests {
                         },
                     }],
                 },
-                call_count: 0,
-                size_estimate: 0,
+                ..Function::new(FunctionId(0), "add_one".to_string())
             },
         );
 
         let object = Object {
-            name: "Test".to_string(),
-            code: Block { statements: vec![] },
             functions,
-            subobjects: Vec::new(),
-            data: BTreeMap::new(),
+            ..Object::new("Test".to_string())
         };
         assert!(validate_object(&object).is_ok());
     }
@@ -858,24 +845,16 @@ mod tests {
         functions.insert(
             FunctionId(0),
             Function {
-                id: FunctionId(0),
-                name: "bad".to_string(),
-                parameters: vec![],
                 returns: vec![Type::Int(BitWidth::I256), Type::Int(BitWidth::I256)],
                 return_values_initial: vec![ValueId(0)],
                 return_values: vec![ValueId(0)],
-                body: Block { statements: vec![] },
-                call_count: 0,
-                size_estimate: 0,
+                ..Function::new(FunctionId(0), "bad".to_string())
             },
         );
 
         let object = Object {
-            name: "Test".to_string(),
-            code: Block { statements: vec![] },
             functions,
-            subobjects: Vec::new(),
-            data: BTreeMap::new(),
+            ..Object::new("Test".to_string())
         };
         let result = validate_object(&object);
         assert!(result.is_err());
@@ -1,11 +1,59 @@
+use std::path::Path;
+
+/// The workspace paths whose changes affect the commit or the uncommitted changes.
+const WATCHED_PATHS: [&str; 4] = [
+    "../../crates",
+    "../../Cargo.lock",
+    "../../.git/HEAD",
+    "../../.git/index",
+];
+
 fn main() {
+    for path in WATCHED_PATHS {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+
     match git2::Repository::open("../..") {
         Ok(repo) => {
             let head = repo.head().expect("should have head");
             let commit = head.peel_to_commit().expect("should have commit");
             let id = &commit.id().to_string()[..7];
             println!("cargo:rustc-env=GIT_COMMIT_HASH={id}");
+            println!(
+                "cargo:rustc-env=GIT_DIRTY_HASH={}",
+                dirty_hash(&repo, &commit).unwrap_or_default()
+            );
+        }
+        Err(_) => {
+            println!("cargo:rustc-env=GIT_COMMIT_HASH=unknown");
+            println!("cargo:rustc-env=GIT_DIRTY_HASH=");
         }
-        Err(_) => println!("cargo:rustc-env=GIT_COMMIT_HASH=unknown"),
     };
 }
+
+/// Returns the FNV-1a hash of the uncommitted changes to the `commit`, including the
+/// untracked files, or `None` if there are none.
+fn dirty_hash(repo: &git2::Repository, commit: &git2::Commit) -> Option<String> {
+    let mut options = git2::DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&commit.tree().ok()?), Some(&mut options))
+        .ok()?;
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut is_dirty = false;
+    diff.print(git2::DiffFormat::Patch, |_, _, line| {
+        is_dirty = true;
+        for byte in std::iter::once(line.origin() as u8).chain(line.content().iter().copied()) {
+            hash = (hash ^ byte as u64).wrapping_mul(0x100000001b3);
+        }
+        true
+    })
+    .ok()?;
+    is_dirty.then(|| format!("{hash:016x}"))
+}
@@ -0,0 +1,111 @@
+//! The PVM assembly annotated with the code it was generated from.
+//!
+//! Like the size report, the annotations are computed from a blob linked
+//! without stripping, which keeps the function symbols and, if source debug
+//! information is emitted, the Yul line and column of every instruction.
+//! The Yul locations are mapped back to the Solidity code through the `@src`
+//! annotations of the solc Yul output.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use polkavm_common::program::ProgramBlob;
+use polkavm_common::program::ProgramCounter;
+use revive_solc_json_interface::standard_json::output::error::source_location::SourceLocation;
+use revive_solc_json_interface::SolcStandardJsonOutputContractPolkaVMAnnotatedInstruction as AnnotatedInstruction;
+use revive_solc_json_interface::SolcStandardJsonOutputContractPolkaVMYulLocation as YulLocation;
+use revive_yul::lexer::token::location::Location;
+use revive_yul::source_map::SourceMap;
+
+use crate::build::size_report::function_name;
+
+/// The innermost frame of a debug information region.
+struct Frame {
+    /// The function name.
+    function: String,
+    /// The Yul code location, if source debug information is emitted.
+    location: Option<YulLocation>,
+}
+
+/// Annotates every instruction of the `unstripped` blob with its function, the pass
+/// of the `outlined_functions` and the Yul and Solidity code locations resolved
+/// with the `source_map`.
+pub fn annotate(
+    unstripped: &[u8],
+    source_map: &SourceMap,
+    outlined_functions: &BTreeMap<String, String>,
+) -> anyhow::Result<Vec<AnnotatedInstruction>> {
+    let blob = ProgramBlob::parse(unstripped.to_vec().into()).map_err(anyhow::Error::msg)?;
+
+    let mut instructions = Vec::new();
+    let mut region: Option<(Range<ProgramCounter>, Option<Frame>)> = None;
+    for instruction in blob.instructions() {
+        if !region
+            .as_ref()
+            .is_some_and(|(range, _)| range.contains(&instruction.offset))
+        {
+            region = frame_at(&blob, instruction.offset)?;
+        }
+        let frame = region.as_ref().and_then(|(_, frame)| frame.as_ref());
+
+        let yul_location = frame.and_then(|frame| frame.location);
+        let source_location = yul_location
+            .and_then(|location| source_map.resolve(Location::new(location.line, location.column)))
+            .map(|annotation| {
+                SourceLocation::new_with_offsets(
+                    annotation.file.to_owned(),
+                    annotation.start,
+                    annotation.end,
+                )
+            });
+        instructions.push(AnnotatedInstruction {
+            offset: instruction.offset.0,
+            instruction: instruction.kind.to_string(),
+            function: frame.map(|frame| frame.function.to_owned()),
+            pass: frame
+                .and_then(|frame| outlined_functions.get(&frame.function))
+                .cloned(),
+            yul_location,
+            source_location,
+        });
+    }
+    Ok(instructions)
+}
+
+/// The instruction range and the innermost frame of the region containing `program_counter`.
+fn frame_at(
+    blob: &ProgramBlob,
+    program_counter: ProgramCounter,
+) -> anyhow::Result<Option<(Range<ProgramCounter>, Option<Frame>)>> {
+    let Some(mut program) = blob
+        .get_debug_line_program_at(program_counter)
+        .map_err(anyhow::Error::msg)?
+    else {
+        return Ok(None);
+    };
+    while let Some(region) = program.run().map_err(anyhow::Error::msg)? {
+        if !region.instruction_range().contains(&program_counter) {
+            continue;
+        }
+        let Some(frame) = region.frames().last() else {
+            return Ok(Some((region.instruction_range(), None)));
+        };
+        let function = function_name(&frame.full_name().map_err(anyhow::Error::msg)?.to_string());
+        if function.is_empty() {
+            return Ok(Some((region.instruction_range(), None)));
+        }
+        // Synthetic code, like the implicit branches to the return block, is at line 0.
+        let location = frame
+            .line()
+            .filter(|line| *line > 0)
+            .map(|line| YulLocation {
+                line,
+                column: frame.column().unwrap_or(1).max(1),
+            });
+        return Ok(Some((
+            region.instruction_range(),
+            Some(Frame { function, location }),
+        )));
+    }
+    Ok(None)
+}
@@ -13,12 +13,21 @@ use revive_common::BYTE_LENGTH_WORD;
 use revive_common::EXTENSION_JSON;
 use revive_common::EXTENSION_POLKAVM_ASSEMBLY;
 use revive_common::EXTENSION_POLKAVM_BINARY;
+use revive_common::EXTENSION_SIZE_REPORT;
 use revive_llvm_context::PolkaVMBuild;
 use revive_solc_json_interface::CombinedJsonContract;
 use revive_solc_json_interface::SolcStandardJsonOutputContract;
+use revive_solc_json_interface::SolcStandardJsonOutputContractPolkaVMAnnotatedInstruction;
+use revive_solc_json_interface::SolcStandardJsonOutputContractPolkaVMImmutableDeclaration;
+use revive_solc_json_interface::SolcStandardJsonOutputContractPolkaVMImmutableLayout;
+use revive_solc_json_interface::SolcStandardJsonOutputError;
+use revive_yul::source_map::SourceMap;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::build::gas_estimates::WeightEstimates;
+use crate::build::size_report::SizeReport;
+
 /// The Solidity contract build.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Contract {
@@ -36,6 +45,17 @@ pub struct Contract {
     pub factory_dependencies_resolved: BTreeMap<[u8; BYTE_LENGTH_WORD], String>,
     /// The binary object format.
     pub object_format: ObjectFormat,
+    /// The code size attribution report, if requested and linked.
+    pub size_report: Option<SizeReport>,
+    /// The weight estimates, if requested and linked.
+    pub weight_estimates: Option<WeightEstimates>,
+    /// The Solidity source map of the Yul code, if the annotated assembly is requested.
+    pub source_map: Option<SourceMap>,
+    /// The annotated assembly, if requested and linked.
+    pub annotated_assembly: Option<Vec<SolcStandardJsonOutputContractPolkaVMAnnotatedInstruction>>,
+    /// The compilation warnings and errors, like exceeded memory budgets.
+    #[serde(default)]
+    pub messages: Vec<SolcStandardJsonOutputError>,
 }
 
 impl Contract {
@@ -47,6 +67,8 @@ impl Contract {
         missing_libraries: BTreeSet<String>,
         factory_dependencies: BTreeSet<String>,
         object_format: ObjectFormat,
+        source_map: Option<SourceMap>,
+        messages: Vec<SolcStandardJsonOutputError>,
     ) -> Self {
         Self {
             identifier,
@@ -56,6 +78,11 @@ impl Contract {
             factory_dependencies,
             factory_dependencies_resolved: BTreeMap::new(),
             object_format,
+            size_report: None,
+            weight_estimates: None,
+            source_map,
+            annotated_assembly: None,
+            messages,
         }
     }
 
@@ -85,6 +112,9 @@ impl Contract {
                 hex::encode(self.build.bytecode)
             )?;
         }
+        if let Some(size_report) = self.size_report {
+            writeln!(std::io::stdout(), "Size report:\n{size_report}")?;
+        }
 
         Ok(())
     }
@@ -155,6 +185,23 @@ impl Contract {
                 .map_err(|error| anyhow::anyhow!("File {file_path:?} writing error: {error}"))?;
         }
 
+        if let Some(size_report) = self.size_report {
+            let file_path = output_path.join(format!(
+                "{file_name}:{}.{EXTENSION_SIZE_REPORT}",
+                self.identifier.name.as_deref().unwrap_or(file_name),
+            ));
+            if file_path.exists() && !overwrite {
+                anyhow::bail!(
+                    "Refusing to overwrite an existing file {file_path:?} (use --overwrite to force)."
+                );
+            }
+            std::fs::write(
+                file_path.as_path(),
+                serde_json::to_vec(&size_report).expect("Always valid"),
+            )
+            .map_err(|error| anyhow::anyhow!("File {file_path:?} writing: {error}"))?;
+        }
+
         Ok(())
     }
 
@@ -192,18 +239,39 @@ impl Contract {
     }
 
     /// Writes the contract text assembly and bytecode to the standard JSON.
+    ///
+    /// The immutable layout variables are resolved using the AST `immutable_declarations`.
     pub fn write_to_standard_json(
         self,
         standard_json_contract: &mut SolcStandardJsonOutputContract,
+        immutable_declarations: &BTreeMap<
+            usize,
+            SolcStandardJsonOutputContractPolkaVMImmutableDeclaration,
+        >,
     ) -> anyhow::Result<()> {
         let bytecode = hex::encode(self.build.bytecode.as_slice());
         let assembly_text = self.build.assembly_text.unwrap_or_default();
 
         standard_json_contract.metadata = self.metadata_json;
-        standard_json_contract
+        let evm = standard_json_contract
             .evm
-            .get_or_insert_with(Default::default)
-            .modify(assembly_text, bytecode);
+            .get_or_insert_with(Default::default);
+        evm.modify(assembly_text.clone(), bytecode);
+        if let Some(weight_estimates) = self.weight_estimates {
+            evm.gas_estimates = Some(weight_estimates.gas_estimates(&evm.method_identifiers));
+        }
+        let polkavm = standard_json_contract
+            .polkavm
+            .get_or_insert_with(Default::default);
+        polkavm.immutable_layout = Some(SolcStandardJsonOutputContractPolkaVMImmutableLayout::new(
+            &self.build.immutables,
+            immutable_declarations,
+        ));
+        polkavm.newyork_ir = self.build.newyork_ir;
+        polkavm.llvm_ir = self.build.llvm_ir;
+        polkavm.llvm_ir_optimized = self.build.llvm_ir_optimized;
+        polkavm.assembly = Some(assembly_text);
+        polkavm.annotated_assembly = self.annotated_assembly;
         standard_json_contract.hash = self.build.bytecode_hash.map(hex::encode);
         standard_json_contract
             .missing_libraries
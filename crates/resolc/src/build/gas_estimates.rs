@@ -0,0 +1,671 @@
+//! The PVM weight estimates.
+//!
+//! pallet-revive meters PVM code with one fuel per executed instruction and charges
+//! host function calls by their benchmarked weights. The estimates are the weights
+//! of the most expensive paths through the control flow graph of the blob, which
+//! bound the weights charged by the [`PALLET_REVIVE_VERSION`] of the pallet. Code
+//! which can't be bounded statically, like loops, recursion, indirect jumps, calls
+//! to other contracts or host functions copying buffers whose length isn't loaded
+//! as an immediate right before the call, makes an estimate infinite.
+//!
+//! The analysis uses the function information of a blob linked without stripping to
+//! tell external functions apart: the estimate of an external function excludes the
+//! code of all other external functions. Code inlined by LLVM is only attributed to
+//! its function if source debug information is emitted, otherwise it is included in
+//! the estimates of all external functions.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use polkavm_common::program::Instruction;
+use polkavm_common::program::ParsedInstruction;
+use polkavm_common::program::ProgramBlob;
+use polkavm_common::program::ProgramCounter;
+use polkavm_common::program::Reg;
+use revive_runtime_api::polkavm_imports;
+use revive_solc_json_interface::SolcStandardJsonOutputContractEVMGasEstimates as GasEstimates;
+use revive_solc_json_interface::SolcStandardJsonOutputContractEVMGasEstimatesCodeDeposit as CodeDeposit;
+use revive_solc_json_interface::SolcStandardJsonOutputContractEVMGasEstimatesCreation as Creation;
+use revive_solc_json_interface::SolcStandardJsonOutputContractEVMWeight as Weight;
+use revive_solc_json_interface::SolcStandardJsonOutputContractEVMWeightEstimate as WeightEstimate;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::build::size_report::functions_at;
+
+/// The name prefix solc uses for the ABI wrappers of external functions.
+const EXTERNAL_FUNCTION_PREFIX: &str = "external_fun_";
+
+/// The `pallet-revive` version the weights and limits are taken from.
+///
+/// The weights are its `SubstrateWeight` benchmark results with the reference
+/// `RocksDbWeight` database weights. They must be derived again when the pallet
+/// the runner is built against changes.
+pub const PALLET_REVIVE_VERSION: &str = "0.18.0";
+
+/// The `ref_time` of a database read.
+const DB_READ: u64 = 25_000_000;
+
+/// The `ref_time` of a database write.
+const DB_WRITE: u64 = 100_000_000;
+
+/// The storage value size charged before accessing the storage.
+const STORAGE_BYTES: u64 = 416;
+
+/// The maximum size of the immutable data.
+const IMMUTABLE_BYTES: u64 = 4 * 1024;
+
+/// The weight of executing one instruction: `instr`.
+const INSTRUCTION: Weight = Weight::new(105_828, 0);
+
+/// The weight of a call transaction, without loading the code: `call`.
+const CALL: Weight = weight(106_215_000, 7_653, 8, 2);
+
+/// The weight of loading one code byte for a call: `call_with_pvm_code_per_byte`.
+const CALL_PER_CODE_BYTE: Weight = Weight::new(1_425, 1);
+
+/// The weight of compiling the code when it is loaded: `basic_block_compilation`.
+const CODE_COMPILATION: Weight = Weight::new(875_548, 0);
+
+/// The weight of a deployment transaction, without uploading the code and the
+/// constructor input: `instantiate_with_code`.
+const INSTANTIATE_WITH_CODE: Weight = weight(124_887_027, 6_927, 9, 6);
+
+/// The weight of uploading one code byte for a deployment: `instantiate_with_code`.
+const INSTANTIATE_WITH_CODE_PER_CODE_BYTE: Weight = Weight::new(20_158, 0);
+
+/// The maximum encoded size of the code information stored alongside the code,
+/// for 32 byte account IDs and 128 bit balances.
+const CODE_INFO_BYTES: u64 = 67;
+
+/// The storage items of the code deposit: the code and its information.
+const CODE_DEPOSIT_ITEMS: u64 = 2;
+
+/// The weight of a host function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostFunction {
+    /// The weight doesn't depend on the arguments.
+    Fixed(Weight),
+    /// The weight grows with the length passed in the argument `register`.
+    PerByte {
+        /// The weight of an empty buffer.
+        base: Weight,
+        /// The weight of every buffer byte.
+        per_byte: Weight,
+        /// The argument register with the buffer length.
+        register: Reg,
+    },
+    /// The weight can't be bounded statically, like calls to other contracts.
+    Unbounded,
+}
+
+/// Returns the weight with the benchmarked `ref_time` and `proof_size`
+/// and the `reads` and `writes` of the database.
+const fn weight(ref_time: u64, proof_size: u64, reads: u64, writes: u64) -> Weight {
+    Weight::new(ref_time + reads * DB_READ + writes * DB_WRITE, proof_size)
+}
+
+/// Returns the weight of the host function imported as `name`, or `None` if the
+/// pallet doesn't provide it.
+///
+/// Every host function is charged the `RuntimeCosts` of its `pallet-revive`
+/// implementation, which are named in the comments. Storage accesses are charged
+/// for the largest value before the actual value is known, which bounds them.
+pub fn host_function(name: &str) -> Option<HostFunction> {
+    use HostFunction::*;
+
+    [
+        // seal_address
+        (polkavm_imports::ADDRESS, Fixed(weight(370_000, 0, 0, 0))),
+        // seal_balance
+        (polkavm_imports::BALANCE, Fixed(weight(14_109_000, 0, 0, 0))),
+        // seal_balance_of
+        (
+            polkavm_imports::BALANCE_OF,
+            Fixed(weight(21_408_000, 4_349, 3, 0)),
+        ),
+        // seal_base_fee
+        (polkavm_imports::BASE_FEE, Fixed(weight(1_181_000, 0, 0, 0))),
+        // seal_block_author
+        (
+            polkavm_imports::BLOCK_AUTHOR,
+            Fixed(weight(22_294_000, 1_627, 1, 0)),
+        ),
+        // seal_block_hash
+        (
+            polkavm_imports::BLOCK_HASH,
+            Fixed(weight(8_112_000, 3_783, 1, 0)),
+        ),
+        // seal_block_number
+        (
+            polkavm_imports::BLOCK_NUMBER,
+            Fixed(weight(317_000, 0, 0, 0)),
+        ),
+        // seal_call_data_copy(n)
+        (
+            polkavm_imports::CALL_DATA_COPY,
+            PerByte {
+                base: Weight::new(333_000, 0),
+                per_byte: Weight::new(150, 0),
+                register: Reg::A1,
+            },
+        ),
+        // seal_call_data_load
+        (
+            polkavm_imports::CALL_DATA_LOAD,
+            Fixed(weight(310_000, 0, 0, 0)),
+        ),
+        // seal_call_data_size
+        (
+            polkavm_imports::CALL_DATA_SIZE,
+            Fixed(weight(312_000, 0, 0, 0)),
+        ),
+        // seal_caller
+        (polkavm_imports::CALLER, Fixed(weight(383_000, 0, 0, 0))),
+        // seal_copy_to_contract(32)
+        (
+            polkavm_imports::CHAIN_ID,
+            Fixed(weight(554_000 + 240 * 32, 0, 0, 0)),
+        ),
+        // seal_code_hash
+        (
+            polkavm_imports::CODE_HASH,
+            Fixed(weight(10_468_000, 3_938, 1, 0)),
+        ),
+        // seal_code_size
+        (
+            polkavm_imports::CODE_SIZE,
+            Fixed(weight(14_017_000, 4_010, 2, 0)),
+        ),
+        // seal_deposit_event(t, n) and on_finalize_block_per_event(n), plus the
+        // extra event charge of 256 KiB ref_time per byte
+        (
+            polkavm_imports::DEPOSIT_EVENT,
+            PerByte {
+                base: Weight::new(5_427_000 + 213, 0),
+                per_byte: Weight::new(1_418 + 7 + 256 * 1024, 0),
+                register: Reg::A3,
+            },
+        ),
+        // seal_gas_limit
+        (polkavm_imports::GAS_LIMIT, Fixed(weight(321_000, 0, 0, 0))),
+        // seal_gas_price
+        (
+            polkavm_imports::GAS_PRICE,
+            Fixed(weight(1_185_000, 0, 0, 0)),
+        ),
+        // seal_get_immutable_data(IMMUTABLE_BYTES)
+        (
+            polkavm_imports::GET_IMMUTABLE_DATA,
+            Fixed(weight(
+                8_660_459 + 610 * IMMUTABLE_BYTES,
+                3_769 + IMMUTABLE_BYTES,
+                1,
+                0,
+            )),
+        ),
+        // seal_get_storage(STORAGE_BYTES) plus get_storage_full - get_storage_empty,
+        // which exceeds the transient storage costs
+        (
+            polkavm_imports::GET_STORAGE,
+            Fixed(weight(
+                10_896_755 + 3_542 * STORAGE_BYTES + (40_501_000 - 9_726_000),
+                247 + STORAGE_BYTES + (10_658 - 648),
+                1,
+                0,
+            )),
+        ),
+        // seal_hash_keccak_256(n)
+        (
+            polkavm_imports::HASH_KECCAK_256,
+            PerByte {
+                base: Weight::new(18_184_835, 0),
+                per_byte: Weight::new(3_577, 0),
+                register: Reg::A1,
+            },
+        ),
+        // seal_now
+        (polkavm_imports::NOW, Fixed(weight(317_000, 0, 0, 0))),
+        // seal_origin
+        (polkavm_imports::ORIGIN, Fixed(weight(351_000, 0, 0, 0))),
+        // seal_ref_time_left
+        (
+            polkavm_imports::REF_TIME_LEFT,
+            Fixed(weight(2_176_000, 0, 0, 0)),
+        ),
+        // seal_return(n)
+        (
+            polkavm_imports::RETURN,
+            PerByte {
+                base: Weight::new(541_104, 0),
+                per_byte: Weight::new(237, 0),
+                register: Reg::A2,
+            },
+        ),
+        // seal_return_data_size
+        (
+            polkavm_imports::RETURNDATASIZE,
+            Fixed(weight(301_000, 0, 0, 0)),
+        ),
+        // seal_set_immutable_data(n)
+        (
+            polkavm_imports::SET_IMMUTABLE_DATA,
+            PerByte {
+                base: weight(2_314_753, 0, 0, 1),
+                per_byte: Weight::new(546, 0),
+                register: Reg::A1,
+            },
+        ),
+        // The larger of clear_storage(n) and seal_set_storage(32, STORAGE_BYTES) plus
+        // set_storage_full - set_storage_empty, which exceeds the transient storage costs
+        (
+            polkavm_imports::SET_STORAGE,
+            Fixed(weight(
+                13_951_002 + (42_487_000 - 11_006_000),
+                247 + STORAGE_BYTES + (10_658 - 648),
+                1,
+                1,
+            )),
+        ),
+        // seal_terminate(1) and seal_terminate_logic
+        (
+            polkavm_imports::TERMINATE,
+            Fixed(weight(21_026_718 + 233_055_000, 6_825 + 7_439, 9, 9)),
+        ),
+        // seal_value_transferred
+        (
+            polkavm_imports::VALUE_TRANSFERRED,
+            Fixed(weight(320_000, 0, 0, 0)),
+        ),
+        // The copied length depends on the return data, the calls and instantiations
+        // on the called code, and `consume_all_gas` on the gas limit.
+        (polkavm_imports::RETURNDATACOPY, Unbounded),
+        (polkavm_imports::CALL, Unbounded),
+        (polkavm_imports::DELEGATE_CALL, Unbounded),
+        (polkavm_imports::INSTANTIATE, Unbounded),
+        (polkavm_imports::INVALID, Unbounded),
+    ]
+    .into_iter()
+    .find_map(|(import, host_function)| (import == name).then_some(host_function))
+}
+
+/// The weight bound of a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    /// The path is excluded from the estimate.
+    Excluded,
+    /// The weight upper bound.
+    Finite(Weight),
+    /// The weight can't be bounded.
+    Infinite,
+}
+
+impl Bound {
+    /// The bound of executing `self` followed by `other`.
+    fn then(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Excluded, _) | (_, Self::Excluded) => Self::Excluded,
+            (Self::Infinite, _) | (_, Self::Infinite) => Self::Infinite,
+            (Self::Finite(first), Self::Finite(second)) => {
+                Self::Finite(first.saturating_add(second))
+            }
+        }
+    }
+
+    /// The bound of executing either `self` or `other`.
+    fn or(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Excluded, bound) | (bound, Self::Excluded) => bound,
+            (Self::Infinite, _) | (_, Self::Infinite) => Self::Infinite,
+            (Self::Finite(first), Self::Finite(second)) => Self::Finite(Weight::new(
+                first.ref_time.max(second.ref_time),
+                first.proof_size.max(second.proof_size),
+            )),
+        }
+    }
+}
+
+impl From<Bound> for WeightEstimate {
+    fn from(bound: Bound) -> Self {
+        match bound {
+            Bound::Finite(weight) => Self::Bounded(weight),
+            Bound::Excluded | Bound::Infinite => Self::Infinite,
+        }
+    }
+}
+
+/// The control flow step of an instruction.
+#[derive(Debug, Default)]
+struct Step {
+    /// The weight of the instruction, `None` if it can't be bounded.
+    weight: Option<Weight>,
+    /// The instruction called before continuing with the successors.
+    call: Option<usize>,
+    /// The instructions possibly executed next.
+    successors: Vec<usize>,
+}
+
+/// The control flow graph of a blob.
+struct ControlFlow {
+    /// The step of every instruction.
+    steps: Vec<Step>,
+    /// The Solidity name of the external function every instruction belongs to.
+    external_functions: Vec<Option<String>>,
+}
+
+impl ControlFlow {
+    /// Builds the control flow graph of the `blob`.
+    fn new(blob: &ProgramBlob) -> anyhow::Result<Self> {
+        let instructions = blob.instructions().collect::<Vec<_>>();
+        let index_of = |target: u32| {
+            instructions
+                .binary_search_by_key(&target, |instruction| instruction.offset.0)
+                .ok()
+        };
+        let imports = blob.imports();
+
+        let mut steps = Vec::with_capacity(instructions.len());
+        for (index, instruction) in instructions.iter().enumerate() {
+            let next = (index + 1 < instructions.len()).then_some(index + 1);
+            let mut step = Step {
+                weight: Some(INSTRUCTION),
+                ..Default::default()
+            };
+            match instruction.kind {
+                Instruction::trap => {}
+                Instruction::jump_indirect(base, 0) if base.get() == Reg::RA => {}
+                Instruction::jump_indirect(..)
+                | Instruction::load_imm_and_jump_indirect(..)
+                | Instruction::memset
+                | Instruction::invalid => step.weight = None,
+                Instruction::ecalli(import) => {
+                    let host_function = imports.get(import as u32).and_then(|symbol| {
+                        std::str::from_utf8(symbol.as_bytes())
+                            .ok()
+                            .and_then(host_function)
+                    });
+                    let weight = match host_function {
+                        Some(HostFunction::Fixed(weight)) => Some(weight),
+                        Some(HostFunction::PerByte {
+                            base,
+                            per_byte,
+                            register,
+                        }) => immediate(&instructions[..index], register)
+                            .map(|length| base.saturating_add(per_byte.saturating_mul(length))),
+                        Some(HostFunction::Unbounded) | None => None,
+                    };
+                    step.weight = weight.map(|weight| weight.saturating_add(INSTRUCTION));
+                    step.successors.extend(next);
+                }
+                Instruction::jump(target) => match index_of(target) {
+                    Some(target) => step.successors.push(target),
+                    None => step.weight = None,
+                },
+                Instruction::load_imm_and_jump(register, _, target) => match index_of(target) {
+                    Some(target) if register.get() == Reg::RA => {
+                        step.call = Some(target);
+                        step.successors.extend(next);
+                    }
+                    Some(target) => step.successors.push(target),
+                    None => step.weight = None,
+                },
+                kind => {
+                    if let Some(target) = branch_target(kind) {
+                        match index_of(target) {
+                            Some(target) => step.successors.push(target),
+                            None => step.weight = None,
+                        }
+                    }
+                    step.successors.extend(next);
+                }
+            }
+            steps.push(step);
+        }
+
+        let mut external_functions = Vec::with_capacity(instructions.len());
+        let mut region: Option<(Range<ProgramCounter>, Option<String>)> = None;
+        for instruction in instructions.iter() {
+            if !region
+                .as_ref()
+                .is_some_and(|(range, _)| range.contains(&instruction.offset))
+            {
+                region = functions_at(blob, instruction.offset)?.map(|(range, names)| {
+                    let name = names
+                        .iter()
+                        .rev()
+                        .find_map(|name| external_function_name(name));
+                    (range, name)
+                });
+            }
+            external_functions.push(region.as_ref().and_then(|(_, name)| name.clone()));
+        }
+
+        Ok(Self {
+            steps,
+            external_functions,
+        })
+    }
+
+    /// The bound of the paths from the `entry` instruction, excluding the
+    /// code of external functions rejected by `include`.
+    fn bound(&self, entry: usize, include: impl Fn(&str) -> bool) -> Bound {
+        let mut bounds = vec![None; self.steps.len()];
+        let mut on_path = vec![false; self.steps.len()];
+        let mut cyclic = vec![false; self.steps.len()];
+
+        let mut stack = vec![(entry, false)];
+        while let Some((index, visited)) = stack.pop() {
+            if bounds[index].is_some() {
+                continue;
+            }
+            if visited {
+                on_path[index] = false;
+                bounds[index] = Some(self.step_bound(index, cyclic[index], &bounds));
+                continue;
+            }
+            if self.external_functions[index]
+                .as_deref()
+                .is_some_and(|name| !include(name))
+            {
+                bounds[index] = Some(Bound::Excluded);
+                continue;
+            }
+
+            on_path[index] = true;
+            stack.push((index, true));
+            let step = &self.steps[index];
+            for successor in step.call.iter().chain(step.successors.iter()).copied() {
+                if on_path[successor] {
+                    cyclic[index] = true;
+                } else if bounds[successor].is_none() {
+                    stack.push((successor, false));
+                }
+            }
+        }
+
+        bounds[entry].expect("Always computed")
+    }
+
+    /// The bound of the paths from the instruction at `index`, given the bounds of its successors.
+    fn step_bound(&self, index: usize, cyclic: bool, bounds: &[Option<Bound>]) -> Bound {
+        let step = &self.steps[index];
+        let Some(weight) = step.weight.filter(|_| !cyclic) else {
+            return Bound::Infinite;
+        };
+        let bound_of = |index: usize| bounds[index].unwrap_or(Bound::Infinite);
+
+        let mut bound = Bound::Finite(weight);
+        if let Some(callee) = step.call {
+            bound = bound.then(bound_of(callee));
+        }
+        let successors = step
+            .successors
+            .iter()
+            .map(|successor| bound_of(*successor))
+            .reduce(Bound::or);
+        match successors {
+            Some(successors) => bound.then(successors),
+            None => bound,
+        }
+    }
+}
+
+/// The PVM weight estimates of a contract.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightEstimates {
+    /// The final blob size in bytes.
+    pub blob: u64,
+    /// The constructor execution estimate.
+    pub deploy: WeightEstimate,
+    /// The estimates of the external functions by their Solidity name.
+    pub external: BTreeMap<String, WeightEstimate>,
+    /// The estimate of the whole call entry point, used for external functions
+    /// whose code can't be told apart.
+    pub call: WeightEstimate,
+}
+
+impl WeightEstimates {
+    /// Estimates the weights of the final `bytecode`, using the function
+    /// information of the same blob linked without stripping.
+    pub fn new(bytecode: &[u8], unstripped: &[u8]) -> anyhow::Result<Self> {
+        let blob = ProgramBlob::parse(unstripped.to_vec().into()).map_err(anyhow::Error::msg)?;
+        let control_flow = ControlFlow::new(&blob)?;
+
+        let entry = |symbol: &str| -> anyhow::Result<usize> {
+            let export = blob
+                .exports()
+                .find(|export| export.symbol().as_bytes() == symbol.as_bytes())
+                .ok_or_else(|| anyhow::anyhow!("The `{symbol}` export is missing"))?;
+            blob.instructions()
+                .position(|instruction| instruction.offset == export.program_counter())
+                .ok_or_else(|| anyhow::anyhow!("The `{symbol}` export is not an instruction"))
+        };
+        let deploy = entry(revive_runtime_api::polkavm_exports::DEPLOY)?;
+        let call = entry(revive_runtime_api::polkavm_exports::CALL)?;
+
+        let mut external = BTreeMap::new();
+        for name in control_flow.external_functions.iter().flatten() {
+            if external.contains_key(name) {
+                continue;
+            }
+            let bound = control_flow.bound(call, |other| other == name);
+            external.insert(name.to_owned(), bound.into());
+        }
+
+        Ok(Self {
+            blob: bytecode.len() as u64,
+            deploy: control_flow.bound(deploy, |_| true).into(),
+            external,
+            call: control_flow.bound(call, |_| true).into(),
+        })
+    }
+
+    /// Returns the standard JSON gas estimates of the external functions
+    /// with the `method_identifiers` signatures.
+    pub fn gas_estimates(&self, method_identifiers: &BTreeMap<String, String>) -> GasEstimates {
+        let call = CALL
+            .saturating_add(CODE_COMPILATION)
+            .saturating_add(CALL_PER_CODE_BYTE.saturating_mul(self.blob));
+        let external = method_identifiers
+            .keys()
+            .map(|signature| {
+                let name = signature.split('(').next().unwrap_or(signature);
+                let estimate = self.external.get(name).copied().unwrap_or(self.call);
+                (signature.to_owned(), estimate.saturating_add(call))
+            })
+            .collect();
+
+        let instantiate = INSTANTIATE_WITH_CODE
+            .saturating_add(CODE_COMPILATION)
+            .saturating_add(INSTANTIATE_WITH_CODE_PER_CODE_BYTE.saturating_mul(self.blob));
+        GasEstimates {
+            creation: Creation {
+                code_deposit: CodeDeposit {
+                    bytes: self.blob + CODE_INFO_BYTES,
+                    items: CODE_DEPOSIT_ITEMS,
+                },
+                execution_cost: self.deploy,
+                total_cost: self.deploy.saturating_add(instantiate),
+            },
+            external,
+        }
+    }
+}
+
+/// The Solidity name of the external function ABI wrapper `name`, like
+/// `transfer` for `external_fun_transfer_42`.
+fn external_function_name(name: &str) -> Option<String> {
+    let name = name.strip_prefix(EXTERNAL_FUNCTION_PREFIX)?;
+    match name.rsplit_once('_') {
+        Some((name, identifier))
+            if !name.is_empty() && identifier.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            Some(name.to_owned())
+        }
+        _ => Some(name.to_owned()),
+    }
+}
+
+/// The length the `register` is loaded with by the immediate loads at the end of
+/// the basic block of the `preceding` instructions, or `None` if it isn't known.
+fn immediate(preceding: &[ParsedInstruction], register: Reg) -> Option<u64> {
+    for instruction in preceding.iter().rev() {
+        if instruction.kind.starts_new_basic_block() {
+            return None;
+        }
+        match instruction.kind {
+            // The host functions take the length as a 32 bit argument.
+            Instruction::load_imm(destination, value) if destination.get() == register => {
+                return Some(value as u32 as u64)
+            }
+            Instruction::load_imm64(destination, value) if destination.get() == register => {
+                return Some(value as u32 as u64)
+            }
+            Instruction::load_imm(destination, _)
+            | Instruction::load_imm64(destination, _)
+            | Instruction::move_reg(destination, _)
+                if destination.get() != register => {}
+            Instruction::store_imm_u8(..)
+            | Instruction::store_imm_u16(..)
+            | Instruction::store_imm_u32(..)
+            | Instruction::store_imm_u64(..)
+            | Instruction::store_imm_indirect_u8(..)
+            | Instruction::store_imm_indirect_u16(..)
+            | Instruction::store_imm_indirect_u32(..)
+            | Instruction::store_imm_indirect_u64(..)
+            | Instruction::store_u8(..)
+            | Instruction::store_u16(..)
+            | Instruction::store_u32(..)
+            | Instruction::store_u64(..)
+            | Instruction::store_indirect_u8(..)
+            | Instruction::store_indirect_u16(..)
+            | Instruction::store_indirect_u32(..)
+            | Instruction::store_indirect_u64(..) => {}
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// The target of the branch `instruction`.
+fn branch_target(instruction: Instruction) -> Option<u32> {
+    match instruction {
+        Instruction::branch_eq_imm(_, _, target)
+        | Instruction::branch_not_eq_imm(_, _, target)
+        | Instruction::branch_less_unsigned_imm(_, _, target)
+        | Instruction::branch_less_signed_imm(_, _, target)
+        | Instruction::branch_greater_or_equal_unsigned_imm(_, _, target)
+        | Instruction::branch_greater_or_equal_signed_imm(_, _, target)
+        | Instruction::branch_less_or_equal_signed_imm(_, _, target)
+        | Instruction::branch_less_or_equal_unsigned_imm(_, _, target)
+        | Instruction::branch_greater_signed_imm(_, _, target)
+        | Instruction::branch_greater_unsigned_imm(_, _, target)
+        | Instruction::branch_eq(_, _, target)
+        | Instruction::branch_not_eq(_, _, target)
+        | Instruction::branch_less_unsigned(_, _, target)
+        | Instruction::branch_less_signed(_, _, target)
+        | Instruction::branch_greater_or_equal_unsigned(_, _, target)
+        | Instruction::branch_greater_or_equal_signed(_, _, target) => Some(target),
+        _ => None,
+    }
+}
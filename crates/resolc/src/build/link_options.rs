@@ -0,0 +1,19 @@
+//! The PVM build linking options.
+
+/// The reports computed for every linked contract.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LinkOptions {
+    /// Whether the code size is attributed to the functions.
+    pub size_report: bool,
+    /// Whether the weights are estimated.
+    pub weight_estimates: bool,
+    /// Whether the assembly is annotated.
+    pub annotated_assembly: bool,
+}
+
+impl LinkOptions {
+    /// Whether any report needs the function information of the unstripped blob.
+    pub fn needs_unstripped(&self) -> bool {
+        self.size_report || self.weight_estimates || self.annotated_assembly
+    }
+}
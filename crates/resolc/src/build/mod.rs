@@ -7,23 +7,34 @@ use std::path::PathBuf;
 
 use normpath::PathExt;
 
+use revive_common::ErrorCode;
 use revive_common::ObjectFormat;
 use revive_common::BYTE_LENGTH_ETH_ADDRESS;
 use revive_llvm_context::polkavm_disassemble;
 use revive_llvm_context::polkavm_hash;
 use revive_llvm_context::polkavm_link;
+use revive_llvm_context::polkavm_strip;
 use revive_llvm_context::DebugConfig;
 use revive_solc_json_interface::combined_json::CombinedJson;
 use revive_solc_json_interface::CombinedJsonContract;
 use revive_solc_json_interface::SolcStandardJsonOutput;
 use revive_solc_json_interface::SolcStandardJsonOutputContract;
+use revive_solc_json_interface::SolcStandardJsonOutputContractPolkaVMImmutableDeclaration;
 use revive_solc_json_interface::SolcStandardJsonOutputError;
 use revive_solc_json_interface::SolcStandardJsonOutputErrorHandler;
 
+use crate::build::annotated_assembly::annotate;
 use crate::build::contract::Contract;
+use crate::build::gas_estimates::WeightEstimates;
+use crate::build::link_options::LinkOptions;
+use crate::build::size_report::SizeReport;
 use crate::solc::version::Version as SolcVersion;
 
+pub mod annotated_assembly;
 pub mod contract;
+pub mod gas_estimates;
+pub mod link_options;
+pub mod size_report;
 
 /// The Solidity project PVM build.
 #[derive(Debug, Default)]
@@ -48,11 +59,12 @@ impl Build {
         }
     }
 
-    /// Links the PVM build.
+    /// Links the PVM build, computing the reports selected by the `options`.
     pub fn link(
         mut self,
         linker_symbols: BTreeMap<String, [u8; BYTE_LENGTH_ETH_ADDRESS]>,
         debug_config: &DebugConfig,
+        options: LinkOptions,
     ) -> Self {
         let mut contracts: BTreeMap<String, Contract> = self
             .results
@@ -66,49 +78,143 @@ impl Build {
                 .iter()
                 .filter(|(_path, contract)| contract.object_format == ObjectFormat::ELF)
             {
+                let factory_dependencies = contract
+                    .factory_dependencies
+                    .iter()
+                    .filter_map(|dependency| {
+                        let bytecode_hash = contracts
+                            .get(dependency)
+                            .as_ref()?
+                            .build
+                            .bytecode_hash
+                            .as_ref()?
+                            .to_owned();
+                        Some((dependency.to_owned(), bytecode_hash))
+                    })
+                    .collect();
+                // The reports need the function information of the unstripped blob, which
+                // is stripped afterwards instead of linking the contract twice.
+                let strip_binary = !debug_config.emit_debug_info;
+                let keep_unstripped = options.needs_unstripped();
                 match polkavm_link(
                     &contract.build.bytecode,
                     &linker_symbols,
-                    &contract
-                        .factory_dependencies
-                        .iter()
-                        .filter_map(|dependency| {
-                            let bytecode_hash = contracts
-                                .get(dependency)
-                                .as_ref()?
-                                .build
-                                .bytecode_hash
-                                .as_ref()?
-                                .to_owned();
-                            Some((dependency.to_owned(), bytecode_hash))
-                        })
-                        .collect(),
-                    !debug_config.emit_debug_info,
+                    &factory_dependencies,
+                    strip_binary && !keep_unstripped,
                 ) {
                     Ok((memory_buffer_linked, ObjectFormat::PVM)) => {
+                        let (memory_buffer_linked, unstripped) =
+                            match (keep_unstripped, strip_binary) {
+                                (false, _) => (memory_buffer_linked, None),
+                                (true, false) => {
+                                    (memory_buffer_linked.clone(), Some(memory_buffer_linked))
+                                }
+                                (true, true) => (
+                                    polkavm_strip(&memory_buffer_linked).unwrap_or_else(|error| {
+                                        panic!("ICE: Stripping the PVM blob failed: {error}")
+                                    }),
+                                    Some(memory_buffer_linked),
+                                ),
+                            };
                         let bytecode_hash = polkavm_hash(&memory_buffer_linked);
                         let assembly_text =
                             polkavm_disassemble(path, &memory_buffer_linked, debug_config)
                                 .unwrap_or_else(|error| {
                                     panic!("ICE: The PVM disassembler failed: {error}")
                                 });
+                        let report = match unstripped
+                            .as_ref()
+                            .filter(|_| options.size_report)
+                            .map(|unstripped| {
+                                SizeReport::new(
+                                    &memory_buffer_linked,
+                                    unstripped,
+                                    &contract.build.outlined_functions,
+                                )
+                            })
+                            .transpose()
+                        {
+                            Ok(report) => report,
+                            Err(error) => {
+                                self.messages.push(SolcStandardJsonOutputError::new_warning(
+                                    format!("{path} size report: {error}"),
+                                    None,
+                                    None,
+                                ));
+                                None
+                            }
+                        };
+                        let weight_estimates = match unstripped
+                            .as_ref()
+                            .filter(|_| options.weight_estimates)
+                            .map(|unstripped| {
+                                WeightEstimates::new(&memory_buffer_linked, unstripped)
+                            })
+                            .transpose()
+                        {
+                            Ok(weight_estimates) => weight_estimates,
+                            Err(error) => {
+                                self.messages.push(SolcStandardJsonOutputError::new_warning(
+                                    format!("{path} weight estimates: {error}"),
+                                    None,
+                                    None,
+                                ));
+                                None
+                            }
+                        };
+                        let annotated_assembly = match unstripped
+                            .as_ref()
+                            .filter(|_| options.annotated_assembly)
+                            .zip(contract.source_map.as_ref())
+                            .map(|(unstripped, source_map)| {
+                                annotate(unstripped, source_map, &contract.build.outlined_functions)
+                            })
+                            .transpose()
+                        {
+                            Ok(annotated_assembly) => annotated_assembly,
+                            Err(error) => {
+                                self.messages.push(SolcStandardJsonOutputError::new_warning(
+                                    format!("{path} annotated assembly: {error}"),
+                                    None,
+                                    None,
+                                ));
+                                None
+                            }
+                        };
                         linkage_data.insert(
                             path.to_owned(),
-                            (memory_buffer_linked, bytecode_hash, assembly_text),
+                            (
+                                memory_buffer_linked,
+                                bytecode_hash,
+                                assembly_text,
+                                report,
+                                weight_estimates,
+                                annotated_assembly,
+                            ),
                         );
                     }
                     Ok((_memory_buffer_linked, ObjectFormat::ELF)) => {}
-                    Err(error) => self
-                        .messages
-                        .push(SolcStandardJsonOutputError::new_error(error, None, None)),
+                    Err(error) => self.messages.push(
+                        SolcStandardJsonOutputError::new_error(error, None, None)
+                            .with_code(ErrorCode::Link),
+                    ),
                 }
             }
             if linkage_data.is_empty() {
                 break;
             }
 
-            for (path, (memory_buffer_linked, bytecode_hash, assembly_text)) in
-                linkage_data.into_iter()
+            for (
+                path,
+                (
+                    memory_buffer_linked,
+                    bytecode_hash,
+                    assembly_text,
+                    report,
+                    weight_estimates,
+                    annotated_assembly,
+                ),
+            ) in linkage_data.into_iter()
             {
                 let contract = contracts.get(path.as_str()).expect("Always exists");
                 let factory_dependencies_resolved = contract
@@ -133,6 +239,9 @@ impl Build {
                 contract.build.assembly_text = Some(assembly_text);
                 contract.factory_dependencies_resolved = factory_dependencies_resolved;
                 contract.object_format = ObjectFormat::PVM;
+                contract.size_report = report;
+                contract.weight_estimates = weight_estimates;
+                contract.annotated_assembly = annotated_assembly;
             }
         }
 
@@ -162,7 +271,12 @@ impl Build {
         self.take_and_write_warnings();
         self.exit_on_error();
 
-        if !output_metadata && !output_assembly && !output_binary {
+        let output_size_report = self.results.values().any(|contract| {
+            contract
+                .as_ref()
+                .is_ok_and(|contract| contract.size_report.is_some())
+        });
+        if !output_metadata && !output_assembly && !output_binary && !output_size_report {
             writeln!(
                 std::io::stderr(),
                 "Compiler run successful. No output requested. Use flags --metadata, --asm, --bin."
@@ -263,6 +377,10 @@ impl Build {
         standard_json: &mut SolcStandardJsonOutput,
         solc_version: &SolcVersion,
     ) -> anyhow::Result<()> {
+        let immutable_declarations =
+            SolcStandardJsonOutputContractPolkaVMImmutableDeclaration::collect(
+                &standard_json.sources,
+            );
         let mut errors = Vec::with_capacity(self.results.len());
         for result in self.results.into_values() {
             let build = match result {
@@ -286,7 +404,7 @@ impl Build {
                     )
                 }) {
                 Some(contract) => {
-                    build.write_to_standard_json(contract)?;
+                    build.write_to_standard_json(contract, &immutable_declarations)?;
                 }
                 None => {
                     let contracts = standard_json
@@ -294,13 +412,14 @@ impl Build {
                         .entry(identifier.path.clone())
                         .or_default();
                     let mut contract = SolcStandardJsonOutputContract::default();
-                    build.write_to_standard_json(&mut contract)?;
+                    build.write_to_standard_json(&mut contract, &immutable_declarations)?;
                     contracts.insert(identifier.name.unwrap_or(identifier.path), contract);
                 }
             }
         }
 
         standard_json.errors.extend(errors);
+        standard_json.errors.extend(self.messages);
         standard_json.version = Some(solc_version.default.to_string());
         standard_json.long_version = Some(solc_version.long.to_owned());
 
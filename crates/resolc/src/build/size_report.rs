@@ -0,0 +1,262 @@
+//! The PVM code size attribution report.
+//!
+//! The PVM blob doesn't know about functions. The report is computed from a
+//! blob linked without stripping, where the PolkaVM linker keeps the ELF
+//! function symbols as debug information for every instruction. Stripping does
+//! not change the code, so the sizes hold for the final blob.
+//!
+//! Code inlined by LLVM is attributed to the function it was inlined into,
+//! unless source debug information is emitted, which keeps the inlined frames.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use polkavm_common::program::ProgramBlob;
+use polkavm_common::program::ProgramCounter;
+use revive_llvm_context::PolkaVMFunctionDeployCode;
+use revive_llvm_context::PolkaVMFunctionEntry;
+use revive_llvm_context::PolkaVMFunctionRuntimeCode;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The name code without function information is attributed to.
+const UNKNOWN_FUNCTION: &str = "<unknown>";
+
+/// The name prefixes solc uses for functions defined in Solidity sources.
+const SOLIDITY_FUNCTION_PREFIXES: [&str; 4] = ["fun_", "modifier_", "constructor_", "usr$"];
+
+/// The origin of a function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FunctionKind {
+    /// A function defined in a Solidity source.
+    Solidity,
+    /// A Yul function generated by solc.
+    Yul,
+    /// A helper outlined by the newyork code generator.
+    NewYork,
+    /// A runtime function of the PolkaVM code generator.
+    Runtime,
+    /// A compiler builtin.
+    Builtin,
+    /// Code without function information.
+    Unknown,
+}
+
+impl FunctionKind {
+    /// Classifies the function `name`, given the `outlined_functions` of the build.
+    pub fn of(name: &str, outlined_functions: &BTreeMap<String, String>) -> Self {
+        let runtime_functions = [
+            PolkaVMFunctionEntry,
+            PolkaVMFunctionDeployCode,
+            PolkaVMFunctionRuntimeCode,
+            "__sbrk_internal",
+            "call",
+            "deploy",
+        ];
+
+        if name == UNKNOWN_FUNCTION {
+            Self::Unknown
+        } else if outlined_functions.contains_key(name) {
+            Self::NewYork
+        } else if name.starts_with("__revive_") || runtime_functions.contains(&name) {
+            Self::Runtime
+        } else if name.starts_with("__") || ["memcpy", "memmove", "memset"].contains(&name) {
+            Self::Builtin
+        } else if SOLIDITY_FUNCTION_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+        {
+            Self::Solidity
+        } else {
+            Self::Yul
+        }
+    }
+}
+
+impl std::fmt::Display for FunctionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Solidity => write!(f, "solidity"),
+            Self::Yul => write!(f, "yul"),
+            Self::NewYork => write!(f, "newyork"),
+            Self::Runtime => write!(f, "runtime"),
+            Self::Builtin => write!(f, "builtin"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// The code size of a function.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionSize {
+    /// The function name.
+    pub name: String,
+    /// The function origin.
+    pub kind: FunctionKind,
+    /// The code size in bytes.
+    pub bytes: usize,
+    /// The number of instructions.
+    pub instructions: usize,
+}
+
+/// The PVM code size attribution report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SizeReport {
+    /// The final blob size in bytes.
+    pub blob: usize,
+    /// The code size in bytes.
+    pub code: usize,
+    /// The code bytes by function origin.
+    pub kinds: BTreeMap<FunctionKind, usize>,
+    /// The functions, largest first.
+    pub functions: Vec<FunctionSize>,
+}
+
+impl SizeReport {
+    /// Attributes the code of the final `bytecode` to functions, using the
+    /// function information of the same blob linked without stripping and the
+    /// `outlined_functions` of the build.
+    pub fn new(
+        bytecode: &[u8],
+        unstripped: &[u8],
+        outlined_functions: &BTreeMap<String, String>,
+    ) -> anyhow::Result<Self> {
+        let blob = ProgramBlob::parse(unstripped.to_vec().into()).map_err(anyhow::Error::msg)?;
+
+        let mut functions = BTreeMap::<String, FunctionSize>::new();
+        let mut region: Option<(Range<ProgramCounter>, String)> = None;
+        for instruction in blob.instructions() {
+            if !region
+                .as_ref()
+                .is_some_and(|(range, _)| range.contains(&instruction.offset))
+            {
+                region = function_at(&blob, instruction.offset)?;
+            }
+            let name = region
+                .as_ref()
+                .map_or(UNKNOWN_FUNCTION, |(_, name)| name.as_str());
+            let function = functions
+                .entry(name.to_owned())
+                .or_insert_with(|| FunctionSize {
+                    name: name.to_owned(),
+                    kind: FunctionKind::of(name, outlined_functions),
+                    bytes: 0,
+                    instructions: 0,
+                });
+            function.bytes += (instruction.next_offset.0 - instruction.offset.0) as usize;
+            function.instructions += 1;
+        }
+
+        let mut functions = functions.into_values().collect::<Vec<_>>();
+        functions.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+        let mut kinds = BTreeMap::new();
+        for function in functions.iter() {
+            *kinds.entry(function.kind).or_default() += function.bytes;
+        }
+
+        Ok(Self {
+            blob: bytecode.len(),
+            code: blob.code().len(),
+            kinds,
+            functions,
+        })
+    }
+}
+
+impl std::fmt::Display for SizeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let percent = |bytes: usize| 100.0 * bytes as f64 / self.code.max(1) as f64;
+
+        writeln!(f, "Blob: {} bytes, code: {} bytes", self.blob, self.code)?;
+        writeln!(f)?;
+        writeln!(f, "{:<10} {:>8} {:>7}", "Origin", "Bytes", "Code")?;
+        for (kind, bytes) in self.kinds.iter() {
+            writeln!(
+                f,
+                "{:<10} {:>8} {:>6.1}%",
+                kind.to_string(),
+                bytes,
+                percent(*bytes)
+            )?;
+        }
+        writeln!(f)?;
+
+        let width = self
+            .functions
+            .iter()
+            .map(|function| function.name.len())
+            .max()
+            .unwrap_or_default()
+            .max("Function".len());
+        writeln!(
+            f,
+            "{:<width$} {:<10} {:>8} {:>7}",
+            "Function", "Origin", "Bytes", "Code"
+        )?;
+        for function in self.functions.iter() {
+            writeln!(
+                f,
+                "{:<width$} {:<10} {:>8} {:>6.1}%",
+                function.name,
+                function.kind.to_string(),
+                function.bytes,
+                percent(function.bytes)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The instruction range and name of the function containing `program_counter`.
+///
+/// Of inlined frames, the innermost function is used.
+fn function_at(
+    blob: &ProgramBlob,
+    program_counter: ProgramCounter,
+) -> anyhow::Result<Option<(Range<ProgramCounter>, String)>> {
+    Ok(functions_at(blob, program_counter)?
+        .and_then(|(range, mut names)| Some((range, names.pop()?))))
+}
+
+/// The instruction range and the names of the functions containing `program_counter`,
+/// from the outermost to the innermost inlined frame.
+pub fn functions_at(
+    blob: &ProgramBlob,
+    program_counter: ProgramCounter,
+) -> anyhow::Result<Option<(Range<ProgramCounter>, Vec<String>)>> {
+    let Some(mut program) = blob
+        .get_debug_line_program_at(program_counter)
+        .map_err(anyhow::Error::msg)?
+    else {
+        return Ok(None);
+    };
+    while let Some(region) = program.run().map_err(anyhow::Error::msg)? {
+        if !region.instruction_range().contains(&program_counter) {
+            continue;
+        }
+        let names = region
+            .frames()
+            .map(|frame| {
+                let name = frame.full_name().map_err(anyhow::Error::msg)?.to_string();
+                Ok(function_name(&name))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if !matches!(names.last(), Some(name) if !name.is_empty()) {
+            return Ok(None);
+        }
+        return Ok(Some((region.instruction_range(), names)));
+    }
+    Ok(None)
+}
+
+/// Removes the suffixes LLVM appends to the names of cloned or renamed functions.
+pub fn function_name(name: &str) -> String {
+    let name = name.split(".llvm.").next().unwrap_or(name);
+    match name.rsplit_once('.') {
+        Some((base, suffix)) if !base.is_empty() && suffix.chars().all(|c| c.is_ascii_digit()) => {
+            base.to_owned()
+        }
+        _ => name.to_owned(),
+    }
+}
@@ -0,0 +1,132 @@
+//! The compilation cache.
+//!
+//! Contract builds are stored on disk or in memory, keyed by the hash of the compiler
+//! version and the recursive process input. The input has the contract IR and
+//! all settings affecting the build: the optimizer settings, the memory
+//! configuration, the LLVM arguments and the metadata hash type.
+//!
+//! Compilers built with uncommitted changes share the version of their commit,
+//! so their entries are keyed on a hash of the changes as well.
+//!
+//! The cache is best effort: unreadable entries are treated as misses and
+//! failing writes are ignored. The in-memory cache is cleared once it holds
+//! [`Cache::MEMORY_CAPACITY`] entries.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use revive_common::Keccak256;
+use revive_common::EXTENSION_JSON;
+
+use crate::build::contract::Contract as ContractBuild;
+use crate::process::input::Input as ProcessInput;
+use crate::ResolcVersion;
+
+/// The hash of the uncommitted changes the compiler was built with, if any.
+const DIRTY_HASH: &str = env!("GIT_DIRTY_HASH");
+
+/// The number of temporary entry files created by this process, which makes their
+/// names unique across the threads.
+static TEMPORARY_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// The compilation cache.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    /// The cache entry storage.
+    storage: Storage,
+}
+
+/// The cache entry storage.
+#[derive(Debug, Clone)]
+enum Storage {
+    /// The entries are files in the directory.
+    Directory(PathBuf),
+    /// The serialized entries are kept in memory, shared by the clones.
+    Memory(Arc<Mutex<HashMap<String, Vec<u8>>>>),
+}
+
+impl Cache {
+    /// The maximum number of in-memory cache entries.
+    pub const MEMORY_CAPACITY: usize = 1024;
+
+    /// Opens the cache in `directory`, creating it if it doesn't exist.
+    pub fn new(directory: PathBuf) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(directory.as_path())
+            .map_err(|error| anyhow::anyhow!("Cache directory {directory:?} creating: {error}"))?;
+        Ok(Self {
+            storage: Storage::Directory(directory),
+        })
+    }
+
+    /// Creates an empty in-memory cache.
+    pub fn new_in_memory() -> Self {
+        Self {
+            storage: Storage::Memory(Default::default()),
+        }
+    }
+
+    /// Returns the cache key of the process `input`.
+    pub fn key(input: &ProcessInput) -> String {
+        let input = serde_json::to_vec(input).expect("Always valid");
+        Keccak256::from_slices(&[
+            ResolcVersion::default().long.as_bytes(),
+            DIRTY_HASH.as_bytes(),
+            input.as_slice(),
+        ])
+        .as_str()
+        .to_owned()
+    }
+
+    /// Returns the cached build for `key`, if any.
+    pub fn get(&self, key: &str) -> Option<ContractBuild> {
+        let entry = match &self.storage {
+            Storage::Directory(directory) => std::fs::read(Self::path(directory, key)).ok()?,
+            Storage::Memory(entries) => entries.lock().ok()?.get(key)?.clone(),
+        };
+        serde_json::from_slice(entry.as_slice()).ok()
+    }
+
+    /// Stores the `build` for `key`.
+    ///
+    /// On disk, the entry is written to a temporary file of its own first, so
+    /// concurrent compilers and threads never read partially written entries.
+    pub fn insert(&self, key: &str, build: &ContractBuild) {
+        let Ok(entry) = serde_json::to_vec(build) else {
+            return;
+        };
+        match &self.storage {
+            Storage::Directory(directory) => {
+                let temporary_path = directory.join(format!(
+                    "{key}.{}.{}.tmp",
+                    std::process::id(),
+                    TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed),
+                ));
+                if std::fs::write(temporary_path.as_path(), entry).is_err()
+                    || std::fs::rename(temporary_path.as_path(), Self::path(directory, key))
+                        .is_err()
+                {
+                    let _ = std::fs::remove_file(temporary_path);
+                }
+            }
+            Storage::Memory(entries) => {
+                let Ok(mut entries) = entries.lock() else {
+                    return;
+                };
+                if entries.len() >= Self::MEMORY_CAPACITY && !entries.contains_key(key) {
+                    entries.clear();
+                }
+                entries.insert(key.to_owned(), entry);
+            }
+        }
+    }
+
+    /// Returns the entry path for `key` in `directory`.
+    fn path(directory: &Path, key: &str) -> PathBuf {
+        directory.join(format!("{key}.{EXTENSION_JSON}"))
+    }
+}
@@ -3,7 +3,7 @@
 use std::{
     fs::File,
     path::PathBuf,
-    process::{Command, Stdio},
+    process::{Child, Command, Stdio},
 };
 
 use crate::SolcCompiler;
@@ -73,6 +73,30 @@ pub const STANDARD_JSON_YUL_NEWYORK_ENABLED_PATH: &str =
 /// `settings.polkavm.newyork` field, so it compiles through the stock pipeline.
 pub const STANDARD_JSON_YUL_NEWYORK_DISABLED_PATH: &str =
     "src/tests/data/standard_json/yul_newyork_disabled.json";
+/// A standard JSON fixture selecting the automatic memory configuration with newyork.
+pub const STANDARD_JSON_MEMORY_CONFIG_AUTO_PATH: &str =
+    "src/tests/data/standard_json/memory_config_auto.json";
+/// The same input as [`STANDARD_JSON_MEMORY_CONFIG_AUTO_PATH`] without newyork, which
+/// can't size the memory automatically.
+pub const STANDARD_JSON_MEMORY_CONFIG_AUTO_YUL_PIPELINE_PATH: &str =
+    "src/tests/data/standard_json/memory_config_auto_yul_pipeline.json";
+/// A standard JSON fixture selecting the gas estimates only.
+pub const STANDARD_JSON_GAS_ESTIMATES_PATH: &str =
+    "src/tests/data/standard_json/gas_estimates.json";
+/// A standard JSON fixture selecting the immutable layout only.
+pub const STANDARD_JSON_IMMUTABLE_LAYOUT_PATH: &str =
+    "src/tests/data/standard_json/immutable_layout.json";
+/// A standard JSON fixture selecting the IRs and assembly of the newyork pipeline.
+pub const STANDARD_JSON_IR_OUTPUTS_PATH: &str = "src/tests/data/standard_json/ir_outputs.json";
+/// A standard JSON fixture selecting the annotated assembly with debug information.
+pub const STANDARD_JSON_ANNOTATED_ASSEMBLY_PATH: &str =
+    "src/tests/data/standard_json/annotated_assembly.json";
+/// A standard JSON fixture with an unsupported instruction in inline assembly.
+pub const STANDARD_JSON_UNSUPPORTED_INSTRUCTION_PATH: &str =
+    "src/tests/data/standard_json/unsupported_instruction.json";
+/// The same input as [`STANDARD_JSON_UNSUPPORTED_INSTRUCTION_PATH`] with newyork.
+pub const STANDARD_JSON_UNSUPPORTED_INSTRUCTION_NEWYORK_PATH: &str =
+    "src/tests/data/standard_json/unsupported_instruction_newyork.json";
 /// The standard JSON PVM codegen for all files on a per-file basis test fixture path.
 ///
 /// These contracts are similar to ones used in an example project.
@@ -150,6 +174,27 @@ pub fn execute_resolc_with_stdin_input(arguments: &[&str], stdin_file_path: &str
     execute_command("resolc", arguments, Some(stdin_file_path))
 }
 
+/// Executes the `resolc-lsp` command with the given `arguments` and file path passed to `stdin`.
+pub fn execute_resolc_lsp_with_stdin_input(
+    arguments: &[&str],
+    stdin_file_path: &str,
+) -> CommandResult {
+    execute_command("resolc-lsp", arguments, Some(stdin_file_path))
+}
+
+/// Spawns the `resolc-lsp` command with the given `arguments` and piped `stdin` and `stdout`.
+pub fn spawn_resolc_lsp(arguments: &[&str]) -> Child {
+    log::trace!("spawning command: 'resolc-lsp {}'", arguments.join(" "));
+
+    Command::new("resolc-lsp")
+        .args(arguments)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap()
+}
+
 /// Executes the `solc` command with the given `arguments`.
 pub fn execute_solc(arguments: &[&str]) -> CommandResult {
     execute_command(SolcCompiler::DEFAULT_EXECUTABLE_NAME, arguments, None)
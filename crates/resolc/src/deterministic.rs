@@ -0,0 +1,117 @@
+//! The deterministic build mode.
+//!
+//! The PVM output depends on the source unit names, which end up in the metadata,
+//! the linker symbols and the contract identifiers. In deterministic mode, input
+//! paths are turned into POSIX paths relative to the working directory, and options
+//! embedding host paths into the output are refused.
+//!
+//! All other inputs affecting the output are recorded in the contract metadata.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use path_slash::PathExt;
+use revive_solc_json_interface::SolcStandardJsonInput;
+
+/// The error message for source debug information, which embeds host paths.
+pub const DEBUG_INFORMATION_ERROR: &str =
+    "Source debug information embeds host paths and is not supported in deterministic mode.";
+
+/// Normalizes the input `paths` and the targets of the `remappings` into POSIX paths
+/// relative to the working directory.
+///
+/// Paths outside of the working directory can't be normalized and are refused.
+pub fn normalize_input_paths(
+    paths: &[PathBuf],
+    remappings: BTreeSet<String>,
+) -> anyhow::Result<(Vec<PathBuf>, BTreeSet<String>)> {
+    let root = std::env::current_dir()?.canonicalize()?;
+
+    let paths = paths
+        .iter()
+        .map(|path| {
+            if path.to_string_lossy() == "-" {
+                return Ok(path.to_owned());
+            }
+            normalize_path(&root, path).map(PathBuf::from)
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    let remappings = remappings
+        .into_iter()
+        .map(|remapping| match remapping.split_once('=') {
+            Some((prefix, target)) if Path::new(target).is_absolute() => Ok(format!(
+                "{prefix}={}",
+                normalize_path(&root, Path::new(target))?
+            )),
+            _ => Ok(remapping),
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok((paths, remappings))
+}
+
+/// Checks that the command line `libraries` don't refer to absolute paths.
+pub fn check_libraries(libraries: &[String]) -> anyhow::Result<()> {
+    for library in libraries {
+        let path = library.split(':').next().unwrap_or_default().trim();
+        if is_host_path(path) {
+            anyhow::bail!(
+                "Library `{library}` has an absolute path, which is not supported in deterministic mode."
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the standard JSON `input` doesn't depend on the host.
+///
+/// The source unit names are chosen by the caller and hence must not be absolute paths.
+pub fn check_standard_json_input(input: &SolcStandardJsonInput) -> anyhow::Result<()> {
+    if input.settings.polkavm.debug_information.unwrap_or(false) {
+        anyhow::bail!(DEBUG_INFORMATION_ERROR);
+    }
+    for path in input.sources.keys() {
+        if is_host_path(path) {
+            anyhow::bail!(
+                "Source `{path}` has an absolute path, which is not supported in deterministic mode."
+            );
+        }
+    }
+    for remapping in input.settings.remappings.iter() {
+        if let Some((_, target)) = remapping.split_once('=') {
+            if is_host_path(target) {
+                anyhow::bail!(
+                    "Remapping `{remapping}` has an absolute target, which is not supported in deterministic mode."
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the POSIX `path` relative to `root`.
+fn normalize_path(root: &Path, path: &Path) -> anyhow::Result<String> {
+    let canonical = path
+        .canonicalize()
+        .map_err(|error| anyhow::anyhow!("File {path:?} reading: {error}"))?;
+    let relative = canonical.strip_prefix(root).map_err(|_| {
+        anyhow::anyhow!(
+            "Path {path:?} is outside of the working directory {root:?}, which is not supported in deterministic mode."
+        )
+    })?;
+    relative
+        .to_slash()
+        .map(|path| path.to_string())
+        .ok_or_else(|| anyhow::anyhow!("Input path {path:?} POSIX conversion error"))
+}
+
+/// Whether `path` is an absolute path on any host.
+fn is_host_path(path: &str) -> bool {
+    Path::new(path).is_absolute()
+        || path.starts_with('/')
+        || path.contains('\\')
+        || (path.as_bytes().first().is_some_and(u8::is_ascii_alphabetic)
+            && path.as_bytes().get(1) == Some(&b':'))
+}
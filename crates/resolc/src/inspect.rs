@@ -0,0 +1,221 @@
+//! The PVM blob and ELF object inspector.
+//!
+//! # Unlinked objects
+//!
+//! Unlinked ELF objects are linked against placeholder library addresses
+//! and factory dependency hashes before they are inspected. The reported
+//! sections, imports and exports hence are the ones of the blob the object
+//! turns into once deploy time linking is complete.
+//!
+//! Undefined symbols naming one of the inspected objects are reported as
+//! factory dependencies, all other symbols as library addresses. Pass the
+//! objects of the factory dependencies along to tell them apart.
+//!
+//! # Immutable data
+//!
+//! The immutable data size is a symbol of the ELF object. It is not recoverable
+//! from linked PVM blobs, which don't carry symbols.
+//!
+//! `resolc` does not embed the metadata hash into the bytecode, hence it can't
+//! be inspected. It is part of the metadata output instead.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use polkavm_common::program::ProgramBlob;
+use revive_common::ObjectFormat;
+use revive_common::BYTE_LENGTH_ETH_ADDRESS;
+use revive_common::BYTE_LENGTH_WORD;
+use revive_linker::elf::ElfObject;
+use revive_llvm_context::polkavm_link;
+use revive_runtime_api::immutable_data::GLOBAL_IMMUTABLE_DATA_SIZE;
+use serde::Serialize;
+
+use crate::linker::factory_dependency_symbol;
+use crate::linker::undefined_symbols;
+
+/// The inspection of a PVM blob or an unlinked ELF object.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Inspection {
+    /// The object format.
+    pub format: ObjectFormat,
+    /// The object size in bytes.
+    pub size: usize,
+    /// The section sizes of the PVM blob.
+    pub sections: Sections,
+    /// The imported host functions.
+    pub imports: Vec<String>,
+    /// The exported entry points.
+    pub exports: Vec<String>,
+    /// The immutable data size in bytes, if known.
+    pub immutable_data_size: Option<u32>,
+    /// The unresolved library address symbols.
+    pub unresolved_libraries: BTreeSet<String>,
+    /// The unresolved factory dependency symbols.
+    pub factory_dependencies: BTreeSet<String>,
+}
+
+/// The section sizes of a PVM blob.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sections {
+    /// The code size in bytes.
+    pub code: usize,
+    /// The number of instructions.
+    pub instructions: usize,
+    /// The number of jump table entries.
+    pub jump_table: u32,
+    /// The initialized read-only data in bytes.
+    pub ro_data: usize,
+    /// The read-only data size in bytes, including zero padding.
+    pub ro_data_size: u32,
+    /// The initialized read-write data in bytes.
+    pub rw_data: usize,
+    /// The read-write data size in bytes, including zero initialized data.
+    pub rw_data_size: u32,
+    /// The stack size in bytes.
+    pub stack_size: u32,
+}
+
+impl Inspection {
+    /// Inspect the `object` at `path`.
+    ///
+    /// The `paths` of all inspected objects tell factory dependencies apart from libraries.
+    pub fn new(path: &str, object: &[u8], paths: &BTreeSet<String>) -> anyhow::Result<Self> {
+        let format =
+            ObjectFormat::try_from(object).map_err(|error| anyhow::anyhow!("{path}: {error}"))?;
+
+        match format {
+            ObjectFormat::PVM => Self::blob(format, object, object),
+            ObjectFormat::ELF => {
+                let factory_dependency_symbols = paths
+                    .iter()
+                    .map(|path| factory_dependency_symbol(path))
+                    .collect::<BTreeSet<_>>();
+                let (factory_dependencies, unresolved_libraries): (BTreeSet<_>, BTreeSet<_>) =
+                    undefined_symbols(object)?
+                        .into_iter()
+                        .partition(|symbol| factory_dependency_symbols.contains(symbol));
+
+                let libraries = unresolved_libraries
+                    .iter()
+                    .map(|symbol| (symbol.clone(), [0; BYTE_LENGTH_ETH_ADDRESS]))
+                    .collect::<BTreeMap<_, _>>();
+                let hashes = factory_dependencies
+                    .iter()
+                    .map(|symbol| (symbol.clone(), [0; BYTE_LENGTH_WORD]))
+                    .collect::<BTreeMap<_, _>>();
+                let (blob, linked_format) = polkavm_link(object, &libraries, &hashes, true)?;
+                if linked_format != ObjectFormat::PVM {
+                    anyhow::bail!(
+                        "{path}: the object has undefined symbols which are not contracts"
+                    );
+                }
+
+                Ok(Self {
+                    immutable_data_size: immutable_data_size(object)?,
+                    unresolved_libraries,
+                    factory_dependencies,
+                    ..Self::blob(format, object, &blob)?
+                })
+            }
+        }
+    }
+
+    /// The inspection of the PVM `blob` linked from `object`.
+    fn blob(format: ObjectFormat, object: &[u8], blob: &[u8]) -> anyhow::Result<Self> {
+        let blob = ProgramBlob::parse(blob.to_vec().into()).map_err(anyhow::Error::msg)?;
+
+        Ok(Self {
+            format,
+            size: object.len(),
+            sections: Sections {
+                code: blob.code().len(),
+                instructions: blob.instructions().count(),
+                jump_table: blob.jump_table().len(),
+                ro_data: blob.ro_data().len(),
+                ro_data_size: blob.ro_data_size(),
+                rw_data: blob.rw_data().len(),
+                rw_data_size: blob.rw_data_size(),
+                stack_size: blob.stack_size(),
+            },
+            imports: blob
+                .imports()
+                .iter()
+                .map(|import| {
+                    import
+                        .map(|symbol| symbol.to_string())
+                        .ok_or_else(|| anyhow::anyhow!("Invalid import symbol"))
+                })
+                .collect::<anyhow::Result<_>>()?,
+            exports: blob
+                .exports()
+                .map(|export| export.symbol().to_string())
+                .collect(),
+            immutable_data_size: None,
+            unresolved_libraries: Default::default(),
+            factory_dependencies: Default::default(),
+        })
+    }
+}
+
+impl std::fmt::Display for Inspection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |symbols: &mut dyn Iterator<Item = &String>| {
+            let symbols = symbols.map(String::as_str).collect::<Vec<_>>();
+            if symbols.is_empty() {
+                "none".to_owned()
+            } else {
+                symbols.join(", ")
+            }
+        };
+
+        writeln!(f, "Format: {} ({} bytes)", self.format, self.size)?;
+        writeln!(
+            f,
+            "Code: {} bytes, {} instructions, {} jump table entries",
+            self.sections.code, self.sections.instructions, self.sections.jump_table
+        )?;
+        writeln!(
+            f,
+            "RO data: {} bytes ({} bytes initialized)",
+            self.sections.ro_data_size, self.sections.ro_data
+        )?;
+        writeln!(
+            f,
+            "RW data: {} bytes ({} bytes initialized)",
+            self.sections.rw_data_size, self.sections.rw_data
+        )?;
+        writeln!(f, "Stack: {} bytes", self.sections.stack_size)?;
+        writeln!(f, "Imports: {}", list(&mut self.imports.iter()))?;
+        writeln!(f, "Exports: {}", list(&mut self.exports.iter()))?;
+        match self.immutable_data_size {
+            Some(size) => writeln!(f, "Immutable data: {size} bytes")?,
+            None => writeln!(f, "Immutable data: unknown")?,
+        }
+        if self.format == ObjectFormat::ELF {
+            writeln!(
+                f,
+                "Unresolved libraries: {}",
+                list(&mut self.unresolved_libraries.iter())
+            )?;
+            writeln!(
+                f,
+                "Factory dependencies: {}",
+                list(&mut self.factory_dependencies.iter())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The immutable data size defined in the ELF `object`.
+fn immutable_data_size(object: &[u8]) -> anyhow::Result<Option<u32>> {
+    let Some(data) = ElfObject::parse(object)?.symbol_data(GLOBAL_IMMUTABLE_DATA_SIZE, 4)? else {
+        return Ok(None);
+    };
+    Ok(Some(u32::from_le_bytes(
+        data.try_into().expect("Always valid"),
+    )))
+}
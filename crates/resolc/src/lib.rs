@@ -26,18 +26,25 @@ use revive_solc_json_interface::SolcStandardJsonInputSettingsOptimizer;
 use revive_solc_json_interface::SolcStandardJsonInputSettingsPolkaVM;
 use revive_solc_json_interface::SolcStandardJsonInputSettingsPolkaVMMemory;
 use revive_solc_json_interface::SolcStandardJsonInputSettingsSelection;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsSelectionFileFlag;
+use revive_solc_json_interface::SolcStandardJsonOutput;
 use revive_solc_json_interface::SolcStandardJsonOutputError;
 use revive_solc_json_interface::SolcStandardJsonOutputErrorHandler;
 
+use crate::cache::Cache;
+use crate::inspect::Inspection;
 use crate::linker::Output;
 
 pub use self::build::contract::Contract as ContractBuild;
+pub use self::build::link_options::LinkOptions;
 pub use self::build::Build;
 pub use self::missing_libraries::MissingLibraries;
 pub use self::process::input::Input as ProcessInput;
 #[cfg(not(target_os = "emscripten"))]
 pub use self::process::native_process::NativeProcess;
 pub use self::process::output::Output as ProcessOutput;
+#[cfg(not(target_os = "emscripten"))]
+pub use self::process::thread_process::ThreadProcess;
 #[cfg(target_os = "emscripten")]
 pub use self::process::worker_process::WorkerProcess;
 pub use self::process::Process;
@@ -55,13 +62,18 @@ pub use self::solc::LAST_SUPPORTED_VERSION as SolcLastSupportedVersion;
 pub use self::version::Version as ResolcVersion;
 
 pub(crate) mod build;
+pub mod cache;
 #[cfg(not(target_os = "emscripten"))]
 pub mod cli_utils;
 pub(crate) mod r#const;
-pub(crate) mod linker;
+pub mod deterministic;
+pub mod inspect;
+pub mod linker;
+pub mod lsp;
 pub(crate) mod missing_libraries;
 pub(crate) mod process;
 pub(crate) mod project;
+pub mod server;
 pub(crate) mod solc;
 #[cfg(not(target_os = "emscripten"))]
 pub mod test_utils;
@@ -84,6 +96,14 @@ pub fn pipeline_name(use_newyork: bool) -> &'static str {
 ///
 /// When `use_newyork` is `true`, each Yul source is routed through the newyork IR
 /// pipeline instead of the direct Yul-to-LLVM path.
+///
+/// When `deterministic` is `true`, the contract metadata records the memory
+/// configuration, the pipeline and the debug information setting as well.
+///
+/// When `size_report` is `true`, the code size of each linked contract is attributed
+/// to its functions.
+///
+/// Contracts found in the `cache` are not recompiled.
 pub fn yul<T: Compiler>(
     solc: &T,
     input_files: &[PathBuf],
@@ -95,6 +115,9 @@ pub fn yul<T: Compiler>(
     llvm_arguments: &[String],
     memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
     use_newyork: bool,
+    deterministic: bool,
+    size_report: bool,
+    cache: Option<&Cache>,
 ) -> anyhow::Result<Build> {
     let libraries = SolcStandardJsonInputSettingsLibraries::try_from(libraries)?;
     let solc_output = solc.validate_yul_paths(input_files, libraries.clone(), messages)?;
@@ -108,13 +131,23 @@ pub fn yul<T: Compiler>(
         optimizer_settings,
         metadata_hash,
         &debug_config,
+        None,
         llvm_arguments,
         memory_config,
+        deterministic,
+        cache,
     )?;
     build.take_and_write_warnings();
     build.check_errors()?;
 
-    let mut build = build.link(linker_symbols, &debug_config);
+    let mut build = build.link(
+        linker_symbols,
+        &debug_config,
+        LinkOptions {
+            size_report,
+            ..Default::default()
+        },
+    );
     build.take_and_write_warnings();
     build.check_errors()?;
     Ok(build)
@@ -122,7 +155,7 @@ pub fn yul<T: Compiler>(
 
 /// Runs the standard output mode.
 ///
-/// See [`yul`] for the meaning of `use_newyork`.
+/// See [`yul`] for the meaning of `use_newyork`, `deterministic`, `size_report` and `cache`.
 pub fn standard_output<T: Compiler>(
     solc: &T,
     input_files: &[PathBuf],
@@ -141,6 +174,9 @@ pub fn standard_output<T: Compiler>(
     llvm_arguments: Vec<String>,
     memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
     use_newyork: bool,
+    deterministic: bool,
+    size_report: bool,
+    cache: Option<&Cache>,
 ) -> anyhow::Result<Build> {
     let solc_version = solc.version()?;
     let mut solc_input = SolcStandardJsonInput::try_from_solidity_paths(
@@ -190,13 +226,23 @@ pub fn standard_output<T: Compiler>(
         optimizer_settings,
         metadata_hash,
         &debug_config,
+        None,
         &solc_input.settings.llvm_arguments,
         memory_config,
+        deterministic,
+        cache,
     )?;
     build.take_and_write_warnings();
     build.check_errors()?;
 
-    let mut build = build.link(linker_symbols, &debug_config);
+    let mut build = build.link(
+        linker_symbols,
+        &debug_config,
+        LinkOptions {
+            size_report,
+            ..Default::default()
+        },
+    );
     build.take_and_write_warnings();
     build.check_errors()?;
 
@@ -205,7 +251,7 @@ pub fn standard_output<T: Compiler>(
 
 /// Runs the standard JSON mode.
 ///
-/// See [`yul`] for the meaning of `use_newyork`.
+/// See [`yul`] for the meaning of `use_newyork` and `cache`.
 pub fn standard_json<T: Compiler>(
     solc: &T,
     metadata_hash: MetadataHash,
@@ -214,25 +260,75 @@ pub fn standard_json<T: Compiler>(
     base_path: Option<String>,
     include_paths: Vec<String>,
     allow_paths: Option<String>,
-    mut debug_config: DebugConfig,
+    debug_config: DebugConfig,
     detect_missing_libraries: bool,
     use_newyork: bool,
+    cache: Option<&Cache>,
 ) -> anyhow::Result<()> {
+    let solc_input = SolcStandardJsonInput::try_from(json_path.as_deref())?;
+    let (solc_output, prune_output) = compile_standard_json(
+        solc,
+        solc_input,
+        metadata_hash,
+        messages,
+        base_path,
+        include_paths,
+        allow_paths,
+        debug_config,
+        detect_missing_libraries,
+        use_newyork,
+        cache,
+    )?;
+    solc_output.write_and_exit(prune_output);
+}
+
+/// Compiles the standard JSON `solc_input`.
+///
+/// Returns the output and the output selection to prune from it.
+/// See [`yul`] for the meaning of `use_newyork` and `cache`.
+pub fn compile_standard_json<T: Compiler>(
+    solc: &T,
+    mut solc_input: SolcStandardJsonInput,
+    metadata_hash: MetadataHash,
+    messages: &mut Vec<SolcStandardJsonOutputError>,
+    base_path: Option<String>,
+    include_paths: Vec<String>,
+    allow_paths: Option<String>,
+    mut debug_config: DebugConfig,
+    detect_missing_libraries: bool,
+    use_newyork: bool,
+    cache: Option<&Cache>,
+) -> anyhow::Result<(
+    SolcStandardJsonOutput,
+    SolcStandardJsonInputSettingsSelection,
+)> {
     let solc_version = solc.version()?;
-    let mut solc_input = SolcStandardJsonInput::try_from(json_path.as_deref())?;
     let language = solc_input.language;
     let prune_output = solc_input.settings.selection_to_prune();
+    let weight_estimates = solc_input.settings.output_selection.contains_any(&[
+        SolcStandardJsonInputSettingsSelectionFileFlag::EVM,
+        SolcStandardJsonInputSettingsSelectionFileFlag::GasEstimates,
+    ]);
+    let annotated_assembly = solc_input
+        .settings
+        .output_selection
+        .contains_any(&[SolcStandardJsonInputSettingsSelectionFileFlag::AnnotatedAssembly]);
     let deployed_libraries = solc_input.settings.libraries.as_paths();
     let linker_symbols = solc_input.settings.libraries.as_linker_symbols()?;
     let optimizer_settings = OptimizerSettings::try_from_cli(solc_input.settings.optimizer.mode)?;
     let detect_missing_libraries =
         solc_input.settings.detect_missing_libraries || detect_missing_libraries;
     let use_newyork = use_newyork || solc_input.settings.polkavm.newyork.unwrap_or(false);
+    let deterministic = solc_input.settings.polkavm.deterministic.unwrap_or(false);
+    if deterministic {
+        deterministic::check_standard_json_input(&solc_input)?;
+    }
     debug_config.emit_debug_info = solc_input
         .settings
         .polkavm
         .debug_information
         .unwrap_or(false);
+    let output_selection = solc_input.settings.output_selection.clone();
 
     solc_input.extend_selection(
         SolcStandardJsonInputSettingsSelection::new_required_for_codegen(
@@ -251,10 +347,34 @@ pub fn standard_json<T: Compiler>(
     solc_output.revive_version = Some(ResolcVersion::default().long);
     solc_output.resolc_pipeline = Some(pipeline_name(use_newyork).to_owned());
 
+    let mut memory_config = solc_input
+        .settings
+        .polkavm
+        .memory_config
+        .unwrap_or_default();
+    if memory_config.auto && !use_newyork {
+        solc_output.errors.push(SolcStandardJsonOutputError::new_warning(
+            "The automatic memory configuration requires the newyork pipeline (`polkavm.newyork`). Using the default sizes instead.",
+            None,
+            None,
+        ));
+        memory_config = memory_config.with_default_sizes();
+    }
+    if !use_newyork
+        && output_selection
+            .contains_any(&[SolcStandardJsonInputSettingsSelectionFileFlag::NewYorkIr])
+    {
+        solc_output.errors.push(SolcStandardJsonOutputError::new_warning(
+            "The `polkavm.newyorkIr` output requires the newyork pipeline (`polkavm.newyork`). It is omitted.",
+            None,
+            None,
+        ));
+    }
+
     if language == SolcStandardJsonInputLanguage::Yul {
         let solc_output = solc.validate_yul_standard_json(&mut solc_input, messages)?;
         if solc_output.has_errors() {
-            solc_output.write_and_exit(prune_output);
+            return Ok((solc_output, prune_output));
         }
     }
 
@@ -267,13 +387,13 @@ pub fn standard_json<T: Compiler>(
     )?;
 
     if solc_output.has_errors() {
-        solc_output.write_and_exit(prune_output);
+        return Ok((solc_output, prune_output));
     }
 
     if detect_missing_libraries {
         let missing_libraries = project.get_missing_libraries(&deployed_libraries);
         missing_libraries.write_to_standard_json(&mut solc_output, &solc_version);
-        solc_output.write_and_exit(prune_output);
+        return Ok((solc_output, prune_output));
     }
 
     let build = project.compile(
@@ -281,26 +401,33 @@ pub fn standard_json<T: Compiler>(
         optimizer_settings,
         metadata_hash,
         &debug_config,
+        Some(&output_selection),
         &solc_input.settings.llvm_arguments,
-        solc_input
-            .settings
-            .polkavm
-            .memory_config
-            .unwrap_or_default(),
+        memory_config,
+        deterministic,
+        cache,
     )?;
     if build.has_errors() {
         build.write_to_standard_json(&mut solc_output, &solc_version)?;
-        solc_output.write_and_exit(prune_output);
+        return Ok((solc_output, prune_output));
     }
 
-    let build = build.link(linker_symbols, &debug_config);
+    let build = build.link(
+        linker_symbols,
+        &debug_config,
+        LinkOptions {
+            size_report: false,
+            weight_estimates,
+            annotated_assembly,
+        },
+    );
     build.write_to_standard_json(&mut solc_output, &solc_version)?;
-    solc_output.write_and_exit(prune_output);
+    Ok((solc_output, prune_output))
 }
 
 /// Runs the combined JSON mode.
 ///
-/// See [`yul`] for the meaning of `use_newyork`.
+/// See [`yul`] for the meaning of `use_newyork`, `deterministic` and `cache`.
 pub fn combined_json<T: Compiler>(
     solc: &T,
     paths: &[PathBuf],
@@ -322,6 +449,8 @@ pub fn combined_json<T: Compiler>(
     llvm_arguments: Vec<String>,
     memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
     use_newyork: bool,
+    deterministic: bool,
+    cache: Option<&Cache>,
 ) -> anyhow::Result<()> {
     let selectors = CombinedJsonSelector::from_cli(format.as_str())
         .into_iter()
@@ -372,6 +501,9 @@ pub fn combined_json<T: Compiler>(
         llvm_arguments,
         memory_config,
         use_newyork,
+        deterministic,
+        false,
+        cache,
     )?
     .write_to_combined_json(&mut combined_json)?;
 
@@ -392,8 +524,8 @@ pub fn combined_json<T: Compiler>(
     std::process::exit(EXIT_CODE_SUCCESS);
 }
 
-/// Links unlinked bytecode files.
-pub fn link(paths: Vec<String>, libraries: Vec<String>) -> anyhow::Result<()> {
+/// Links unlinked bytecode files in place and returns the linking report.
+pub fn link(paths: Vec<String>, libraries: Vec<String>) -> anyhow::Result<linker::Report> {
     #[cfg(feature = "parallel")]
     let iter = paths.into_par_iter();
     #[cfg(not(feature = "parallel"))]
@@ -419,10 +551,19 @@ pub fn link(paths: Vec<String>, libraries: Vec<String>) -> anyhow::Result<()> {
     })
     .collect::<anyhow::Result<()>>()?;
 
-    for (path, _) in output.unlinked {
-        println!("Warning: file '{path}' still unresolved");
-    }
-    println!("Linking completed");
+    Ok(output.report)
+}
 
-    std::process::exit(EXIT_CODE_SUCCESS);
+/// Inspects the PVM blobs and ELF objects at `paths`.
+pub fn inspect(paths: Vec<String>) -> anyhow::Result<BTreeMap<String, Inspection>> {
+    let inputs = paths.iter().cloned().collect::<BTreeSet<_>>();
+    paths
+        .into_iter()
+        .map(|path| {
+            let object =
+                std::fs::read(path.as_str()).map_err(|error| anyhow::anyhow!("{path}: {error}"))?;
+            let inspection = Inspection::new(&path, &object, &inputs)?;
+            Ok((path, inspection))
+        })
+        .collect()
 }
@@ -17,12 +17,22 @@
 //! time linking" feature). Since factory dependency symbols can be
 //! resolved only after the the final PVM blob linking step, missing
 //! libraries may further lead to unresolved factory dependencies.
+//!
+//! # Report
+//!
+//! Library addresses and factory dependencies are both undefined
+//! symbols named after the contract they refer to. Symbols naming one
+//! of the linked objects are reported as factory dependencies, all
+//! other symbols as libraries.
 
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 
 use revive_common::{ObjectFormat, EXTENSION_POLKAVM_BINARY};
+use revive_linker::elf::ElfObject;
 use revive_llvm_context::{polkavm_hash, polkavm_link};
 use revive_solc_json_interface::SolcStandardJsonInputSettingsLibraries;
+use serde::{Deserialize, Serialize};
 
 /// The Solidity to PolkaVM compiler deploy time linking outputs.
 pub struct Output {
@@ -30,6 +40,45 @@ pub struct Output {
     pub linked: BTreeMap<String, Vec<u8>>,
     /// The unlinked objects.
     pub unlinked: Vec<(String, Vec<u8>)>,
+    /// The linking report.
+    pub report: Report,
+}
+
+/// The deploy time linking report.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Report {
+    /// The report of each object, by path.
+    pub files: BTreeMap<String, FileReport>,
+}
+
+impl Report {
+    /// The paths of the objects which are PVM blobs after linking.
+    pub fn linked(&self) -> impl Iterator<Item = &str> {
+        self.files
+            .iter()
+            .filter(|(_, file)| file.linked)
+            .map(|(path, _)| path.as_str())
+    }
+
+    /// The paths of the objects which are still unlinked ELF objects.
+    pub fn unlinked(&self) -> impl Iterator<Item = &str> {
+        self.files
+            .iter()
+            .filter(|(_, file)| !file.linked)
+            .map(|(path, _)| path.as_str())
+    }
+}
+
+/// The deploy time linking report of a single object.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileReport {
+    /// Whether the object is a PVM blob after linking.
+    pub linked: bool,
+    /// The library symbols which are still unresolved.
+    pub unresolved_libraries: BTreeSet<String>,
+    /// The factory dependencies, with their hash if they are resolved.
+    pub factory_dependencies: BTreeMap<String, Option<String>>,
 }
 
 impl Output {
@@ -46,10 +95,14 @@ impl Output {
         let mut linked = BTreeMap::default();
         let mut unlinked = Vec::default();
         let mut factory_dependencies = BTreeMap::default();
+        let mut references = BTreeMap::default();
 
         for (path, bytecode) in bytecodes {
             match ObjectFormat::try_from(bytecode.as_slice()) {
-                Ok(ObjectFormat::ELF) => unlinked.push((path.clone(), bytecode.clone())),
+                Ok(ObjectFormat::ELF) => {
+                    references.insert(path.clone(), undefined_symbols(bytecode)?);
+                    unlinked.push((path.clone(), bytecode.clone()));
+                }
                 Ok(ObjectFormat::PVM) => {
                     factory_dependencies
                         .insert(factory_dependency_symbol(path), polkavm_hash(bytecode));
@@ -86,11 +139,50 @@ impl Output {
             }
         }
 
-        Ok(Self { linked, unlinked })
+        let factory_dependency_symbols = bytecodes
+            .keys()
+            .map(|path| factory_dependency_symbol(path))
+            .collect::<BTreeSet<_>>();
+        let mut report = Report::default();
+        for path in bytecodes.keys() {
+            let mut file = FileReport {
+                linked: !unlinked.iter().any(|(unlinked, _)| unlinked == path),
+                ..Default::default()
+            };
+            for symbol in references.remove(path).unwrap_or_default() {
+                if factory_dependency_symbols.contains(&symbol) {
+                    let hash = factory_dependencies
+                        .get(&symbol)
+                        .map(|hash| format!("0x{}", hex::encode(hash)));
+                    file.factory_dependencies.insert(symbol, hash);
+                } else if !file.linked && !linker_symbols.contains_key(&symbol) {
+                    file.unresolved_libraries.insert(symbol);
+                }
+            }
+            report.files.insert(path.clone(), file);
+        }
+
+        Ok(Self {
+            linked,
+            unlinked,
+            report,
+        })
     }
 }
 
-fn factory_dependency_symbol(path: &str) -> String {
+/// The undefined symbols of the ELF `object` referring to contracts.
+///
+/// Contract symbols are named `<path>:<name>`, which tells them apart from
+/// the compiler builtins the object may reference.
+pub fn undefined_symbols(object: &[u8]) -> anyhow::Result<BTreeSet<String>> {
+    Ok(ElfObject::parse(object)?
+        .undefined_symbols()?
+        .into_iter()
+        .filter(|symbol| symbol.contains(':'))
+        .collect())
+}
+
+pub(crate) fn factory_dependency_symbol(path: &str) -> String {
     path.trim_end_matches(&format!(".{EXTENSION_POLKAVM_BINARY}"))
         .to_string()
 }
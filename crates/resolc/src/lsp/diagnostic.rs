@@ -0,0 +1,111 @@
+//! The language server diagnostics.
+
+use serde::Serialize;
+
+use revive_solc_json_interface::SolcStandardJsonOutputError;
+
+/// The diagnostic source shown by editors.
+const SOURCE: &str = "resolc";
+
+/// The LSP error severity.
+const SEVERITY_ERROR: u8 = 1;
+/// The LSP warning severity.
+const SEVERITY_WARNING: u8 = 2;
+/// The LSP information severity.
+const SEVERITY_INFORMATION: u8 = 3;
+
+/// The zero-based line and UTF-16 code unit offset in a document.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    /// The line.
+    pub line: u32,
+    /// The UTF-16 code unit offset in the line.
+    pub character: u32,
+}
+
+impl Position {
+    /// Returns the position of the byte `offset` in `content`.
+    ///
+    /// `solc` reports byte offsets into the UTF-8 source code, while LSP clients
+    /// count characters in UTF-16 code units by default.
+    pub fn from_offset(content: &str, offset: usize) -> Self {
+        let mut position = Self::default();
+        for (index, character) in content.char_indices() {
+            if index >= offset {
+                break;
+            }
+            if character == '\n' {
+                position.line += 1;
+                position.character = 0;
+            } else {
+                position.character += character.len_utf16() as u32;
+            }
+        }
+        position
+    }
+}
+
+/// The range between two positions in a document.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Range {
+    /// The start position.
+    pub start: Position,
+    /// The end position, exclusive.
+    pub end: Position,
+}
+
+impl Range {
+    /// Returns the range of the `start` and `end` byte offsets in `content`.
+    pub fn from_offsets(content: &str, start: usize, end: usize) -> Self {
+        Self {
+            start: Position::from_offset(content, start),
+            end: Position::from_offset(content, end.max(start)),
+        }
+    }
+}
+
+/// The diagnostic published to the client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// The range the diagnostic applies to.
+    pub range: Range,
+    /// The severity.
+    pub severity: u8,
+    /// The `solc` or `resolc` error code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// The diagnostic source.
+    pub source: &'static str,
+    /// The message.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// A shortcut constructor from the standard JSON output `error`.
+    pub fn from_error(error: &SolcStandardJsonOutputError, range: Range) -> Self {
+        // `solc` reports the severities in lower case, `resolc` capitalized.
+        let severity = match error.severity.to_ascii_lowercase().as_str() {
+            "error" => SEVERITY_ERROR,
+            "warning" => SEVERITY_WARNING,
+            _ => SEVERITY_INFORMATION,
+        };
+        Self {
+            range,
+            severity,
+            code: error.error_code.to_owned(),
+            source: SOURCE,
+            message: error.message.to_owned(),
+        }
+    }
+
+    /// A shortcut constructor for the PVM code size of the contract `name`.
+    pub fn code_size(name: &str, size: usize, range: Range) -> Self {
+        Self {
+            range,
+            severity: SEVERITY_INFORMATION,
+            code: None,
+            source: SOURCE,
+            message: format!("The contract `{name}` compiles to {size} bytes of PVM code."),
+        }
+    }
+}
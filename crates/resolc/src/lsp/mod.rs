@@ -0,0 +1,515 @@
+//! The language server.
+//!
+//! A Language Server Protocol front-end reporting the `solc` diagnostics together
+//! with the PVM ones live in editors: the PVM compatibility lints, the unsupported
+//! constructs, the heap and stack budget and the code size of the contracts.
+//!
+//! Documents are synchronized in full. Every changed document is compiled like a
+//! standard JSON input, with the other open documents shadowing their files on disk.
+//! Only the contracts of the changed document are selected for code generation, and
+//! contracts with unchanged IR are taken from the cache. Changes received while
+//! compiling are coalesced into the next compilation. Compiler panics are published
+//! as diagnostics of the document, so they don't take the server down.
+
+pub mod diagnostic;
+pub mod transport;
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::panic::AssertUnwindSafe;
+use std::path::Path;
+use std::sync::mpsc;
+
+use revive_common::MetadataHash;
+use revive_llvm_context::DebugConfig;
+use revive_solc_json_interface::SolcStandardJsonInput;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsPolkaVMMemory;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+use revive_solc_json_interface::SolcStandardJsonOutputError;
+
+use crate::cache::Cache;
+use crate::process::panic_message;
+use crate::server::Request;
+use crate::server::Response;
+use crate::server::ERROR_CODE_INVALID_REQUEST;
+use crate::server::ERROR_CODE_METHOD_NOT_FOUND;
+use crate::server::ERROR_CODE_PARSE;
+use crate::server::JSON_RPC_VERSION;
+use crate::Compiler;
+use crate::ResolcVersion;
+
+use self::diagnostic::Diagnostic;
+use self::diagnostic::Range;
+use self::transport::path_to_uri;
+use self::transport::read_message;
+use self::transport::uri_to_path;
+use self::transport::write_message;
+
+/// The server name reported to the client.
+pub const SERVER_NAME: &str = "resolc-lsp";
+
+/// The full text document synchronization kind.
+const TEXT_DOCUMENT_SYNC_KIND_FULL: u8 = 1;
+
+/// An open document.
+#[derive(Debug)]
+struct Document {
+    /// The URI given by the client.
+    uri: String,
+    /// The source code.
+    text: String,
+}
+
+/// The language server.
+pub struct LanguageServer<'a, T: Compiler> {
+    /// The `solc` compiler.
+    solc: &'a T,
+    /// The `solc` base path.
+    base_path: Option<String>,
+    /// The `solc` include paths.
+    include_paths: Vec<String>,
+    /// The `solc` allowed paths, the workspace root by default.
+    allow_paths: Option<String>,
+    /// The LLVM optimization level.
+    optimization: char,
+    /// The PolkaVM memory configuration.
+    memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+    /// Whether the newyork IR pipeline is enabled.
+    use_newyork: bool,
+    /// The compilation cache.
+    cache: Option<&'a Cache>,
+    /// The open documents by path.
+    documents: BTreeMap<String, Document>,
+    /// The paths of the files with diagnostics published by compiling each document.
+    published: BTreeMap<String, BTreeSet<String>>,
+    /// Whether the shutdown was requested.
+    is_shut_down: bool,
+}
+
+impl<'a, T: Compiler> LanguageServer<'a, T> {
+    /// A shortcut constructor.
+    ///
+    /// Call [`crate::ThreadProcess::initialize`] first to compile contracts on threads of this process.
+    pub fn new(
+        solc: &'a T,
+        base_path: Option<String>,
+        include_paths: Vec<String>,
+        allow_paths: Option<String>,
+        optimization: char,
+        memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+        use_newyork: bool,
+        cache: Option<&'a Cache>,
+    ) -> Self {
+        Self {
+            solc,
+            base_path,
+            include_paths,
+            allow_paths,
+            optimization,
+            memory_config,
+            use_newyork,
+            cache,
+            documents: BTreeMap::new(),
+            published: BTreeMap::new(),
+            is_shut_down: false,
+        }
+    }
+
+    /// Serves the messages read from stdin, writing the responses and diagnostics to stdout.
+    ///
+    /// Stdin is read on a separate thread, so that the changes received while
+    /// compiling are handled together.
+    pub fn serve_stdio(&mut self) -> anyhow::Result<()> {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut reader = std::io::stdin().lock();
+            loop {
+                let message = match read_message(&mut reader) {
+                    Ok(Some(message)) => Ok(message),
+                    Ok(None) => break,
+                    Err(error) => Err(error.to_string()),
+                };
+                let is_error = message.is_err();
+                if sender.send(message).is_err() || is_error {
+                    break;
+                }
+            }
+        });
+        self.serve(receiver, std::io::stdout().lock())
+    }
+
+    /// Serves the messages received from `receiver`, writing the responses and diagnostics to `writer`.
+    ///
+    /// Returns when the client exits or the messages end.
+    pub fn serve<W: Write>(
+        &mut self,
+        receiver: mpsc::Receiver<Result<Vec<u8>, String>>,
+        mut writer: W,
+    ) -> anyhow::Result<()> {
+        while let Ok(message) = receiver.recv() {
+            let mut changed = BTreeSet::new();
+            for message in std::iter::once(message).chain(receiver.try_iter()) {
+                let message = message.map_err(|error| anyhow::anyhow!("Stdin reading: {error}"))?;
+                if self.handle(message.as_slice(), &mut changed, &mut writer)? {
+                    return Ok(());
+                }
+            }
+            for path in changed.into_iter() {
+                self.check(path, &mut writer)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles the `message`, writing the response, if any, to `writer`.
+    /// The paths of the changed documents are added to `changed`.
+    ///
+    /// Returns whether the client exits.
+    fn handle<W: Write>(
+        &mut self,
+        message: &[u8],
+        changed: &mut BTreeSet<String>,
+        writer: &mut W,
+    ) -> anyhow::Result<bool> {
+        let request = match revive_common::deserialize_from_slice::<Request>(message) {
+            Ok(request) if request.jsonrpc == JSON_RPC_VERSION => request,
+            Ok(request) => {
+                let message = format!("Unsupported JSON-RPC version `{}`", request.jsonrpc);
+                let id = request.id.unwrap_or_default();
+                let response = Response::error(id, ERROR_CODE_INVALID_REQUEST, message);
+                write_message(writer, &response)?;
+                return Ok(false);
+            }
+            Err(error) => {
+                let response = Response::error(serde_json::Value::Null, ERROR_CODE_PARSE, error);
+                write_message(writer, &response)?;
+                return Ok(false);
+            }
+        };
+
+        let result = match (request.method.as_str(), request.id.is_some()) {
+            ("exit", _) => return Ok(true),
+            (_, true) if self.is_shut_down => Err((
+                ERROR_CODE_INVALID_REQUEST,
+                "The server is shut down".to_owned(),
+            )),
+            ("initialize", _) => Ok(self.initialize(&request.params)),
+            ("shutdown", _) => {
+                self.is_shut_down = true;
+                Ok(serde_json::Value::Null)
+            }
+            ("textDocument/didOpen", _) => {
+                let document = &request.params["textDocument"];
+                let uri = document["uri"].as_str().unwrap_or_default();
+                if let (Some(path), Some(text)) = (uri_to_path(uri), document["text"].as_str()) {
+                    let document = Document {
+                        uri: uri.to_owned(),
+                        text: text.to_owned(),
+                    };
+                    self.documents.insert(path.clone(), document);
+                    changed.insert(path);
+                }
+                Ok(serde_json::Value::Null)
+            }
+            ("textDocument/didChange", _) => {
+                let uri = request.params["textDocument"]["uri"]
+                    .as_str()
+                    .unwrap_or_default();
+                let text = request.params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+                if let (Some(path), Some(text)) = (uri_to_path(uri), text) {
+                    if let Some(document) = self.documents.get_mut(path.as_str()) {
+                        document.text = text.to_owned();
+                        changed.insert(path);
+                    }
+                }
+                Ok(serde_json::Value::Null)
+            }
+            ("textDocument/didClose", _) => {
+                let uri = request.params["textDocument"]["uri"]
+                    .as_str()
+                    .unwrap_or_default();
+                if let Some(path) = uri_to_path(uri) {
+                    changed.remove(path.as_str());
+                    for path in self.published.remove(path.as_str()).unwrap_or_default() {
+                        self.publish(writer, path.as_str(), vec![])?;
+                    }
+                    self.documents.remove(path.as_str());
+                }
+                Ok(serde_json::Value::Null)
+            }
+            (method, true) => Err((
+                ERROR_CODE_METHOD_NOT_FOUND,
+                format!("Unknown method `{method}`"),
+            )),
+            // Other notifications, like `initialized`, are ignored.
+            (_, false) => Ok(serde_json::Value::Null),
+        };
+
+        if let Some(id) = request.id {
+            let response = match result {
+                Ok(result) => Response::result(id, result),
+                Err((code, message)) => Response::error(id, code, message),
+            };
+            write_message(writer, &response)?;
+        }
+        Ok(false)
+    }
+
+    /// Handles the `initialize` request with `params`, returning the server capabilities.
+    fn initialize(&mut self, params: &serde_json::Value) -> serde_json::Value {
+        // The sources are named by their absolute paths, which `solc` only reads
+        // from the allowed paths.
+        if self.allow_paths.is_none() {
+            self.allow_paths = params["rootUri"].as_str().and_then(uri_to_path);
+        }
+
+        serde_json::json!({
+            "capabilities": {
+                "textDocumentSync": {
+                    "openClose": true,
+                    "change": TEXT_DOCUMENT_SYNC_KIND_FULL,
+                },
+            },
+            "serverInfo": {
+                "name": SERVER_NAME,
+                "version": ResolcVersion::default().long,
+            },
+        })
+    }
+
+    /// Compiles the document at `path` and publishes the diagnostics to `writer`.
+    fn check<W: Write>(&mut self, path: String, writer: &mut W) -> anyhow::Result<()> {
+        if !self.documents.contains_key(path.as_str()) {
+            return Ok(());
+        }
+
+        let output = self.compile(path.as_str());
+        let mut diagnostics = self.diagnostics(path.as_str(), &output);
+        diagnostics.entry(path.clone()).or_default();
+
+        // Clear the diagnostics of the files without any left.
+        let published = diagnostics.keys().cloned().collect::<BTreeSet<String>>();
+        for stale in self
+            .published
+            .insert(path, published)
+            .unwrap_or_default()
+            .into_iter()
+        {
+            diagnostics.entry(stale).or_default();
+        }
+
+        for (path, diagnostics) in diagnostics.into_iter() {
+            self.publish(writer, path.as_str(), diagnostics)?;
+        }
+        Ok(())
+    }
+
+    /// Compiles the open documents, selecting the contracts of the document at `path`
+    /// for code generation.
+    fn compile(&self, path: &str) -> SolcStandardJsonOutput {
+        let sources = self
+            .documents
+            .iter()
+            .map(|(path, document)| {
+                (
+                    path.to_owned(),
+                    serde_json::json!({ "content": document.text }),
+                )
+            })
+            .collect::<serde_json::Map<String, serde_json::Value>>();
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": sources,
+            "settings": {
+                "optimizer": {
+                    "enabled": true,
+                    "mode": self.optimization.to_string(),
+                },
+                "outputSelection": {
+                    path: {
+                        "": ["ast"],
+                        "*": ["evm.bytecode.object"],
+                    },
+                },
+                "polkavm": {
+                    "memoryConfig": self.memory_config,
+                    "newyork": self.use_newyork,
+                },
+            },
+        });
+
+        let mut messages = Vec::new();
+        let output = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            serde_json::from_value::<SolcStandardJsonInput>(input)
+                .map_err(anyhow::Error::from)
+                .and_then(|input| {
+                    crate::compile_standard_json(
+                        self.solc,
+                        input,
+                        MetadataHash::None,
+                        &mut messages,
+                        self.base_path.clone(),
+                        self.include_paths.clone(),
+                        self.allow_paths.clone(),
+                        DebugConfig::new(None, false),
+                        false,
+                        self.use_newyork,
+                        self.cache,
+                    )
+                })
+        }));
+        match output {
+            Ok(Ok((mut output, _))) => {
+                output.errors.extend(messages);
+                output
+            }
+            Ok(Err(error)) => {
+                messages.push(SolcStandardJsonOutputError::from_error(error, None, None));
+                SolcStandardJsonOutput::new_with_messages(messages)
+            }
+            Err(payload) => {
+                messages.push(SolcStandardJsonOutputError::new_error(
+                    format!("ICE: The compiler panicked: {}", panic_message(payload)),
+                    None,
+                    None,
+                ));
+                SolcStandardJsonOutput::new_with_messages(messages)
+            }
+        }
+    }
+
+    /// Returns the diagnostics of compiling the document at `path` by file path.
+    ///
+    /// Messages without a source location are reported at the start of the document,
+    /// and messages about a contract at its name.
+    fn diagnostics(
+        &self,
+        path: &str,
+        output: &SolcStandardJsonOutput,
+    ) -> BTreeMap<String, Vec<Diagnostic>> {
+        let mut diagnostics = BTreeMap::<String, Vec<Diagnostic>>::new();
+        for error in output.errors.iter() {
+            let (file, range) = match error.source_location.as_ref() {
+                Some(location) if location.start >= 0 => (
+                    location.file.to_owned(),
+                    self.range(
+                        location.file.as_str(),
+                        location.start as usize,
+                        location.end.max(location.start) as usize,
+                    ),
+                ),
+                Some(location) => match location.file.rsplit_once(':') {
+                    Some((file, name)) if output.sources.contains_key(file) => {
+                        (file.to_owned(), self.contract_range(output, file, name))
+                    }
+                    _ => (location.file.to_owned(), Range::default()),
+                },
+                None => (path.to_owned(), Range::default()),
+            };
+            diagnostics
+                .entry(file)
+                .or_default()
+                .push(Diagnostic::from_error(error, range));
+        }
+
+        for (name, contract) in output.contracts.get(path).into_iter().flatten() {
+            let Some(bytecode) = contract
+                .evm
+                .as_ref()
+                .and_then(|evm| evm.bytecode.as_ref())
+                .filter(|bytecode| !bytecode.object.is_empty())
+            else {
+                continue;
+            };
+            diagnostics
+                .entry(path.to_owned())
+                .or_default()
+                .push(Diagnostic::code_size(
+                    name.as_str(),
+                    bytecode.object.len() / 2,
+                    self.contract_range(output, path, name.as_str()),
+                ));
+        }
+        diagnostics
+    }
+
+    /// Returns the range of the name of the contract `name` defined in the file at `path`.
+    fn contract_range(&self, output: &SolcStandardJsonOutput, path: &str, name: &str) -> Range {
+        let offsets = output
+            .sources
+            .get(path)
+            .and_then(|source| source.ast.get("nodes"))
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .find(|node| {
+                node["nodeType"].as_str() == Some("ContractDefinition")
+                    && node["name"].as_str() == Some(name)
+            })
+            .and_then(|node| node.get("nameLocation").or_else(|| node.get("src")))
+            .and_then(serde_json::Value::as_str)
+            .and_then(|location| {
+                let mut parts = location.split(':');
+                let start = parts.next()?.parse::<usize>().ok()?;
+                let length = parts.next()?.parse::<usize>().ok()?;
+                Some((start, start + length))
+            });
+        match offsets {
+            Some((start, end)) => self.range(path, start, end),
+            None => Range::default(),
+        }
+    }
+
+    /// Returns the range of the `start` and `end` byte offsets in the file at `path`.
+    fn range(&self, path: &str, start: usize, end: usize) -> Range {
+        match self.documents.get(path) {
+            Some(document) => Range::from_offsets(document.text.as_str(), start, end),
+            None => self
+                .resolve(path)
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .map(|content| Range::from_offsets(content.as_str(), start, end))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Resolves the file at the source unit `path` in the base and include paths.
+    fn resolve(&self, path: &str) -> Option<String> {
+        std::iter::once(None)
+            .chain(self.base_path.iter().map(Some))
+            .chain(self.include_paths.iter().map(Some))
+            .map(|directory| match directory {
+                Some(directory) => Path::new(directory).join(path),
+                None => Path::new(path).to_path_buf(),
+            })
+            .find(|path| path.is_file())
+            .map(|path| path.to_string_lossy().into_owned())
+    }
+
+    /// Publishes the `diagnostics` of the file at `path` to `writer`.
+    fn publish<W: Write>(
+        &self,
+        writer: &mut W,
+        path: &str,
+        diagnostics: Vec<Diagnostic>,
+    ) -> anyhow::Result<()> {
+        let uri = match self.documents.get(path) {
+            Some(document) => document.uri.to_owned(),
+            None => path_to_uri(self.resolve(path).as_deref().unwrap_or(path)),
+        };
+        write_message(
+            writer,
+            &serde_json::json!({
+                "jsonrpc": JSON_RPC_VERSION,
+                "method": "textDocument/publishDiagnostics",
+                "params": {
+                    "uri": uri,
+                    "diagnostics": diagnostics,
+                },
+            }),
+        )
+    }
+}
@@ -0,0 +1,117 @@
+//! The language server message transport.
+//!
+//! Every message is a JSON-RPC object preceded by the `Content-Length` header
+//! and an empty line, as in HTTP.
+
+use std::io::BufRead;
+use std::io::Write;
+
+/// The content length header name.
+const CONTENT_LENGTH: &str = "Content-Length";
+
+/// The maximum message content length in bytes.
+///
+/// Guards against allocating whatever a malformed header asks for.
+pub const MAX_CONTENT_LENGTH: usize = 64 * 1024 * 1024;
+
+/// Reads the next message content from `reader`.
+///
+/// Returns `None` at the end of the input, and an error if the content is longer
+/// than [`MAX_CONTENT_LENGTH`].
+pub fn read_message<R: BufRead>(reader: &mut R) -> anyhow::Result<Option<Vec<u8>>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            match content_length {
+                Some(_) => break,
+                None => continue,
+            }
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case(CONTENT_LENGTH) {
+                content_length = Some(value.trim().parse::<usize>().map_err(|error| {
+                    anyhow::anyhow!("Invalid `{CONTENT_LENGTH}` header `{line}`: {error}")
+                })?);
+            }
+        }
+    }
+
+    let content_length = content_length.unwrap_or_default();
+    if content_length > MAX_CONTENT_LENGTH {
+        anyhow::bail!(
+            "The `{CONTENT_LENGTH}` of {content_length} bytes exceeds the limit of {MAX_CONTENT_LENGTH} bytes"
+        );
+    }
+    let mut content = vec![0; content_length];
+    reader.read_exact(content.as_mut_slice())?;
+    Ok(Some(content))
+}
+
+/// Writes the `message` to `writer`.
+pub fn write_message<W: Write, M: serde::Serialize>(
+    writer: &mut W,
+    message: &M,
+) -> anyhow::Result<()> {
+    let content = serde_json::to_vec(message)?;
+    write!(writer, "{CONTENT_LENGTH}: {}\r\n\r\n", content.len())?;
+    writer.write_all(content.as_slice())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Returns the file system path of the `file` scheme `uri`.
+pub fn uri_to_path(uri: &str) -> Option<String> {
+    let path = uri.strip_prefix("file://")?;
+    // The authority is empty for local files.
+    let path = &path[path.find('/')?..];
+
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| path.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    let path = String::from_utf8(decoded).ok()?;
+
+    // Windows paths have the drive letter after the leading slash, like `/C:/contracts`.
+    match path.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => Some(path[1..].to_owned()),
+        _ => Some(path),
+    }
+}
+
+/// Returns the `file` scheme URI of the file system `path`.
+pub fn path_to_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            byte => uri.push_str(format!("%{byte:02X}").as_str()),
+        }
+    }
+    uri
+}
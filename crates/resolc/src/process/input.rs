@@ -31,6 +31,8 @@ pub struct Input {
     pub llvm_arguments: Vec<String>,
     /// The PVM memory configuration.
     pub memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+    /// Whether the build is deterministic.
+    pub deterministic: bool,
     /// Missing unlinked libraries.
     pub missing_libraries: BTreeSet<String>,
     /// Factory dependencies.
@@ -49,6 +51,7 @@ impl Input {
         debug_config: DebugConfig,
         llvm_arguments: Vec<String>,
         memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+        deterministic: bool,
         missing_libraries: BTreeSet<String>,
         factory_dependencies: BTreeSet<String>,
         identifier_paths: BTreeMap<String, String>,
@@ -61,6 +64,7 @@ impl Input {
             debug_config,
             llvm_arguments,
             memory_config,
+            deterministic,
             missing_libraries,
             factory_dependencies,
             identifier_paths,
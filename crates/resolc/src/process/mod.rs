@@ -1,8 +1,6 @@
 //! Process for compiling a single compilation unit.
 
 use revive_solc_json_interface::SolcStandardJsonOutputError;
-use serde::de::DeserializeOwned;
-use serde::Serialize;
 
 use self::input::Input;
 use self::output::Output;
@@ -11,6 +9,8 @@ pub mod input;
 #[cfg(not(target_os = "emscripten"))]
 pub mod native_process;
 pub mod output;
+#[cfg(not(target_os = "emscripten"))]
+pub mod thread_process;
 #[cfg(target_os = "emscripten")]
 pub mod worker_process;
 
@@ -19,8 +19,14 @@ pub trait Process {
     fn run(input: Input) -> anyhow::Result<()>;
 
     /// Runs this process recursively to compile a single contract.
-    fn call<I: Serialize, O: DeserializeOwned>(
-        path: &str,
-        input: I,
-    ) -> Result<O, SolcStandardJsonOutputError>;
+    fn call(path: &str, input: Input) -> Result<Output, SolcStandardJsonOutputError>;
+}
+
+/// Returns the message of the panic `payload`.
+pub(crate) fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(ToString::to_string))
+        .unwrap_or_else(|| "unknown panic".to_owned())
 }
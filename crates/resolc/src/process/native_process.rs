@@ -10,9 +10,8 @@ use revive_common::deserialize_from_slice;
 use revive_common::EXIT_CODE_SUCCESS;
 use revive_solc_json_interface::standard_json::output::error::source_location::SourceLocation;
 use revive_solc_json_interface::SolcStandardJsonOutputError;
-use serde::de::DeserializeOwned;
-use serde::Serialize;
 
+use super::panic_message;
 use super::Input;
 use super::Output;
 use super::Process;
@@ -28,32 +27,7 @@ pub struct NativeProcess;
 
 impl Process for NativeProcess {
     fn run(input: Input) -> anyhow::Result<()> {
-        let source_location = SourceLocation::new(input.contract.identifier.path.to_owned());
-
-        let result = std::thread::Builder::new()
-            .stack_size(crate::RAYON_WORKER_STACK_SIZE)
-            .spawn(move || {
-                input
-                    .contract
-                    .compile(
-                        input.solc_version,
-                        input.optimizer_settings,
-                        input.metadata_hash,
-                        input.debug_config,
-                        &input.llvm_arguments,
-                        input.memory_config,
-                        input.missing_libraries,
-                        input.factory_dependencies,
-                        input.identifier_paths,
-                    )
-                    .map(Output::new)
-                    .map_err(|error| {
-                        SolcStandardJsonOutputError::new_error(error, Some(source_location), None)
-                    })
-            })
-            .expect("Threading error")
-            .join()
-            .expect("Threading error");
+        let result = compile(input);
 
         serde_json::to_writer(std::io::stdout(), &result)
             .map_err(|error| anyhow::anyhow!("Stdout writing error: {error}"))?;
@@ -61,11 +35,7 @@ impl Process for NativeProcess {
         Ok(())
     }
 
-    fn call<I, O>(path: &str, input: I) -> Result<O, SolcStandardJsonOutputError>
-    where
-        I: Serialize,
-        O: DeserializeOwned,
-    {
+    fn call(path: &str, input: Input) -> Result<Output, SolcStandardJsonOutputError> {
         let executable = EXECUTABLE.get().unwrap_or(&DEFAULT_EXECUTABLE);
         let mut command = Command::new(executable.as_path());
         command.stdin(std::process::Stdio::piped());
@@ -114,3 +84,43 @@ impl Process for NativeProcess {
         }
     }
 }
+
+/// Compiles the contract of the `input` on a thread with a sufficiently large stack.
+///
+/// A panic of the compiler is reported as an internal compiler error of the contract.
+pub(crate) fn compile(input: Input) -> Result<Output, SolcStandardJsonOutputError> {
+    let path = input.contract.identifier.path.to_owned();
+    let source_location = SourceLocation::new(path.clone());
+
+    std::thread::Builder::new()
+        .stack_size(crate::RAYON_WORKER_STACK_SIZE)
+        .spawn(move || {
+            input
+                .contract
+                .compile(
+                    input.solc_version,
+                    input.optimizer_settings,
+                    input.metadata_hash,
+                    input.debug_config,
+                    &input.llvm_arguments,
+                    input.memory_config,
+                    input.deterministic,
+                    input.missing_libraries,
+                    input.factory_dependencies,
+                    input.identifier_paths,
+                )
+                .map(Output::new)
+                .map_err(|error| {
+                    SolcStandardJsonOutputError::from_error(error, Some(source_location), None)
+                })
+        })
+        .expect("Threading error")
+        .join()
+        .unwrap_or_else(|payload| {
+            Err(SolcStandardJsonOutputError::new_error(
+                format!("ICE: The compiler panicked: {}", panic_message(payload)),
+                Some(SourceLocation::new(path)),
+                None,
+            ))
+        })
+}
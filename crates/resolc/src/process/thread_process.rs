@@ -0,0 +1,94 @@
+//! Process for compiling a single compilation unit on a thread of this process.
+//!
+//! Used by long-lived compilers, which initialize LLVM only once. LLVM options are
+//! global to a process, hence contracts requiring other options than the ones this
+//! process was initialized with are compiled in a recursive process instead.
+
+use std::sync::OnceLock;
+
+use revive_llvm_context::initialize_llvm;
+use revive_llvm_context::OptimizerSettingsSizeLevel;
+use revive_llvm_context::PolkaVMTarget;
+use revive_solc_json_interface::SolcStandardJsonOutputError;
+
+use super::native_process::compile;
+use super::native_process::NativeProcess;
+use super::Input;
+use super::Output;
+use super::Process;
+
+/// The LLVM options this process was initialized with.
+static LLVM_OPTIONS: OnceLock<LLVMOptions> = OnceLock::new();
+
+/// The process global LLVM options.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct LLVMOptions {
+    /// Whether the newyork size level `z` options are set.
+    newyork_size_level_z: bool,
+    /// The extra LLVM arguments.
+    llvm_arguments: Vec<String>,
+}
+
+impl LLVMOptions {
+    /// A shortcut constructor.
+    pub(crate) fn new(
+        size_level: OptimizerSettingsSizeLevel,
+        use_newyork: bool,
+        llvm_arguments: &[String],
+    ) -> Self {
+        Self {
+            newyork_size_level_z: use_newyork && size_level == OptimizerSettingsSizeLevel::Z,
+            llvm_arguments: llvm_arguments.to_owned(),
+        }
+    }
+}
+
+pub struct ThreadProcess;
+
+impl ThreadProcess {
+    /// Initializes LLVM and enables compiling contracts on threads of this process.
+    pub fn initialize(
+        size_level: OptimizerSettingsSizeLevel,
+        use_newyork: bool,
+        llvm_arguments: &[String],
+    ) {
+        initialize_llvm(
+            PolkaVMTarget::PVM,
+            crate::DEFAULT_EXECUTABLE_NAME,
+            size_level,
+            use_newyork,
+            llvm_arguments,
+        );
+        let _ = LLVM_OPTIONS.set(LLVMOptions::new(size_level, use_newyork, llvm_arguments));
+    }
+
+    /// Whether contracts are compiled on threads of this process.
+    pub fn is_initialized() -> bool {
+        LLVM_OPTIONS.get().is_some()
+    }
+
+    /// Whether contracts requiring the `llvm_options` are compiled on threads of this
+    /// process, reusing its LLVM initialization.
+    pub(crate) fn reuses_llvm(llvm_options: &LLVMOptions) -> bool {
+        LLVM_OPTIONS.get() == Some(llvm_options)
+    }
+}
+
+impl Process for ThreadProcess {
+    fn run(input: Input) -> anyhow::Result<()> {
+        NativeProcess::run(input)
+    }
+
+    fn call(path: &str, input: Input) -> Result<Output, SolcStandardJsonOutputError> {
+        let llvm_options = LLVMOptions::new(
+            input.optimizer_settings.level_middle_end_size,
+            input.contract.ir.is_newyork(),
+            &input.llvm_arguments,
+        );
+        if !Self::reuses_llvm(&llvm_options) {
+            return NativeProcess::call(path, input);
+        }
+
+        compile(input)
+    }
+}
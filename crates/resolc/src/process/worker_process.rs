@@ -2,9 +2,7 @@
 
 use std::ffi::{c_char, c_void, CStr, CString};
 
-use serde::de::DeserializeOwned;
 use serde::Deserialize;
-use serde::Serialize;
 
 use revive_common::deserialize_from_slice;
 use revive_solc_json_interface::standard_json::output::error::source_location::SourceLocation;
@@ -46,13 +44,14 @@ impl Process for WorkerProcess {
                 input.debug_config,
                 &input.llvm_arguments,
                 input.memory_config,
+                input.deterministic,
                 input.missing_libraries,
                 input.factory_dependencies,
                 input.identifier_paths,
             )
             .map(Output::new)
             .map_err(|error| {
-                SolcStandardJsonOutputError::new_error(error, Some(source_location), None)
+                SolcStandardJsonOutputError::from_error(error, Some(source_location), None)
             });
 
         serde_json::to_writer(std::io::stdout(), &result)
@@ -61,11 +60,7 @@ impl Process for WorkerProcess {
         Ok(())
     }
 
-    fn call<I, O>(_path: &str, input: I) -> Result<O, SolcStandardJsonOutputError>
-    where
-        I: Serialize,
-        O: DeserializeOwned,
-    {
+    fn call(_path: &str, input: Input) -> Result<Output, SolcStandardJsonOutputError> {
         let input_json = serde_json::to_vec(&input).expect("Always valid");
         let input_str = String::from_utf8(input_json).expect("Input shall be valid");
         let input_cstring = CString::new(input_str).expect("CString allocation failed");
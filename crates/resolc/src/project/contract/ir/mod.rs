@@ -2,6 +2,7 @@
 
 use std::collections::BTreeSet;
 
+use revive_yul::source_map::SourceMap;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -37,6 +38,14 @@ impl IR {
         }
     }
 
+    /// Takes the Solidity source map of the Yul code.
+    pub fn take_source_map(&mut self) -> SourceMap {
+        match self {
+            IR::Yul(ref mut yul) => std::mem::take(&mut yul.source_map),
+            IR::NewYork(ref mut newyork) => std::mem::take(&mut newyork.source_map),
+        }
+    }
+
     /// Get the list of missing deployable libraries.
     pub fn get_missing_libraries(&self) -> BTreeSet<String> {
         match self {
@@ -4,15 +4,28 @@
 //! 1. Parse Yul source to Yul AST
 //! 2. Translate Yul AST to newyork IR
 //! 3. Run heap optimization analysis
-//! 4. Generate LLVM IR from newyork IR
+//! 4. Size the automatic memory configuration and check the estimated memory
+//!    budget against the memory configuration
+//! 5. Generate LLVM IR from newyork IR
 
 use std::collections::BTreeSet;
 
 use inkwell::debug_info::AsDIScope;
+use revive_common::CodedError;
+use revive_common::ErrorCode;
 use revive_llvm_context::PolkaVMCodeType;
-use revive_newyork::{LlvmCodegen, TranslationResult};
+use revive_newyork::budget::SIZE_GRANULARITY_BYTES;
+use revive_newyork::{LlvmCodegen, MemoryBudget, TranslationResult};
+use revive_solc_json_interface::standard_json::output::error::source_location::SourceLocation;
+use revive_solc_json_interface::PolkaVMDefaultHeapMemorySize;
+use revive_solc_json_interface::PolkaVMDefaultStackMemorySize;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsPolkaVMMemory;
+use revive_solc_json_interface::SolcStandardJsonOutputError;
+use revive_yul::lexer::token::location::Location;
 use revive_yul::lexer::Lexer;
 use revive_yul::parser::statement::object::Object as YulObject;
+use revive_yul::source_map::LocatedError;
+use revive_yul::source_map::SourceMap;
 use serde::{Deserialize, Serialize};
 
 /// The contract compiled via newyork IR.
@@ -20,6 +33,8 @@ use serde::{Deserialize, Serialize};
 pub struct NewYork {
     /// The Yul AST object (source).
     pub yul_object: YulObject,
+    /// The Solidity source map of the Yul code.
+    pub source_map: SourceMap,
 }
 
 impl NewYork {
@@ -34,7 +49,10 @@ impl NewYork {
         let object = YulObject::parse(&mut lexer, None)
             .map_err(|error| anyhow::anyhow!("Yul parsing: {error:?}"))?;
 
-        Ok(Some(Self { yul_object: object }))
+        Ok(Some(Self {
+            yul_object: object,
+            source_map: lexer.into_source_map(),
+        }))
     }
 
     /// Get the list of missing deployable libraries.
@@ -46,12 +64,142 @@ impl NewYork {
     ///
     /// `capture_ir_snapshot` requests the mid-pipeline IR snapshot, which is dumped via
     /// [`revive_llvm_context::DebugConfig`] when a debug output directory is configured.
-    fn translate_to_ir(&self, capture_ir_snapshot: bool) -> anyhow::Result<TranslationResult> {
-        revive_newyork::translate_yul_object(&self.yul_object, capture_ir_snapshot)
-            .map_err(|e| anyhow::anyhow!("newyork IR translation: {e}"))
+    pub fn translate(self, capture_ir_snapshot: bool) -> anyhow::Result<NewYorkTranslation> {
+        let translation_result =
+            revive_newyork::translate_yul_object(&self.yul_object, capture_ir_snapshot)
+                .map_err(|error| located_error(error.error_code(), error.location(), error))?;
+        Ok(NewYorkTranslation {
+            yul_object: self.yul_object,
+            translation_result,
+        })
     }
 }
 
+/// The contract translated to newyork IR.
+pub struct NewYorkTranslation {
+    /// The Yul AST object (source).
+    yul_object: YulObject,
+    /// The newyork IR and its analysis results.
+    translation_result: TranslationResult,
+}
+
+impl NewYorkTranslation {
+    /// Returns the estimated memory budget.
+    pub fn memory_budget(&self) -> &MemoryBudget {
+        &self.translation_result.memory_budget
+    }
+
+    /// Prints the optimized newyork IR, annotated with the inferred type widths.
+    pub fn print_ir(&self) -> String {
+        revive_newyork::print_object_with_types(
+            &self.translation_result.object,
+            &self.translation_result.type_info,
+        )
+    }
+
+    /// Resolves the automatic `memory_config` from the estimated memory budget.
+    ///
+    /// Sizes given explicitly are kept. Memory which can't be bounded statically gets at
+    /// least the default size, and the stack never less than [`AUTO_MIN_STACK_SIZE`].
+    /// Other memory configurations are returned unchanged.
+    pub fn memory_config(
+        &self,
+        memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+    ) -> SolcStandardJsonInputSettingsPolkaVMMemory {
+        if !memory_config.auto {
+            return memory_config;
+        }
+
+        let budget = self.memory_budget();
+        let heap_size = budget.recommended_heap_size().unwrap_or_else(|| {
+            budget
+                .heap_high_water
+                .next_multiple_of(SIZE_GRANULARITY_BYTES)
+                .max(PolkaVMDefaultHeapMemorySize as u64)
+        });
+        let stack_size = budget
+            .recommended_stack_size()
+            .map_or(PolkaVMDefaultStackMemorySize as u64, |size| {
+                size.max(AUTO_MIN_STACK_SIZE)
+            });
+        SolcStandardJsonInputSettingsPolkaVMMemory {
+            heap_size: memory_config
+                .heap_size
+                .or(Some(u32::try_from(heap_size).unwrap_or(u32::MAX))),
+            stack_size: memory_config
+                .stack_size
+                .or(Some(u32::try_from(stack_size).unwrap_or(u32::MAX))),
+            auto: true,
+        }
+    }
+
+    /// Checks the estimated memory budget against the `memory_config`.
+    ///
+    /// The checks are warnings: static heap accesses past the heap size only trap if they
+    /// are reached, the stack estimate is a lower bound and recursion can't be bounded.
+    /// Only the newyork pipeline estimates the memory budget.
+    pub fn check_memory_budget(
+        &self,
+        memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+        path: &str,
+    ) -> Vec<SolcStandardJsonOutputError> {
+        let budget = self.memory_budget();
+        let heap_size = memory_config
+            .heap_size
+            .unwrap_or(PolkaVMDefaultHeapMemorySize) as u64;
+        let stack_size = memory_config
+            .stack_size
+            .unwrap_or(PolkaVMDefaultStackMemorySize) as u64;
+
+        let mut messages = Vec::new();
+        if budget.heap_high_water > heap_size {
+            messages.push(SolcStandardJsonOutputError::new_warning(
+                format!(
+                    "The contract statically accesses {} bytes of heap memory, exceeding the heap size of {heap_size} bytes, which traps at runtime if reached.
+Set `polkavm.memoryConfig.heapSize` (`--heap-size`) to at least {}.",
+                    budget.heap_high_water, budget.heap_high_water,
+                ),
+                Some(SourceLocation::new(path.to_owned())),
+                None,
+            )
+            .with_code(ErrorCode::HeapSize));
+        }
+        if budget.stack_estimate > stack_size {
+            messages.push(SolcStandardJsonOutputError::new_warning(
+                format!(
+                    "The contract is estimated to use at least {} bytes of stack in call chains {} frames deep, exceeding the stack size of {stack_size} bytes.
+Set `polkavm.memoryConfig.stackSize` (`--stack-size`) to at least {}.",
+                    budget.stack_estimate, budget.max_call_depth, budget.stack_estimate,
+                ),
+                Some(SourceLocation::new(path.to_owned())),
+                None,
+            )
+            .with_code(ErrorCode::StackSize));
+        }
+        if !budget.recursive_functions.is_empty() {
+            messages.push(
+                SolcStandardJsonOutputError::new_warning(
+                    format!(
+                        "The stack usage of the recursive functions {} can't be bounded statically.
+Deep recursion may exceed the stack size of {stack_size} bytes and trap at runtime.",
+                        budget.recursive_functions.join(", "),
+                    ),
+                    Some(SourceLocation::new(path.to_owned())),
+                    None,
+                )
+                .with_code(ErrorCode::Recursion),
+            );
+        }
+        messages
+    }
+}
+
+/// The minimum stack size of the automatic memory configuration in bytes.
+///
+/// The stack estimate doesn't cover the values spilled by the register allocator, which
+/// dominate the stack usage of small contracts.
+const AUTO_MIN_STACK_SIZE: u64 = 16 * 1024;
+
 /// Code-size threshold for emitting the outlined single-word keccak256 helper.
 ///
 /// The helper body costs ~150 bytes; each call site it replaces saves ~20 bytes through
@@ -64,7 +212,7 @@ const KECCAK_SINGLE_THRESHOLD: usize = 8;
 /// sites bloats the binary beyond the call-overhead savings on PolkaVM.
 const SBRK_NOINLINE_THRESHOLD: usize = 30;
 
-impl revive_llvm_context::PolkaVMWriteLLVM for NewYork {
+impl revive_llvm_context::PolkaVMWriteLLVM for NewYorkTranslation {
     fn declare(&mut self, context: &mut revive_llvm_context::PolkaVMContext) -> anyhow::Result<()> {
         self.yul_object.declare(context)?;
 
@@ -81,8 +229,7 @@ impl revive_llvm_context::PolkaVMWriteLLVM for NewYork {
     }
 
     fn into_llvm(self, context: &mut revive_llvm_context::PolkaVMContext) -> anyhow::Result<()> {
-        let translation_result =
-            self.translate_to_ir(context.debug_config().output_directory.is_some())?;
+        let translation_result = self.translation_result;
         let ir_object = translation_result.object;
         let heap_opt = translation_result.heap_opt;
         let type_info = translation_result.type_info;
@@ -230,7 +377,13 @@ impl revive_llvm_context::PolkaVMWriteLLVM for NewYork {
         let mut codegen = LlvmCodegen::new(heap_opt, type_info, inline_decisions);
         codegen
             .generate_object(&ir_object, context)
-            .map_err(|e| anyhow::anyhow!("newyork LLVM codegen: {e}"))?;
+            .map_err(|error| {
+                located_error(
+                    error.error_code(),
+                    error.location(),
+                    format!("newyork LLVM codegen: {error}"),
+                )
+            })?;
 
         context.set_debug_location(
             self.yul_object.location.line,
@@ -243,3 +396,17 @@ impl revive_llvm_context::PolkaVMWriteLLVM for NewYork {
         Ok(())
     }
 }
+
+/// Converts a newyork error into an error with the `error_code`, located at the Yul
+/// `location` of the statement, function or object code it occurs in.
+fn located_error(
+    error_code: ErrorCode,
+    location: Option<Location>,
+    message: impl std::fmt::Display,
+) -> anyhow::Error {
+    let error = anyhow::Error::from(CodedError::new(error_code, message));
+    match location {
+        Some(location) => LocatedError::locate(error, location),
+        None => error,
+    }
+}
@@ -7,12 +7,15 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use revive_yul::parser::statement::object::Object;
+use revive_yul::source_map::SourceMap;
 
 /// The contract Yul source code.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Yul {
     /// The Yul AST object.
     pub object: Object,
+    /// The Solidity source map of the Yul code.
+    pub source_map: SourceMap,
 }
 
 impl Yul {
@@ -26,7 +29,10 @@ impl Yul {
         let object = Object::parse(&mut lexer, None)
             .map_err(|error| anyhow::anyhow!("Yul parsing: {error:?}"))?;
 
-        Ok(Some(Self { object }))
+        Ok(Some(Self {
+            object,
+            source_map: lexer.into_source_map(),
+        }))
     }
 
     /// Get the list of missing deployable libraries.
@@ -1,12 +1,16 @@
 //! The Solidity contract metadata.
 
 use revive_llvm_context::OptimizerSettings;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsPolkaVMMemory;
 use serde::Serialize;
 
 use crate::ResolcVersion;
 
 /// The Solidity contract metadata.
 /// Is used to append the metadata hash to the contract bytecode.
+///
+/// In deterministic mode, the remaining inputs affecting the PVM output are recorded
+/// as well, so that builds can be reproduced.
 #[derive(Debug, Serialize)]
 pub struct Metadata {
     /// The `solc` metadata.
@@ -19,6 +23,15 @@ pub struct Metadata {
     pub optimizer_settings: OptimizerSettings,
     /// The extra LLVM arguments give used for manual control.
     pub llvm_arguments: Vec<String>,
+    /// The PVM memory configuration, in deterministic mode.
+    #[serde(rename = "memoryConfig", skip_serializing_if = "Option::is_none")]
+    pub memory_config: Option<SolcStandardJsonInputSettingsPolkaVMMemory>,
+    /// The IR pipeline, `yul` or `newyork`, in deterministic mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline: Option<String>,
+    /// Whether source debug information is emitted, in deterministic mode.
+    #[serde(rename = "debugInformation", skip_serializing_if = "Option::is_none")]
+    pub debug_information: Option<bool>,
 }
 
 impl Metadata {
@@ -28,6 +41,10 @@ impl Metadata {
         solc_version: Option<semver::Version>,
         optimizer_settings: OptimizerSettings,
         llvm_arguments: Vec<String>,
+        memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+        use_newyork: bool,
+        debug_information: bool,
+        deterministic: bool,
     ) -> Self {
         Self {
             solc_metadata,
@@ -35,6 +52,9 @@ impl Metadata {
             revive_version: ResolcVersion::default().long,
             optimizer_settings,
             llvm_arguments,
+            memory_config: deterministic.then_some(memory_config),
+            pipeline: deterministic.then(|| crate::pipeline_name(use_newyork).to_owned()),
+            debug_information: deterministic.then_some(debug_information),
         }
     }
 }
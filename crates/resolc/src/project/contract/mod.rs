@@ -3,8 +3,10 @@
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 
+use revive_common::CodedError;
 use revive_common::ContractIdentifier;
 use revive_common::Keccak256;
+use revive_common::LocatedError;
 use revive_common::MetadataHash;
 use revive_common::ObjectFormat;
 use revive_llvm_context::DebugConfig;
@@ -13,7 +15,10 @@ use revive_llvm_context::OptimizerSettings;
 use revive_llvm_context::PolkaVMContext;
 use revive_llvm_context::PolkaVMContextSolidityData;
 use revive_llvm_context::PolkaVMContextYulData;
+use revive_solc_json_interface::standard_json::output::error::source_location::SourceLocation;
 use revive_solc_json_interface::SolcStandardJsonInputSettingsPolkaVMMemory;
+use revive_yul::lexer::token::location::Location;
+use revive_yul::source_map::SourceMap;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -22,6 +27,7 @@ use revive_llvm_context::PolkaVMWriteLLVM;
 use crate::build::contract::Contract as ContractBuild;
 use crate::solc::version::Version as SolcVersion;
 
+use self::ir::newyork::NewYorkTranslation;
 use self::ir::IR;
 use self::metadata::Metadata;
 
@@ -60,20 +66,50 @@ impl Contract {
     }
 
     /// Compiles the specified contract, setting its build artifacts.
+    ///
+    /// Errors located in the Yul code are mapped back to the Solidity source code.
     pub fn compile(
-        self,
+        mut self,
         solc_version: Option<SolcVersion>,
         optimizer_settings: OptimizerSettings,
         metadata_hash: MetadataHash,
         mut debug_config: DebugConfig,
         llvm_arguments: &[String],
         memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+        deterministic: bool,
         missing_libraries: BTreeSet<String>,
         factory_dependencies: BTreeSet<String>,
         identifier_paths: BTreeMap<String, String>,
     ) -> anyhow::Result<ContractBuild> {
         let llvm = inkwell::context::Context::create();
         let optimizer = Optimizer::new(optimizer_settings);
+        debug_config.set_contract_path(&self.identifier.full_path);
+        let source_map = self.ir.take_source_map();
+        let emit_source_map = debug_config.emit_source_map;
+
+        // The newyork translation sizes the automatic memory configuration recorded in
+        // the metadata, hence it has to happen first.
+        let (yul, translation) = match self.ir {
+            IR::Yul(yul) => (Some(yul), None),
+            IR::NewYork(newyork) => {
+                let translation = newyork
+                    .translate(debug_config.output_directory.is_some())
+                    .map_err(|error| {
+                        LocatedError::<Location>::map(error, |error| {
+                            CodedError::wrap(error, |message| {
+                                format!("NewYork IR generator: {message}")
+                            })
+                        })
+                    })
+                    .map_err(|error| locate_error(error, &source_map))?;
+                (None, Some(translation))
+            }
+        };
+        let memory_config = match translation.as_ref() {
+            Some(translation) => translation.memory_config(memory_config),
+            None => memory_config,
+        };
+
         let metadata = Metadata::new(
             self.metadata_json,
             solc_version
@@ -81,6 +117,10 @@ impl Contract {
                 .map(|version| version.default.to_owned()),
             optimizer.settings().to_owned(),
             llvm_arguments.to_owned(),
+            memory_config,
+            translation.is_some(),
+            debug_config.emit_debug_info,
+            deterministic,
         );
         let metadata_json = serde_json::to_value(&metadata).expect("Always valid");
         let metadata_json_bytes = serde_json::to_vec(&metadata_json).expect("Always valid");
@@ -89,12 +129,31 @@ impl Contract {
             MetadataHash::IPFS => todo!("IPFS hash isn't supported yet"),
             MetadataHash::None => None,
         };
-        debug_config.set_contract_path(&self.identifier.full_path);
 
         let full_path = self.identifier.full_path.as_str();
-        let build = match self.ir {
-            IR::Yul(yul) => compile_ir(
-                yul,
+        let mut messages = Vec::new();
+        let newyork_ir = translation
+            .as_ref()
+            .filter(|_| debug_config.emit_newyork_ir)
+            .map(NewYorkTranslation::print_ir);
+        let mut build = match translation {
+            Some(translation) => {
+                messages = translation.check_memory_budget(memory_config, full_path);
+                compile_ir(
+                    translation,
+                    &llvm,
+                    optimizer,
+                    true,
+                    debug_config,
+                    memory_config,
+                    identifier_paths,
+                    full_path,
+                    metadata_bytes,
+                )
+                .map_err(|error| locate_error(error, &source_map))?
+            }
+            None => compile_ir(
+                yul.expect("Always exists without the newyork translation"),
                 &llvm,
                 optimizer,
                 false,
@@ -103,19 +162,10 @@ impl Contract {
                 identifier_paths,
                 full_path,
                 metadata_bytes,
-            )?,
-            IR::NewYork(newyork) => compile_ir(
-                newyork,
-                &llvm,
-                optimizer,
-                true,
-                debug_config,
-                memory_config,
-                identifier_paths,
-                full_path,
-                metadata_bytes,
-            )?,
+            )
+            .map_err(|error| locate_error(error, &source_map))?,
         };
+        build.newyork_ir = newyork_ir;
 
         Ok(ContractBuild::new(
             self.identifier,
@@ -124,6 +174,8 @@ impl Contract {
             missing_libraries,
             factory_dependencies,
             ObjectFormat::ELF,
+            emit_source_map.then_some(source_map),
+            messages,
         ))
     }
 
@@ -137,6 +189,27 @@ impl Contract {
     }
 }
 
+/// Locates the `error` at the Solidity source code range of the Yul statement it occurs in.
+///
+/// The location is dropped if the Yul code is not annotated with a Solidity source location.
+fn locate_error(error: anyhow::Error, source_map: &SourceMap) -> anyhow::Error {
+    let located_error = match error.downcast::<LocatedError<Location>>() {
+        Ok(located_error) => located_error,
+        Err(error) => return error,
+    };
+    match source_map.resolve(located_error.location) {
+        Some(annotation) => LocatedError::locate(
+            located_error.error,
+            SourceLocation::new_with_offsets(
+                annotation.file.to_owned(),
+                annotation.start,
+                annotation.end,
+            ),
+        ),
+        None => located_error.error,
+    }
+}
+
 /// Lowers an IR object to an LLVM module and links it into a PolkaVM build.
 ///
 /// Shared by the `Yul` and `NewYork` arms of [`Contract::compile`], which differ
@@ -168,8 +241,13 @@ fn compile_ir<T: PolkaVMWriteLLVM>(
     } else {
         "LLVM IR generator"
     };
-    ir.into_llvm(&mut context)
-        .map_err(|error| anyhow::anyhow!("{generator_error_label}: {error}"))?;
+    ir.into_llvm(&mut context).map_err(|error| {
+        LocatedError::<Location>::map(error, |error| {
+            CodedError::wrap(error, |message| {
+                format!("{generator_error_label}: {message}")
+            })
+        })
+    })?;
 
     context.build(full_path, metadata_bytes)
 }
@@ -13,6 +13,8 @@ use revive_common::MetadataHash;
 use revive_llvm_context::DebugConfig;
 use revive_llvm_context::OptimizerSettings;
 use revive_solc_json_interface::SolcStandardJsonInputSettingsPolkaVMMemory;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsSelection;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsSelectionFileFlag;
 use revive_solc_json_interface::SolcStandardJsonInputSource;
 use revive_solc_json_interface::SolcStandardJsonOutputError;
 use serde::Deserialize;
@@ -24,6 +26,7 @@ use revive_solc_json_interface::SolcStandardJsonOutput;
 
 use crate::build::contract::Contract as ContractBuild;
 use crate::build::Build;
+use crate::cache::Cache;
 use crate::missing_libraries::MissingLibraries;
 use crate::process::input::Input as ProcessInput;
 use crate::process::Process;
@@ -68,23 +71,33 @@ impl Project {
     }
 
     /// Compiles all contracts, returning their build artifacts.
+    ///
+    /// Contracts found in the `cache` are not recompiled. The cache is bypassed
+    /// if the IRs are dumped into the debug output directory.
+    ///
+    /// The IRs and the source map a contract keeps in its build artifacts are
+    /// the ones its file selects in the standard JSON `output_selection`.
     pub fn compile(
         self,
         messages: &mut Vec<SolcStandardJsonOutputError>,
         optimizer_settings: OptimizerSettings,
         metadata_hash: MetadataHash,
         debug_config: &DebugConfig,
+        output_selection: Option<&SolcStandardJsonInputSettingsSelection>,
         llvm_arguments: &[String],
         memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+        deterministic: bool,
+        cache: Option<&Cache>,
     ) -> anyhow::Result<Build> {
         let deployed_libraries = self.libraries.as_paths();
+        let cache = cache.filter(|_| debug_config.output_directory.is_none());
 
         #[cfg(feature = "parallel")]
         let iter = self.contracts.into_par_iter();
         #[cfg(not(feature = "parallel"))]
         let iter = self.contracts.into_iter();
 
-        let results = iter
+        let mut results = iter
             .map(|(path, mut contract)| {
                 let factory_dependencies = contract
                     .ir
@@ -98,18 +111,44 @@ impl Project {
                     })
                     .collect();
                 let missing_libraries = contract.get_missing_libraries(&deployed_libraries);
+                let mut debug_config = debug_config.clone();
+                if let Some(output_selection) = output_selection {
+                    let path = &contract.identifier.path;
+                    debug_config.emit_newyork_ir = output_selection.contains(
+                        path,
+                        SolcStandardJsonInputSettingsSelectionFileFlag::NewYorkIr,
+                    );
+                    debug_config.emit_llvm_ir = output_selection
+                        .contains(path, SolcStandardJsonInputSettingsSelectionFileFlag::LLVMIr);
+                    debug_config.emit_llvm_ir_optimized = output_selection.contains(
+                        path,
+                        SolcStandardJsonInputSettingsSelectionFileFlag::LLVMIrOptimized,
+                    );
+                    debug_config.emit_source_map = output_selection.contains(
+                        path,
+                        SolcStandardJsonInputSettingsSelectionFileFlag::AnnotatedAssembly,
+                    );
+                }
                 let input = ProcessInput::new(
                     contract,
                     self.version.clone(),
                     metadata_hash,
                     optimizer_settings.clone(),
-                    debug_config.clone(),
+                    debug_config,
                     llvm_arguments.to_owned(),
                     memory_config,
+                    deterministic,
                     missing_libraries,
                     factory_dependencies,
                     self.identifier_paths.clone(),
                 );
+                let cache_key = cache.map(|_| Cache::key(&input));
+                if let Some(build) = cache
+                    .zip(cache_key.as_deref())
+                    .and_then(|(cache, key)| cache.get(key))
+                {
+                    return (path, Ok(build));
+                }
                 let result: Result<ProcessOutput, SolcStandardJsonOutputError> = {
                     #[cfg(target_os = "emscripten")]
                     {
@@ -117,13 +156,23 @@ impl Project {
                     }
                     #[cfg(not(target_os = "emscripten"))]
                     {
-                        crate::NativeProcess::call(path.as_str(), input)
+                        if crate::ThreadProcess::is_initialized() {
+                            crate::ThreadProcess::call(path.as_str(), input)
+                        } else {
+                            crate::NativeProcess::call(path.as_str(), input)
+                        }
                     }
                 };
                 let result = result.map(|output| output.build);
+                if let (Some(cache), Some(key), Ok(build)) = (cache, cache_key, result.as_ref()) {
+                    cache.insert(key.as_str(), build);
+                }
                 (path, result)
             })
             .collect::<BTreeMap<String, Result<ContractBuild, SolcStandardJsonOutputError>>>();
+        for build in results.values_mut().flatten() {
+            messages.append(&mut build.messages);
+        }
         Ok(Build::new(results, messages))
     }
 
@@ -27,6 +27,10 @@ pub struct Arguments {
     #[arg(long = "supported-solc-versions")]
     pub supported_solc_versions: bool,
 
+    /// Print the explanation of a resolc error code, for example `R2001`, and exit.
+    #[arg(long = "explain", value_name = "CODE")]
+    pub explain: Option<String>,
+
     /// Specify the input paths and remappings.
     /// If an argument contains a '=', it is considered a remapping.
     /// Multiple Solidity files can be passed in the default Solidity mode.
@@ -57,6 +61,13 @@ pub struct Arguments {
     #[arg(long = "overwrite")]
     pub overwrite: bool,
 
+    /// Cache the contract builds in the specified directory.
+    /// Contracts with unchanged IR and settings are not recompiled by later runs.
+    ///
+    /// The cache is bypassed if the IRs are dumped with `--debug-output-dir`.
+    #[arg(long = "cache-dir")]
+    pub cache_directory: Option<PathBuf>,
+
     /// Set the LLVM optimization parameter -O[0 | 1 | 2 | 3 | s | z].
     /// Use `3` for best performance and `z` for minimal size.
     /// Controls the LLVM optimization level only; independent of `--newyork`,
@@ -126,6 +137,29 @@ pub struct Arguments {
     #[arg(long)]
     pub link: bool,
 
+    /// Switch to inspection mode, printing the section sizes, imported host functions,
+    /// exported entry points and immutable data size of PVM blobs and ELF objects.
+    ///
+    /// Unlinked ELF objects additionally list their unresolved library and factory dependency symbols.
+    /// Symbols naming one of the input files are reported as factory dependencies.
+    #[arg(long)]
+    pub inspect: bool,
+
+    /// Switch to server mode, serving JSON-RPC requests to compile standard JSON inputs.
+    ///
+    /// Requests are read line by line from stdin, or from connections to the Unix domain socket
+    /// at the given path. LLVM is initialized once with the `--optimization`, `--newyork` and
+    /// `--llvm-arg` options. Requests needing other LLVM options are compiled in recursive processes.
+    #[arg(long = "server")]
+    pub server: Option<Option<PathBuf>>,
+
+    /// Print the linker mode report or the inspection mode results as JSON to stdout.
+    ///
+    /// The linker mode report has the linked and unlinked files, with the unresolved
+    /// library symbols and the factory dependency hashes of each file.
+    #[arg(long = "json")]
+    pub json: bool,
+
     /// Set the metadata hash type.
     /// Available types: `none`, `ipfs`, `keccak256`.
     #[arg(long, default_value_t = MetadataHash::Keccak256)]
@@ -139,6 +173,21 @@ pub struct Arguments {
     #[arg(long = "bin")]
     pub output_binary: bool,
 
+    /// Output a report attributing the PolkaVM code size of the contracts to their functions:
+    /// Solidity functions, Yul functions, newyork outlined helpers, runtime functions and builtins.
+    ///
+    /// Printed as a table, or written as JSON into `<file>:<contract>.size.json` files with `--output-dir`.
+    /// Inlined functions are attributed to their caller, unless source debug information is emitted.
+    #[arg(long = "size-report")]
+    pub size_report: bool,
+
+    /// Switch to deterministic mode, for builds reproducible across hosts.
+    ///
+    /// Input paths and remapping targets are turned into POSIX paths relative to the working directory.
+    /// Options embedding host paths into the output, like source debug information, are refused.
+    #[arg(long = "deterministic")]
+    pub deterministic: bool,
+
     /// Output metadata of the compiled project.
     #[arg(long = "metadata")]
     pub output_metadata: bool,
@@ -238,6 +287,14 @@ impl Arguments {
             ));
         }
 
+        if self.explain.is_some() && std::env::args().count() > 3 {
+            messages.push(SolcStandardJsonOutputError::new_error(
+                "No other options are allowed while explaining an error code.",
+                None,
+                None,
+            ));
+        }
+
         if self.metadata_hash == MetadataHash::IPFS {
             messages.push(SolcStandardJsonOutputError::new_error(
                 format!(
@@ -255,6 +312,8 @@ impl Arguments {
             self.combined_json.is_some(),
             self.standard_json.is_some(),
             self.link,
+            self.inspect,
+            self.server.is_some(),
         ]
         .iter()
         .filter(|&&x| x)
@@ -262,7 +321,15 @@ impl Arguments {
         let acceptable_count = 1 + self.standard_json.is_some() as usize;
         if modes > acceptable_count {
             messages.push(SolcStandardJsonOutputError::new_error(
-                "Only one mode is allowed at the same time: Yul, combined JSON, standard JSON, link.",
+                "Only one mode is allowed at the same time: Yul, combined JSON, standard JSON, link, inspect, server.",
+                None,
+                None,
+            ));
+        }
+
+        if self.json && !self.link && !self.inspect {
+            messages.push(SolcStandardJsonOutputError::new_error(
+                "JSON output is only supported in linker and inspection modes.",
                 None,
                 None,
             ));
@@ -314,6 +381,53 @@ impl Arguments {
             }
         }
 
+        if self.server.is_some() {
+            if !self.inputs.is_empty() {
+                messages.push(SolcStandardJsonOutputError::new_error(
+                    "Input files must be passed via compile requests in server mode.",
+                    None,
+                    None,
+                ));
+            }
+            if self.output_directory.is_some() {
+                messages.push(SolcStandardJsonOutputError::new_error(
+                    "Output directory cannot be used in server mode.",
+                    None,
+                    None,
+                ));
+            }
+        }
+
+        if self.cache_directory.is_some() && (self.link || self.inspect) {
+            messages.push(SolcStandardJsonOutputError::new_error(
+                "The compilation cache is not used in linker and inspection modes.",
+                None,
+                None,
+            ));
+        }
+
+        if self.size_report
+            && (self.combined_json.is_some()
+                || self.standard_json.is_some()
+                || self.link
+                || self.inspect
+                || self.server.is_some())
+        {
+            messages.push(SolcStandardJsonOutputError::new_error(
+                "The size report is only supported in the default Solidity and Yul modes.",
+                None,
+                None,
+            ));
+        }
+
+        if self.deterministic && self.emit_source_debug_info {
+            messages.push(SolcStandardJsonOutputError::new_error(
+                resolc::deterministic::DEBUG_INFORMATION_ERROR,
+                None,
+                None,
+            ));
+        }
+
         if self.combined_json.is_some() && (self.output_assembly || self.output_binary) {
             messages.push(SolcStandardJsonOutputError::new_error(
                 "Cannot output assembly or binary outside of JSON in combined JSON mode.",
@@ -424,6 +538,13 @@ impl Arguments {
                     None,
                 ));
             }
+            if self.deterministic {
+                messages.push(SolcStandardJsonOutputError::new_error(
+                    "Deterministic mode must be enabled in standard JSON input polkavm settings.",
+                    None,
+                    None,
+                ));
+            }
         }
 
         messages
@@ -4,7 +4,9 @@ use std::{io::Write, path::PathBuf};
 
 use clap::error::ErrorKind;
 use resolc::Process;
-use revive_common::{deserialize_from_str, EVMVersion, EXIT_CODE_FAILURE, EXIT_CODE_SUCCESS};
+use revive_common::{
+    deserialize_from_str, EVMVersion, ErrorCode, EXIT_CODE_FAILURE, EXIT_CODE_SUCCESS,
+};
 use revive_llvm_context::{initialize_llvm, DebugConfig, OptimizerSettings, PolkaVMTarget};
 use revive_solc_json_interface::{
     ResolcWarning, SolcStandardJsonInputSettingsPolkaVMMemory,
@@ -43,7 +45,7 @@ fn main() -> anyhow::Result<()> {
                 .expect("Stderr writing error");
         }
         if let Err(error) = main_inner(arguments, &mut messages) {
-            messages.push(SolcStandardJsonOutputError::new_error(error, None, None));
+            messages.push(SolcStandardJsonOutputError::from_error(error, None, None));
         }
     }
 
@@ -96,6 +98,19 @@ fn main_inner(
         return Ok(());
     }
 
+    if let Some(code) = arguments.explain.as_deref() {
+        let error_code = ErrorCode::from_code(code)
+            .ok_or_else(|| anyhow::anyhow!("Unknown error code `{code}`."))?;
+        writeln!(
+            std::io::stdout(),
+            "{}: {}\n\n{}",
+            error_code.code(),
+            error_code.title(),
+            error_code.explanation()
+        )?;
+        return Ok(());
+    }
+
     #[cfg(feature = "parallel")]
     rayon::ThreadPoolBuilder::new()
         .stack_size(resolc::RAYON_WORKER_STACK_SIZE)
@@ -149,7 +164,12 @@ fn main_inner(
         None => DebugConfig::new(None, arguments.emit_source_debug_info),
     };
 
-    let (input_files, remappings) = arguments.split_input_files_and_remappings()?;
+    let (mut input_files, mut remappings) = arguments.split_input_files_and_remappings()?;
+    if arguments.deterministic {
+        resolc::deterministic::check_libraries(arguments.libraries.as_slice())?;
+        (input_files, remappings) =
+            resolc::deterministic::normalize_input_paths(input_files.as_slice(), remappings)?;
+    }
 
     let suppressed_warnings = ResolcWarning::try_from_strings(
         arguments.suppress_warnings.unwrap_or_default().as_slice(),
@@ -181,6 +201,10 @@ fn main_inner(
     );
 
     let use_newyork = arguments.newyork;
+    let cache = arguments
+        .cache_directory
+        .map(resolc::cache::Cache::new)
+        .transpose()?;
 
     let build = if arguments.yul {
         resolc::yul(
@@ -194,6 +218,9 @@ fn main_inner(
             &arguments.llvm_arguments,
             memory_config,
             use_newyork,
+            arguments.deterministic,
+            arguments.size_report,
+            cache.as_ref(),
         )
     } else if let Some(standard_json) = arguments.standard_json {
         resolc::standard_json(
@@ -207,8 +234,31 @@ fn main_inner(
             debug_config,
             arguments.detect_missing_libraries,
             use_newyork,
+            cache.as_ref(),
         )?;
         return Ok(());
+    } else if let Some(path) = arguments.server {
+        #[cfg(not(target_os = "emscripten"))]
+        resolc::ThreadProcess::initialize(
+            optimizer_settings.level_middle_end_size,
+            use_newyork,
+            &arguments.llvm_arguments,
+        );
+        let server = resolc::server::Server::new(
+            &solc,
+            arguments.metadata_hash,
+            arguments.base_path,
+            arguments.include_paths,
+            arguments.allow_paths,
+            debug_config,
+            use_newyork,
+            cache.as_ref(),
+        );
+        match path {
+            Some(path) => server.serve_socket(path.as_path())?,
+            None => server.serve_stdio()?,
+        }
+        return Ok(());
     } else if let Some(format) = arguments.combined_json {
         resolc::combined_json(
             &solc,
@@ -231,10 +281,33 @@ fn main_inner(
             arguments.llvm_arguments,
             memory_config,
             use_newyork,
+            arguments.deterministic,
+            cache.as_ref(),
         )?;
         return Ok(());
     } else if arguments.link {
-        return resolc::link(arguments.inputs, arguments.libraries);
+        let report = resolc::link(arguments.inputs, arguments.libraries)?;
+        if arguments.json {
+            serde_json::to_writer(std::io::stdout(), &report)?;
+            writeln!(std::io::stdout())?;
+            return Ok(());
+        }
+        for path in report.unlinked() {
+            writeln!(std::io::stdout(), "Warning: file '{path}' still unresolved")?;
+        }
+        writeln!(std::io::stdout(), "Linking completed")?;
+        return Ok(());
+    } else if arguments.inspect {
+        let inspections = resolc::inspect(arguments.inputs)?;
+        if arguments.json {
+            serde_json::to_writer(std::io::stdout(), &inspections)?;
+            writeln!(std::io::stdout())?;
+            return Ok(());
+        }
+        for (path, inspection) in inspections {
+            writeln!(std::io::stdout(), "{path}:\n{inspection}")?;
+        }
+        return Ok(());
     } else {
         resolc::standard_output(
             &solc,
@@ -254,6 +327,9 @@ fn main_inner(
             arguments.llvm_arguments,
             memory_config,
             use_newyork,
+            arguments.deterministic,
+            arguments.size_report,
+            cache.as_ref(),
         )
     }?;
 
@@ -0,0 +1,141 @@
+//! Solidity to PolkaVM language server binary.
+
+#[cfg(not(target_os = "emscripten"))]
+use std::{io::Write, path::PathBuf};
+
+#[cfg(not(target_os = "emscripten"))]
+use clap::Parser;
+#[cfg(not(target_os = "emscripten"))]
+use revive_llvm_context::OptimizerSettings;
+#[cfg(not(target_os = "emscripten"))]
+use revive_solc_json_interface::{
+    PolkaVMDefaultHeapMemorySize, PolkaVMDefaultStackMemorySize,
+    SolcStandardJsonInputSettingsPolkaVMMemory,
+};
+
+#[cfg(target_env = "musl")]
+#[global_allocator]
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+/// Serves the Language Server Protocol over stdio, reporting the `solc` diagnostics
+/// together with the PVM compatibility lints, unsupported constructs, heap and stack
+/// budget and code size of the contracts in the open documents.
+/// Example: resolc-lsp --solc ./solc -O3
+#[cfg(not(target_os = "emscripten"))]
+#[derive(Debug, Parser)]
+#[command(name = "The PolkaVM Solidity language server")]
+struct Arguments {
+    /// Print the version and exit.
+    #[arg(long = "version")]
+    version: bool,
+
+    /// Serve over stdio. This is the only transport and the default, accepted
+    /// since LSP clients pass it.
+    #[arg(long = "stdio")]
+    #[allow(dead_code)]
+    stdio: bool,
+
+    /// Set the given path as the root of the source tree instead of the root of the filesystem.
+    /// Passed to `solc` without changes.
+    #[arg(long = "base-path")]
+    base_path: Option<String>,
+
+    /// Make an additional source directory available to the default import callback.
+    /// Can be used multiple times. Can only be used if the base path has a non-empty value.
+    /// Passed to `solc` without changes.
+    #[arg(long = "include-path")]
+    include_paths: Vec<String>,
+
+    /// Allow a given path for imports. A list of paths can be supplied by separating them with a comma.
+    /// Passed to `solc` without changes. The workspace root by default.
+    #[arg(long = "allow-paths")]
+    allow_paths: Option<String>,
+
+    /// Cache the contract builds in the specified directory instead of memory.
+    /// Contracts with unchanged IR and settings are not recompiled.
+    #[arg(long = "cache-dir")]
+    cache_directory: Option<PathBuf>,
+
+    /// Set the LLVM optimization parameter -O[0 | 1 | 2 | 3 | s | z].
+    #[arg(short = 'O', long = "optimization", default_value = "z")]
+    optimization: char,
+
+    /// Route Yul lowering through the experimental newyork IR pipeline.
+    /// The heap and stack budget is only diagnosed by this pipeline.
+    #[arg(long = "newyork")]
+    newyork: bool,
+
+    /// Specify the path to the `solc` executable. By default, the one in `${PATH}` is used.
+    #[arg(long = "solc")]
+    solc: Option<String>,
+
+    /// These are passed to LLVM as the command line to allow manual control.
+    #[arg(long = "llvm-arg")]
+    llvm_arguments: Vec<String>,
+
+    /// The emulated EVM linear heap memory static buffer size in bytes.
+    #[arg(long = "heap-size", default_value_t = PolkaVMDefaultHeapMemorySize)]
+    heap_size: u32,
+
+    /// The contracts total stack size in bytes.
+    #[arg(long = "stack-size", default_value_t = PolkaVMDefaultStackMemorySize)]
+    stack_size: u32,
+}
+
+#[cfg(not(target_os = "emscripten"))]
+fn main() -> anyhow::Result<()> {
+    let arguments = Arguments::parse();
+
+    if arguments.version {
+        writeln!(
+            std::io::stdout(),
+            "{} version {}",
+            resolc::lsp::SERVER_NAME,
+            resolc::ResolcVersion::default().long
+        )?;
+        return Ok(());
+    }
+
+    #[cfg(feature = "parallel")]
+    rayon::ThreadPoolBuilder::new()
+        .stack_size(resolc::RAYON_WORKER_STACK_SIZE)
+        .build_global()
+        .expect("Thread pool configuration failure");
+
+    let optimizer_settings = OptimizerSettings::try_from_cli(arguments.optimization)?;
+    resolc::ThreadProcess::initialize(
+        optimizer_settings.level_middle_end_size,
+        arguments.newyork,
+        &arguments.llvm_arguments,
+    );
+
+    let solc = resolc::SolcCompiler::new(
+        arguments
+            .solc
+            .unwrap_or_else(|| resolc::SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned()),
+    )?;
+    let cache = match arguments.cache_directory {
+        Some(directory) => resolc::cache::Cache::new(directory)?,
+        None => resolc::cache::Cache::new_in_memory(),
+    };
+
+    resolc::lsp::LanguageServer::new(
+        &solc,
+        arguments.base_path,
+        arguments.include_paths,
+        arguments.allow_paths,
+        arguments.optimization,
+        SolcStandardJsonInputSettingsPolkaVMMemory::new(
+            Some(arguments.heap_size),
+            Some(arguments.stack_size),
+        ),
+        arguments.newyork,
+        Some(&cache),
+    )
+    .serve_stdio()
+}
+
+#[cfg(target_os = "emscripten")]
+fn main() -> anyhow::Result<()> {
+    anyhow::bail!("The language server requires a native `solc` executable.")
+}
@@ -0,0 +1,310 @@
+//! The compile server.
+//!
+//! A long-lived compiler serving JSON-RPC 2.0 requests, one JSON object per line,
+//! over stdio or a local socket. LLVM is initialized once and contracts are
+//! compiled on threads of the server process, see [`crate::ThreadProcess`].
+//!
+//! The methods are:
+//! - `compile`: compiles the standard JSON input passed as the parameters and
+//!   returns the standard JSON output, like `resolc --standard-json`.
+//! - `version`: returns the `resolc` and `solc` versions.
+//! - `shutdown`: stops the server after responding.
+//!
+//! A panic while handling a request is reported as an internal error, and the
+//! server keeps serving the following requests.
+
+use std::io::BufRead;
+use std::io::Write;
+use std::panic::AssertUnwindSafe;
+use std::path::Path;
+
+use revive_common::MetadataHash;
+use revive_llvm_context::DebugConfig;
+use revive_solc_json_interface::SolcStandardJsonInput;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+use revive_solc_json_interface::SolcStandardJsonOutputError;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::cache::Cache;
+use crate::process::panic_message;
+use crate::Compiler;
+use crate::ResolcVersion;
+
+/// The JSON-RPC version.
+pub(crate) const JSON_RPC_VERSION: &str = "2.0";
+
+/// The JSON-RPC error code for invalid JSON.
+pub(crate) const ERROR_CODE_PARSE: i64 = -32700;
+/// The JSON-RPC error code for invalid request objects.
+pub(crate) const ERROR_CODE_INVALID_REQUEST: i64 = -32600;
+/// The JSON-RPC error code for unknown methods.
+pub(crate) const ERROR_CODE_METHOD_NOT_FOUND: i64 = -32601;
+/// The JSON-RPC error code for invalid method parameters.
+pub(crate) const ERROR_CODE_INVALID_PARAMS: i64 = -32602;
+/// The JSON-RPC error code for internal errors.
+pub(crate) const ERROR_CODE_INTERNAL: i64 = -32603;
+
+/// A JSON-RPC request.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Request {
+    /// The JSON-RPC version.
+    pub(crate) jsonrpc: String,
+    /// The request identifier, absent for notifications.
+    #[serde(default)]
+    pub(crate) id: Option<serde_json::Value>,
+    /// The method name.
+    pub(crate) method: String,
+    /// The method parameters.
+    #[serde(default)]
+    pub(crate) params: serde_json::Value,
+}
+
+/// A JSON-RPC response.
+#[derive(Debug, Serialize)]
+pub(crate) struct Response {
+    /// The JSON-RPC version.
+    jsonrpc: &'static str,
+    /// The request identifier.
+    id: serde_json::Value,
+    /// The method result.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    /// The method error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ResponseError>,
+}
+
+/// A JSON-RPC error.
+#[derive(Debug, Serialize)]
+struct ResponseError {
+    /// The error code.
+    code: i64,
+    /// The error message.
+    message: String,
+}
+
+impl Response {
+    /// A successful response to the request `id`.
+    pub(crate) fn result(id: serde_json::Value, result: serde_json::Value) -> Self {
+        Self {
+            jsonrpc: JSON_RPC_VERSION,
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    /// An error response to the request `id`.
+    pub(crate) fn error(id: serde_json::Value, code: i64, message: impl ToString) -> Self {
+        Self {
+            jsonrpc: JSON_RPC_VERSION,
+            id,
+            result: None,
+            error: Some(ResponseError {
+                code,
+                message: message.to_string(),
+            }),
+        }
+    }
+}
+
+/// The compile server.
+pub struct Server<'a, T: Compiler> {
+    /// The `solc` compiler.
+    solc: &'a T,
+    /// The metadata hash type.
+    metadata_hash: MetadataHash,
+    /// The `solc` base path.
+    base_path: Option<String>,
+    /// The `solc` include paths.
+    include_paths: Vec<String>,
+    /// The `solc` allowed paths.
+    allow_paths: Option<String>,
+    /// The debug output config.
+    debug_config: DebugConfig,
+    /// Whether the newyork IR pipeline is enabled for all requests.
+    use_newyork: bool,
+    /// The compilation cache.
+    cache: Option<&'a Cache>,
+}
+
+impl<'a, T: Compiler> Server<'a, T> {
+    /// A shortcut constructor.
+    ///
+    /// Call [`crate::ThreadProcess::initialize`] first to compile contracts on threads of this process.
+    pub fn new(
+        solc: &'a T,
+        metadata_hash: MetadataHash,
+        base_path: Option<String>,
+        include_paths: Vec<String>,
+        allow_paths: Option<String>,
+        debug_config: DebugConfig,
+        use_newyork: bool,
+        cache: Option<&'a Cache>,
+    ) -> Self {
+        Self {
+            solc,
+            metadata_hash,
+            base_path,
+            include_paths,
+            allow_paths,
+            debug_config,
+            use_newyork,
+            cache,
+        }
+    }
+
+    /// Serves requests from stdin, writing the responses to stdout.
+    pub fn serve_stdio(&self) -> anyhow::Result<()> {
+        self.serve(std::io::stdin().lock(), std::io::stdout().lock())?;
+        Ok(())
+    }
+
+    /// Serves connections to the Unix domain socket at `path` one after another,
+    /// until a client requests the shutdown.
+    #[cfg(unix)]
+    pub fn serve_socket(&self, path: &Path) -> anyhow::Result<()> {
+        let listener = std::os::unix::net::UnixListener::bind(path)
+            .map_err(|error| anyhow::anyhow!("Socket {path:?} binding: {error}"))?;
+        let result = (|| {
+            for stream in listener.incoming() {
+                let stream = stream?;
+                if self.serve(std::io::BufReader::new(stream.try_clone()?), stream)? {
+                    break;
+                }
+            }
+            Ok(())
+        })();
+        let _ = std::fs::remove_file(path);
+        result
+    }
+
+    /// Serving sockets is only supported on Unix.
+    #[cfg(not(unix))]
+    pub fn serve_socket(&self, path: &Path) -> anyhow::Result<()> {
+        anyhow::bail!("Serving the socket {path:?} is only supported on Unix")
+    }
+
+    /// Serves the requests read from `reader`, writing the responses to `writer`.
+    ///
+    /// Returns whether the shutdown was requested.
+    pub fn serve<R: BufRead, W: Write>(&self, reader: R, mut writer: W) -> anyhow::Result<bool> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (response, shutdown) = self.handle(line.as_str());
+            if let Some(response) = response {
+                serde_json::to_writer(&mut writer, &response)?;
+                writeln!(writer)?;
+                writer.flush()?;
+            }
+            if shutdown {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Handles the request `line`, returning the response unless it is a
+    /// notification, and whether the shutdown was requested.
+    fn handle(&self, line: &str) -> (Option<Response>, bool) {
+        let request = match revive_common::deserialize_from_str::<serde_json::Value>(line) {
+            Ok(request) => request,
+            Err(error) => {
+                let response = Response::error(serde_json::Value::Null, ERROR_CODE_PARSE, error);
+                return (Some(response), false);
+            }
+        };
+        let id = request.get("id").cloned().unwrap_or_default();
+        let request = match serde_json::from_value::<Request>(request) {
+            Ok(request) if request.jsonrpc == JSON_RPC_VERSION => request,
+            Ok(request) => {
+                let message = format!("Unsupported JSON-RPC version `{}`", request.jsonrpc);
+                let response = Response::error(id, ERROR_CODE_INVALID_REQUEST, message);
+                return (Some(response), false);
+            }
+            Err(error) => {
+                let response = Response::error(id, ERROR_CODE_INVALID_REQUEST, error);
+                return (Some(response), false);
+            }
+        };
+
+        let shutdown = request.method == "shutdown";
+        let response = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            self.dispatch(request.method.as_str(), request.params)
+        }))
+        .unwrap_or_else(|payload| {
+            Err((
+                ERROR_CODE_INTERNAL,
+                format!("ICE: The compiler panicked: {}", panic_message(payload)),
+            ))
+        });
+
+        let response = request.id.map(|id| match response {
+            Ok(result) => Response::result(id, result),
+            Err((code, message)) => Response::error(id, code, message),
+        });
+        (response, shutdown)
+    }
+
+    /// Calls the `method` with the `params`, returning the result or the error code and message.
+    fn dispatch(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, (i64, String)> {
+        match method {
+            "compile" => match serde_json::from_value::<SolcStandardJsonInput>(params) {
+                Ok(input) => Ok(self.compile(input)),
+                Err(error) => Err((ERROR_CODE_INVALID_PARAMS, error.to_string())),
+            },
+            "version" => self
+                .solc
+                .version()
+                .map(|solc_version| {
+                    serde_json::json!({
+                        "version": ResolcVersion::default().long,
+                        "solcVersion": solc_version.long,
+                    })
+                })
+                .map_err(|error| (ERROR_CODE_INTERNAL, error.to_string())),
+            "shutdown" => Ok(serde_json::Value::Null),
+            method => Err((
+                ERROR_CODE_METHOD_NOT_FOUND,
+                format!("Unknown method `{method}`"),
+            )),
+        }
+    }
+
+    /// Compiles the standard JSON `input`.
+    fn compile(&self, input: SolcStandardJsonInput) -> serde_json::Value {
+        let mut messages = Vec::new();
+        let output = match crate::compile_standard_json(
+            self.solc,
+            input,
+            self.metadata_hash,
+            &mut messages,
+            self.base_path.clone(),
+            self.include_paths.clone(),
+            self.allow_paths.clone(),
+            self.debug_config.clone(),
+            false,
+            self.use_newyork,
+            self.cache,
+        ) {
+            Ok((mut output, prune_output)) => {
+                output.prune(prune_output);
+                output
+            }
+            Err(error) => {
+                messages.push(SolcStandardJsonOutputError::from_error(error, None, None));
+                SolcStandardJsonOutput::new_with_messages(messages)
+            }
+        };
+        serde_json::to_value(output).expect("Always valid")
+    }
+}
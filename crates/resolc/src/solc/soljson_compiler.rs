@@ -68,7 +68,7 @@ impl Compiler for SoljsonCompiler {
         suppressed_warnings.extend_from_slice(input.settings.suppressed_warnings.as_slice());
 
         input.resolve_sources();
-        output.preprocess_ast(&input.sources, &suppressed_warnings)?;
+        output.preprocess_ast(&input.sources, &suppressed_warnings, &input.settings.lints)?;
 
         Ok(output)
     }
@@ -19,12 +19,14 @@ use revive_solc_json_interface::SolcStandardJsonInput;
 use revive_solc_json_interface::SolcStandardJsonInputSettingsLibraries;
 use revive_solc_json_interface::SolcStandardJsonInputSettingsMetadata;
 use revive_solc_json_interface::SolcStandardJsonInputSettingsOptimizer;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsPolkaVMMemory;
 use revive_solc_json_interface::SolcStandardJsonInputSettingsSelection;
 use revive_solc_json_interface::SolcStandardJsonInputSource;
 use revive_solc_json_interface::SolcStandardJsonOutput;
 use revive_solc_json_interface::SolcStandardJsonOutputErrorHandler;
 use std::sync::LazyLock;
 
+use crate::build::link_options::LinkOptions;
 use crate::project::Project;
 use crate::solc::solc_compiler::SolcCompiler;
 use crate::solc::Compiler;
@@ -36,6 +38,14 @@ static EVM_BLOB_CACHE: LazyLock<Mutex<HashMap<CachedBlob, Vec<u8>>>> =
 static EVM_RUNTIME_BLOB_CACHE: LazyLock<Mutex<HashMap<CachedBlob, Vec<u8>>>> =
     LazyLock::new(Default::default);
 static YUL_IR_CACHE: LazyLock<Mutex<HashMap<CachedBlob, String>>> = LazyLock::new(Default::default);
+static ABI_CACHE: LazyLock<Mutex<HashMap<CachedBlob, serde_json::Value>>> =
+    LazyLock::new(Default::default);
+static OBJECT_CACHE: LazyLock<Mutex<HashMap<CachedBlob, Vec<u8>>>> =
+    LazyLock::new(Default::default);
+
+/// The file name single Solidity sources are compiled as.
+/// Library paths of contracts compiled by [`compile_object`] refer to it.
+pub const SOLIDITY_FILE_NAME: &str = "contract.sol";
 
 const DEBUG_CONFIG: revive_llvm_context::DebugConfig = DebugConfig::new(None, true);
 
@@ -77,6 +87,76 @@ pub fn build_solidity_with_options(
     optimizer_settings: OptimizerSettings,
     solc_optimizer_enabled: bool,
     suppressed_warnings: Vec<ResolcWarning>,
+) -> anyhow::Result<SolcStandardJsonOutput> {
+    build_solidity_with_memory_config(
+        sources,
+        libraries,
+        remappings,
+        optimizer_settings,
+        solc_optimizer_enabled,
+        suppressed_warnings,
+        Default::default(),
+    )
+}
+
+/// Builds the Solidity project with the PVM `memory_config` and returns the standard JSON output.
+pub fn build_solidity_with_memory_config(
+    sources: BTreeMap<String, SolcStandardJsonInputSource>,
+    libraries: SolcStandardJsonInputSettingsLibraries,
+    remappings: BTreeSet<String>,
+    optimizer_settings: OptimizerSettings,
+    solc_optimizer_enabled: bool,
+    suppressed_warnings: Vec<ResolcWarning>,
+    memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+) -> anyhow::Result<SolcStandardJsonOutput> {
+    let debug_config = DebugConfig::new(None, optimizer_settings.middle_end_as_string() != "z");
+    build_solidity_project(
+        sources,
+        libraries,
+        remappings,
+        optimizer_settings,
+        solc_optimizer_enabled,
+        suppressed_warnings,
+        memory_config,
+        MetadataHash::Keccak256,
+        debug_config,
+        false,
+    )
+}
+
+/// Builds the Solidity project in deterministic mode and returns the standard JSON output.
+///
+/// The metadata hash, which depends on the `resolc` commit, is not appended.
+pub fn build_solidity_deterministic(
+    sources: BTreeMap<String, SolcStandardJsonInputSource>,
+    optimizer_settings: OptimizerSettings,
+) -> anyhow::Result<SolcStandardJsonOutput> {
+    build_solidity_project(
+        sources,
+        Default::default(),
+        Default::default(),
+        optimizer_settings,
+        true,
+        Default::default(),
+        Default::default(),
+        MetadataHash::None,
+        DebugConfig::new(None, false),
+        true,
+    )
+}
+
+/// Builds the Solidity project and returns the standard JSON output.
+fn build_solidity_project(
+    sources: BTreeMap<String, SolcStandardJsonInputSource>,
+    libraries: SolcStandardJsonInputSettingsLibraries,
+    remappings: BTreeSet<String>,
+    optimizer_settings: OptimizerSettings,
+    solc_optimizer_enabled: bool,
+    suppressed_warnings: Vec<ResolcWarning>,
+    memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+    metadata_hash: MetadataHash,
+    debug_config: DebugConfig,
+    deterministic: bool,
 ) -> anyhow::Result<SolcStandardJsonOutput> {
     check_dependencies();
     inkwell::support::enable_llvm_pretty_stack_trace();
@@ -118,7 +198,6 @@ pub fn build_solidity_with_options(
         return Ok(output);
     }
     let use_newyork = cfg!(feature = "newyork");
-    let debug_config = DebugConfig::new(None, optimizer_settings.middle_end_as_string() != "z");
     let linker_symbols = libraries.as_linker_symbols()?;
     let build = Project::try_from_standard_json_output(
         &mut output,
@@ -130,14 +209,17 @@ pub fn build_solidity_with_options(
     .compile(
         &mut vec![],
         optimizer_settings,
-        MetadataHash::Keccak256,
+        metadata_hash,
         &debug_config,
+        None,
         Default::default(),
-        Default::default(),
+        memory_config,
+        deterministic,
+        None,
     )?;
     build.check_errors()?;
 
-    let build = build.link(linker_symbols, &debug_config);
+    let build = build.link(linker_symbols, &debug_config, LinkOptions::default());
     build.check_errors()?;
     build.write_to_standard_json(&mut output, &solc_version)?;
     output.check_errors()?;
@@ -292,13 +374,16 @@ pub fn build_yul<T: ToString + Display>(
         optimizer_settings,
         MetadataHash::Keccak256,
         &DEBUG_CONFIG,
+        None,
         Default::default(),
         Default::default(),
+        false,
+        None,
     )?;
     build.take_and_write_warnings();
     build.check_errors()?;
 
-    let mut build = build.link(Default::default(), &DEBUG_CONFIG);
+    let mut build = build.link(Default::default(), &DEBUG_CONFIG, LinkOptions::default());
     build.take_and_write_warnings();
     build.check_errors()?;
 
@@ -343,12 +428,19 @@ pub fn build_yul_standard_json(
         optimizer_settings,
         MetadataHash::Keccak256,
         &DEBUG_CONFIG,
+        None,
         Default::default(),
         Default::default(),
+        false,
+        None,
     )?;
     build.check_errors()?;
 
-    let build = build.link(Default::default(), &Default::default());
+    let build = build.link(
+        Default::default(),
+        &Default::default(),
+        LinkOptions::default(),
+    );
     build.check_errors()?;
     build.write_to_standard_json(&mut output, &solc.version()?)?;
 
@@ -389,7 +481,7 @@ pub fn compile_blob_with_options(
         return blob.clone();
     }
 
-    let file_name = "contract.sol";
+    let file_name = SOLIDITY_FILE_NAME;
     let contracts = build_solidity_with_options(
         BTreeMap::from([(
             file_name.to_owned(),
@@ -420,6 +512,132 @@ pub fn compile_blob_with_options(
     blob
 }
 
+/// Compile the blob of `contract_name` found in given `source_code` with the PVM `memory_config`.
+///
+/// The blob is not cached.
+pub fn compile_blob_with_memory_config(
+    contract_name: &str,
+    source_code: &str,
+    optimizer_settings: OptimizerSettings,
+    memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+) -> anyhow::Result<Vec<u8>> {
+    let output = build_solidity_with_memory_config(
+        BTreeMap::from([(
+            SOLIDITY_FILE_NAME.to_owned(),
+            SolcStandardJsonInputSource::from(source_code.to_owned()),
+        )]),
+        Default::default(),
+        Default::default(),
+        optimizer_settings,
+        true,
+        Default::default(),
+        memory_config,
+    )?;
+    contract_blob(&output, contract_name)
+}
+
+/// Compile the blob of `contract_name` found in given `source_code` with the automatic PVM
+/// memory configuration, returning the blob and the memory configuration it was sized to.
+///
+/// The automatic memory configuration requires the newyork pipeline. The blob is not cached.
+pub fn compile_blob_with_auto_memory_config(
+    contract_name: &str,
+    source_code: &str,
+    optimizer_settings: OptimizerSettings,
+) -> anyhow::Result<(Vec<u8>, SolcStandardJsonInputSettingsPolkaVMMemory)> {
+    if !cfg!(feature = "newyork") {
+        anyhow::bail!("the automatic memory configuration requires the newyork pipeline");
+    }
+
+    // The chosen sizes are only recorded in the metadata in deterministic mode.
+    let debug_config = DebugConfig::new(None, optimizer_settings.middle_end_as_string() != "z");
+    let output = build_solidity_project(
+        BTreeMap::from([(
+            SOLIDITY_FILE_NAME.to_owned(),
+            SolcStandardJsonInputSource::from(source_code.to_owned()),
+        )]),
+        Default::default(),
+        Default::default(),
+        optimizer_settings,
+        true,
+        Default::default(),
+        SolcStandardJsonInputSettingsPolkaVMMemory::auto(),
+        MetadataHash::Keccak256,
+        debug_config,
+        true,
+    )?;
+    let metadata = output
+        .contracts
+        .get(SOLIDITY_FILE_NAME)
+        .and_then(|contracts| contracts.get(contract_name))
+        .map(|contract| contract.metadata["memoryConfig"].to_owned())
+        .ok_or_else(|| anyhow::anyhow!("the contract `{contract_name}` has no metadata"))?;
+    let memory_config = serde_json::from_value(metadata)?;
+    Ok((contract_blob(&output, contract_name)?, memory_config))
+}
+
+/// Returns the blob of `contract_name` in the `output`.
+fn contract_blob(output: &SolcStandardJsonOutput, contract_name: &str) -> anyhow::Result<Vec<u8>> {
+    let bytecode = output
+        .contracts
+        .get(SOLIDITY_FILE_NAME)
+        .and_then(|contracts| contracts.get(contract_name))
+        .and_then(|contract| contract.evm.as_ref())
+        .and_then(|evm| evm.bytecode.as_ref())
+        .ok_or_else(|| anyhow::anyhow!("the contract `{contract_name}` has no bytecode"))?;
+    Ok(hex::decode(bytecode.object.as_str())?)
+}
+
+/// Compile the object of `contract_name` found in given `source_code` without libraries.
+/// Contracts referencing libraries result in an unlinked ELF object, to be linked at
+/// deploy time with [`crate::linker::Output::try_from`]; others in a PVM blob.
+pub fn compile_object(
+    contract_name: &str,
+    source_code: &str,
+    solc_optimizer_enabled: bool,
+) -> Vec<u8> {
+    let optimizer_settings = OptimizerSettings::cycles();
+    let id = CachedBlob {
+        contract_name: contract_name.to_owned(),
+        opt: optimizer_settings.middle_end_as_string(),
+        solc_optimizer_enabled,
+        solidity: source_code.to_owned(),
+        use_newyork: cfg!(feature = "newyork"),
+    };
+
+    if let Some(object) = OBJECT_CACHE.lock().unwrap().get(&id) {
+        return object.clone();
+    }
+
+    let contracts = build_solidity_with_options(
+        BTreeMap::from([(
+            SOLIDITY_FILE_NAME.to_owned(),
+            SolcStandardJsonInputSource::from(source_code.to_owned()),
+        )]),
+        Default::default(),
+        Default::default(),
+        optimizer_settings,
+        solc_optimizer_enabled,
+        Default::default(),
+    )
+    .expect("source should compile")
+    .contracts;
+    let bytecode = contracts[SOLIDITY_FILE_NAME][contract_name]
+        .evm
+        .as_ref()
+        .expect("source should produce EVM output")
+        .bytecode
+        .as_ref()
+        .expect("source should produce assembly text")
+        .object
+        .as_str();
+    let object = hex::decode(bytecode).expect("hex encoding should always be valid");
+
+    OBJECT_CACHE.lock().unwrap().insert(id, object.clone());
+
+    object
+}
+
 /// Compile the EVM bin-runtime of `contract_name` found in given `source_code`.
 /// The `solc` optimizer will be enabled
 pub fn compile_evm_bin_runtime(
@@ -446,6 +664,56 @@ pub fn compile_evm_deploy_code(
     )
 }
 
+/// Get the ABI JSON of `contract_name` found in given `source_code`.
+/// Only `solc` is invoked; the code is not compiled to PVM.
+pub fn compile_abi(
+    contract_name: &str,
+    source_code: &str,
+    libraries: SolcStandardJsonInputSettingsLibraries,
+) -> serde_json::Value {
+    let id = CachedBlob {
+        contract_name: contract_name.to_owned(),
+        solidity: source_code.to_owned(),
+        solc_optimizer_enabled: false,
+        opt: String::new(),
+        use_newyork: false,
+    };
+
+    if let Some(abi) = ABI_CACHE.lock().unwrap().get(&id) {
+        return abi.clone();
+    }
+
+    check_dependencies();
+    let solc = SolcCompiler::new(SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned())
+        .expect("solc should be available");
+    let file_name = SOLIDITY_FILE_NAME;
+    let mut input = SolcStandardJsonInput::try_from_solidity_sources(
+        None,
+        BTreeMap::from([(
+            file_name.to_owned(),
+            SolcStandardJsonInputSource::from(source_code.to_owned()),
+        )]),
+        libraries,
+        Default::default(),
+        SolcStandardJsonInputSettingsSelection::new_required_for_tests(),
+        SolcStandardJsonInputSettingsOptimizer::new(false, Default::default(), Default::default()),
+        SolcStandardJsonInputSettingsMetadata::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        false,
+    )
+    .expect("the standard JSON input should be valid");
+    let output = solc
+        .standard_json(&mut input, &mut vec![], None, vec![], None)
+        .expect("source should compile");
+    let abi = output.contracts[file_name][contract_name].abi.clone();
+
+    ABI_CACHE.lock().unwrap().insert(id, abi.clone());
+
+    abi
+}
+
 /// Convert `(path, solidity)` tuples to a standard JSON input source.
 pub fn sources<T: ToString>(sources: &[(T, T)]) -> BTreeMap<String, SolcStandardJsonInputSource> {
     BTreeMap::from_iter(
@@ -501,7 +769,7 @@ fn compile_evm(
         return blob.clone();
     }
 
-    let file_name = "contract.sol";
+    let file_name = SOLIDITY_FILE_NAME;
     let contracts = build_solidity_with_options_evm(
         BTreeMap::from([(
             file_name.into(),
@@ -581,13 +849,16 @@ pub fn compile_yul_blob_with_options(
         optimizer_settings,
         MetadataHash::Keccak256,
         &DEBUG_CONFIG,
+        None,
         Default::default(),
         Default::default(),
+        false,
+        None,
     )
     .expect("yul should compile");
     build.take_and_write_warnings();
     build.check_errors().expect("yul build should succeed");
-    let mut build = build.link(Default::default(), &DEBUG_CONFIG);
+    let mut build = build.link(Default::default(), &DEBUG_CONFIG, LinkOptions::default());
     build.take_and_write_warnings();
     build.check_errors().expect("yul link should succeed");
 
@@ -687,7 +958,7 @@ pub fn compile_to_yul(
         return yul.clone();
     }
 
-    let file_name = "contract.sol";
+    let file_name = SOLIDITY_FILE_NAME;
     let sources = BTreeMap::from([(
         file_name.to_owned(),
         SolcStandardJsonInputSource::from(source_code.to_owned()),
@@ -0,0 +1,124 @@
+//! The tests for the `polkavm.annotatedAssembly` standard JSON output.
+
+use revive_solc_json_interface::SolcStandardJsonOutput;
+
+use crate::cli_utils::{
+    assert_command_success, execute_resolc_with_stdin_input, STANDARD_JSON_ANNOTATED_ASSEMBLY_PATH,
+};
+
+const JSON_OPTION: &str = "--standard-json";
+
+#[test]
+fn annotates_instructions() {
+    let output =
+        execute_resolc_with_stdin_input(&[JSON_OPTION], STANDARD_JSON_ANNOTATED_ASSEMBLY_PATH);
+    assert_command_success(&output, "Selecting the annotated assembly");
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+    assert!(output.errors.is_empty(), "{:?}", output.errors);
+
+    let contract = &output.contracts["C.sol"]["C"];
+    assert!(
+        contract.evm.is_none(),
+        "unrequested outputs should be pruned"
+    );
+    let instructions = contract
+        .polkavm
+        .as_ref()
+        .and_then(|polkavm| polkavm.annotated_assembly.as_ref())
+        .unwrap();
+
+    assert!(!instructions.is_empty());
+    assert!(
+        instructions
+            .windows(2)
+            .all(|pair| pair[0].offset < pair[1].offset),
+        "instructions should be ordered by offset"
+    );
+    assert!(instructions
+        .iter()
+        .all(|instruction| !instruction.instruction.is_empty()));
+    assert!(instructions
+        .iter()
+        .any(|instruction| instruction.function.is_some()));
+    assert!(
+        instructions
+            .iter()
+            .all(|instruction| instruction.pass.is_none()),
+        "the default pipeline doesn't outline newyork helpers"
+    );
+    assert!(
+        instructions.iter().any(|instruction| instruction
+            .source_location
+            .as_ref()
+            .is_some_and(|location| location.file == "C.sol" && location.start >= 0)),
+        "the debug information should map instructions to the Solidity source"
+    );
+}
+
+#[test]
+fn omits_source_locations_without_debug_information() {
+    let input = std::fs::read_to_string(STANDARD_JSON_ANNOTATED_ASSEMBLY_PATH)
+        .unwrap()
+        .replace("\"debugInformation\": true", "\"debugInformation\": false");
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("input.json");
+    std::fs::write(&path, input).unwrap();
+
+    let output = execute_resolc_with_stdin_input(&[JSON_OPTION], path.to_str().unwrap());
+    assert_command_success(&output, "Selecting the annotated assembly");
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+
+    let instructions = output.contracts["C.sol"]["C"]
+        .polkavm
+        .as_ref()
+        .and_then(|polkavm| polkavm.annotated_assembly.as_ref())
+        .unwrap();
+    assert!(instructions
+        .iter()
+        .any(|instruction| instruction.function.is_some()));
+    assert!(instructions
+        .iter()
+        .all(|instruction| instruction.source_location.is_none()));
+}
+
+#[test]
+fn attributes_outlined_helpers_to_passes() {
+    let input = std::fs::read_to_string(STANDARD_JSON_ANNOTATED_ASSEMBLY_PATH)
+        .unwrap()
+        .replace("\"debugInformation\": true", "\"newyork\": true");
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("input.json");
+    std::fs::write(&path, input).unwrap();
+
+    let output = execute_resolc_with_stdin_input(&[JSON_OPTION], path.to_str().unwrap());
+    assert_command_success(&output, "Selecting the annotated assembly");
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+
+    let instructions = output.contracts["C.sol"]["C"]
+        .polkavm
+        .as_ref()
+        .and_then(|polkavm| polkavm.annotated_assembly.as_ref())
+        .unwrap();
+    let mut passes = std::collections::BTreeMap::new();
+    for instruction in instructions.iter() {
+        let Some(function) = instruction.function.as_deref() else {
+            continue;
+        };
+        let pass = passes
+            .entry(function)
+            .or_insert(instruction.pass.as_deref());
+        assert_eq!(
+            *pass,
+            instruction.pass.as_deref(),
+            "all instructions of `{function}` should have the same pass"
+        );
+    }
+    for (function, pass) in passes {
+        assert!(
+            pass.is_none()
+                || function.starts_with("__revive_")
+                || function.starts_with("__keccak256_slot_"),
+            "`{function}` is not an outlined helper but has the pass `{pass:?}`"
+        );
+    }
+}
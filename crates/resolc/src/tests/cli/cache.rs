@@ -0,0 +1,73 @@
+//! The tests for running resolc with the compilation cache.
+
+use tempfile::tempdir;
+
+use crate::cli_utils::{
+    assert_command_failure, assert_command_success, execute_resolc, SOLIDITY_CONTRACT_PATH,
+};
+
+const CACHE_DIRECTORY_OPTION: &str = "--cache-dir";
+
+#[test]
+fn reuses_cached_builds() {
+    let cache_directory = tempdir().unwrap();
+    let cache_directory_path = cache_directory.path().to_str().unwrap();
+    let arguments = &[
+        SOLIDITY_CONTRACT_PATH,
+        CACHE_DIRECTORY_OPTION,
+        cache_directory_path,
+        "--bin",
+    ];
+
+    let uncached = execute_resolc(arguments);
+    assert_command_success(&uncached, "Populating the cache");
+    let entries = std::fs::read_dir(cache_directory.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    assert_eq!(entries.len(), 1, "{entries:?}");
+
+    let cached = execute_resolc(arguments);
+    assert_command_success(&cached, "Reading the cache");
+    assert_eq!(uncached.stdout, cached.stdout);
+    assert_eq!(
+        std::fs::read_dir(cache_directory.path()).unwrap().count(),
+        1
+    );
+}
+
+#[test]
+fn settings_are_part_of_the_key() {
+    let cache_directory = tempdir().unwrap();
+    let cache_directory_path = cache_directory.path().to_str().unwrap();
+
+    for optimization in ["-O0", "-O3"] {
+        let output = execute_resolc(&[
+            SOLIDITY_CONTRACT_PATH,
+            CACHE_DIRECTORY_OPTION,
+            cache_directory_path,
+            optimization,
+            "--bin",
+        ]);
+        assert_command_success(&output, "Compiling with a cache");
+    }
+
+    assert_eq!(
+        std::fs::read_dir(cache_directory.path()).unwrap().count(),
+        2
+    );
+}
+
+#[test]
+fn fails_in_linker_mode() {
+    let cache_directory = tempdir().unwrap();
+    let output = execute_resolc(&[
+        "--link",
+        CACHE_DIRECTORY_OPTION,
+        cache_directory.path().to_str().unwrap(),
+    ]);
+    assert_command_failure(&output, "The cache in linker mode");
+    assert!(output
+        .stderr
+        .contains("The compilation cache is not used in linker and inspection modes."));
+}
@@ -0,0 +1,91 @@
+//! The tests for running resolc in deterministic mode.
+
+use crate::cli_utils::{
+    absolute_path, assert_command_failure, assert_command_success, execute_resolc,
+    SOLIDITY_CONTRACT_PATH,
+};
+
+const DETERMINISTIC_OPTION: &str = "--deterministic";
+
+#[test]
+fn normalizes_absolute_input_paths() {
+    let relative = execute_resolc(&[
+        SOLIDITY_CONTRACT_PATH,
+        DETERMINISTIC_OPTION,
+        "--bin",
+        "--metadata",
+    ]);
+    assert_command_success(&relative, "Providing a relative input path");
+
+    let absolute_path = absolute_path(SOLIDITY_CONTRACT_PATH);
+    let absolute = execute_resolc(&[
+        absolute_path.as_str(),
+        DETERMINISTIC_OPTION,
+        "--bin",
+        "--metadata",
+    ]);
+    assert_command_success(&absolute, "Providing an absolute input path");
+
+    assert!(!absolute.stdout.contains(absolute_path.as_str()));
+    assert_eq!(relative.stdout, absolute.stdout);
+}
+
+#[test]
+fn records_inputs_in_metadata() {
+    let output = execute_resolc(&[
+        SOLIDITY_CONTRACT_PATH,
+        DETERMINISTIC_OPTION,
+        "--metadata",
+        "--heap-size",
+        "4096",
+    ]);
+    assert_command_success(&output, "Providing a valid input file");
+
+    for pattern in &[
+        "\"optimizer_settings\"",
+        "\"llvm_arguments\"",
+        "\"heapSize\":4096",
+        "\"pipeline\":\"yul\"",
+        "\"debugInformation\":false",
+    ] {
+        assert!(
+            output.stdout.contains(pattern),
+            "Expected the metadata to contain `{pattern}`: {}",
+            output.stdout
+        );
+    }
+}
+
+#[test]
+fn refuses_source_debug_information() {
+    let output = execute_resolc(&[SOLIDITY_CONTRACT_PATH, DETERMINISTIC_OPTION, "-g"]);
+    assert_command_failure(&output, "Emitting debug information in deterministic mode");
+    assert!(output
+        .stderr
+        .contains(crate::deterministic::DEBUG_INFORMATION_ERROR));
+}
+
+#[test]
+fn refuses_paths_outside_of_the_working_directory() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("contract.sol");
+    std::fs::copy(SOLIDITY_CONTRACT_PATH, &path).unwrap();
+
+    let output = execute_resolc(&[path.to_str().unwrap(), DETERMINISTIC_OPTION, "--bin"]);
+    assert_command_failure(&output, "Providing a path outside of the working directory");
+    assert!(output.stderr.contains("outside of the working directory"));
+}
+
+#[test]
+fn records_inputs_only_in_deterministic_mode() {
+    let output = execute_resolc(&[SOLIDITY_CONTRACT_PATH, "--metadata"]);
+    assert_command_success(&output, "Providing a valid input file");
+
+    for pattern in &["\"memoryConfig\"", "\"pipeline\"", "\"debugInformation\""] {
+        assert!(
+            !output.stdout.contains(pattern),
+            "Expected the metadata not to contain `{pattern}`: {}",
+            output.stdout
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! The tests for running resolc with the `--explain` option.
+
+use crate::cli_utils::{assert_command_failure, assert_command_success, execute_resolc};
+
+#[test]
+fn explains_error_code() {
+    let resolc_result = execute_resolc(&["--explain", "R2001"]);
+    assert_command_success(&resolc_result, "Explaining a known error code");
+
+    assert!(resolc_result
+        .stdout
+        .starts_with("R2001: The `CALLCODE` instruction is not supported"));
+    assert!(resolc_result.stdout.contains("DELEGATECALL"));
+}
+
+#[test]
+fn explains_error_code_ignoring_case() {
+    let resolc_result = execute_resolc(&["--explain", "r3001"]);
+    assert_command_success(&resolc_result, "Explaining a lowercase error code");
+
+    assert!(resolc_result.stdout.starts_with("R3001:"));
+}
+
+#[test]
+fn fails_with_unknown_error_code() {
+    let resolc_result = execute_resolc(&["--explain", "R0000"]);
+    assert_command_failure(&resolc_result, "Explaining an unknown error code");
+
+    assert!(resolc_result.stderr.contains("Unknown error code `R0000`."));
+}
+
+#[test]
+fn fails_with_other_options() {
+    let resolc_result = execute_resolc(&["--explain", "R2001", "--bin"]);
+    assert_command_failure(
+        &resolc_result,
+        "Explaining an error code with other options",
+    );
+
+    assert!(resolc_result
+        .stderr
+        .contains("No other options are allowed while explaining an error code."));
+}
@@ -0,0 +1,64 @@
+//! The tests for the `evm.gasEstimates` standard JSON output.
+
+use revive_solc_json_interface::SolcStandardJsonOutput;
+use revive_solc_json_interface::SolcStandardJsonOutputContractEVMWeightEstimate as WeightEstimate;
+
+use crate::cli_utils::{
+    assert_command_success, execute_resolc_with_stdin_input, STANDARD_JSON_GAS_ESTIMATES_PATH,
+};
+
+const JSON_OPTION: &str = "--standard-json";
+
+#[test]
+fn estimates_weights() {
+    let output = execute_resolc_with_stdin_input(&[JSON_OPTION], STANDARD_JSON_GAS_ESTIMATES_PATH);
+    assert_command_success(&output, "Selecting the gas estimates");
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+    assert!(output.errors.is_empty(), "{:?}", output.errors);
+
+    let evm = output.contracts["C.sol"]["C"].evm.as_ref().unwrap();
+    assert!(
+        evm.bytecode.is_none(),
+        "unrequested outputs should be pruned"
+    );
+    let gas_estimates = evm.gas_estimates.as_ref().unwrap();
+
+    let signatures = gas_estimates.external.keys().collect::<Vec<_>>();
+    assert_eq!(
+        signatures,
+        ["forward(address)", "set(uint256)", "value()"],
+        "every external function should be estimated"
+    );
+    assert_eq!(
+        gas_estimates.external["forward(address)"],
+        WeightEstimate::Infinite,
+        "calls to other contracts can't be bounded"
+    );
+
+    let creation = &gas_estimates.creation;
+    assert_eq!(creation.code_deposit.items, 2);
+    assert!(creation.code_deposit.bytes > 0);
+    if let (WeightEstimate::Bounded(execution), WeightEstimate::Bounded(total)) =
+        (creation.execution_cost, creation.total_cost)
+    {
+        assert!(execution.ref_time < total.ref_time);
+    }
+}
+
+#[test]
+fn omits_unrequested_estimates() {
+    let input = std::fs::read_to_string(STANDARD_JSON_GAS_ESTIMATES_PATH)
+        .unwrap()
+        .replace("evm.gasEstimates", "evm.bytecode");
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("input.json");
+    std::fs::write(&path, input).unwrap();
+
+    let output = execute_resolc_with_stdin_input(&[JSON_OPTION], path.to_str().unwrap());
+    assert_command_success(&output, "Selecting the bytecode");
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+
+    let evm = output.contracts["C.sol"]["C"].evm.as_ref().unwrap();
+    assert!(evm.bytecode.is_some());
+    assert!(evm.gas_estimates.is_none());
+}
@@ -0,0 +1,81 @@
+//! The tests for the `polkavm.immutableLayout` standard JSON output.
+
+use revive_solc_json_interface::SolcStandardJsonOutput;
+
+use crate::cli_utils::{
+    assert_command_success, execute_resolc_with_stdin_input, STANDARD_JSON_IMMUTABLE_LAYOUT_PATH,
+};
+
+const JSON_OPTION: &str = "--standard-json";
+
+#[test]
+fn lays_out_immutables() {
+    let output =
+        execute_resolc_with_stdin_input(&[JSON_OPTION], STANDARD_JSON_IMMUTABLE_LAYOUT_PATH);
+    assert_command_success(&output, "Selecting the immutable layout");
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+    assert!(output.errors.is_empty(), "{:?}", output.errors);
+
+    let contract = &output.contracts["C.sol"]["C"];
+    assert!(
+        contract.evm.is_none(),
+        "unrequested outputs should be pruned"
+    );
+    let layout = contract
+        .polkavm
+        .as_ref()
+        .and_then(|polkavm| polkavm.immutable_layout.as_ref())
+        .unwrap();
+    assert_eq!(layout.size, 64);
+
+    let offsets = layout
+        .immutables
+        .iter()
+        .map(|immutable| immutable.offset)
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, [0, 32], "immutables should be ordered by offset");
+
+    let mut variables = layout
+        .immutables
+        .iter()
+        .map(|immutable| {
+            assert_eq!(immutable.size, 32);
+            assert_eq!(
+                immutable.ast_id.map(|id| id.to_string()),
+                Some(immutable.key.clone())
+            );
+            (
+                immutable.contract.as_deref().unwrap(),
+                immutable.label.as_deref().unwrap(),
+                immutable.r#type.as_deref().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    variables.sort();
+    assert_eq!(
+        variables,
+        [
+            ("C.sol:B", "owner", "address"),
+            ("C.sol:C", "created", "uint256"),
+        ],
+        "inherited immutables should be attributed to the declaring contract"
+    );
+}
+
+#[test]
+fn omits_unrequested_layout() {
+    let input = std::fs::read_to_string(STANDARD_JSON_IMMUTABLE_LAYOUT_PATH)
+        .unwrap()
+        .replace("polkavm.immutableLayout", "evm.bytecode");
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("input.json");
+    std::fs::write(&path, input).unwrap();
+
+    let output = execute_resolc_with_stdin_input(&[JSON_OPTION], path.to_str().unwrap());
+    assert_command_success(&output, "Selecting the bytecode");
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+
+    let contract = &output.contracts["C.sol"]["C"];
+    assert!(contract.evm.as_ref().unwrap().bytecode.is_some());
+    assert!(contract.polkavm.is_none());
+}
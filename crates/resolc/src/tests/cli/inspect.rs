@@ -0,0 +1,91 @@
+use crate::cli_utils::{
+    assert_command_failure, assert_command_success, execute_resolc, SOLIDITY_CONTRACT_PATH,
+    SOLIDITY_DEPENDENCY_CONTRACT_PATH,
+};
+
+/// Test inspecting unlinked ELF objects with libraries and factory dependencies.
+#[test]
+fn inspects_unlinked_objects() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_directory = temp_dir.path().to_path_buf();
+    let source_path = temp_dir.path().to_path_buf().join("dependency.sol");
+    std::fs::copy(SOLIDITY_DEPENDENCY_CONTRACT_PATH, &source_path).unwrap();
+
+    assert_command_success(
+        &execute_resolc(&[
+            source_path.to_str().unwrap(),
+            "--bin",
+            "-o",
+            &output_directory.to_string_lossy(),
+        ]),
+        "Missing libraries should compile fine",
+    );
+
+    let blob_path = output_directory.join("dependency.sol:TestAssert.pvm");
+    let dependency_blob_path = output_directory.join("dependency.sol:Dependency.pvm");
+    let output = execute_resolc(&[
+        "--inspect",
+        "--json",
+        blob_path.to_str().unwrap(),
+        dependency_blob_path.to_str().unwrap(),
+    ]);
+    assert_command_success(&output, "The inspection mode should work");
+
+    let inspections: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+    let inspection = &inspections[blob_path.to_str().unwrap()];
+    assert_eq!(inspection["format"], "ELF", "{inspections}");
+    let exports = inspection["exports"].as_array().unwrap();
+    assert!(exports.contains(&"call".into()) && exports.contains(&"deploy".into()));
+    assert!(!inspection["imports"].as_array().unwrap().is_empty());
+    assert_eq!(inspection["immutableDataSize"], 0);
+    let symbol = |name: &str| format!("{}:{name}", source_path.to_str().unwrap());
+    assert_eq!(
+        inspection["unresolvedLibraries"],
+        serde_json::json!([symbol("Assert")])
+    );
+    assert_eq!(
+        inspection["factoryDependencies"],
+        serde_json::json!([symbol("Dependency")])
+    );
+}
+
+/// Test inspecting a linked PVM blob in the text format.
+#[test]
+fn inspects_blobs() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_directory = temp_dir.path().to_path_buf();
+
+    assert_command_success(
+        &execute_resolc(&[
+            SOLIDITY_CONTRACT_PATH,
+            "--bin",
+            "-o",
+            &output_directory.to_string_lossy(),
+        ]),
+        "The contract should compile",
+    );
+
+    let blob_path = std::fs::read_dir(&output_directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|extension| extension == "pvm"))
+        .unwrap();
+    let output = execute_resolc(&["--inspect", blob_path.to_str().unwrap()]);
+    assert_command_success(&output, "The inspection mode should work");
+    assert!(output.stdout.contains("Format: PVM"), "{}", output.stdout);
+    assert!(output.stdout.contains("Exports: "));
+    assert!(output.stdout.contains("Immutable data: unknown"));
+    assert!(!output.stdout.contains("Unresolved libraries"));
+}
+
+#[test]
+fn json_requires_link_or_inspect() {
+    let output = execute_resolc(&[SOLIDITY_CONTRACT_PATH, "--json"]);
+    assert_command_failure(
+        &output,
+        "JSON output outside of linker and inspection modes",
+    );
+    assert!(output
+        .stderr
+        .contains("JSON output is only supported in linker and inspection modes."));
+}
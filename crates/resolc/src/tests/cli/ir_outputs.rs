@@ -0,0 +1,60 @@
+//! The tests for the `polkavm` IR and assembly standard JSON outputs.
+
+use revive_solc_json_interface::SolcStandardJsonOutput;
+
+use crate::cli_utils::{
+    assert_command_success, execute_resolc_with_stdin_input, STANDARD_JSON_IR_OUTPUTS_PATH,
+};
+
+const JSON_OPTION: &str = "--standard-json";
+
+#[test]
+fn emits_intermediate_representations() {
+    let output = execute_resolc_with_stdin_input(&[JSON_OPTION], STANDARD_JSON_IR_OUTPUTS_PATH);
+    assert_command_success(&output, "Selecting the IR outputs");
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+    assert!(output.errors.is_empty(), "{:?}", output.errors);
+
+    let contract = &output.contracts["C.sol"]["C"];
+    assert!(
+        contract.evm.is_none(),
+        "unrequested outputs should be pruned"
+    );
+    let polkavm = contract.polkavm.as_ref().unwrap();
+    assert!(!polkavm.newyork_ir.as_deref().unwrap().is_empty());
+    assert!(polkavm.llvm_ir.as_deref().unwrap().contains("define "));
+    assert!(polkavm
+        .llvm_ir_optimized
+        .as_deref()
+        .unwrap()
+        .contains("define "));
+    assert!(!polkavm.assembly.as_deref().unwrap().is_empty());
+}
+
+#[test]
+fn warns_about_newyork_ir_of_yul_pipeline() {
+    let input = std::fs::read_to_string(STANDARD_JSON_IR_OUTPUTS_PATH)
+        .unwrap()
+        .replace("\"newyork\": true", "\"newyork\": false");
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("input.json");
+    std::fs::write(&path, input).unwrap();
+
+    let output = execute_resolc_with_stdin_input(&[JSON_OPTION], path.to_str().unwrap());
+    assert_command_success(&output, "Selecting the IR outputs");
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+
+    assert!(
+        output
+            .errors
+            .iter()
+            .any(|error| error.severity == "Warning"
+                && error.message.contains("`polkavm.newyorkIr`")),
+        "{:?}",
+        output.errors
+    );
+    let polkavm = output.contracts["C.sol"]["C"].polkavm.as_ref().unwrap();
+    assert!(polkavm.newyork_ir.is_none());
+    assert!(polkavm.llvm_ir.is_some());
+    assert!(polkavm.llvm_ir_optimized.is_some());
+}
@@ -62,3 +62,39 @@ fn emits_unlinked_binary_warning() {
     assert_command_success(&output, "Missing libraries should compile fine");
     assert!(output.stderr.contains("is unlinked"));
 }
+
+#[test]
+fn deploy_time_linking_json_report() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_directory = temp_dir.path().to_path_buf();
+    let source_path = temp_dir.path().to_path_buf().join("dependency.sol");
+    std::fs::copy(SOLIDITY_DEPENDENCY_CONTRACT_PATH, &source_path).unwrap();
+
+    assert_command_success(
+        &execute_resolc(&[
+            source_path.to_str().unwrap(),
+            "--bin",
+            "-o",
+            &output_directory.to_string_lossy(),
+        ]),
+        "Missing libraries should compile fine",
+    );
+
+    let blob_path = temp_dir
+        .path()
+        .to_path_buf()
+        .join("dependency.sol:TestAssert.pvm");
+    let output = execute_resolc(&["--link", "--json", blob_path.to_str().unwrap()]);
+    assert_command_success(&output, "The linker mode with JSON output should work");
+
+    let report: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+    let file = &report["files"][blob_path.to_str().unwrap()];
+    assert_eq!(file["linked"], false);
+    let unresolved = file["unresolvedLibraries"].as_array().unwrap();
+    assert!(
+        unresolved
+            .iter()
+            .any(|symbol| symbol.as_str().unwrap().ends_with("dependency.sol:Assert")),
+        "{report}"
+    );
+}
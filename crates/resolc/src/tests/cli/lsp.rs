@@ -0,0 +1,394 @@
+//! The tests for the `resolc-lsp` language server.
+
+use std::io::BufReader;
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout};
+
+use tempfile::tempdir;
+
+use crate::cli_utils::{
+    assert_command_success, execute_resolc_lsp_with_stdin_input, spawn_resolc_lsp,
+    SOLIDITY_CONTRACT_PATH, STANDARD_JSON_UNSUPPORTED_INSTRUCTION_PATH,
+};
+use crate::lsp::transport::{path_to_uri, read_message, write_message};
+
+const GASLEFT_SOURCE: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Gas {
+    function remainingGas() public view returns (uint256) {
+        return gasleft();
+    }
+}
+"#;
+
+const SYNTAX_ERROR_SOURCE: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Broken {
+    function f() public {
+        uint256 x =
+    }
+}
+"#;
+
+const NO_GASLEFT_SOURCE: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Gas {
+    function remainingGas() public pure returns (uint256) {
+        return 0;
+    }
+}
+"#;
+
+/// An interactive session with the language server.
+struct Session {
+    /// The server process.
+    server: Child,
+    /// The server stdin.
+    stdin: ChildStdin,
+    /// The server stdout.
+    stdout: BufReader<ChildStdout>,
+    /// The URI of the document in the workspace.
+    uri: String,
+    /// The next request ID.
+    id: u64,
+}
+
+impl Session {
+    /// Starts the server with `arguments` in the workspace `root` and initializes it.
+    fn start(root: &Path, arguments: &[&str]) -> Self {
+        let mut server = spawn_resolc_lsp(arguments);
+        let stdin = server.stdin.take().unwrap();
+        let stdout = BufReader::new(server.stdout.take().unwrap());
+        let mut session = Self {
+            server,
+            stdin,
+            stdout,
+            uri: path_to_uri(root.join("Contract.sol").to_str().unwrap()),
+            id: 0,
+        };
+        session.request(
+            "initialize",
+            serde_json::json!({
+                "rootUri": path_to_uri(root.to_str().unwrap()),
+            }),
+        );
+        session.notify("initialized", serde_json::json!({}));
+        session
+    }
+
+    /// Sends the request `method` with `params` and waits for the response.
+    fn request(&mut self, method: &str, params: serde_json::Value) -> serde_json::Value {
+        self.id += 1;
+        let id = self.id;
+        self.send(
+            serde_json::json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }),
+        );
+        self.receive(|message| message["id"] == id)
+    }
+
+    /// Sends the notification `method` with `params`.
+    fn notify(&mut self, method: &str, params: serde_json::Value) {
+        self.send(serde_json::json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    /// Sends the `message` to the server.
+    fn send(&mut self, message: serde_json::Value) {
+        write_message(&mut self.stdin, &message).unwrap();
+    }
+
+    /// Returns the next message matching `predicate`, skipping the other ones.
+    fn receive<P: Fn(&serde_json::Value) -> bool>(&mut self, predicate: P) -> serde_json::Value {
+        loop {
+            let message = read_message(&mut self.stdout)
+                .unwrap()
+                .expect("The server exited");
+            let message = serde_json::from_slice(message.as_slice()).unwrap();
+            if predicate(&message) {
+                return message;
+            }
+        }
+    }
+
+    /// Returns the next diagnostics published for the document.
+    fn diagnostics(&mut self) -> Vec<serde_json::Value> {
+        let uri = self.uri.to_owned();
+        self.receive(|message| {
+            message["method"] == "textDocument/publishDiagnostics"
+                && message["params"]["uri"] == uri.as_str()
+        })["params"]["diagnostics"]
+            .as_array()
+            .unwrap()
+            .to_owned()
+    }
+
+    /// Opens the document with `text` and returns its diagnostics.
+    fn open(&mut self, text: &str) -> Vec<serde_json::Value> {
+        let uri = self.uri.to_owned();
+        self.notify("textDocument/didOpen", serde_json::json!({
+            "textDocument": { "uri": uri, "languageId": "solidity", "version": 1, "text": text },
+        }));
+        self.diagnostics()
+    }
+
+    /// Changes the document to `text` and returns its diagnostics.
+    fn change(&mut self, text: &str) -> Vec<serde_json::Value> {
+        let uri = self.uri.to_owned();
+        self.notify(
+            "textDocument/didChange",
+            serde_json::json!({
+                "textDocument": { "uri": uri, "version": 2 },
+                "contentChanges": [{ "text": text }],
+            }),
+        );
+        self.diagnostics()
+    }
+
+    /// Closes the document and returns its diagnostics.
+    fn close(&mut self) -> Vec<serde_json::Value> {
+        let uri = self.uri.to_owned();
+        self.notify(
+            "textDocument/didClose",
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+            }),
+        );
+        self.diagnostics()
+    }
+
+    /// Shuts the server down and waits for it to exit.
+    fn exit(mut self) {
+        let response = self.request("shutdown", serde_json::Value::Null);
+        assert!(response["result"].is_null(), "{response}");
+        self.notify("exit", serde_json::Value::Null);
+        drop(self.stdin);
+        assert!(self.server.wait().unwrap().success());
+    }
+}
+
+/// Returns whether any of the `diagnostics` has a message containing `text`.
+fn contains(diagnostics: &[serde_json::Value], text: &str) -> bool {
+    diagnostics.iter().any(|diagnostic| {
+        diagnostic["message"]
+            .as_str()
+            .is_some_and(|message| message.contains(text))
+    })
+}
+
+/// Opens the document `source` in the workspace `root` and exits,
+/// returning the messages written by the server.
+fn open(root: &Path, source: &str) -> Vec<serde_json::Value> {
+    let path = root.join("Contract.sol");
+    std::fs::write(&path, source).unwrap();
+    let root_uri = path_to_uri(root.to_str().unwrap());
+    let uri = path_to_uri(path.to_str().unwrap());
+
+    let mut requests = Vec::new();
+    for request in [
+        serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {
+            "rootUri": root_uri,
+        } }),
+        serde_json::json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+        serde_json::json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+            "textDocument": { "uri": uri, "languageId": "solidity", "version": 1, "text": source },
+        } }),
+        serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+        serde_json::json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ] {
+        write_message(&mut requests, &request).unwrap();
+    }
+    let requests_path = root.join("requests");
+    std::fs::write(&requests_path, requests).unwrap();
+
+    let output = execute_resolc_lsp_with_stdin_input(&[], requests_path.to_str().unwrap());
+    assert_command_success(&output, "Serving the language server");
+
+    let mut reader = output.stdout.as_bytes();
+    let mut messages = Vec::new();
+    while let Some(message) = read_message(&mut reader).unwrap() {
+        messages.push(serde_json::from_slice(message.as_slice()).unwrap());
+    }
+    messages
+}
+
+/// Returns the diagnostics last published for the document in the workspace `root`.
+fn diagnostics(root: &Path, messages: &[serde_json::Value]) -> Vec<serde_json::Value> {
+    let uri = path_to_uri(root.join("Contract.sol").to_str().unwrap());
+    messages
+        .iter()
+        .rev()
+        .find(|message| {
+            message["method"] == "textDocument/publishDiagnostics"
+                && message["params"]["uri"] == uri.as_str()
+        })
+        .and_then(|message| message["params"]["diagnostics"].as_array())
+        .expect("The diagnostics were not published")
+        .to_owned()
+}
+
+#[test]
+fn publishes_pvm_diagnostics() {
+    let root = tempdir().unwrap();
+    let messages = open(root.path(), GASLEFT_SOURCE);
+
+    let initialize = messages
+        .iter()
+        .find(|message| message["id"] == 1)
+        .expect("The initialize response is missing");
+    assert_eq!(
+        initialize["result"]["capabilities"]["textDocumentSync"]["change"],
+        1
+    );
+    assert_eq!(initialize["result"]["serverInfo"]["name"], "resolc-lsp");
+    assert!(messages
+        .iter()
+        .any(|message| message["id"] == 2 && message["result"].is_null()));
+
+    let diagnostics = diagnostics(root.path(), messages.as_slice());
+    let gasleft = diagnostics
+        .iter()
+        .find(|diagnostic| {
+            diagnostic["message"]
+                .as_str()
+                .unwrap()
+                .contains("resolc-disable-next-line gasleft")
+        })
+        .unwrap_or_else(|| panic!("The gasleft lint is missing: {diagnostics:?}"));
+    assert_eq!(gasleft["severity"], 2);
+    assert_eq!(gasleft["range"]["start"]["line"], 5);
+    assert_eq!(gasleft["source"], "resolc");
+
+    let code_size = diagnostics
+        .iter()
+        .find(|diagnostic| {
+            diagnostic["message"]
+                .as_str()
+                .unwrap()
+                .contains("bytes of PVM code")
+        })
+        .unwrap_or_else(|| panic!("The code size is missing: {diagnostics:?}"));
+    assert_eq!(code_size["severity"], 3);
+    assert_eq!(
+        code_size["range"],
+        serde_json::json!({
+            "start": { "line": 3, "character": 9 },
+            "end": { "line": 3, "character": 12 },
+        })
+    );
+}
+
+#[test]
+fn publishes_syntax_errors() {
+    let root = tempdir().unwrap();
+    let messages = open(root.path(), SYNTAX_ERROR_SOURCE);
+
+    let diagnostics = diagnostics(root.path(), messages.as_slice());
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic["severity"] == 1 && diagnostic["source"] == "resolc"),
+        "{diagnostics:?}"
+    );
+    assert!(
+        diagnostics.iter().all(|diagnostic| !diagnostic["message"]
+            .as_str()
+            .unwrap()
+            .contains("bytes of PVM code")),
+        "{diagnostics:?}"
+    );
+}
+
+#[test]
+fn updates_diagnostics_over_the_document_lifecycle() {
+    let root = tempdir().unwrap();
+    std::fs::write(root.path().join("Contract.sol"), GASLEFT_SOURCE).unwrap();
+    let mut session = Session::start(root.path(), &[]);
+
+    let diagnostics = session.open(GASLEFT_SOURCE);
+    assert!(
+        contains(&diagnostics, "resolc-disable-next-line gasleft"),
+        "{diagnostics:?}"
+    );
+    assert!(
+        contains(&diagnostics, "bytes of PVM code"),
+        "{diagnostics:?}"
+    );
+
+    let diagnostics = session.change(NO_GASLEFT_SOURCE);
+    assert!(
+        !contains(&diagnostics, "resolc-disable-next-line gasleft"),
+        "{diagnostics:?}"
+    );
+    assert!(
+        contains(&diagnostics, "bytes of PVM code"),
+        "{diagnostics:?}"
+    );
+
+    let diagnostics = session.change(SYNTAX_ERROR_SOURCE);
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic["severity"] == 1),
+        "{diagnostics:?}"
+    );
+    assert!(
+        !contains(&diagnostics, "bytes of PVM code"),
+        "{diagnostics:?}"
+    );
+
+    let diagnostics = session.close();
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+
+    session.exit();
+}
+
+#[test]
+fn publishes_memory_budget_diagnostics() {
+    let source = std::fs::read_to_string(SOLIDITY_CONTRACT_PATH).unwrap();
+    for (option, message) in [
+        ("--heap-size", "exceeding the heap size of 64 bytes"),
+        ("--stack-size", "exceeding the stack size of 64 bytes"),
+    ] {
+        let root = tempdir().unwrap();
+        std::fs::write(root.path().join("Contract.sol"), source.as_str()).unwrap();
+        let mut session = Session::start(root.path(), &["--newyork", option, "64"]);
+
+        let diagnostics = session.open(source.as_str());
+        let budget = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic["message"].as_str().unwrap().contains(message))
+            .unwrap_or_else(|| panic!("The budget diagnostic is missing: {diagnostics:?}"));
+        assert_eq!(budget["severity"], 2);
+
+        session.exit();
+    }
+}
+
+#[test]
+fn publishes_unsupported_constructs() {
+    let input: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(STANDARD_JSON_UNSUPPORTED_INSTRUCTION_PATH).unwrap(),
+    )
+    .unwrap();
+    let source = input["sources"]["C.sol"]["content"].as_str().unwrap();
+    let root = tempdir().unwrap();
+    std::fs::write(root.path().join("Contract.sol"), source).unwrap();
+    let mut session = Session::start(root.path(), &[]);
+
+    let diagnostics = session.open(source);
+    let unsupported = diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic["code"] == "R2003")
+        .unwrap_or_else(|| panic!("The unsupported instruction is missing: {diagnostics:?}"));
+    assert_eq!(unsupported["severity"], 1);
+    assert!(unsupported["message"]
+        .as_str()
+        .unwrap()
+        .contains("The `EXTCODECOPY` instruction"));
+    // The error is located at the instruction call in the inline assembly.
+    assert_eq!(unsupported["range"]["start"]["line"], 6);
+
+    session.exit();
+}
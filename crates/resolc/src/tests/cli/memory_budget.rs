@@ -0,0 +1,123 @@
+//! The tests for the static heap and stack memory budget checks and the automatic
+//! memory configuration.
+
+use revive_solc_json_interface::{
+    PolkaVMDefaultHeapMemorySize, PolkaVMDefaultStackMemorySize, SolcStandardJsonOutput,
+};
+
+use crate::cli_utils::{
+    assert_command_success, execute_resolc, execute_resolc_with_stdin_input,
+    SOLIDITY_CONTRACT_PATH, STANDARD_JSON_MEMORY_CONFIG_AUTO_PATH,
+    STANDARD_JSON_MEMORY_CONFIG_AUTO_YUL_PIPELINE_PATH,
+};
+
+const NEWYORK_OPTION: &str = "--newyork";
+const JSON_OPTION: &str = "--standard-json";
+
+/// Returns the memory configuration recorded in the deterministic metadata of the contract `C`.
+fn recorded_memory_config(output: &SolcStandardJsonOutput) -> serde_json::Value {
+    output.contracts["C.sol"]["C"].metadata["memoryConfig"].to_owned()
+}
+
+#[test]
+fn default_memory_config_is_sufficient() {
+    let output = execute_resolc(&[SOLIDITY_CONTRACT_PATH, NEWYORK_OPTION, "--bin"]);
+    assert_command_success(&output, "Compiling with the default memory configuration");
+    assert!(
+        !output.stderr.contains("polkavm.memoryConfig"),
+        "{}",
+        output.stderr
+    );
+}
+
+#[test]
+fn warns_with_too_small_heap() {
+    let output = execute_resolc(&[
+        SOLIDITY_CONTRACT_PATH,
+        NEWYORK_OPTION,
+        "--heap-size",
+        "64",
+        "--bin",
+    ]);
+    assert_command_success(
+        &output,
+        "Compiling with a heap smaller than the static heap usage",
+    );
+    assert!(
+        output
+            .stderr
+            .contains("exceeding the heap size of 64 bytes"),
+        "{}",
+        output.stderr
+    );
+    assert!(output.stderr.contains("`polkavm.memoryConfig.heapSize`"));
+}
+
+#[test]
+fn default_pipeline_does_not_check_heap() {
+    let output = execute_resolc(&[SOLIDITY_CONTRACT_PATH, "--heap-size", "64", "--bin"]);
+    assert_command_success(&output, "Compiling without newyork with a small heap");
+    assert!(
+        !output.stderr.contains("polkavm.memoryConfig"),
+        "{}",
+        output.stderr
+    );
+}
+
+#[test]
+fn warns_with_too_small_stack() {
+    let output = execute_resolc(&[
+        SOLIDITY_CONTRACT_PATH,
+        NEWYORK_OPTION,
+        "--stack-size",
+        "64",
+        "--bin",
+    ]);
+    assert_command_success(&output, "Compiling with a stack smaller than the estimate");
+    assert!(
+        output
+            .stderr
+            .contains("exceeding the stack size of 64 bytes"),
+        "{}",
+        output.stderr
+    );
+    assert!(output.stderr.contains("`polkavm.memoryConfig.stackSize`"));
+}
+
+#[test]
+fn auto_memory_config_is_recorded_in_metadata() {
+    let output =
+        execute_resolc_with_stdin_input(&[JSON_OPTION], STANDARD_JSON_MEMORY_CONFIG_AUTO_PATH);
+    assert_command_success(&output, "Compiling with the automatic memory configuration");
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+    assert!(!output.errors.iter().any(|error| error.is_error()));
+
+    let memory_config = recorded_memory_config(&output);
+    assert_eq!(memory_config["auto"], true, "{memory_config}");
+    let heap_size = memory_config["heapSize"].as_u64().unwrap();
+    let stack_size = memory_config["stackSize"].as_u64().unwrap();
+    assert!(heap_size > 0 && heap_size <= PolkaVMDefaultHeapMemorySize as u64);
+    assert!(stack_size > 0 && stack_size <= PolkaVMDefaultStackMemorySize as u64);
+}
+
+#[test]
+fn auto_memory_config_requires_newyork() {
+    let output = execute_resolc_with_stdin_input(
+        &[JSON_OPTION],
+        STANDARD_JSON_MEMORY_CONFIG_AUTO_YUL_PIPELINE_PATH,
+    );
+    assert_command_success(
+        &output,
+        "Compiling the automatic memory configuration without newyork",
+    );
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+    assert!(output.errors.iter().any(|error| error.is_warning()
+        && error
+            .message
+            .contains("automatic memory configuration requires the newyork pipeline")));
+
+    let memory_config = recorded_memory_config(&output);
+    assert_eq!(memory_config.get("auto"), None, "{memory_config}");
+    assert_eq!(memory_config["heapSize"], PolkaVMDefaultHeapMemorySize);
+    assert_eq!(memory_config["stackSize"], PolkaVMDefaultStackMemorySize);
+}
@@ -1,12 +1,25 @@
 //! The `resolc` CLI tests.
 
+mod annotated_assembly;
 mod asm;
 mod bin;
+mod cache;
 mod combined_json;
+mod deterministic;
+mod explain;
+mod gas_estimates;
+mod immutable_layout;
+mod inspect;
+mod ir_outputs;
 mod linker;
 mod llvm_arguments;
+mod lsp;
+mod memory_budget;
 mod optimization;
 mod output_dir;
+mod server;
+mod size_report;
+mod source_location;
 mod standard_json;
 mod usage;
 mod yul;
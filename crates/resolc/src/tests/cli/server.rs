@@ -0,0 +1,83 @@
+//! The tests for running resolc in server mode.
+
+use tempfile::tempdir;
+
+use crate::cli_utils::{
+    assert_command_failure, assert_command_success, execute_resolc,
+    execute_resolc_with_stdin_input, SOLIDITY_CONTRACT_PATH, STANDARD_JSON_NEWYORK_DISABLED_PATH,
+};
+
+const SERVER_OPTION: &str = "--server";
+
+/// Serves the `requests` over stdio, returning the responses.
+fn serve(requests: &[serde_json::Value]) -> Vec<serde_json::Value> {
+    let temp_dir = tempdir().unwrap();
+    let requests_path = temp_dir.path().join("requests.jsonl");
+    let requests = requests
+        .iter()
+        .map(|request| format!("{request}\n"))
+        .collect::<String>();
+    std::fs::write(&requests_path, requests).unwrap();
+
+    let output = execute_resolc_with_stdin_input(&[SERVER_OPTION], requests_path.to_str().unwrap());
+    assert_command_success(&output, "Serving requests");
+
+    output
+        .stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn compiles_like_standard_json() {
+    let input: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(STANDARD_JSON_NEWYORK_DISABLED_PATH).unwrap())
+            .unwrap();
+    let responses = serve(&[
+        serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "compile", "params": input }),
+        serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "compile", "params": input }),
+        serde_json::json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" }),
+    ]);
+    assert_eq!(responses.len(), 3, "{responses:?}");
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(responses[1]["id"], 2);
+    assert_eq!(responses[2]["result"], serde_json::Value::Null);
+
+    let standard_json = execute_resolc(&["--standard-json", STANDARD_JSON_NEWYORK_DISABLED_PATH]);
+    assert_command_success(&standard_json, "Compiling the standard JSON input");
+    let standard_json: serde_json::Value = serde_json::from_str(&standard_json.stdout).unwrap();
+
+    let bytecode = &standard_json["contracts"]["C.sol"]["C"]["evm"]["bytecode"]["object"];
+    assert!(bytecode.is_string(), "{standard_json}");
+    for response in &responses[..2] {
+        assert_eq!(
+            &response["result"]["contracts"]["C.sol"]["C"]["evm"]["bytecode"]["object"], bytecode,
+            "{response}"
+        );
+    }
+}
+
+#[test]
+fn reports_invalid_requests() {
+    let responses = serve(&[
+        serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "version" }),
+        serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "unknown" }),
+        serde_json::json!({ "jsonrpc": "2.0", "id": 3, "method": "compile", "params": 42 }),
+        serde_json::json!({ "jsonrpc": "2.0", "method": "version" }),
+    ]);
+    assert_eq!(responses.len(), 3, "{responses:?}");
+    assert!(responses[0]["result"]["version"].is_string());
+    assert!(responses[0]["result"]["solcVersion"].is_string());
+    assert_eq!(responses[1]["error"]["code"], -32601);
+    assert_eq!(responses[2]["error"]["code"], -32602);
+}
+
+#[test]
+fn fails_with_input_files() {
+    let output = execute_resolc(&[SOLIDITY_CONTRACT_PATH, SERVER_OPTION]);
+    assert_command_failure(&output, "Passing input files in server mode");
+    assert!(output
+        .stderr
+        .contains("Input files must be passed via compile requests in server mode."));
+}
@@ -0,0 +1,101 @@
+//! The tests for running resolc with the size report option.
+
+use tempfile::tempdir;
+
+use crate::cli_utils::{
+    assert_command_failure, assert_command_success, execute_resolc, SOLIDITY_CONTRACT_PATH,
+};
+
+const SIZE_REPORT_OPTION: &str = "--size-report";
+
+#[test]
+fn prints_size_report_table() {
+    let output = execute_resolc(&[SOLIDITY_CONTRACT_PATH, SIZE_REPORT_OPTION]);
+    assert_command_success(&output, "Providing a valid input file");
+
+    for pattern in &["Size report:", "Function", "Origin", "runtime"] {
+        assert!(
+            output.stdout.contains(pattern),
+            "Expected the output to contain `{pattern}`: {}",
+            output.stdout
+        );
+    }
+}
+
+#[test]
+fn writes_size_report_json() {
+    let temp_dir = tempdir().unwrap();
+    let output = execute_resolc(&[
+        SOLIDITY_CONTRACT_PATH,
+        SIZE_REPORT_OPTION,
+        "--bin",
+        "-o",
+        temp_dir.path().to_str().unwrap(),
+    ]);
+    assert_command_success(&output, "Writing the size report");
+
+    let report_path = std::fs::read_dir(temp_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.to_string_lossy().ends_with(".size.json"))
+        .expect("the size report should be written");
+    let blob_path = report_path.to_string_lossy().replace(".size.json", ".pvm");
+    let blob = std::fs::read(blob_path).unwrap();
+
+    let report: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(report_path).unwrap()).unwrap();
+    assert_eq!(report["blob"], blob.len());
+    let code = report["code"].as_u64().unwrap();
+    let attributed = report["functions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|function| function["bytes"].as_u64().unwrap())
+        .sum::<u64>();
+    assert_eq!(attributed, code, "{report}");
+    let kinds = report["kinds"]
+        .as_object()
+        .unwrap()
+        .values()
+        .map(|bytes| bytes.as_u64().unwrap())
+        .sum::<u64>();
+    assert_eq!(kinds, code);
+}
+
+#[test]
+fn size_report_keeps_the_blob() {
+    let blob = |arguments: &[&str]| {
+        let temp_dir = tempdir().unwrap();
+        let output_directory = temp_dir.path().to_str().unwrap();
+        let output = execute_resolc(
+            &[
+                &[SOLIDITY_CONTRACT_PATH, "--bin", "-o", output_directory],
+                arguments,
+            ]
+            .concat(),
+        );
+        assert_command_success(&output, "Writing the blob");
+        std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| path.extension().is_some_and(|extension| extension == "pvm"))
+            .map(|path| std::fs::read(path).unwrap())
+            .expect("the blob should be written")
+    };
+
+    assert_eq!(blob(&[SIZE_REPORT_OPTION]), blob(&[]));
+}
+
+#[test]
+fn fails_in_combined_json_mode() {
+    let output = execute_resolc(&[
+        SOLIDITY_CONTRACT_PATH,
+        SIZE_REPORT_OPTION,
+        "--combined-json",
+        "bin",
+    ]);
+    assert_command_failure(&output, "The size report in combined JSON mode");
+    assert!(output
+        .stderr
+        .contains("The size report is only supported in the default Solidity and Yul modes."));
+}
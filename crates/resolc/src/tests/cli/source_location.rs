@@ -0,0 +1,55 @@
+//! The tests for mapping backend errors to the Solidity source code.
+
+use revive_solc_json_interface::SolcStandardJsonOutput;
+
+use crate::cli_utils::{
+    assert_command_success, execute_resolc_with_stdin_input,
+    STANDARD_JSON_UNSUPPORTED_INSTRUCTION_NEWYORK_PATH, STANDARD_JSON_UNSUPPORTED_INSTRUCTION_PATH,
+};
+
+const JSON_OPTION: &str = "--standard-json";
+
+/// The unsupported instruction call in the inline assembly of the fixtures.
+const UNSUPPORTED_INSTRUCTION_CALL: &str = "extcodecopy(target, add(code, 0x20), 0, 32)";
+
+/// Compiles the standard JSON input at `path` and asserts the unsupported instruction
+/// error to point at the Solidity code of the instruction call.
+fn assert_error_located(path: &str) {
+    let input: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let source_code = input["sources"]["C.sol"]["content"].as_str().unwrap();
+    let start = source_code.find(UNSUPPORTED_INSTRUCTION_CALL).unwrap() as isize;
+    let end = start + UNSUPPORTED_INSTRUCTION_CALL.len() as isize;
+
+    let output = execute_resolc_with_stdin_input(&[JSON_OPTION], path);
+    assert_command_success(&output, "Compiling an unsupported instruction");
+    let output: SolcStandardJsonOutput = serde_json::from_str(&output.stdout).unwrap();
+
+    let error = output
+        .errors
+        .iter()
+        .find(|error| error.error_code.as_deref() == Some("R2003"))
+        .unwrap_or_else(|| panic!("{:?}", output.errors));
+    let source_location = error.source_location.as_ref().unwrap();
+    assert_eq!(source_location.file, "C.sol");
+    assert_eq!(
+        (source_location.start, source_location.end),
+        (start, end),
+        "{source_location:?}"
+    );
+    assert!(
+        error.message.contains("The `EXTCODECOPY` instruction"),
+        "{}",
+        error.message
+    );
+}
+
+#[test]
+fn locates_yul_pipeline_errors() {
+    assert_error_located(STANDARD_JSON_UNSUPPORTED_INSTRUCTION_PATH);
+}
+
+#[test]
+fn locates_newyork_errors() {
+    assert_error_located(STANDARD_JSON_UNSUPPORTED_INSTRUCTION_NEWYORK_PATH);
+}
@@ -0,0 +1,75 @@
+//! The compilation cache unit tests.
+
+use revive_common::ContractIdentifier;
+use revive_common::ObjectFormat;
+use revive_llvm_context::PolkaVMBuild;
+
+use crate::build::contract::Contract as ContractBuild;
+use crate::cache::Cache;
+
+/// The key of the cache entries in the tests.
+const KEY: &str = "key";
+
+/// Returns a contract build with the `bytecode`.
+fn build(bytecode: Vec<u8>) -> ContractBuild {
+    ContractBuild::new(
+        ContractIdentifier::new("C.sol".to_owned(), Some("C".to_owned())),
+        PolkaVMBuild::new(None, bytecode),
+        serde_json::json!({}),
+        Default::default(),
+        Default::default(),
+        ObjectFormat::PVM,
+        None,
+        vec![],
+    )
+}
+
+#[test]
+fn threads_insert_the_same_entry() {
+    let directory = tempfile::tempdir().unwrap();
+    let cache = Cache::new(directory.path().to_path_buf()).unwrap();
+
+    std::thread::scope(|scope| {
+        for thread in 0..8u8 {
+            let cache = &cache;
+            scope.spawn(move || {
+                for _ in 0..16 {
+                    cache.insert(KEY, &build(vec![thread; 1024]));
+                }
+            });
+        }
+    });
+
+    let bytecode = cache.get(KEY).unwrap().build.bytecode;
+    assert_eq!(bytecode.len(), 1024);
+    assert!(bytecode.iter().all(|byte| *byte == bytecode[0]));
+    // No temporary files are left behind.
+    assert_eq!(std::fs::read_dir(directory.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn in_memory_clones_share_entries() {
+    let cache = Cache::new_in_memory();
+    assert!(cache.get(KEY).is_none());
+
+    cache.clone().insert(KEY, &build(vec![1; 16]));
+
+    assert_eq!(cache.get(KEY).unwrap().build.bytecode, vec![1; 16]);
+}
+
+#[test]
+fn in_memory_is_cleared_at_capacity() {
+    let cache = Cache::new_in_memory();
+    for index in 0..Cache::MEMORY_CAPACITY {
+        cache.insert(index.to_string().as_str(), &build(vec![]));
+    }
+    assert!(cache.get("0").is_some());
+
+    // Replacing an entry keeps the others.
+    cache.insert("0", &build(vec![1]));
+    assert!(cache.get("1").is_some());
+
+    cache.insert(KEY, &build(vec![]));
+    assert!(cache.get(KEY).is_some());
+    assert!(cache.get("0").is_none());
+}
@@ -0,0 +1,90 @@
+//! The weight estimates unit tests.
+
+use polkavm_common::program::asm;
+use polkavm_common::program::Instruction;
+use polkavm_common::program::InstructionSetKind;
+use polkavm_common::program::Reg;
+use polkavm_common::writer::ProgramBlobBuilder;
+use revive_runtime_api::polkavm_exports;
+use revive_runtime_api::polkavm_imports;
+use revive_solc_json_interface::SolcStandardJsonOutputContractEVMWeightEstimate as WeightEstimate;
+
+use crate::build::gas_estimates::host_function;
+use crate::build::gas_estimates::HostFunction;
+use crate::build::gas_estimates::WeightEstimates;
+use crate::build::gas_estimates::PALLET_REVIVE_VERSION;
+
+/// Returns the deploy estimate of a blob running the `code`, which may call `seal_return`.
+fn deploy(code: &[Instruction]) -> WeightEstimate {
+    let mut builder = ProgramBlobBuilder::new(InstructionSetKind::ReviveV1);
+    builder.add_import(polkavm_imports::RETURN.as_bytes());
+    builder.add_export_by_basic_block(0, polkavm_exports::DEPLOY.as_bytes());
+    builder.add_export_by_basic_block(0, polkavm_exports::CALL.as_bytes());
+    builder.set_code(code, &[]);
+    let blob = builder.to_vec().unwrap();
+    WeightEstimates::new(&blob, &blob).unwrap().deploy
+}
+
+#[test]
+fn bounds_host_functions_with_immediate_lengths() {
+    let Some(HostFunction::PerByte { per_byte, .. }) = host_function(polkavm_imports::RETURN)
+    else {
+        panic!("`seal_return` should be charged per byte");
+    };
+    let returning = |length| deploy(&[asm::load_imm(Reg::A2, length), asm::ecalli(0), asm::ret()]);
+
+    let (WeightEstimate::Bounded(short), WeightEstimate::Bounded(long)) =
+        (returning(32), returning(64))
+    else {
+        panic!("returning an immediate length should be bounded");
+    };
+    assert_eq!(long, short.saturating_add(per_byte.saturating_mul(32)));
+}
+
+#[test]
+fn dynamic_lengths_are_infinite() {
+    assert_eq!(
+        deploy(&[asm::ecalli(0), asm::ret()]),
+        WeightEstimate::Infinite
+    );
+    assert_eq!(
+        deploy(&[
+            asm::load_imm(Reg::A2, 32),
+            asm::add_64(Reg::A2, Reg::A2, Reg::A3),
+            asm::ecalli(0),
+            asm::ret(),
+        ]),
+        WeightEstimate::Infinite
+    );
+}
+
+#[test]
+fn covers_the_runtime_api_imports() {
+    for import in polkavm_imports::IMPORTS {
+        assert!(
+            host_function(import).is_some(),
+            "the `{import}` host function has no weight"
+        );
+    }
+}
+
+/// The host function weights are taken from the locked `pallet-revive`.
+/// Updating it requires revisiting them and the imports of the runtime API.
+#[test]
+fn weights_match_the_locked_pallet_revive() {
+    let lock = std::fs::read_to_string(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../Cargo.lock"),
+    )
+    .unwrap();
+    let versions = lock
+        .split("[[package]]")
+        .filter(|package| package.contains("\nname = \"pallet-revive\"\n"))
+        .filter_map(|package| {
+            package
+                .lines()
+                .find_map(|line| line.strip_prefix("version = "))
+                .map(|version| version.trim_matches('"').to_owned())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(versions, [PALLET_REVIVE_VERSION]);
+}
@@ -0,0 +1,189 @@
+//! The Solidity compiler unit tests for the PVM compatibility lints.
+
+use std::collections::BTreeMap;
+
+use revive_solc_json_interface::ResolcLintSeverity;
+use revive_solc_json_interface::SolcStandardJsonInput;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsSelection;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+
+use crate::solc::solc_compiler::SolcCompiler;
+use crate::solc::Compiler;
+use crate::test_utils::sources;
+
+pub const LINTS_TEST_SOURCE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract LintsExample {
+    function isContract(address account) public view returns (bool) {
+        return account.code.length > 0;
+    }
+
+    function ownCode() public view returns (bytes memory) {
+        return address(this).code;
+    }
+
+    function remainingGas() public view returns (uint256) {
+        return gasleft() - 1000;
+    }
+
+    function forwardGas(address account) public returns (bool success) {
+        assembly {
+            success := call(gas(), account, 0, 0, 0, 0, 0)
+        }
+    }
+
+    function limitedCall(address account) public returns (bool success) {
+        (success, ) = account.call{gas: 2300}("");
+    }
+
+    function currentBlockHash() public view returns (bytes32 hash) {
+        assembly {
+            hash := blockhash(number())
+        }
+    }
+
+    function previousBlockHash() public view returns (bytes32) {
+        return blockhash(block.number - 1);
+    }
+
+    function clone(bytes32 code) public returns (address instance) {
+        assembly {
+            mstore(0, code)
+            instance := create(0, 0, 0x20)
+        }
+    }
+
+    function deploy() public returns (address instance) {
+        bytes memory code = type(Empty).creationCode;
+        assembly {
+            instance := create(0, add(code, 0x20), mload(code))
+        }
+    }
+}
+
+contract Empty {}"#;
+
+pub const DISABLED_TEST_SOURCE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract DisabledExample {
+    function remainingGas() public view returns (uint256) {
+        // resolc-disable-next-line gasleft
+        return gasleft() / 2;
+    }
+
+    function currentBlockHash() public view returns (bytes32) {
+        // resolc-disable-next-line
+        return blockhash(block.number);
+    }
+
+    function isOriginSender() public view returns (bool) {
+        // resolc-disable-next-line gasleft
+        return tx.origin == msg.sender;
+    }
+}"#;
+
+/// Runs `solc` with the `lints` settings and returns the output with the lint messages.
+fn lint(source: &str, lints: &[(&str, ResolcLintSeverity)]) -> SolcStandardJsonOutput {
+    let solc = SolcCompiler::new(SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned()).unwrap();
+    let mut input = SolcStandardJsonInput::try_from_solidity_sources(
+        None,
+        sources(&[("test.sol", source)]),
+        Default::default(),
+        Default::default(),
+        SolcStandardJsonInputSettingsSelection::new_required_for_tests(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        false,
+    )
+    .unwrap();
+    input.settings.lints = lints
+        .iter()
+        .map(|(id, severity)| (id.to_string(), *severity))
+        .collect::<BTreeMap<_, _>>();
+    solc.standard_json(&mut input, &mut vec![], None, vec![], None)
+        .unwrap()
+}
+
+/// Returns the severities of the messages emitted by the lint `id`.
+fn findings(output: &SolcStandardJsonOutput, id: &str) -> Vec<String> {
+    let hint = format!("resolc-disable-next-line {id}`");
+    output
+        .errors
+        .iter()
+        .filter(|error| error.message.contains(hint.as_str()))
+        .map(|error| error.severity.to_owned())
+        .collect()
+}
+
+#[test]
+fn default_severities() {
+    let output = lint(LINTS_TEST_SOURCE, &[]);
+
+    assert_eq!(findings(&output, "extcodesize"), vec!["warning"]);
+    assert_eq!(findings(&output, "this-code"), vec!["warning"]);
+    assert_eq!(findings(&output, "gasleft"), vec!["warning"]);
+    assert_eq!(findings(&output, "call-gas-stipend"), vec!["warning"]);
+    assert_eq!(findings(&output, "blockhash"), vec!["warning"]);
+    assert_eq!(findings(&output, "create-inline-bytecode"), vec!["warning"]);
+    assert!(findings(&output, "selfdestruct").is_empty());
+}
+
+#[test]
+fn configured_severities() {
+    let output = lint(
+        LINTS_TEST_SOURCE,
+        &[
+            ("gasleft", ResolcLintSeverity::Error),
+            ("blockhash", ResolcLintSeverity::Off),
+            ("unknown-lint", ResolcLintSeverity::Error),
+        ],
+    );
+
+    assert_eq!(findings(&output, "gasleft"), vec!["error"]);
+    assert!(findings(&output, "blockhash").is_empty());
+    assert_eq!(findings(&output, "extcodesize"), vec!["warning"]);
+    assert!(output
+        .errors
+        .iter()
+        .any(|error| error.is_warning() && error.message.contains("Unknown lint `unknown-lint`")));
+}
+
+#[test]
+fn disable_next_line() {
+    let output = lint(DISABLED_TEST_SOURCE, &[]);
+
+    assert!(findings(&output, "gasleft").is_empty());
+    assert!(findings(&output, "blockhash").is_empty());
+    assert_eq!(findings(&output, "tx-origin"), vec!["warning"]);
+}
+
+#[test]
+fn disable_next_line_requires_a_comment() {
+    let output = lint(
+        r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract NotDisabledExample {
+    function halfGas() public view returns (uint256) {
+        /* resolc-disable-next-line gasleft */
+        return gasleft() / 2;
+    }
+
+    function quarterGas() public view returns (uint256 quarter, string memory marker) {
+        marker = "// resolc-disable-next-line gasleft";
+        quarter = gasleft() / 4;
+    }
+}"#,
+        &[],
+    );
+
+    assert_eq!(findings(&output, "gasleft"), vec!["warning", "warning"]);
+}
@@ -0,0 +1,246 @@
+//! The language server unit tests.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::Mutex;
+
+use revive_solc_json_interface::combined_json::CombinedJson;
+use revive_solc_json_interface::CombinedJsonSelector;
+use revive_solc_json_interface::SolcStandardJsonInput;
+use revive_solc_json_interface::SolcStandardJsonInputSettingsPolkaVMMemory;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+use revive_solc_json_interface::SolcStandardJsonOutputError;
+
+use crate::lsp::diagnostic::Position;
+use crate::lsp::diagnostic::Range;
+use crate::lsp::transport::path_to_uri;
+use crate::lsp::transport::read_message;
+use crate::lsp::transport::uri_to_path;
+use crate::lsp::transport::MAX_CONTENT_LENGTH;
+use crate::lsp::LanguageServer;
+use crate::Compiler;
+use crate::SolcVersion;
+
+/// The URI of the document opened in the tests.
+const DOCUMENT_URI: &str = "file:///contracts/Contract.sol";
+
+/// The URI of the other document opened in the tests.
+const OTHER_DOCUMENT_URI: &str = "file:///contracts/Other.sol";
+
+/// The message the [`RecordingCompiler`] panics with, if set to panic.
+const PANIC_MESSAGE: &str = "solc crashed";
+
+/// A `solc` recording the source code of every compilation.
+///
+/// Every compilation fails, or panics if `panics` is set.
+#[derive(Default)]
+struct RecordingCompiler {
+    /// Whether the compilations panic.
+    panics: bool,
+    /// The source code of every compilation.
+    contents: Mutex<Vec<String>>,
+}
+
+impl Compiler for RecordingCompiler {
+    fn standard_json(
+        &self,
+        input: &mut SolcStandardJsonInput,
+        _messages: &mut Vec<SolcStandardJsonOutputError>,
+        _base_path: Option<String>,
+        _include_paths: Vec<String>,
+        _allow_paths: Option<String>,
+    ) -> anyhow::Result<SolcStandardJsonOutput> {
+        let content = input
+            .sources
+            .values()
+            .filter_map(|source| source.content.to_owned())
+            .collect::<String>();
+        self.contents.lock().unwrap().push(content);
+        if self.panics {
+            panic!("{PANIC_MESSAGE}");
+        }
+        anyhow::bail!("unsupported")
+    }
+
+    fn combined_json(
+        &self,
+        _paths: &[PathBuf],
+        _selectors: HashSet<CombinedJsonSelector>,
+    ) -> anyhow::Result<CombinedJson> {
+        anyhow::bail!("unsupported")
+    }
+
+    fn version(&self) -> anyhow::Result<SolcVersion> {
+        Ok(SolcVersion::new(
+            "0.8.36".to_owned(),
+            semver::Version::new(0, 8, 36),
+        ))
+    }
+}
+
+/// Serves the `messages` at once with the `solc`, returning the published diagnostics.
+fn serve(solc: &RecordingCompiler, messages: &[serde_json::Value]) -> Vec<serde_json::Value> {
+    let (sender, receiver) = mpsc::channel();
+    for message in messages.iter() {
+        sender
+            .send(Ok(serde_json::to_vec(message).unwrap()))
+            .unwrap();
+    }
+    drop(sender);
+
+    let mut output = Vec::new();
+    LanguageServer::new(
+        solc,
+        None,
+        vec![],
+        None,
+        'z',
+        SolcStandardJsonInputSettingsPolkaVMMemory::default(),
+        false,
+        None,
+    )
+    .serve(receiver, &mut output)
+    .unwrap();
+
+    let mut reader = output.as_slice();
+    let mut published = Vec::new();
+    while let Some(message) = read_message(&mut reader).unwrap() {
+        let message = serde_json::from_slice::<serde_json::Value>(message.as_slice()).unwrap();
+        if message["method"] == "textDocument/publishDiagnostics" {
+            published.push(message["params"].to_owned());
+        }
+    }
+    published
+}
+
+/// Returns the `didOpen` notification of the document at `uri` with `text`.
+fn did_open(uri: &str, text: &str) -> serde_json::Value {
+    serde_json::json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+        "textDocument": { "uri": uri, "languageId": "solidity", "version": 1, "text": text },
+    } })
+}
+
+/// Returns the `didChange` notification of the document changed to `text`.
+fn did_change(text: &str) -> serde_json::Value {
+    serde_json::json!({ "jsonrpc": "2.0", "method": "textDocument/didChange", "params": {
+        "textDocument": { "uri": DOCUMENT_URI, "version": 2 },
+        "contentChanges": [{ "text": text }],
+    } })
+}
+
+#[test]
+fn coalesces_pending_changes() {
+    let solc = RecordingCompiler::default();
+    let published = serve(
+        &solc,
+        &[
+            did_open(DOCUMENT_URI, "contract A {}"),
+            did_change("contract B {}"),
+            did_change("contract C {}"),
+        ],
+    );
+
+    assert_eq!(*solc.contents.lock().unwrap(), vec!["contract C {}"]);
+    assert_eq!(published.len(), 1, "{published:?}");
+    assert_eq!(published[0]["uri"], DOCUMENT_URI);
+}
+
+#[test]
+fn publishes_compiler_panics() {
+    let solc = RecordingCompiler {
+        panics: true,
+        ..Default::default()
+    };
+    let published = serve(
+        &solc,
+        &[
+            did_open(DOCUMENT_URI, "contract A {}"),
+            did_open(OTHER_DOCUMENT_URI, "contract B {}"),
+        ],
+    );
+
+    // The server keeps compiling after the first panic.
+    assert_eq!(solc.contents.lock().unwrap().len(), 2);
+    assert_eq!(published.len(), 2, "{published:?}");
+    for params in published.iter() {
+        assert_eq!(params["diagnostics"][0]["severity"], 1);
+        let message = params["diagnostics"][0]["message"].as_str().unwrap();
+        assert!(message.contains("ICE: The compiler panicked"), "{message}");
+        assert!(message.contains(PANIC_MESSAGE), "{message}");
+    }
+}
+
+#[test]
+fn rejects_oversized_messages() {
+    let header = format!("Content-Length: {}\r\n\r\n", MAX_CONTENT_LENGTH + 1);
+    assert!(read_message(&mut header.as_bytes()).is_err());
+
+    let message = "Content-Length: 2\r\n\r\n{}";
+    assert_eq!(
+        read_message(&mut message.as_bytes()).unwrap().as_deref(),
+        Some(b"{}".as_slice())
+    );
+}
+
+#[test]
+fn positions_count_utf16_code_units() {
+    let content = "// ä\n/* 😀 */ contract C {}\n";
+
+    assert_eq!(
+        Position::from_offset(content, 0),
+        Position {
+            line: 0,
+            character: 0
+        }
+    );
+    // The `ä` is two bytes in UTF-8, but one code unit in UTF-16.
+    assert_eq!(
+        Position::from_offset(content, 5),
+        Position {
+            line: 0,
+            character: 4
+        }
+    );
+    // The emoji is four bytes in UTF-8, but two code units in UTF-16.
+    let start = content.find("contract").unwrap();
+    assert_eq!(
+        Range::from_offsets(content, start, start + "contract".len()),
+        Range {
+            start: Position {
+                line: 1,
+                character: 9
+            },
+            end: Position {
+                line: 1,
+                character: 17
+            },
+        }
+    );
+    assert_eq!(
+        Position::from_offset(content, usize::MAX),
+        Position {
+            line: 2,
+            character: 0
+        }
+    );
+}
+
+#[test]
+fn uris_round_trip() {
+    for path in ["/contracts/Token.sol", "/my contracts/Ä.sol"] {
+        assert_eq!(
+            uri_to_path(path_to_uri(path).as_str()).as_deref(),
+            Some(path)
+        );
+    }
+    assert_eq!(
+        path_to_uri("/my contracts/Token.sol"),
+        "file:///my%20contracts/Token.sol"
+    );
+    assert_eq!(
+        uri_to_path("file:///c%3A/contracts/Token.sol").as_deref(),
+        Some("c:/contracts/Token.sol")
+    );
+    assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+}
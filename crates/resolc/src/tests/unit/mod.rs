@@ -1,12 +1,18 @@
 //! The Solidity compiler unit tests.
 
+mod cache;
 mod factory_dependency;
+mod gas_estimates;
 mod ir_artifacts;
 mod libraries;
+mod lints;
+mod lsp;
 mod messages;
 mod optimizer;
 mod remappings;
+mod reproducible;
 mod runtime_code;
+mod server;
 mod standard_json;
 mod unsupported_opcodes;
 mod yul_function_scoping;
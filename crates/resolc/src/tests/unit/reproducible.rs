@@ -0,0 +1,276 @@
+//! The Solidity compiler unit tests for reproducible builds.
+//!
+//! Compiles the Solidity test data corpus in deterministic mode with every optimizer
+//! settings combination and compares the hashes of the contract bytecode and metadata
+//! with the hashes checked in at [`HASHES_PATH`]. The CI runs this test on every
+//! supported host.
+//!
+//! The standard JSON inputs at [`INPUTS_PATH`] compile the corpus with the optimization
+//! modes among the combinations. Their outputs must match the recorded hashes of the
+//! same settings, both compiled by this host and by the build given in
+//! [`OUTPUTS_VARIABLE`], like the wasm one, which can't run this test itself.
+//!
+//! The hashes depend on the `solc` version they were recorded with. After an
+//! intended change of the output, set `RESOLC_UPDATE_REPRODUCIBLE_HASHES` to
+//! record them again.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use revive_common::Keccak256;
+use revive_common::MetadataHash;
+use revive_llvm_context::DebugConfig;
+use revive_llvm_context::OptimizerSettings;
+use revive_solc_json_interface::SolcStandardJsonInput;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::solc::solc_compiler::SolcCompiler;
+use crate::solc::Compiler;
+use crate::test_utils::{build_solidity_deterministic, sources};
+
+/// The environment variable requesting to record the hashes.
+const UPDATE_VARIABLE: &str = "RESOLC_UPDATE_REPRODUCIBLE_HASHES";
+
+/// The environment variable with the path of the standard JSON outputs of another
+/// build, by input name.
+const OUTPUTS_VARIABLE: &str = "RESOLC_REPRODUCIBLE_OUTPUTS";
+
+/// The Solidity test data corpus directory, relative to the `resolc` crate.
+const CORPUS_DIRECTORY: &str = "src/tests/data/solidity";
+
+/// The checked in hashes, relative to the `resolc` crate.
+const HASHES_PATH: &str = "src/tests/data/reproducible/hashes.json";
+
+/// The checked in standard JSON inputs, relative to the `resolc` crate.
+const INPUTS_PATH: &str = "src/tests/data/reproducible/inputs.json";
+
+/// The standard JSON optimization modes.
+const MODES: [char; 6] = ['0', '1', '2', '3', 's', 'z'];
+
+/// The hashes of the corpus build.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Hashes {
+    /// The `solc` version the hashes were recorded with.
+    solc_version: String,
+    /// The bytecode and metadata hashes by contract and optimizer settings.
+    hashes: BTreeMap<String, String>,
+}
+
+/// Returns the corpus source code by file name.
+fn corpus() -> BTreeMap<String, String> {
+    let corpus = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(CORPUS_DIRECTORY);
+    std::fs::read_dir(corpus)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            (name, std::fs::read_to_string(&path).unwrap())
+        })
+        .collect()
+}
+
+/// Returns the hashes of the contract bytecode and metadata in the `output` built with
+/// the `optimizer_settings`, by contract and settings.
+///
+/// The `resolc` version is removed from the metadata, since it contains the commit.
+fn contract_hashes(
+    output: &SolcStandardJsonOutput,
+    optimizer_settings: &OptimizerSettings,
+) -> BTreeMap<String, String> {
+    let mut hashes = BTreeMap::new();
+    for (file, contracts) in output.contracts.iter() {
+        for (contract_name, contract) in contracts.iter() {
+            let bytecode = contract
+                .evm
+                .as_ref()
+                .and_then(|evm| evm.bytecode.as_ref())
+                .map(|bytecode| bytecode.object.as_str())
+                .unwrap_or_default();
+            let mut metadata = contract.metadata.to_owned();
+            if let Some(metadata) = metadata.as_object_mut() {
+                metadata.remove("revive_version");
+            }
+            let metadata = metadata.to_string();
+            let hash = Keccak256::from_slices(&[bytecode.as_bytes(), metadata.as_bytes()]);
+            hashes.insert(
+                format!("{file}:{contract_name} {optimizer_settings}"),
+                hash.as_str().to_owned(),
+            );
+        }
+    }
+    hashes
+}
+
+/// Compiles the corpus with the `optimizer_settings`, returning the hashes
+/// of the contract bytecode and metadata by contract and settings.
+fn corpus_hashes(optimizer_settings: &OptimizerSettings) -> BTreeMap<String, String> {
+    let mut hashes = BTreeMap::new();
+    for (name, source) in corpus() {
+        let output = build_solidity_deterministic(
+            sources(&[(name.as_str(), source.as_str())]),
+            optimizer_settings.to_owned(),
+        )
+        .unwrap();
+        hashes.extend(contract_hashes(&output, optimizer_settings));
+    }
+    hashes
+}
+
+/// Returns the standard JSON inputs compiling the corpus in deterministic mode with the
+/// optimization modes among the optimizer settings combinations, by file name and mode.
+fn standard_json_inputs() -> BTreeMap<String, serde_json::Value> {
+    let combinations = OptimizerSettings::combinations();
+    let modes = MODES
+        .into_iter()
+        .filter(|mode| combinations.contains(&OptimizerSettings::try_from_cli(*mode).unwrap()))
+        .collect::<Vec<char>>();
+
+    let mut inputs = BTreeMap::new();
+    for (name, source) in corpus() {
+        for mode in modes.iter() {
+            let input = serde_json::json!({
+                "language": "Solidity",
+                "sources": {
+                    name.as_str(): { "content": source },
+                },
+                "settings": {
+                    "optimizer": { "enabled": true, "mode": mode.to_string() },
+                    "outputSelection": { "*": { "*": ["evm.bytecode.object", "metadata"] } },
+                    "polkavm": { "deterministic": true },
+                },
+            });
+            inputs.insert(format!("{name} {mode}"), input);
+        }
+    }
+    inputs
+}
+
+/// Returns the hashes of the standard JSON `outputs`, by contract and the settings
+/// of the mode in the input name.
+fn standard_json_hashes(
+    outputs: &BTreeMap<String, SolcStandardJsonOutput>,
+) -> BTreeMap<String, String> {
+    let mut hashes = BTreeMap::new();
+    for (name, output) in outputs.iter() {
+        let mode = name.chars().last().unwrap();
+        let optimizer_settings = OptimizerSettings::try_from_cli(mode).unwrap();
+        hashes.extend(contract_hashes(output, &optimizer_settings));
+    }
+    hashes
+}
+
+/// Compiles the standard JSON `inputs` like `resolc --standard-json`, returning the
+/// outputs by input name.
+fn compile_standard_json_inputs(
+    solc: &SolcCompiler,
+    inputs: &BTreeMap<String, serde_json::Value>,
+) -> BTreeMap<String, SolcStandardJsonOutput> {
+    inputs
+        .iter()
+        .map(|(name, input)| {
+            let input = serde_json::from_value::<SolcStandardJsonInput>(input.to_owned()).unwrap();
+            let (output, _) = crate::compile_standard_json(
+                solc,
+                input,
+                MetadataHash::Keccak256,
+                &mut vec![],
+                None,
+                vec![],
+                None,
+                DebugConfig::new(None, false),
+                false,
+                false,
+                None,
+            )
+            .unwrap();
+            (name.to_owned(), output)
+        })
+        .collect()
+}
+
+/// Asserts the `hashes` of the `build` to match the `expected` ones.
+fn assert_hashes(
+    expected: &BTreeMap<String, String>,
+    hashes: &BTreeMap<String, String>,
+    build: &str,
+) {
+    assert!(!hashes.is_empty(), "The {build} has no contracts");
+    for (contract, hash) in hashes.iter() {
+        assert_eq!(
+            Some(hash),
+            expected.get(contract),
+            "The {build} of {contract} differs from {HASHES_PATH}; set {UPDATE_VARIABLE} if the change is intended",
+        );
+    }
+}
+
+#[test]
+fn reproducible_across_hosts() {
+    let solc = SolcCompiler::new(SolcCompiler::DEFAULT_EXECUTABLE_NAME.to_owned()).unwrap();
+    let mut hashes = Hashes {
+        solc_version: solc.version().unwrap().default.to_string(),
+        hashes: BTreeMap::new(),
+    };
+    for optimizer_settings in OptimizerSettings::combinations() {
+        hashes.hashes.extend(corpus_hashes(&optimizer_settings));
+    }
+    let inputs = standard_json_inputs();
+
+    let manifest_directory = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let hashes_path = manifest_directory.join(HASHES_PATH);
+    let inputs_path = manifest_directory.join(INPUTS_PATH);
+    if std::env::var_os(UPDATE_VARIABLE).is_some() {
+        for (path, value) in [
+            (&hashes_path, serde_json::to_value(&hashes).unwrap()),
+            (&inputs_path, serde_json::to_value(&inputs).unwrap()),
+        ] {
+            let mut json = serde_json::to_string_pretty(&value).unwrap();
+            json.push('\n');
+            std::fs::write(path, json).unwrap();
+        }
+        return;
+    }
+
+    let expected: Hashes = serde_json::from_slice(&std::fs::read(&hashes_path).unwrap()).unwrap();
+    assert_eq!(
+        expected.solc_version, hashes.solc_version,
+        "The hashes were recorded with another solc version; set {UPDATE_VARIABLE} to record them with this one",
+    );
+    assert_hashes(&expected.hashes, &hashes.hashes, "build");
+    assert_eq!(
+        hashes.hashes.len(),
+        expected.hashes.len(),
+        "The corpus differs from {HASHES_PATH}; set {UPDATE_VARIABLE} to record it",
+    );
+
+    let expected_inputs: BTreeMap<String, serde_json::Value> =
+        serde_json::from_slice(&std::fs::read(&inputs_path).unwrap()).unwrap();
+    assert_eq!(
+        expected_inputs, inputs,
+        "The standard JSON inputs differ from {INPUTS_PATH}; set {UPDATE_VARIABLE} to record them",
+    );
+    let outputs = compile_standard_json_inputs(&solc, &inputs);
+    assert_hashes(
+        &expected.hashes,
+        &standard_json_hashes(&outputs),
+        "standard JSON build",
+    );
+
+    if let Some(path) = std::env::var_os(OUTPUTS_VARIABLE) {
+        let outputs: BTreeMap<String, SolcStandardJsonOutput> =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(
+            outputs.keys().collect::<Vec<_>>(),
+            inputs.keys().collect::<Vec<_>>(),
+            "The outputs at {path:?} don't compile {INPUTS_PATH}",
+        );
+        assert_hashes(
+            &expected.hashes,
+            &standard_json_hashes(&outputs),
+            format!("build at {path:?}").as_str(),
+        );
+    }
+}
@@ -17,8 +17,8 @@ contract Test {
 }
     "#;
 
-    build_solidity(sources(&[("test.sol", code)]))
-        .unwrap()
+    let output = build_solidity(sources(&[("test.sol", code)])).unwrap();
+    let error = output
         .errors
         .iter()
         .find(|error| {
@@ -27,4 +27,6 @@ contract Test {
                 .contains("Error: Deploy and runtime code are merged in PVM")
         })
         .unwrap();
+    assert_eq!(error.error_code.as_deref(), Some("R1001"));
+    assert!(error.to_string().contains("resolc --explain R1001"));
 }
@@ -0,0 +1,123 @@
+//! The compile server unit tests.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use revive_common::MetadataHash;
+use revive_llvm_context::DebugConfig;
+use revive_llvm_context::OptimizerSettingsSizeLevel;
+use revive_solc_json_interface::combined_json::CombinedJson;
+use revive_solc_json_interface::CombinedJsonSelector;
+use revive_solc_json_interface::SolcStandardJsonInput;
+use revive_solc_json_interface::SolcStandardJsonOutput;
+use revive_solc_json_interface::SolcStandardJsonOutputError;
+
+use crate::cli_utils::STANDARD_JSON_NEWYORK_DISABLED_PATH;
+use crate::process::thread_process::LLVMOptions;
+use crate::server::Server;
+use crate::server::ERROR_CODE_INTERNAL;
+use crate::Compiler;
+use crate::SolcVersion;
+
+/// The message the [`PanickingCompiler`] panics with.
+const PANIC_MESSAGE: &str = "solc crashed";
+
+/// A `solc` panicking on every compilation.
+struct PanickingCompiler;
+
+impl Compiler for PanickingCompiler {
+    fn standard_json(
+        &self,
+        _input: &mut SolcStandardJsonInput,
+        _messages: &mut Vec<SolcStandardJsonOutputError>,
+        _base_path: Option<String>,
+        _include_paths: Vec<String>,
+        _allow_paths: Option<String>,
+    ) -> anyhow::Result<SolcStandardJsonOutput> {
+        panic!("{PANIC_MESSAGE}")
+    }
+
+    fn combined_json(
+        &self,
+        _paths: &[PathBuf],
+        _selectors: HashSet<CombinedJsonSelector>,
+    ) -> anyhow::Result<CombinedJson> {
+        anyhow::bail!("unsupported")
+    }
+
+    fn version(&self) -> anyhow::Result<SolcVersion> {
+        Ok(SolcVersion::new(
+            "0.8.36".to_owned(),
+            semver::Version::new(0, 8, 36),
+        ))
+    }
+}
+
+#[test]
+fn serves_requests_after_a_panic() {
+    let input: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(STANDARD_JSON_NEWYORK_DISABLED_PATH).unwrap())
+            .unwrap();
+    let requests = [
+        serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "compile", "params": input }),
+        serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "version" }),
+    ]
+    .iter()
+    .map(|request| format!("{request}\n"))
+    .collect::<String>();
+
+    let server = Server::new(
+        &PanickingCompiler,
+        MetadataHash::Keccak256,
+        None,
+        vec![],
+        None,
+        DebugConfig::new(None, false),
+        false,
+        None,
+    );
+    let mut output = Vec::new();
+    assert!(!server.serve(requests.as_bytes(), &mut output).unwrap());
+
+    let responses = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(responses.len(), 2, "{responses:?}");
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(responses[0]["error"]["code"], ERROR_CODE_INTERNAL);
+    assert!(responses[0]["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains(PANIC_MESSAGE));
+    assert_eq!(responses[1]["id"], 2);
+    assert_eq!(responses[1]["result"]["solcVersion"], "0.8.36");
+}
+
+#[test]
+fn llvm_initialization_is_reused() {
+    let initialized = LLVMOptions::new(OptimizerSettingsSizeLevel::Zero, false, &[]);
+
+    // Only the newyork size level `z` and the extra arguments set LLVM options.
+    assert_eq!(
+        initialized,
+        LLVMOptions::new(OptimizerSettingsSizeLevel::Z, false, &[])
+    );
+    assert_eq!(
+        initialized,
+        LLVMOptions::new(OptimizerSettingsSizeLevel::S, true, &[])
+    );
+    assert_ne!(
+        initialized,
+        LLVMOptions::new(OptimizerSettingsSizeLevel::Z, true, &[])
+    );
+    assert_ne!(
+        initialized,
+        LLVMOptions::new(
+            OptimizerSettingsSizeLevel::Zero,
+            false,
+            &["-riscv-enable-machine-outliner".to_owned()]
+        )
+    );
+}
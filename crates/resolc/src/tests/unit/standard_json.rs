@@ -80,12 +80,20 @@ fn standard_json_selection_to_prune_with_evm_child_per_file() {
         SolcStandardJsonInputSettingsSelectionFileFlag::Devdoc,
         SolcStandardJsonInputSettingsSelectionFileFlag::Userdoc,
         SolcStandardJsonInputSettingsSelectionFileFlag::StorageLayout,
+        SolcStandardJsonInputSettingsSelectionFileFlag::TransientStorageLayout,
         SolcStandardJsonInputSettingsSelectionFileFlag::AST,
         SolcStandardJsonInputSettingsSelectionFileFlag::Yul,
         SolcStandardJsonInputSettingsSelectionFileFlag::EVMLA,
         SolcStandardJsonInputSettingsSelectionFileFlag::EVMDBC,
         SolcStandardJsonInputSettingsSelectionFileFlag::Assembly,
+        SolcStandardJsonInputSettingsSelectionFileFlag::GasEstimates,
         SolcStandardJsonInputSettingsSelectionFileFlag::Ir,
+        SolcStandardJsonInputSettingsSelectionFileFlag::ImmutableLayout,
+        SolcStandardJsonInputSettingsSelectionFileFlag::NewYorkIr,
+        SolcStandardJsonInputSettingsSelectionFileFlag::LLVMIr,
+        SolcStandardJsonInputSettingsSelectionFileFlag::LLVMIrOptimized,
+        SolcStandardJsonInputSettingsSelectionFileFlag::PolkaVMAssembly,
+        SolcStandardJsonInputSettingsSelectionFileFlag::AnnotatedAssembly,
     ];
 
     for (file_name, selection_to_prune_per_file) in &selection_to_prune.files.files {
@@ -128,10 +136,17 @@ fn standard_json_selection_to_prune_with_evm_parent_for_all() {
         SolcStandardJsonInputSettingsSelectionFileFlag::Devdoc,
         SolcStandardJsonInputSettingsSelectionFileFlag::Userdoc,
         SolcStandardJsonInputSettingsSelectionFileFlag::StorageLayout,
+        SolcStandardJsonInputSettingsSelectionFileFlag::TransientStorageLayout,
         SolcStandardJsonInputSettingsSelectionFileFlag::AST,
         SolcStandardJsonInputSettingsSelectionFileFlag::Yul,
         SolcStandardJsonInputSettingsSelectionFileFlag::EVMLA,
         SolcStandardJsonInputSettingsSelectionFileFlag::Ir,
+        SolcStandardJsonInputSettingsSelectionFileFlag::ImmutableLayout,
+        SolcStandardJsonInputSettingsSelectionFileFlag::NewYorkIr,
+        SolcStandardJsonInputSettingsSelectionFileFlag::LLVMIr,
+        SolcStandardJsonInputSettingsSelectionFileFlag::LLVMIrOptimized,
+        SolcStandardJsonInputSettingsSelectionFileFlag::PolkaVMAssembly,
+        SolcStandardJsonInputSettingsSelectionFileFlag::AnnotatedAssembly,
     ];
 
     // Verify that every expected flag exists in the selection to prune for the `all` wildcard.
@@ -0,0 +1,495 @@
+//! ABI-aware calldata encoding and result decoding for [`Specs`].
+//!
+//! Calls can be written as a function signature with string arguments instead of
+//! hex encoded calldata. The arguments are coerced into the parameter types, e.g.
+//! `"1000"` or `"1e3"` for a `uint256` and `"0x0101..01"` for an `address`.
+//!
+//! The ABI of contracts compiled from Solidity sources is known to the runner and
+//! used to resolve function names, return values and custom errors.
+
+use std::collections::BTreeMap;
+
+use alloy_dyn_abi::{DynSolType, DynSolValue, ErrorExt, JsonAbiExt, Specifier};
+use alloy_json_abi::{Constructor, Error, Function, JsonAbi, Param};
+use alloy_sol_types::{Panic, Revert, SolError};
+
+use crate::*;
+
+/// The ABI of a contract call or instantiation, used to decode its result.
+#[derive(Clone, Debug, Default)]
+pub struct CallAbi {
+    /// The return parameters of the called function.
+    outputs: Vec<Param>,
+    /// The custom errors of the called contract.
+    errors: Vec<Error>,
+}
+
+impl CallAbi {
+    /// Decode the return values from the call `output`.
+    pub fn decode_output(&self, output: &[u8]) -> Result<Vec<DynSolValue>, String> {
+        let types = resolve(&self.outputs)?;
+        match DynSolType::Tuple(types).abi_decode_sequence(output) {
+            Ok(DynSolValue::Tuple(values)) => Ok(values),
+            Ok(value) => Ok(vec![value]),
+            Err(error) => Err(format!(
+                "invalid return data 0x{}: {error}",
+                hex::encode(output)
+            )),
+        }
+    }
+
+    /// Coerce the `expected` return values into the return types and compare them
+    /// against the values decoded from the call `output`.
+    pub fn verify_values(&self, expected: &[String], output: &[u8]) {
+        let types = resolve(&self.outputs).unwrap_or_else(|error| panic!("{error}"));
+        assert_eq!(
+            types.len(),
+            expected.len(),
+            "expected {} return values, the function returns {}",
+            expected.len(),
+            types.len()
+        );
+        let expected = types
+            .iter()
+            .zip(expected)
+            .map(|(ty, value)| coerce(ty, value).unwrap_or_else(|error| panic!("{error}")))
+            .collect::<Vec<_>>();
+        let actual = self
+            .decode_output(output)
+            .unwrap_or_else(|error| panic!("{error}"));
+        assert_eq!(
+            actual.iter().map(format_value).collect::<Vec<_>>(),
+            expected.iter().map(format_value).collect::<Vec<_>>(),
+            "return value mismatch"
+        );
+    }
+
+    /// Decode the revert reason from the revert `output`.
+    ///
+    /// `Error(string)` reverts decode into the reason string, panics into `Panic(<code>)`
+    /// and custom errors of the contract into `<name>(<arguments>)`.
+    pub fn decode_revert(&self, output: &[u8]) -> Option<String> {
+        if let Ok(revert) = Revert::abi_decode(output) {
+            return Some(revert.reason);
+        }
+        if let Ok(panic) = Panic::abi_decode(output) {
+            return Some(format!("Panic(0x{:x})", panic.code));
+        }
+
+        let error = self
+            .errors
+            .iter()
+            .find(|error| output.starts_with(error.selector().as_slice()))?;
+        let decoded = error.decode_error(output).ok()?;
+        Some(format!(
+            "{}({})",
+            error.name,
+            decoded
+                .body
+                .iter()
+                .map(format_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+    /// Describe the `result` of the call, decoding its return values or revert reason.
+    pub fn describe(&self, result: &CallResult) -> String {
+        self.describe_output(result.did_revert(), &result.output())
+    }
+
+    fn describe_output(&self, reverted: bool, output: &[u8]) -> String {
+        if reverted {
+            return match self.decode_revert(output) {
+                Some(reason) => format!("reverted: {reason}"),
+                None => format!("reverted: 0x{}", hex::encode(output)),
+            };
+        }
+        match self.decode_output(output) {
+            Ok(values) if !self.outputs.is_empty() => format!(
+                "returned: ({})",
+                values
+                    .iter()
+                    .map(format_value)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            _ => format!("returned: 0x{}", hex::encode(output)),
+        }
+    }
+}
+
+/// The contract ABIs known to a [`Specs`] run, keyed by the index of their instantiation.
+#[derive(Default)]
+pub(crate) struct Abis {
+    contracts: BTreeMap<usize, JsonAbi>,
+    names: BTreeMap<usize, String>,
+}
+
+impl Abis {
+    /// Remember the ABI of the contract instantiated as `index` from `code` and
+    /// encode the constructor `args`.
+    ///
+    /// The constructor parameters are taken from the `constructor` signature if
+    /// provided, otherwise from the contract ABI.
+    pub fn instantiate(
+        &mut self,
+        index: usize,
+        code: &Code,
+        constructor: Option<&str>,
+        args: &[String],
+        data: Vec<u8>,
+    ) -> Result<(Vec<u8>, CallAbi), String> {
+        if let Some(name) = contract_name(code) {
+            self.names.insert(index, name.to_owned());
+        }
+        let contract = self.contract(&TestAddress::Instantiated(index));
+        let abi = code.abi();
+        let errors = abi
+            .as_ref()
+            .map(|abi| abi.errors().cloned().collect())
+            .unwrap_or_default();
+        let constructor = match (constructor, &abi) {
+            (Some(signature), _) => Some(Constructor::parse(signature).map_err(|error| {
+                format!("invalid constructor signature '{signature}' of {contract}: {error}")
+            })?),
+            (None, Some(abi)) if !args.is_empty() => abi.constructor().cloned(),
+            (None, _) => None,
+        };
+        if let Some(abi) = abi {
+            self.contracts.insert(index, abi);
+        }
+
+        let data = match constructor {
+            Some(constructor) => {
+                if !data.is_empty() {
+                    return Err(format!(
+                        "either provide the constructor arguments or the calldata of {contract}"
+                    ));
+                }
+                let args = coerce_all(&constructor.inputs, args)
+                    .map_err(|error| format!("the constructor of {contract}: {error}"))?;
+                constructor.abi_encode_input(&args).map_err(|error| {
+                    format!("invalid constructor arguments of {contract}: {error}")
+                })?
+            }
+            None if !args.is_empty() => {
+                return Err(format!(
+                    "the constructor arguments of {contract} require a constructor signature or a contract ABI"
+                ))
+            }
+            None => data,
+        };
+
+        Ok((
+            data,
+            CallAbi {
+                outputs: vec![],
+                errors,
+            },
+        ))
+    }
+
+    /// The ABI of the contract at `address`, if known.
+    pub fn get(&self, address: &TestAddress) -> Option<&JsonAbi> {
+        match address {
+            TestAddress::Instantiated(index) => self.contracts.get(index),
+            _ => None,
+        }
+    }
+
+    /// Describe the contract at `address` in error messages, by name if known.
+    fn contract(&self, address: &TestAddress) -> String {
+        match address {
+            TestAddress::Instantiated(index) => match self.names.get(index) {
+                Some(name) => format!("contract '{name}' (instantiated as {index})"),
+                None => format!("the contract instantiated as {index}"),
+            },
+            address => format!("{address:?}"),
+        }
+    }
+
+    /// Encode the calldata for calling `function` with `args` on the contract `dest`.
+    ///
+    /// The `function` is either a signature like `transfer(address,uint256)`, optionally
+    /// with return types, or the name of a function in the ABI of `dest`. Without a
+    /// `function`, the raw calldata is decoded against the ABI of `dest` if known.
+    pub fn call(
+        &self,
+        dest: &TestAddress,
+        function: Option<&str>,
+        args: &[String],
+        data: Vec<u8>,
+    ) -> Result<(Vec<u8>, CallAbi), String> {
+        let contract = self.contract(dest);
+        let abi = self.get(dest);
+        let errors = abi
+            .map(|abi| abi.errors().cloned().collect())
+            .unwrap_or_default();
+
+        let Some(function) = function else {
+            if !args.is_empty() {
+                return Err(format!(
+                    "the arguments of the call to {contract} require a function"
+                ));
+            }
+            let outputs = data
+                .get(..4)
+                .and_then(|selector| abi?.function_by_selector(selector.try_into().ok()?))
+                .map(|function| function.outputs.clone())
+                .unwrap_or_default();
+            return Ok((data, CallAbi { outputs, errors }));
+        };
+        if !data.is_empty() {
+            return Err(format!(
+                "either provide the function '{function}' and its arguments or the calldata of the call to {contract}"
+            ));
+        }
+
+        let function = resolve_function(abi, &contract, function)?;
+        let signature = function.signature();
+        let args = coerce_all(&function.inputs, args)
+            .map_err(|error| format!("function '{signature}' of {contract}: {error}"))?;
+        let data = function.abi_encode_input(&args).map_err(|error| {
+            format!("invalid arguments for function '{signature}' of {contract}: {error}")
+        })?;
+
+        Ok((
+            data,
+            CallAbi {
+                outputs: function.outputs,
+                errors,
+            },
+        ))
+    }
+}
+
+/// The name of the contract compiled from `code`, if any.
+fn contract_name(code: &Code) -> Option<&str> {
+    match code {
+        #[cfg(feature = "resolc")]
+        Code::Solidity { contract, .. }
+        | Code::SolidityLinked { contract, .. }
+        | Code::Yul { contract, .. } => Some(contract),
+        _ => None,
+    }
+}
+
+/// Resolve the `function` signature or name, preferring the definition in the `abi`
+/// of the `contract`.
+///
+/// Overloaded functions are resolved by their full signature, e.g. `transfer(address,uint256)`.
+fn resolve_function(
+    abi: Option<&JsonAbi>,
+    contract: &str,
+    function: &str,
+) -> Result<Function, String> {
+    if !function.contains('(') {
+        let Some(abi) = abi else {
+            return Err(format!(
+                "function '{function}' of {contract} requires a signature, the ABI is unknown"
+            ));
+        };
+        return match abi.function(function).map(Vec::as_slice) {
+            Some([function]) => Ok(function.clone()),
+            Some(overloads) => Err(format!(
+                "function '{function}' of {contract} is overloaded, provide one of the signatures {}",
+                overloads
+                    .iter()
+                    .map(|function| format!("'{}'", function.signature()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            None => Err(format!("function '{function}' not found in the ABI of {contract}")),
+        };
+    }
+
+    let parsed = Function::parse(function).map_err(|error| {
+        format!("invalid signature '{function}' of the function called on {contract}: {error}")
+    })?;
+    Ok(abi
+        .and_then(|abi| abi.function_by_selector(parsed.selector()))
+        .cloned()
+        .unwrap_or(parsed))
+}
+
+fn resolve(params: &[Param]) -> Result<Vec<DynSolType>, String> {
+    params
+        .iter()
+        .map(|param| {
+            param
+                .resolve()
+                .map_err(|error| format!("unsupported type {}: {error}", param.ty))
+        })
+        .collect()
+}
+
+fn coerce(ty: &DynSolType, value: &str) -> Result<DynSolValue, String> {
+    ty.coerce_str(value)
+        .map_err(|error| format!("invalid {ty} value '{value}': {error}"))
+}
+
+fn coerce_all(params: &[Param], args: &[String]) -> Result<Vec<DynSolValue>, String> {
+    if params.len() != args.len() {
+        return Err(format!(
+            "expected {} arguments, got {}",
+            params.len(),
+            args.len()
+        ));
+    }
+    resolve(params)?
+        .iter()
+        .zip(args)
+        .map(|(ty, value)| coerce(ty, value))
+        .collect()
+}
+
+/// Format a decoded value the way it is written in specs.
+pub(crate) fn format_value(value: &DynSolValue) -> String {
+    let list = |values: &[DynSolValue]| {
+        values
+            .iter()
+            .map(format_value)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match value {
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Int(value, _) => value.to_string(),
+        DynSolValue::Uint(value, _) => value.to_string(),
+        DynSolValue::FixedBytes(value, size) => format!("0x{}", hex::encode(&value[..*size])),
+        DynSolValue::Address(value) => value.to_checksum(None),
+        DynSolValue::Bytes(value) => format!("0x{}", hex::encode(value)),
+        DynSolValue::String(value) => value.clone(),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            format!("[{}]", list(values))
+        }
+        DynSolValue::Tuple(values) => format!("({})", list(values)),
+        value => format!("{value:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_sol_types::SolValue;
+
+    use super::*;
+
+    #[test]
+    fn encode_call() {
+        let (data, abi) = Abis::default()
+            .call(
+                &TestAddress::Alice,
+                Some("transfer(address,uint256) returns (bool)"),
+                &[
+                    "0x0101010101010101010101010101010101010101".into(),
+                    "1000".into(),
+                ],
+                vec![],
+            )
+            .unwrap();
+        assert_eq!(hex::encode(&data[..4]), "a9059cbb");
+        assert_eq!(
+            data[4..],
+            (
+                alloy_primitives::Address::repeat_byte(1),
+                alloy_primitives::U256::from(1000)
+            )
+                .abi_encode_params()
+        );
+
+        abi.verify_values(&["true".into()], &true.abi_encode());
+    }
+
+    #[test]
+    fn decode_revert() {
+        let abi = CallAbi {
+            outputs: vec![],
+            errors: vec![Error::parse("InsufficientBalance(uint256,uint256)").unwrap()],
+        };
+
+        let reason = Revert::from("insufficient balance").abi_encode();
+        assert_eq!(
+            abi.decode_revert(&reason).as_deref(),
+            Some("insufficient balance")
+        );
+
+        let panic = Panic::from(0x11).abi_encode();
+        assert_eq!(abi.decode_revert(&panic).as_deref(), Some("Panic(0x11)"));
+
+        let error = Error::parse("InsufficientBalance(uint256,uint256)").unwrap();
+        let custom = error
+            .abi_encode_input(&[
+                DynSolValue::Uint(alloy_primitives::U256::from(1), 256),
+                DynSolValue::Uint(alloy_primitives::U256::from(2), 256),
+            ])
+            .unwrap();
+        assert_eq!(
+            abi.decode_revert(&custom).as_deref(),
+            Some("InsufficientBalance(1, 2)")
+        );
+    }
+
+    #[test]
+    fn describe() {
+        let (_, abi) = Abis::default()
+            .call(
+                &TestAddress::Alice,
+                Some("balanceOf(address) returns (uint256, bool)"),
+                &["0x0101010101010101010101010101010101010101".into()],
+                vec![],
+            )
+            .unwrap();
+
+        let returned = (alloy_primitives::U256::from(7), true).abi_encode_params();
+        assert_eq!(abi.describe_output(false, &returned), "returned: (7, true)");
+
+        let reverted = Revert::from("insufficient balance").abi_encode();
+        assert_eq!(
+            abi.describe_output(true, &reverted),
+            "reverted: insufficient balance"
+        );
+        assert_eq!(abi.describe_output(true, &[]), "reverted: 0x");
+    }
+
+    #[test]
+    fn resolve_overloads() {
+        let mut abi = JsonAbi::new();
+        for signature in [
+            "transfer(address) returns (bool)",
+            "transfer(address,uint256) returns (bool)",
+        ] {
+            let function = Function::parse(signature).unwrap();
+            abi.functions
+                .entry(function.name.clone())
+                .or_default()
+                .push(function);
+        }
+        let mut abis = Abis::default();
+        abis.contracts.insert(0, abi);
+        abis.names.insert(0, "Token".to_string());
+        let dest = TestAddress::Instantiated(0);
+        let args = [
+            "0x0101010101010101010101010101010101010101".to_string(),
+            "1".to_string(),
+        ];
+
+        assert_eq!(
+            abis.call(&dest, Some("transfer"), &args, vec![])
+                .unwrap_err(),
+            "function 'transfer' of contract 'Token' (instantiated as 0) is overloaded, \
+            provide one of the signatures 'transfer(address)', 'transfer(address,uint256)'"
+        );
+
+        let (data, abi) = abis
+            .call(&dest, Some("transfer(address,uint256)"), &args, vec![])
+            .unwrap();
+        assert_eq!(hex::encode(&data[..4]), "a9059cbb");
+        abi.verify_values(&["true".into()], &true.abi_encode());
+
+        assert_eq!(
+            abis.call(&dest, Some("approve"), &args, vec![])
+                .unwrap_err(),
+            "function 'approve' not found in the ABI of contract 'Token' (instantiated as 0)"
+        );
+    }
+}
@@ -0,0 +1,993 @@
+//! Post-mortem step debugger for PVM contracts.
+//!
+//! The [`Recorder`] records the execution of traced contract calls instruction by
+//! instruction: executed instructions, register and memory accesses, host function
+//! calls, storage accesses and contract call frames. Once the execution finished, the
+//! [`Debugger`] replays the recording interactively, stepping forward and backward,
+//! stopping at breakpoints on function symbols, source lines (when the blob contains
+//! debug information, see `resolc -g`) or program counters. The execution itself can
+//! not be inspected or altered while it is running.
+//!
+//! The recording stops after [`DEFAULT_INSTRUCTION_LIMIT`] instructions unless another
+//! limit is set with [`Recorder::with_limit`]. The debugger keeps a snapshot of the
+//! machine state every [`CHECKPOINT_INTERVAL`] instructions, so inspecting the state
+//! replays at most that many instructions.
+//!
+//! Registers and memory are reconstructed from the accesses observed so far; values
+//! written by host functions (e.g. `call_data_copy`) are not visible until loaded.
+//! Recording instructions requires the [`crate::init_logger`] logger.
+
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    io::{BufRead, Write},
+    rc::Rc,
+};
+
+use pallet_revive::{
+    tracing::{FrameTraceInfo, Tracing, PVM_FUEL_NAME},
+    ExecReturnValue, Key,
+};
+use polkadot_sdk::{
+    sp_core::{H160, U256},
+    sp_runtime::DispatchError,
+};
+
+use crate::logger::{self, InterpreterLog, InterpreterLogSink};
+use crate::symbols::{CodeTracker, Symbol, Symbolizer};
+use crate::*;
+
+/// The PolkaVM registers in encoding order.
+const REGISTERS: [&str; 13] = [
+    "ra", "sp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4", "a5",
+];
+
+/// The default number of bytes printed by the `memory` command.
+const DEFAULT_MEMORY_LENGTH: u32 = 64;
+
+/// The default maximum number of recorded instructions.
+pub const DEFAULT_INSTRUCTION_LIMIT: usize = 1_000_000;
+
+/// The number of instructions between the machine state snapshots of the debugger.
+pub const CHECKPOINT_INTERVAL: usize = 1024;
+
+const HELP: &str = "\
+commands:
+  step [n], s [n]            execute the next n instructions (default 1)
+  reverse-step [n], rs [n]   go back n instructions (default 1)
+  continue, c                run until the next breakpoint or the end
+  reverse-continue, rc       run backwards until the previous breakpoint or the start
+  break <location>, b        break on a function symbol, a source `file:line` or a program counter
+  breakpoints                list the breakpoints
+  delete <n>                 delete the breakpoint with index n
+  where, w                   show the current instruction
+  backtrace, bt              show the contract call and host function stack
+  registers, r               show the registers
+  memory <address> [len], m  show the memory (`??` for bytes not observed yet)
+  storage                    show the storage slots observed for the current contract
+  help, h                    show this message
+  quit, q                    exit the debugger";
+
+/// A recorded event of the traced execution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Event {
+    /// A contract frame executing the code at `address` with the storage of `storage` was entered.
+    EnterFrame { address: H160, storage: H160 },
+    /// The current contract frame exited.
+    ExitFrame { reverted: bool },
+    /// The instruction at `program_counter` is executed.
+    Instruction {
+        program_counter: u32,
+        instruction: String,
+    },
+    /// A register was accessed by the last instruction.
+    Register { register: String, value: u64 },
+    /// The memory was accessed by the last instruction.
+    Memory { address: u32, size: u8, value: u64 },
+    /// A host function is called.
+    HostCall { name: &'static str, args: Vec<u64> },
+    /// The current host function returned.
+    HostReturn { returned: Option<u64> },
+    /// A storage slot of the current contract was accessed.
+    Storage {
+        key: Vec<u8>,
+        value: Option<Vec<u8>>,
+    },
+}
+
+/// The recorder state shared between the tracer and the logger.
+struct RecorderState {
+    events: Vec<Event>,
+    /// The number of recorded instructions.
+    instructions: usize,
+    /// The maximum number of recorded instructions.
+    limit: usize,
+    /// Whether events were dropped after reaching the `limit`.
+    truncated: bool,
+    /// The compiled handlers per frame: handler index to program counter and instruction.
+    compiled: Vec<BTreeMap<u32, (u32, String)>>,
+    /// The host function calls in progress per frame.
+    ecalls: Vec<Vec<&'static str>>,
+    code: CodeTracker,
+    /// The interpreter log capture while a traced call is executing.
+    capture: Option<logger::Capture>,
+}
+
+impl Default for RecorderState {
+    fn default() -> Self {
+        Self {
+            events: vec![],
+            instructions: 0,
+            limit: DEFAULT_INSTRUCTION_LIMIT,
+            truncated: false,
+            compiled: vec![],
+            ecalls: vec![],
+            code: Default::default(),
+            capture: None,
+        }
+    }
+}
+
+impl InterpreterLogSink for RecorderState {
+    fn interpreter_log(&mut self, log: InterpreterLog) {
+        let Some(compiled) = self.compiled.last_mut() else {
+            return;
+        };
+
+        match log {
+            InterpreterLog::Compiled {
+                handler,
+                program_counter,
+                instruction,
+            } => {
+                compiled.insert(handler, (program_counter, instruction.to_string()));
+            }
+            InterpreterLog::Executed { handler } => {
+                if let Some((program_counter, instruction)) = compiled.get(&handler).cloned() {
+                    if self.instructions == self.limit {
+                        self.truncated = true;
+                    } else {
+                        self.instructions += 1;
+                    }
+                    self.push(Event::Instruction {
+                        program_counter,
+                        instruction,
+                    });
+                }
+            }
+            InterpreterLog::Register { register, value } => self.push(Event::Register {
+                register: register.to_string(),
+                value,
+            }),
+            InterpreterLog::Memory {
+                address,
+                size,
+                value,
+            } => self.push(Event::Memory {
+                address,
+                size,
+                value,
+            }),
+            InterpreterLog::Other => {}
+        }
+    }
+}
+
+impl RecorderState {
+    /// Record `event` unless the instruction limit was reached.
+    fn push(&mut self, event: Event) {
+        if !self.truncated {
+            self.events.push(event);
+        }
+    }
+
+    fn exit_frame(&mut self, reverted: bool) {
+        self.compiled.pop();
+        self.ecalls.pop();
+        self.push(Event::ExitFrame { reverted });
+        if self.compiled.is_empty() {
+            self.capture = None;
+        }
+    }
+}
+
+/// A [`Tracing`] implementation recording the execution for the [`Debugger`].
+#[derive(Clone, Default)]
+pub struct Recorder {
+    state: Rc<RefCell<RecorderState>>,
+}
+
+impl Recorder {
+    /// A recorder stopping after `limit` instructions.
+    pub fn with_limit(limit: usize) -> Self {
+        let recorder = Self::default();
+        recorder.state.borrow_mut().limit = limit;
+        recorder
+    }
+
+    /// Take the recording collected so far.
+    ///
+    /// Ends the interpreter log capture of a traced call that did not finish (e.g. panicked).
+    pub fn recording(&self) -> Recording {
+        let mut state = self.state.borrow_mut();
+        state.capture = None;
+        state.compiled.clear();
+        state.ecalls.clear();
+        state.instructions = 0;
+        Recording {
+            events: std::mem::take(&mut state.events),
+            truncated: std::mem::take(&mut state.truncated),
+            code: std::mem::take(&mut state.code),
+        }
+    }
+}
+
+impl Tracing for Recorder {
+    fn instantiate_code(&mut self, code: &pallet_revive::Code, _salt: Option<&[u8; 32]>) {
+        self.state.borrow_mut().code.instantiate_code(code);
+    }
+
+    fn enter_child_span(
+        &mut self,
+        from: H160,
+        to: H160,
+        delegate_call: Option<H160>,
+        _is_read_only: bool,
+        _value: U256,
+        _input: &[u8],
+        _gas_limit: u64,
+    ) {
+        if self.state.borrow().compiled.is_empty() {
+            let capture = logger::Capture::new(self.state.clone());
+            self.state.borrow_mut().capture = Some(capture);
+        }
+
+        let mut state = self.state.borrow_mut();
+        state.code.enter_frame(to);
+        state.compiled.push(Default::default());
+        state.ecalls.push(Default::default());
+        state.push(Event::EnterFrame {
+            address: to,
+            storage: delegate_call.map(|_| from).unwrap_or(to),
+        });
+    }
+
+    fn exit_child_span(&mut self, output: &ExecReturnValue, _gas_used: u64, _weight: Weight) {
+        self.state.borrow_mut().exit_frame(output.did_revert());
+    }
+
+    fn exit_child_span_with_error(
+        &mut self,
+        _error: DispatchError,
+        _gas_used: u64,
+        _weight: Weight,
+    ) {
+        self.state.borrow_mut().exit_frame(true);
+    }
+
+    fn enter_ecall(&mut self, name: &'static str, args: &[u64], _trace_info: &dyn FrameTraceInfo) {
+        let mut state = self.state.borrow_mut();
+        let Some(ecalls) = state.ecalls.last_mut() else {
+            return;
+        };
+        ecalls.push(name);
+        if name != PVM_FUEL_NAME {
+            state.push(Event::HostCall {
+                name,
+                args: args.to_vec(),
+            });
+        }
+    }
+
+    fn exit_step(&mut self, _trace_info: &dyn FrameTraceInfo, returned: Option<u64>) {
+        let mut state = self.state.borrow_mut();
+        let Some(name) = state.ecalls.last_mut().and_then(Vec::pop) else {
+            return;
+        };
+        if name != PVM_FUEL_NAME {
+            state.push(Event::HostReturn { returned });
+        }
+    }
+
+    fn storage_read(&mut self, key: &Key, value: Option<&[u8]>) {
+        self.state.borrow_mut().push(Event::Storage {
+            key: key.unhashed().to_vec(),
+            value: value.map(<[u8]>::to_vec),
+        });
+    }
+
+    fn storage_write(&mut self, key: &Key, _old_value: Option<Vec<u8>>, new_value: Option<&[u8]>) {
+        self.state.borrow_mut().push(Event::Storage {
+            key: key.unhashed().to_vec(),
+            value: new_value.map(<[u8]>::to_vec),
+        });
+    }
+}
+
+/// A recorded execution.
+#[derive(Default)]
+pub struct Recording {
+    events: Vec<Event>,
+    /// Whether the recording stopped at the instruction limit.
+    truncated: bool,
+    code: CodeTracker,
+}
+
+impl Recording {
+    /// The number of recorded instructions.
+    pub fn instructions(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|event| matches!(event, Event::Instruction { .. }))
+            .count()
+    }
+
+    /// Whether the recording stopped at the instruction limit before the execution finished.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+/// An executed instruction.
+struct Step {
+    /// The index of the [`Event::Instruction`].
+    event: usize,
+    /// The address of the contract executing the instruction.
+    address: H160,
+    /// The contract call depth.
+    depth: usize,
+    program_counter: u32,
+}
+
+/// A debugger breakpoint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Breakpoint {
+    /// Stop when entering the function with the given symbol.
+    Function(String),
+    /// Stop when entering the given source line.
+    Line { file: String, line: u32 },
+    /// Stop at the given program counter.
+    ProgramCounter(u32),
+}
+
+impl std::fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Function(name) => write!(f, "function {name}"),
+            Self::Line { file, line } => write!(f, "line {file}:{line}"),
+            Self::ProgramCounter(program_counter) => write!(f, "pc {program_counter}"),
+        }
+    }
+}
+
+impl std::str::FromStr for Breakpoint {
+    type Err = String;
+
+    fn from_str(location: &str) -> Result<Self, Self::Err> {
+        if location.is_empty() {
+            return Err("expected a function symbol, file:line or program counter".to_string());
+        }
+        if let Ok(program_counter) = location.parse() {
+            return Ok(Self::ProgramCounter(program_counter));
+        }
+        if let Some((file, line)) = location.rsplit_once(':') {
+            if let Ok(line) = line.parse() {
+                return Ok(Self::Line {
+                    file: file.to_string(),
+                    line,
+                });
+            }
+        }
+        Ok(Self::Function(location.to_string()))
+    }
+}
+
+/// The contract frame state reconstructed from the recorded events.
+#[derive(Clone, Default)]
+struct FrameState {
+    address: H160,
+    storage: H160,
+    registers: BTreeMap<String, u64>,
+    memory: BTreeMap<u32, u8>,
+    host_call: Option<(&'static str, Vec<u64>)>,
+    program_counter: Option<u32>,
+    /// The storage before the frame was entered, restored if the frame reverts.
+    storage_snapshot: BTreeMap<H160, BTreeMap<Vec<u8>, Option<Vec<u8>>>>,
+}
+
+/// The machine state reconstructed from the recorded events.
+#[derive(Clone, Default)]
+struct MachineState {
+    frames: Vec<FrameState>,
+    storage: BTreeMap<H160, BTreeMap<Vec<u8>, Option<Vec<u8>>>>,
+}
+
+impl MachineState {
+    fn apply(&mut self, event: &Event) {
+        match event {
+            Event::EnterFrame { address, storage } => self.frames.push(FrameState {
+                address: *address,
+                storage: *storage,
+                storage_snapshot: self.storage.clone(),
+                ..Default::default()
+            }),
+            Event::ExitFrame { reverted } => {
+                if let Some(frame) = self.frames.pop() {
+                    if *reverted {
+                        self.storage = frame.storage_snapshot;
+                    }
+                }
+            }
+            Event::Instruction {
+                program_counter, ..
+            } => {
+                if let Some(frame) = self.frames.last_mut() {
+                    frame.program_counter = Some(*program_counter);
+                }
+            }
+            Event::Register { register, value } => {
+                if let Some(frame) = self.frames.last_mut() {
+                    frame.registers.insert(register.clone(), *value);
+                }
+            }
+            Event::Memory {
+                address,
+                size,
+                value,
+            } => {
+                if let Some(frame) = self.frames.last_mut() {
+                    for (offset, byte) in value.to_le_bytes()[..*size as usize].iter().enumerate() {
+                        frame
+                            .memory
+                            .insert(address.wrapping_add(offset as u32), *byte);
+                    }
+                }
+            }
+            Event::HostCall { name, args } => {
+                if let Some(frame) = self.frames.last_mut() {
+                    frame.host_call = Some((name, args.clone()));
+                }
+            }
+            Event::HostReturn { .. } => {
+                if let Some(frame) = self.frames.last_mut() {
+                    frame.host_call = None;
+                }
+            }
+            Event::Storage { key, value } => {
+                if let Some(frame) = self.frames.last() {
+                    self.storage
+                        .entry(frame.storage)
+                        .or_default()
+                        .insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+}
+
+/// An interactive debugger replaying a [`Recording`].
+pub struct Debugger {
+    recording: Recording,
+    steps: Vec<Step>,
+    /// The machine state at every [`CHECKPOINT_INTERVAL`]th step.
+    checkpoints: Vec<MachineState>,
+    cursor: usize,
+    breakpoints: Vec<Breakpoint>,
+    symbolizer: Symbolizer,
+}
+
+impl Debugger {
+    /// Create a new debugger positioned at the first recorded instruction.
+    pub fn new(recording: Recording) -> Self {
+        let mut steps = vec![];
+        let mut checkpoints = vec![];
+        let mut frames = vec![];
+        let mut state = MachineState::default();
+        for (index, event) in recording.events.iter().enumerate() {
+            state.apply(event);
+            match event {
+                Event::EnterFrame { address, .. } => frames.push(*address),
+                Event::ExitFrame { .. } => {
+                    frames.pop();
+                }
+                Event::Instruction {
+                    program_counter, ..
+                } => {
+                    if steps.len() % CHECKPOINT_INTERVAL == 0 {
+                        checkpoints.push(state.clone());
+                    }
+                    steps.push(Step {
+                        event: index,
+                        address: frames.last().copied().unwrap_or_default(),
+                        depth: frames.len(),
+                        program_counter: *program_counter,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            recording,
+            steps,
+            checkpoints,
+            cursor: 0,
+            breakpoints: vec![],
+            symbolizer: Symbolizer::default(),
+        }
+    }
+
+    /// Run the debugger, reading commands from `input` until it is exhausted or `quit`.
+    pub fn run(&mut self, mut input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+        if self.steps.is_empty() {
+            writeln!(
+                output,
+                "no instructions were recorded (is the runner logger installed?)"
+            )?;
+            return Ok(());
+        }
+        writeln!(
+            output,
+            "recorded {} instructions; type `help` for the list of commands",
+            self.steps.len()
+        )?;
+        if self.recording.truncated {
+            writeln!(
+                output,
+                "the recording stopped at the instruction limit before the execution finished"
+            )?;
+        }
+        self.print_where(&mut output)?;
+
+        let mut line = String::new();
+        loop {
+            write!(output, "(revive) ")?;
+            output.flush()?;
+            line.clear();
+            if input.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            if !self.execute(line.trim(), &mut output)? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Execute a single command. Returns `false` if the debugger should exit.
+    fn execute(&mut self, command: &str, output: &mut impl Write) -> std::io::Result<bool> {
+        let mut words = command.split_whitespace();
+        let Some(command) = words.next() else {
+            return Ok(true);
+        };
+        let argument = words.next();
+        let count = || {
+            argument
+                .and_then(|count| count.parse().ok())
+                .unwrap_or(1usize)
+        };
+
+        match command {
+            "step" | "s" => {
+                self.cursor = (self.cursor + count()).min(self.steps.len() - 1);
+                self.print_where(output)?;
+            }
+            "reverse-step" | "rs" => {
+                self.cursor = self.cursor.saturating_sub(count());
+                self.print_where(output)?;
+            }
+            "continue" | "c" => {
+                let hit =
+                    (self.cursor + 1..self.steps.len()).find(|step| self.is_breakpoint(*step));
+                self.stop_at(hit, self.steps.len() - 1, output)?;
+            }
+            "reverse-continue" | "rc" => {
+                let hit = (0..self.cursor)
+                    .rev()
+                    .find(|step| self.is_breakpoint(*step));
+                self.stop_at(hit, 0, output)?;
+            }
+            "break" | "b" => match argument.unwrap_or_default().parse::<Breakpoint>() {
+                Ok(breakpoint) => {
+                    writeln!(
+                        output,
+                        "breakpoint {}: {breakpoint}",
+                        self.breakpoints.len()
+                    )?;
+                    self.breakpoints.push(breakpoint);
+                }
+                Err(error) => writeln!(output, "{error}")?,
+            },
+            "breakpoints" => {
+                for (index, breakpoint) in self.breakpoints.iter().enumerate() {
+                    writeln!(output, "{index}: {breakpoint}")?;
+                }
+            }
+            "delete" => match argument.and_then(|index| index.parse::<usize>().ok()) {
+                Some(index) if index < self.breakpoints.len() => {
+                    let breakpoint = self.breakpoints.remove(index);
+                    writeln!(output, "deleted breakpoint {index}: {breakpoint}")?;
+                }
+                _ => writeln!(output, "expected a valid breakpoint index")?,
+            },
+            "where" | "w" => self.print_where(output)?,
+            "backtrace" | "bt" => self.print_backtrace(output)?,
+            "registers" | "r" => self.print_registers(output)?,
+            "memory" | "m" => {
+                let address = argument.and_then(parse_number);
+                let length = words
+                    .next()
+                    .and_then(parse_number)
+                    .unwrap_or(DEFAULT_MEMORY_LENGTH);
+                match address {
+                    Some(address) => self.print_memory(address, length, output)?,
+                    None => writeln!(output, "expected a memory address")?,
+                }
+            }
+            "storage" => self.print_storage(output)?,
+            "help" | "h" => writeln!(output, "{HELP}")?,
+            "quit" | "q" => return Ok(false),
+            command => writeln!(output, "unknown command `{command}`; type `help`")?,
+        }
+
+        Ok(true)
+    }
+
+    fn stop_at(
+        &mut self,
+        hit: Option<usize>,
+        end: usize,
+        output: &mut impl Write,
+    ) -> std::io::Result<()> {
+        match hit {
+            Some(step) => {
+                self.cursor = step;
+                writeln!(output, "breakpoint hit")?;
+            }
+            None => {
+                self.cursor = end;
+                writeln!(output, "no breakpoint hit")?;
+            }
+        }
+        self.print_where(output)
+    }
+
+    fn symbol(&mut self, step: usize) -> Symbol {
+        let Step {
+            address,
+            program_counter,
+            ..
+        } = self.steps[step];
+        match self.recording.code.get(&address) {
+            Some(code) => self.symbolizer.symbol(code, program_counter),
+            None => Symbol::default(),
+        }
+    }
+
+    fn is_breakpoint(&mut self, step: usize) -> bool {
+        if self.breakpoints.is_empty() {
+            return false;
+        }
+
+        let symbol = self.symbol(step);
+        let previous = match step {
+            0 => None,
+            step if self.steps[step - 1].depth == self.steps[step].depth => {
+                Some(self.symbol(step - 1))
+            }
+            _ => None,
+        };
+        let program_counter = self.steps[step].program_counter;
+
+        self.breakpoints.iter().any(|breakpoint| match breakpoint {
+            Breakpoint::ProgramCounter(target) => *target == program_counter,
+            Breakpoint::Function(name) => {
+                symbol.functions.last() == Some(name)
+                    && previous.as_ref().and_then(|symbol| symbol.functions.last()) != Some(name)
+            }
+            Breakpoint::Line { file, line } => {
+                let matches = |symbol: &Symbol| {
+                    symbol
+                        .location
+                        .as_ref()
+                        .is_some_and(|(path, at)| path.ends_with(file.as_str()) && at == line)
+                };
+                matches(&symbol) && !previous.as_ref().is_some_and(matches)
+            }
+        })
+    }
+
+    /// The machine state before executing the current instruction.
+    ///
+    /// The accesses of the current instruction are recorded after it, hence are not applied.
+    fn state(&self) -> MachineState {
+        let checkpoint = self.cursor / CHECKPOINT_INTERVAL;
+        let mut state = self.checkpoints[checkpoint].clone();
+        let start = self.steps[checkpoint * CHECKPOINT_INTERVAL].event;
+        for event in &self.recording.events[start + 1..=self.steps[self.cursor].event] {
+            state.apply(event);
+        }
+        state
+    }
+
+    fn print_where(&mut self, output: &mut impl Write) -> std::io::Result<()> {
+        let step = &self.steps[self.cursor];
+        let Event::Instruction { instruction, .. } = &self.recording.events[step.event] else {
+            unreachable!("steps always point to instructions");
+        };
+        writeln!(
+            output,
+            "#{} {:?} pc {}: {instruction}",
+            self.cursor, step.address, step.program_counter
+        )?;
+
+        let symbol = self.symbol(self.cursor);
+        if let Some(function) = symbol.functions.last() {
+            match symbol.location {
+                Some((path, line)) => writeln!(output, "    in {function} at {path}:{line}")?,
+                None => writeln!(output, "    in {function}")?,
+            }
+        }
+        Ok(())
+    }
+
+    fn print_backtrace(&mut self, output: &mut impl Write) -> std::io::Result<()> {
+        let state = self.state();
+        for (depth, frame) in state.frames.iter().enumerate().rev() {
+            let functions = match (
+                frame.program_counter,
+                self.recording.code.get(&frame.address),
+            ) {
+                (Some(program_counter), Some(code)) => {
+                    self.symbolizer.symbol(code, program_counter).functions
+                }
+                _ => vec![],
+            };
+            write!(output, "#{depth} {:?}", frame.address)?;
+            if let Some(program_counter) = frame.program_counter {
+                write!(output, " pc {program_counter}")?;
+            }
+            for function in functions.iter().rev() {
+                write!(output, " in {function}")?;
+            }
+            writeln!(output)?;
+            if let Some((name, args)) = &frame.host_call {
+                writeln!(output, "    host function {name}{args:x?}")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn print_registers(&self, output: &mut impl Write) -> std::io::Result<()> {
+        let state = self.state();
+        let Some(frame) = state.frames.last() else {
+            return Ok(());
+        };
+        for register in REGISTERS {
+            match frame.registers.get(register) {
+                Some(value) => writeln!(output, "{register:>2} = 0x{value:016x}")?,
+                None => writeln!(output, "{register:>2} = ?")?,
+            }
+        }
+        Ok(())
+    }
+
+    fn print_memory(
+        &self,
+        address: u32,
+        length: u32,
+        output: &mut impl Write,
+    ) -> std::io::Result<()> {
+        let state = self.state();
+        let Some(frame) = state.frames.last() else {
+            return Ok(());
+        };
+        for row in (0..length).step_by(16) {
+            let row_address = address.wrapping_add(row);
+            write!(output, "0x{row_address:08x}:")?;
+            for offset in row..(row + 16).min(length) {
+                match frame.memory.get(&address.wrapping_add(offset)) {
+                    Some(byte) => write!(output, " {byte:02x}")?,
+                    None => write!(output, " ??")?,
+                }
+            }
+            writeln!(output)?;
+        }
+        Ok(())
+    }
+
+    fn print_storage(&self, output: &mut impl Write) -> std::io::Result<()> {
+        let state = self.state();
+        let Some(frame) = state.frames.last() else {
+            return Ok(());
+        };
+        let Some(slots) = state.storage.get(&frame.storage) else {
+            return writeln!(output, "no storage slots observed for {:?}", frame.storage);
+        };
+        for (key, value) in slots {
+            match value {
+                Some(value) => writeln!(output, "{} = {}", hex::encode(key), hex::encode(value))?,
+                None => writeln!(output, "{} = <empty>", hex::encode(key))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_number(value: &str) -> Option<u32> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording() -> Recording {
+        let address = H160([0xaa; 20]);
+        let instruction = |program_counter: u32| Event::Instruction {
+            program_counter,
+            instruction: format!("instruction {program_counter}"),
+        };
+        Recording {
+            events: vec![
+                Event::EnterFrame {
+                    address,
+                    storage: address,
+                },
+                instruction(0),
+                Event::Register {
+                    register: "a0".to_string(),
+                    value: 0x2a,
+                },
+                instruction(3),
+                Event::Memory {
+                    address: 0x20000,
+                    size: 2,
+                    value: 0xbeef,
+                },
+                Event::HostCall {
+                    name: "set_storage_or_clear",
+                    args: vec![0, 0x20000],
+                },
+                Event::Storage {
+                    key: vec![1; 32],
+                    value: Some(vec![2; 32]),
+                },
+                Event::HostReturn { returned: Some(0) },
+                instruction(5),
+                Event::ExitFrame { reverted: false },
+            ],
+            truncated: false,
+            code: Default::default(),
+        }
+    }
+
+    /// Record the instantiation of the baseline fixture.
+    fn record(recorder: &mut Recorder) -> Recording {
+        Specs {
+            actions: vec![SpecsAction::instantiate(
+                Code::Bytes(include_bytes!("../fixtures/Baseline.pvm").to_vec()),
+                vec![],
+            )],
+            ..Default::default()
+        }
+        .run_with_tracer(recorder);
+        recorder.recording()
+    }
+
+    fn debug(commands: &str) -> String {
+        let mut output = vec![];
+        Debugger::new(recording())
+            .run(commands.as_bytes(), &mut output)
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn step_and_inspect() {
+        let output = debug("s 2\nr\nm 0x20000 4\nstorage\nrs\nw\n");
+        assert!(output.contains("recorded 3 instructions"));
+        assert!(output.contains("#2 0xaaaa"));
+        assert!(output.contains("a0 = 0x000000000000002a"));
+        assert!(output.contains("0x00020000: ef be ?? ??"));
+        assert!(output.contains(&format!("{} = {}", "01".repeat(32), "02".repeat(32))));
+        assert!(output.contains("#1 0xaaaa"));
+    }
+
+    #[test]
+    fn breakpoints() {
+        let output = debug("b 5\nc\nbreakpoints\ndelete 0\nrc\n");
+        assert!(output.contains("breakpoint 0: pc 5"));
+        assert!(output.contains("breakpoint hit\n#2"));
+        assert!(output.contains("0: pc 5"));
+        assert!(output.contains("no breakpoint hit\n#0"));
+    }
+
+    #[test]
+    fn parse_breakpoint() {
+        assert_eq!(
+            "12".parse::<Breakpoint>(),
+            Ok(Breakpoint::ProgramCounter(12))
+        );
+        assert_eq!(
+            "Token.sol:7".parse::<Breakpoint>(),
+            Ok(Breakpoint::Line {
+                file: "Token.sol".to_string(),
+                line: 7
+            })
+        );
+        assert_eq!(
+            "fun_transfer_12".parse::<Breakpoint>(),
+            Ok(Breakpoint::Function("fun_transfer_12".to_string()))
+        );
+    }
+
+    #[test]
+    fn debug_fixture() {
+        let recording = record(&mut Recorder::default());
+        let instructions = recording.instructions();
+        assert!(instructions > 0);
+        assert!(!recording.truncated());
+
+        let mut output = vec![];
+        Debugger::new(recording)
+            .run("c\nbt\nr\n".as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(&format!("recorded {instructions} instructions")));
+        assert!(output.contains(&format!("no breakpoint hit\n#{}", instructions - 1)));
+        assert!(output.contains("#0 0x"), "{output}");
+        assert!(output.contains(" a0 = "), "{output}");
+    }
+
+    #[test]
+    fn instruction_limit() {
+        let recording = record(&mut Recorder::with_limit(10));
+        assert_eq!(recording.instructions(), 10);
+        assert!(recording.truncated());
+
+        let mut output = vec![];
+        Debugger::new(recording)
+            .run("s 20\n".as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("stopped at the instruction limit"));
+        assert!(output.contains("#9 0x"));
+    }
+
+    #[test]
+    fn checkpoints() {
+        let address = H160([0xaa; 20]);
+        let mut events = vec![Event::EnterFrame {
+            address,
+            storage: address,
+        }];
+        for program_counter in 0..3 * CHECKPOINT_INTERVAL as u32 {
+            events.push(Event::Instruction {
+                program_counter,
+                instruction: String::new(),
+            });
+            events.push(Event::Register {
+                register: "a0".to_string(),
+                value: program_counter.into(),
+            });
+        }
+        let mut debugger = Debugger::new(Recording {
+            events,
+            ..Default::default()
+        });
+        assert_eq!(debugger.checkpoints.len(), 3);
+
+        for cursor in [0, CHECKPOINT_INTERVAL - 1, CHECKPOINT_INTERVAL, 2500] {
+            debugger.cursor = cursor;
+            let registers = &debugger.state().frames[0].registers;
+            // The register access of the current instruction is not applied yet.
+            assert_eq!(
+                registers.get("a0").copied(),
+                cursor.checked_sub(1).map(|value| value as u64)
+            );
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Host function call tracking shared by the weight attributing tracers.
+//!
+//! The pallet reports the PolkaVM interpreter fuel synchronizations like host function
+//! calls named [`PVM_FUEL_NAME`]. [`Ecalls`] separates them from the actual host function
+//! calls and attributes the weight consumed by nested contract calls to the nested frames.
+
+use pallet_revive::tracing::PVM_FUEL_NAME;
+
+use crate::*;
+
+/// A host function call in progress.
+struct Ecall {
+    name: &'static str,
+    start: Weight,
+    nested: Weight,
+}
+
+/// A finished host function call or interpreter fuel synchronization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Exited {
+    /// The interpreter fuel consumed since the last synchronization.
+    Fuel(Weight),
+    /// The weight consumed by a host function, excluding nested contract calls.
+    HostFunction { name: &'static str, weight: Weight },
+}
+
+/// The host function calls in progress in a contract call frame.
+#[derive(Default)]
+pub(crate) struct Ecalls {
+    ecalls: Vec<Ecall>,
+    /// The weight consumed when the last fuel synchronization finished.
+    fuel_mark: Option<Weight>,
+}
+
+impl Ecalls {
+    /// Enter the host function `name` after `consumed` weight.
+    pub fn enter(&mut self, name: &'static str, consumed: Weight) {
+        // The host function overhead is charged right after the fuel synchronization.
+        let start = match name {
+            PVM_FUEL_NAME => consumed,
+            _ => self.fuel_mark.take().unwrap_or(consumed),
+        };
+        self.ecalls.push(Ecall {
+            name,
+            start,
+            nested: Weight::zero(),
+        });
+    }
+
+    /// Exit the innermost host function after `consumed` weight.
+    pub fn exit(&mut self, consumed: Weight) -> Option<Exited> {
+        let ecall = self.ecalls.pop()?;
+        let weight = consumed
+            .saturating_sub(ecall.start)
+            .saturating_sub(ecall.nested);
+        Some(match ecall.name {
+            PVM_FUEL_NAME => {
+                self.fuel_mark = Some(consumed);
+                Exited::Fuel(weight)
+            }
+            name => Exited::HostFunction { name, weight },
+        })
+    }
+
+    /// Exclude the `weight` consumed by a nested contract call from the innermost host function.
+    pub fn nested(&mut self, weight: Weight) {
+        if let Some(ecall) = self.ecalls.last_mut() {
+            ecall.nested = ecall.nested.saturating_add(weight);
+        }
+    }
+
+    /// The innermost host function in progress.
+    pub fn current(&self) -> Option<&'static str> {
+        self.ecalls.last().map(|ecall| ecall.name)
+    }
+}
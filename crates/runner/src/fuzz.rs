@@ -0,0 +1,366 @@
+//! Differential fuzzing of contracts against the EVM.
+//!
+//! The [`Fuzzer`] generates random sequences of ABI-valid calls to a contract and
+//! turns them into differential [`Specs`]: the calls are executed on the EVM first
+//! and pallet-revive is expected to agree on the success, output, balances and
+//! storage after each call.
+//!
+//! The calls are written as function signatures with string arguments, hence a
+//! failing sequence can be saved as a readable `runner.json` spec and replayed.
+
+use std::collections::BTreeSet;
+
+use alloy_dyn_abi::{DynSolType, DynSolValue, Specifier};
+use alloy_json_abi::{Function, JsonAbi, Param, StateMutability};
+use alloy_primitives::{Address, I256, U256};
+
+use crate::{abi::format_value, *};
+
+/// The number of bytes and elements generated for dynamic types at most.
+const MAX_DYNAMIC_LENGTH: u64 = 64;
+/// The number of elements generated for dynamic arrays at most.
+const MAX_ARRAY_LENGTH: u64 = 4;
+/// The value sent with payable calls at most, small enough for the sequences to stay
+/// within the genesis balances of the EVM.
+const MAX_VALUE: u64 = 1_000_000;
+
+/// Generates random call sequences for a contract.
+pub struct Fuzzer {
+    code: Code,
+    constructor: Option<Vec<DynSolType>>,
+    payable_constructor: bool,
+    functions: Vec<(String, Vec<DynSolType>)>,
+    payable: BTreeSet<String>,
+    rng: Rng,
+}
+
+impl Fuzzer {
+    /// Create a fuzzer for the contract deployed from `code` with the given `abi`.
+    ///
+    /// Functions with parameter types that can not be generated are skipped.
+    /// The `seed` determines the generated sequences.
+    pub fn new(code: Code, abi: &JsonAbi, seed: u64) -> Self {
+        let functions = callable(abi.functions());
+        assert!(
+            !functions.is_empty(),
+            "the contract has no functions which can be fuzzed"
+        );
+
+        let constructor = abi
+            .constructor()
+            .map(|constructor| {
+                types(&constructor.inputs).unwrap_or_else(|| {
+                    panic!("the constructor parameters of the contract can not be generated")
+                })
+            })
+            .filter(|types| !types.is_empty());
+        let payable_constructor = abi
+            .constructor()
+            .is_some_and(|constructor| constructor.state_mutability == StateMutability::Payable);
+        let payable = abi
+            .functions()
+            .filter(|function| function.state_mutability == StateMutability::Payable)
+            .map(Function::signature)
+            .collect();
+
+        Self {
+            code,
+            constructor,
+            payable_constructor,
+            functions,
+            payable,
+            rng: Rng(seed),
+        }
+    }
+
+    /// Generate differential [`Specs`] deploying the contract and calling `depth`
+    /// randomly chosen functions with random arguments.
+    ///
+    /// The contract is deployed by [`TestAddress::Alice`], the calls are made by a random
+    /// one of Alice, Bob and Charlie. Payable functions are sent a random value.
+    pub fn sequence(&mut self, depth: usize) -> Specs {
+        let args = self
+            .constructor
+            .clone()
+            .map(|types| self.rng.arguments(&types))
+            .unwrap_or_default();
+        let mut actions = vec![SpecsAction::Instantiate {
+            origin: TestAddress::Alice,
+            value: match self.payable_constructor {
+                true => self.rng.value_amount(),
+                false => 0,
+            },
+            gas_limit: None,
+            storage_deposit_limit: None,
+            code: self.code.clone(),
+            data: vec![],
+            salt: OptionalHex::default(),
+            constructor: None,
+            args,
+        }];
+
+        for _ in 0..depth {
+            let index = self.rng.below(self.functions.len() as u64) as usize;
+            let (signature, types) = self.functions[index].clone();
+            let origin = match self.rng.below(3) {
+                0 => TestAddress::Alice,
+                1 => TestAddress::Bob,
+                _ => TestAddress::Charlie,
+            };
+            let value = match self.payable.contains(&signature) {
+                true => self.rng.value_amount(),
+                false => 0,
+            };
+            actions.push(SpecsAction::Call {
+                origin,
+                dest: TestAddress::Instantiated(0),
+                value,
+                gas_limit: None,
+                storage_deposit_limit: None,
+                data: vec![],
+                call: Some(signature),
+                args: self.rng.arguments(&types),
+            });
+        }
+
+        Specs {
+            differential: true,
+            actions,
+            ..Default::default()
+        }
+    }
+}
+
+/// Run the differential `specs`, returning the reason if the execution on
+/// pallet-revive diverges from the EVM.
+///
+/// Divergences are detected by catching the panic of the failing expectation; the
+/// caller may want to install a silent panic hook while fuzzing.
+pub fn check(specs: Specs) -> Result<(), String> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        specs.run();
+    }))
+    .map_err(panic_message)
+}
+
+/// The message of a caught panic.
+pub(crate) fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(ToString::to_string))
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// The signatures and parameter types of the `functions` which can be called with
+/// generated arguments.
+pub(crate) fn callable<'a>(
+    functions: impl Iterator<Item = &'a Function>,
+) -> Vec<(String, Vec<DynSolType>)> {
+    functions
+        .filter_map(|function| Some((function.signature(), types(&function.inputs)?)))
+        .collect()
+}
+
+/// Resolve the parameter types, or `None` if any of them can not be generated.
+fn types(params: &[Param]) -> Option<Vec<DynSolType>> {
+    params
+        .iter()
+        .map(|param| param.resolve().ok().filter(supported))
+        .collect()
+}
+
+fn supported(ty: &DynSolType) -> bool {
+    match ty {
+        DynSolType::Function => false,
+        DynSolType::Array(ty) | DynSolType::FixedArray(ty, _) => supported(ty),
+        DynSolType::Tuple(types) => types.iter().all(supported),
+        _ => true,
+    }
+}
+
+/// A small deterministic SplitMix64 generator, so failing seeds can be reproduced.
+pub(crate) struct Rng(pub u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    fn bytes(&mut self, length: usize) -> Vec<u8> {
+        (0..length).map(|_| self.next() as u8).collect()
+    }
+
+    /// A random value to send with a call, biased towards edge cases.
+    fn value_amount(&mut self) -> Balance {
+        match self.below(4) {
+            0 => 0,
+            1 => 1,
+            _ => self.below(MAX_VALUE + 1) as Balance,
+        }
+    }
+
+    /// Random arguments of the parameter `types`, formatted the way they are written in specs.
+    pub fn arguments(&mut self, types: &[DynSolType]) -> Vec<String> {
+        types
+            .iter()
+            .map(|ty| format_value(&self.value(ty)))
+            .collect()
+    }
+
+    /// A random word truncated to `bits`, biased towards edge cases.
+    fn word(&mut self, bits: usize) -> U256 {
+        let max = U256::MAX >> (256 - bits);
+        let word = match self.below(8) {
+            0 => U256::ZERO,
+            1 => U256::from(1),
+            2 => max,
+            3 => max - U256::from(1),
+            4 | 5 => U256::from(self.below(256)),
+            _ => U256::from_limbs([self.next(), self.next(), self.next(), self.next()]),
+        };
+        word & max
+    }
+
+    /// A random value of type `ty`.
+    fn value(&mut self, ty: &DynSolType) -> DynSolValue {
+        match ty {
+            DynSolType::Bool => DynSolValue::Bool(self.below(2) == 1),
+            DynSolType::Uint(bits) => DynSolValue::Uint(self.word(*bits), *bits),
+            DynSolType::Int(bits) => {
+                let mut word = self.word(*bits);
+                if *bits < 256 && word.bit(bits - 1) {
+                    word |= U256::MAX << *bits;
+                }
+                DynSolValue::Int(I256::from_raw(word), *bits)
+            }
+            DynSolType::Address => DynSolValue::Address(match self.below(5) {
+                0 => Address::ZERO,
+                1 => Address::from(ALICE.0),
+                2 => Address::from(BOB.0),
+                3 => Address::from(CHARLIE.0),
+                _ => Address::from_slice(&self.bytes(20)),
+            }),
+            DynSolType::FixedBytes(size) => {
+                let mut word = [0; 32];
+                word[..*size].copy_from_slice(&self.bytes(*size));
+                DynSolValue::FixedBytes(word.into(), *size)
+            }
+            DynSolType::Bytes => {
+                let length = self.below(MAX_DYNAMIC_LENGTH + 1) as usize;
+                DynSolValue::Bytes(self.bytes(length))
+            }
+            DynSolType::String => {
+                const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+                let length = 1 + self.below(MAX_DYNAMIC_LENGTH) as usize;
+                DynSolValue::String(
+                    (0..length)
+                        .map(|_| ALPHABET[self.below(ALPHABET.len() as u64) as usize] as char)
+                        .collect(),
+                )
+            }
+            DynSolType::Array(ty) => {
+                let length = self.below(MAX_ARRAY_LENGTH + 1);
+                DynSolValue::Array((0..length).map(|_| self.value(ty)).collect())
+            }
+            DynSolType::FixedArray(ty, length) => {
+                DynSolValue::FixedArray((0..*length).map(|_| self.value(ty)).collect())
+            }
+            DynSolType::Tuple(types) => {
+                DynSolValue::Tuple(types.iter().map(|ty| self.value(ty)).collect())
+            }
+            ty => unreachable!("unsupported type {ty}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_round_trip() {
+        let mut rng = Rng(0);
+        for ty in [
+            "bool",
+            "uint8",
+            "uint256",
+            "int8",
+            "int256",
+            "address",
+            "bytes4",
+            "bytes",
+            "string",
+            "uint16[]",
+            "int32[3]",
+            "(address,string,bytes32)",
+        ] {
+            let ty = DynSolType::parse(ty).unwrap();
+            for _ in 0..64 {
+                let value = rng.value(&ty);
+                let formatted = format_value(&value);
+                let coerced = ty
+                    .coerce_str(&formatted)
+                    .unwrap_or_else(|error| panic!("{ty} value '{formatted}': {error}"));
+                assert_eq!(format_value(&coerced), formatted);
+            }
+        }
+    }
+
+    #[test]
+    fn sequences_are_deterministic() {
+        let abi = JsonAbi::parse([
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function callback(function f)",
+        ])
+        .unwrap();
+        let sequence = |seed| {
+            let specs = Fuzzer::new(Code::Bytes(vec![]), &abi, seed).sequence(4);
+            serde_json::to_string(&specs).unwrap()
+        };
+
+        assert_eq!(sequence(1), sequence(1));
+        assert_ne!(sequence(1), sequence(2));
+        assert!(!sequence(1).contains("callback"));
+        assert!(sequence(1).contains("transfer(address,uint256)"));
+    }
+
+    #[test]
+    fn sequences_vary_caller_and_value() {
+        let abi = JsonAbi::parse([
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function deposit() payable",
+        ])
+        .unwrap();
+        let specs = Fuzzer::new(Code::Bytes(vec![]), &abi, 0).sequence(64);
+
+        let mut origins = BTreeSet::new();
+        let mut deposited = false;
+        for action in &specs.actions[1..] {
+            let SpecsAction::Call {
+                origin,
+                value,
+                call: Some(signature),
+                ..
+            } = action
+            else {
+                panic!("expected a call, got {action:?}");
+            };
+            origins.insert(format!("{origin:?}"));
+            match signature.as_str() {
+                "deposit()" => deposited |= *value > 0,
+                _ => assert_eq!(*value, 0, "{signature} is not payable"),
+            }
+        }
+        assert_eq!(origins.len(), 3);
+        assert!(deposited);
+    }
+}
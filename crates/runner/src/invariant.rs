@@ -0,0 +1,397 @@
+//! Invariant testing of deployed contracts.
+//!
+//! An [`InvariantTest`] deploys the contracts under test with its `setup` [`Specs`]
+//! and then calls random state changing functions of the targets from random
+//! actors. The [`Invariant`]s are verified after every call; a violating call
+//! sequence is shrunk and returned as [`Specs`] reproducing the violation.
+
+use alloy_dyn_abi::DynSolType;
+use alloy_json_abi::{Function, StateMutability};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fuzz::{callable, panic_message, Rng},
+    specs::PalletExecutor,
+    *,
+};
+
+/// The name of the snapshot every call sequence starts from.
+const SETUP_SNAPSHOT: &str = "invariant-setup";
+
+/// A property of the deployed contracts which must hold after every call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Invariant {
+    /// The view function `call` of `contract` returns `true`
+    Call {
+        contract: TestAddress,
+        /// The function signature, or the function name if the contract ABI is known
+        call: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// The storage of `contract` at `key` holds `expected`
+    Storage {
+        contract: TestAddress,
+        #[serde(with = "hex::serde")]
+        key: [u8; 32],
+        #[serde(default, with = "hex::serde")]
+        expected: [u8; 32],
+    },
+}
+
+impl Invariant {
+    /// The actions verifying the invariant.
+    pub fn actions(&self) -> Vec<SpecsAction> {
+        match self.clone() {
+            Self::Call {
+                contract,
+                call,
+                args,
+            } => vec![
+                SpecsAction::Call {
+                    origin: TestAddress::Alice,
+                    dest: contract,
+                    value: 0,
+                    gas_limit: None,
+                    storage_deposit_limit: None,
+                    data: vec![],
+                    call: Some(call),
+                    args,
+                },
+                SpecsAction::VerifyCall(VerifyCallExpectation {
+                    values: Some(vec!["true".to_string()]),
+                    ..Default::default()
+                }),
+            ],
+            Self::Storage {
+                contract,
+                key,
+                expected,
+            } => vec![SpecsAction::VerifyStorage {
+                contract,
+                key,
+                expected,
+            }],
+        }
+    }
+}
+
+/// A contract called by an [`InvariantTest`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InvariantTarget {
+    pub contract: TestAddress,
+    /// The signatures of the functions to call
+    /// Defaults to the state changing functions in the contract ABI
+    #[serde(default)]
+    pub functions: Vec<String>,
+}
+
+/// Random call sequences against deployed contracts, verifying invariants after every call
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InvariantTest {
+    /// The specs deploying the contracts under test
+    pub setup: Specs,
+    /// The contracts to call
+    pub targets: Vec<InvariantTarget>,
+    /// The accounts making the calls
+    pub actors: Vec<TestAddress>,
+    /// The invariants verified after the setup and after every call
+    pub invariants: Vec<Invariant>,
+    /// The number of call sequences
+    pub runs: usize,
+    /// The number of calls in each sequence
+    pub depth: usize,
+    /// The seed of the random generator
+    pub seed: u64,
+}
+
+impl Default for InvariantTest {
+    fn default() -> Self {
+        Self {
+            setup: Default::default(),
+            targets: vec![],
+            actors: vec![TestAddress::Alice, TestAddress::Bob, TestAddress::Charlie],
+            invariants: vec![],
+            runs: 64,
+            depth: 16,
+            seed: 0,
+        }
+    }
+}
+
+/// A violated invariant.
+#[derive(Clone, Debug)]
+pub struct InvariantFailure {
+    /// The panic message of the failing verification
+    pub reason: String,
+    /// The setup followed by the shrunk call sequence and the invariant verifications
+    /// Running these specs reproduces the violation
+    pub specs: Specs,
+}
+
+impl InvariantTest {
+    /// Run the invariant test.
+    ///
+    /// Reverting calls are part of the sequences and do not fail the test. Failing
+    /// verifications are caught; the caller may want to install a silent panic hook.
+    pub fn run(self) -> Result<(), InvariantFailure> {
+        assert!(
+            !self.setup.differential,
+            "invariant tests are not supported in differential mode"
+        );
+        assert!(!self.targets.is_empty(), "no invariant targets provided");
+        assert!(!self.actors.is_empty(), "no invariant actors provided");
+        assert!(!self.invariants.is_empty(), "no invariants provided");
+
+        let mut executor = PalletExecutor::new(self.setup.balances.clone(), None);
+        for action in self.setup.actions() {
+            executor.execute(action);
+        }
+        executor.execute(SpecsAction::Snapshot {
+            name: SETUP_SNAPSHOT.to_string(),
+        });
+        if let Err(reason) = self.verify(&mut executor) {
+            return Err(self.failure(&mut executor, vec![], reason));
+        }
+
+        let targets = self
+            .targets
+            .iter()
+            .map(|target| (target.contract.clone(), functions(target, &executor)))
+            .collect::<Vec<_>>();
+        let mut rng = Rng(self.seed);
+
+        for _ in 0..self.runs {
+            let calls = (0..self.depth)
+                .map(|_| {
+                    let (contract, functions) = &targets[rng.below(targets.len() as u64) as usize];
+                    let (signature, types) = &functions[rng.below(functions.len() as u64) as usize];
+                    SpecsAction::Call {
+                        origin: self.actors[rng.below(self.actors.len() as u64) as usize].clone(),
+                        dest: contract.clone(),
+                        value: 0,
+                        gas_limit: None,
+                        storage_deposit_limit: None,
+                        data: vec![],
+                        call: Some(signature.clone()),
+                        args: rng.arguments(types),
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            if let Some((length, _)) = self.replay(&mut executor, &calls) {
+                let calls = shrink(calls[..length].to_vec(), |calls| {
+                    self.replay(&mut executor, calls).map(|(length, _)| length)
+                });
+                let (_, reason) = self
+                    .replay(&mut executor, &calls)
+                    .expect("the shrunk sequence should violate the invariants");
+                return Err(self.failure(&mut executor, calls, reason));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify the invariants on the current state.
+    fn verify(&self, executor: &mut PalletExecutor) -> Result<(), String> {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            for invariant in &self.invariants {
+                for action in invariant.actions() {
+                    executor.execute(action);
+                }
+            }
+        }))
+        .map_err(panic_message)
+    }
+
+    /// Execute the `calls` from the setup state, returning the number of calls
+    /// executed until an invariant was violated and the reason.
+    fn replay(
+        &self,
+        executor: &mut PalletExecutor,
+        calls: &[SpecsAction],
+    ) -> Option<(usize, String)> {
+        executor.rewind(SETUP_SNAPSHOT);
+        for (index, call) in calls.iter().enumerate() {
+            executor.execute(call.clone());
+            if let Err(reason) = self.verify(executor) {
+                return Some((index + 1, reason));
+            }
+        }
+        None
+    }
+
+    /// Build the [`InvariantFailure`] for the violating `calls`, expecting each call
+    /// to succeed or revert the way it did when replayed.
+    fn failure(
+        &self,
+        executor: &mut PalletExecutor,
+        calls: Vec<SpecsAction>,
+        reason: String,
+    ) -> InvariantFailure {
+        executor.rewind(SETUP_SNAPSHOT);
+        let mut actions = self.setup.actions.clone();
+        for call in calls {
+            executor.execute(call.clone());
+            let success = !executor
+                .results
+                .last()
+                .expect("the call should have a result")
+                .did_revert();
+            actions.push(call);
+            actions.push(SpecsAction::VerifyCall(VerifyCallExpectation {
+                success,
+                ..Default::default()
+            }));
+        }
+        actions.extend(self.invariants.iter().flat_map(Invariant::actions));
+
+        InvariantFailure {
+            reason,
+            specs: Specs {
+                actions,
+                ..self.setup.clone()
+            },
+        }
+    }
+}
+
+/// The functions of the `target` with their parameter types.
+fn functions(
+    target: &InvariantTarget,
+    executor: &PalletExecutor,
+) -> Vec<(String, Vec<DynSolType>)> {
+    let functions = if target.functions.is_empty() {
+        let abi = executor.abis.get(&target.contract).unwrap_or_else(|| {
+            panic!(
+                "the ABI of {:?} is unknown, provide the functions to call",
+                target.contract
+            )
+        });
+        callable(abi.functions().filter(|function| {
+            !matches!(
+                function.state_mutability,
+                StateMutability::Pure | StateMutability::View
+            )
+        }))
+    } else {
+        let functions = target
+            .functions
+            .iter()
+            .map(|signature| {
+                Function::parse(signature).unwrap_or_else(|error| {
+                    panic!("invalid function signature '{signature}': {error}")
+                })
+            })
+            .collect::<Vec<_>>();
+        callable(functions.iter())
+    };
+    assert!(
+        !functions.is_empty(),
+        "{:?} has no functions which can be called",
+        target.contract
+    );
+    functions
+}
+
+/// Remove calls from the failing `calls` as long as they keep failing.
+///
+/// `fails` returns the number of calls until the failure, the calls after it are
+/// dropped as well.
+fn shrink<T: Clone>(mut calls: Vec<T>, mut fails: impl FnMut(&[T]) -> Option<usize>) -> Vec<T> {
+    loop {
+        let mut shrunk = false;
+        let mut index = 0;
+        while index < calls.len() {
+            let mut candidate = calls.clone();
+            candidate.remove(index);
+            match fails(&candidate) {
+                Some(length) => {
+                    candidate.truncate(length);
+                    calls = candidate;
+                    shrunk = true;
+                }
+                None => index += 1,
+            }
+        }
+        if !shrunk {
+            return calls;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrink_to_minimal_sequence() {
+        // Fails once a 3 is followed by a 7.
+        let fails = |calls: &[u8]| {
+            let three = calls.iter().position(|call| *call == 3)?;
+            calls[three..]
+                .iter()
+                .position(|call| *call == 7)
+                .map(|seven| three + seven + 1)
+        };
+
+        assert_eq!(shrink(vec![1, 3, 2, 3, 5, 7], fails), vec![3, 7]);
+    }
+
+    #[test]
+    fn storage_invariant() {
+        let test = |expected| InvariantTest {
+            setup: Specs {
+                actions: vec![SpecsAction::instantiate(
+                    Code::Bytes(include_bytes!("../fixtures/Baseline.pvm").to_vec()),
+                    vec![],
+                )
+                .with_gas_limit(GAS_LIMIT)
+                .unwrap()
+                .with_storage_deposit_limit(DEPOSIT_LIMIT)
+                .unwrap()],
+                ..Default::default()
+            },
+            targets: vec![InvariantTarget {
+                contract: TestAddress::Instantiated(0),
+                functions: vec!["transfer(address,uint256)".to_string()],
+            }],
+            invariants: vec![Invariant::Storage {
+                contract: TestAddress::Instantiated(0),
+                key: [0; 32],
+                expected,
+            }],
+            runs: 4,
+            depth: 4,
+            ..Default::default()
+        };
+
+        assert!(test([0; 32]).run().is_ok());
+
+        let failure = test([1; 32]).run().unwrap_err();
+        assert!(failure.reason.contains("key 0x00"), "{}", failure.reason);
+        assert_eq!(failure.specs.actions.len(), 2);
+    }
+
+    #[test]
+    fn sequences_discard_their_results() {
+        let mut executor = PalletExecutor::new(Specs::default().balances, None);
+        executor.execute(SpecsAction::instantiate(
+            Code::Bytes(include_bytes!("../fixtures/Baseline.pvm").to_vec()),
+            vec![],
+        ));
+        executor.execute(SpecsAction::Snapshot {
+            name: SETUP_SNAPSHOT.to_string(),
+        });
+
+        for _ in 0..4 {
+            executor.rewind(SETUP_SNAPSHOT);
+            executor.execute(SpecsAction::call(TestAddress::Instantiated(0), vec![]));
+            executor.execute(SpecsAction::call(TestAddress::Instantiated(0), vec![]));
+        }
+        assert_eq!(executor.results.len(), 3);
+        assert_eq!(executor.call_abis.len(), 3);
+    }
+}
@@ -6,21 +6,18 @@
 //! ## Example
 //! ```rust
 //! use revive_runner::*;
-//! use SpecsAction::*;
 //! Specs {
 //!     differential: false,
 //!     balances: vec![(ALICE, 1_000_000_000)],
-//!     actions: vec![Instantiate {
-//!         origin: TestAddress::Alice,
-//!         value: 0,
-//!         gas_limit: Some(GAS_LIMIT),
-//!         storage_deposit_limit: Some(DEPOSIT_LIMIT),
-//!         code: Code::Bytes(include_bytes!("../fixtures/Baseline.pvm").to_vec()),
-//!         data: vec![],
-//!         salt: Default::default(),
-//!     }],
+//!     actions: vec![SpecsAction::instantiate(
+//!         Code::Bytes(include_bytes!("../fixtures/Baseline.pvm").to_vec()),
+//!         vec![],
+//!     )
+//!     .with_gas_limit(GAS_LIMIT)?
+//!     .with_storage_deposit_limit(DEPOSIT_LIMIT)?],
 //! }
 //! .run();
+//! # Ok::<(), String>(())
 //! ```
 
 use std::time::Duration;
@@ -42,11 +39,31 @@ use polkadot_sdk::{
 use revive_solc_json_interface::SolcStandardJsonInputSettingsLibraries;
 use serde::{Deserialize, Serialize};
 
+pub use crate::abi::CallAbi;
+pub use crate::debugger::*;
+pub use crate::fuzz::{check, Fuzzer};
+pub use crate::invariant::*;
+pub use crate::logger::init_logger;
+#[cfg(feature = "solidity")]
+pub use crate::memory::*;
+pub use crate::profiler::*;
 use crate::runtime::*;
 pub use crate::specs::*;
-
+pub use crate::weights::*;
+
+mod abi;
+mod debugger;
+mod ecalls;
+mod fuzz;
+mod invariant;
+mod logger;
+#[cfg(feature = "solidity")]
+mod memory;
+mod profiler;
 mod runtime;
 mod specs;
+mod symbols;
+mod weights;
 
 #[cfg(not(feature = "resolc"))]
 pub(crate) const NO_SOLIDITY_FRONTEND: &str =
@@ -85,6 +102,8 @@ impl ExtBuilder {
 
     /// Build the externalities
     pub fn build(self) -> sp_io::TestExternalities {
+        // The `log` bridge of `sp_tracing` would otherwise take the place of the runner logger
+        let _ = init_logger();
         sp_tracing::try_init_simple();
 
         let mut t = frame_system::GenesisConfig::<Runtime>::default()
@@ -140,6 +159,12 @@ pub struct VerifyCallExpectation {
     pub output: OptionalHex<Vec<u8>>,
     /// Expected call result
     pub success: bool,
+    /// When provided, the expected return values decoded against the function ABI
+    #[serde(default)]
+    pub values: Option<Vec<String>>,
+    /// When provided, the expected revert reason decoded against the contract ABI
+    #[serde(default)]
+    pub revert_reason: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -186,18 +211,25 @@ impl Default for VerifyCallExpectation {
             gas_consumed: None,
             output: OptionalHex(None),
             success: true,
+            values: None,
+            revert_reason: None,
         }
     }
 }
 
 impl VerifyCallExpectation {
-    /// Verify that the expectations are met
-    fn verify(self, result: &CallResult) {
+    /// Verify that the expectations are met, decoding the call result using `abi`
+    fn verify(self, result: &CallResult, abi: &CallAbi) {
         // Check if the call was successful. Here `self.success` is an expectation given by the user and `result` is the actual call result.
+        let revert_reason = result
+            .did_revert()
+            .then(|| abi.decode_revert(&result.output()))
+            .flatten();
         assert_eq!(
             self.success,
             !result.did_revert(),
-            "contract execution result mismatch: {result:?}"
+            "contract execution result mismatch (revert reason: {}): {result:?}",
+            revert_reason.as_deref().unwrap_or("unknown")
         );
 
         if let Some(gas_consumed) = self.gas_consumed {
@@ -207,6 +239,14 @@ impl VerifyCallExpectation {
         if let OptionalHex(Some(data)) = self.output {
             assert_eq!(data, result.output());
         }
+
+        if let Some(values) = self.values {
+            abi.verify_values(&values, &result.output());
+        }
+
+        if let Some(expected) = self.revert_reason {
+            assert_eq!(Some(expected), revert_reason, "revert reason mismatch");
+        }
     }
 }
 
@@ -226,7 +266,7 @@ pub enum CallResult {
 
 impl CallResult {
     /// Check if the call was successful
-    fn did_revert(&self) -> bool {
+    pub fn did_revert(&self) -> bool {
         match self {
             Self::Exec { result, .. } => result
                 .result
@@ -242,7 +282,7 @@ impl CallResult {
     }
 
     /// Get the output of the call
-    fn output(&self) -> Vec<u8> {
+    pub fn output(&self) -> Vec<u8> {
         match self {
             Self::Exec { result, .. } => result
                 .result
@@ -258,7 +298,7 @@ impl CallResult {
     }
 
     /// Get the gas consumed by the call
-    fn gas_consumed(&self) -> u128 {
+    pub fn gas_consumed(&self) -> u128 {
         match self {
             Self::Exec { result, .. } => result.gas_consumed,
             Self::Instantiate { result, .. } => result.gas_consumed,
@@ -277,6 +317,21 @@ pub enum Code {
         #[serde(default)]
         libraries: SolcStandardJsonInputSettingsLibraries,
     },
+    /// Compile a single solidity source and link the blob of `contract` at deploy time
+    ///
+    /// The `libraries` map source files to library names to the accounts the libraries
+    /// were instantiated at earlier in the run. Libraries defined in `path` itself are
+    /// listed under `path`.
+    #[cfg(feature = "resolc")]
+    SolidityLinked {
+        path: Option<std::path::PathBuf>,
+        solc_optimizer: Option<bool>,
+        contract: String,
+        libraries: std::collections::BTreeMap<
+            String,
+            std::collections::BTreeMap<String, crate::specs::TestAddress>,
+        >,
+    },
     /// Compile a raw Yul source file and use the blob of `contract`.
     ///
     /// The PVM blob is produced via revive's Yul-to-LLVM path (bypassing solc's
@@ -301,6 +356,42 @@ impl Default for Code {
     }
 }
 
+impl Code {
+    /// The contract ABI, if the code is compiled from a Solidity source
+    pub fn abi(&self) -> Option<alloy_json_abi::JsonAbi> {
+        match self {
+            #[cfg(feature = "solidity")]
+            Code::Solidity {
+                path: Some(path),
+                contract,
+                libraries,
+                ..
+            } => {
+                let Ok(source_code) = std::fs::read_to_string(path) else {
+                    panic!("Failed to read source code from {}", path.display());
+                };
+                let abi =
+                    resolc::test_utils::compile_abi(contract, &source_code, libraries.clone());
+                Some(serde_json::from_value(abi).expect("solc should emit a valid ABI"))
+            }
+            #[cfg(feature = "solidity")]
+            Code::SolidityLinked {
+                path: Some(path),
+                contract,
+                ..
+            } => {
+                let Ok(source_code) = std::fs::read_to_string(path) else {
+                    panic!("Failed to read source code from {}", path.display());
+                };
+                let abi =
+                    resolc::test_utils::compile_abi(contract, &source_code, Default::default());
+                Some(serde_json::from_value(abi).expect("solc should emit a valid ABI"))
+            }
+            _ => None,
+        }
+    }
+}
+
 impl From<Code> for pallet_revive::Code {
     fn from(val: Code) -> Self {
         match val {
@@ -326,6 +417,10 @@ impl From<Code> for pallet_revive::Code {
                 ))
             }
             #[cfg(feature = "solidity")]
+            Code::SolidityLinked { contract, .. } => {
+                panic!("the deploy time libraries of contract '{contract}' are not linked")
+            }
+            #[cfg(feature = "solidity")]
             Code::Yul { path, contract } => {
                 let Ok(source_code) = std::fs::read_to_string(&path) else {
                     panic!("Failed to read Yul source from {}", path.display());
@@ -348,23 +443,173 @@ mod tests {
 
     #[test]
     fn instantiate_works() {
-        use specs::SpecsAction::*;
         let specs = Specs {
             differential: false,
             balances: vec![(ALICE, 1_000_000_000)],
-            actions: vec![Instantiate {
-                origin: TestAddress::Alice,
-                value: 0,
-                gas_limit: Some(GAS_LIMIT),
-                storage_deposit_limit: Some(DEPOSIT_LIMIT),
-                code: Code::Bytes(include_bytes!("../fixtures/Baseline.pvm").to_vec()),
-                data: vec![],
-                salt: OptionalHex::default(),
-            }],
+            actions: vec![SpecsAction::instantiate(
+                Code::Bytes(include_bytes!("../fixtures/Baseline.pvm").to_vec()),
+                vec![],
+            )
+            .with_gas_limit(GAS_LIMIT)
+            .unwrap()
+            .with_storage_deposit_limit(DEPOSIT_LIMIT)
+            .unwrap()],
         };
         specs.run();
     }
 
+    #[test]
+    fn weight_report_works() {
+        let mut report = WeightReport::default();
+        Specs {
+            differential: false,
+            balances: vec![(ALICE, 1_000_000_000)],
+            actions: vec![SpecsAction::instantiate(
+                Code::Bytes(include_bytes!("../fixtures/Baseline.pvm").to_vec()),
+                vec![],
+            )
+            .with_gas_limit(GAS_LIMIT)
+            .unwrap()
+            .with_storage_deposit_limit(DEPOSIT_LIMIT)
+            .unwrap()],
+        }
+        .run_with_tracer(&mut report);
+
+        assert!(report.host_functions.contains_key("seal_return"));
+        assert!(!report
+            .host_functions
+            .contains_key(pallet_revive::tracing::PVM_FUEL_NAME));
+        assert!(report.interpreter.ref_time > 0);
+        assert_eq!(
+            report.total().calls,
+            report
+                .host_functions
+                .values()
+                .map(|weight| weight.calls)
+                .sum::<u64>()
+        );
+        assert!(report.total().ref_time > report.interpreter.ref_time);
+    }
+
+    #[test]
+    fn snapshot_and_revert() {
+        use specs::SpecsAction::*;
+        // The existential deposit is not part of the spendable balance
+        let initial = (1_000_000_000_000 - 1_000) * ETH_RATIO;
+        let transfer = SpecsAction::call(TestAddress::Bob, vec![])
+            .with_value(ETH_RATIO)
+            .unwrap();
+        let directory = tempfile::tempdir().unwrap();
+        let state = directory.path().join("snapshot-and-revert.state");
+        Specs {
+            actions: vec![
+                Snapshot {
+                    name: "genesis".into(),
+                },
+                transfer.clone(),
+                DumpState {
+                    path: state.clone(),
+                },
+                transfer,
+                VerifyBalance {
+                    origin: TestAddress::Bob,
+                    expected: initial + 2 * ETH_RATIO,
+                },
+                Revert {
+                    name: "genesis".into(),
+                },
+                VerifyBalance {
+                    origin: TestAddress::Bob,
+                    expected: initial,
+                },
+                LoadState {
+                    path: state.clone(),
+                },
+                VerifyBalance {
+                    origin: TestAddress::Bob,
+                    expected: initial + ETH_RATIO,
+                },
+            ],
+            ..Default::default()
+        }
+        .run();
+    }
+
+    #[cfg(feature = "resolc")]
+    #[test]
+    fn advance_blocks() {
+        use specs::SpecsAction::*;
+        // Verify the `number()` and `timestamp()` of the Block contract.
+        let verify = |number: u64, timestamp: u64| {
+            [("8381f58a", number), ("b80777ea", timestamp)]
+                .into_iter()
+                .flat_map(|(selector, expected)| {
+                    [
+                        SpecsAction::call(
+                            TestAddress::Instantiated(0),
+                            hex::decode(selector).unwrap(),
+                        ),
+                        VerifyCall(VerifyCallExpectation {
+                            output: OptionalHex::from(
+                                alloy_primitives::U256::from(expected)
+                                    .to_be_bytes::<32>()
+                                    .to_vec(),
+                            ),
+                            ..Default::default()
+                        }),
+                    ]
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let actions = [
+            vec![
+                AdvanceBlocks {
+                    count: 3,
+                    seconds: 6,
+                },
+                SpecsAction::instantiate(
+                    Code::Solidity {
+                        path: Some(
+                            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                                .join("../integration/contracts/Block.sol"),
+                        ),
+                        solc_optimizer: None,
+                        contract: "Block".to_string(),
+                        libraries: Default::default(),
+                    },
+                    vec![],
+                ),
+            ],
+            verify(4, 18),
+            vec![SetTimestamp {
+                seconds: 1_700_000_000,
+            }],
+            verify(4, 1_700_000_000),
+            vec![AdvanceBlocks {
+                count: 1,
+                seconds: 6,
+            }],
+            verify(5, 1_700_000_006),
+        ]
+        .concat();
+        Specs {
+            actions,
+            ..Default::default()
+        }
+        .run();
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows in milliseconds")]
+    fn timestamp_overflow() {
+        Specs {
+            actions: vec![specs::SpecsAction::SetTimestamp { seconds: u64::MAX }],
+            ..Default::default()
+        }
+        .run();
+    }
+
     #[test]
     fn instantiate_with_json() {
         serde_json::from_str::<Specs>(
@@ -390,4 +635,31 @@ mod tests {
         .unwrap()
         .run();
     }
+
+    /// Fails when the pallet rejects a host function of the runtime API, or
+    /// accepts one which isn't provided, because the weight estimates of the
+    /// compiler assume the host functions of the locked pallet.
+    #[test]
+    fn pallet_provides_the_runtime_api_imports() {
+        use polkavm_common::program::{asm, InstructionSetKind};
+        use polkavm_common::writer::ProgramBlobBuilder;
+        use revive_runtime_api::{polkavm_exports, polkavm_imports};
+
+        let upload = |imports: &[&str]| {
+            let mut builder = ProgramBlobBuilder::new(InstructionSetKind::ReviveV1);
+            for import in imports {
+                builder.add_import(import.as_bytes());
+            }
+            builder.add_export_by_basic_block(0, polkavm_exports::DEPLOY.as_bytes());
+            builder.add_export_by_basic_block(0, polkavm_exports::CALL.as_bytes());
+            builder.set_code(&[asm::ret()], &[]);
+            pallet_revive::ContractBlob::<Runtime>::from_pvm_code(
+                builder.to_vec().unwrap(),
+                AccountId32::new([1; 32]),
+            )
+        };
+
+        assert!(upload(&polkavm_imports::IMPORTS).is_ok());
+        assert!(upload(&["unknown_host_function"]).is_err());
+    }
 }
@@ -0,0 +1,383 @@
+//! The runner logger and the PolkaVM interpreter log capture.
+//!
+//! The pallet tracing hooks report EVM opcodes, but only the host function calls of
+//! PolkaVM contracts. Instead, the interpreter debug logs are parsed and fed into an
+//! active [`InterpreterLogSink`], like the [`crate::Profiler`] or the [`crate::Recorder`].
+//!
+//! The log format is the one of the `polkavm` interpreter the pallet executes contracts
+//! with, which the tests pin.
+
+use std::{cell::RefCell, rc::Rc, sync::Mutex};
+
+/// The log target prefix of the PolkaVM interpreter and its instruction handlers.
+const INTERPRETER_LOG_TARGET: &str = "polkavm::interpreter";
+
+thread_local! {
+    /// The sink receiving the interpreter logs of the current thread.
+    static ACTIVE_SINK: RefCell<Option<Rc<RefCell<dyn InterpreterLogSink>>>> = const { RefCell::new(None) };
+}
+
+/// The number of threads with an active sink and the maximum log level before the first
+/// of them was activated.
+///
+/// The maximum log level is global, so it is only restored once no thread captures logs.
+static ACTIVE_THREADS: Mutex<(usize, log::LevelFilter)> = Mutex::new((0, log::LevelFilter::Off));
+
+/// Install the runner logger.
+///
+/// The logger forwards all records to `env_logger` (configured via `RUST_LOG`) and
+/// additionally feeds the PolkaVM interpreter logs into the active sink.
+/// It is installed by [`crate::ExtBuilder::build`] unless another logger was set before.
+pub fn init_logger() -> Result<(), log::SetLoggerError> {
+    let inner = env_logger::Builder::from_default_env().build();
+    let max_level = inner.filter();
+    log::set_logger(Box::leak(Box::new(Logger { inner })))?;
+    log::set_max_level(max_level);
+    Ok(())
+}
+
+/// The logger forwarding to `env_logger` and the active sink.
+struct Logger {
+    inner: env_logger::Logger,
+}
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        (metadata.target().starts_with("polkavm") && is_active()) || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if record.target().starts_with(INTERPRETER_LOG_TARGET) {
+            ACTIVE_SINK.with(|active| {
+                if let Some(sink) = active.borrow().as_ref() {
+                    let line = record.args().to_string();
+                    sink.borrow_mut()
+                        .interpreter_log(InterpreterLog::parse(&line));
+                }
+            });
+        }
+
+        if self.inner.matches(record) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush()
+    }
+}
+
+/// Receives the parsed interpreter logs while activated.
+pub(crate) trait InterpreterLogSink {
+    fn interpreter_log(&mut self, log: InterpreterLog);
+}
+
+/// Route the interpreter logs of the current thread into `sink`.
+///
+/// The interpreter only emits its debug logs for instances created while a sink is active.
+fn activate(sink: Rc<RefCell<dyn InterpreterLogSink>>) {
+    if ACTIVE_SINK.with(|active| active.borrow_mut().replace(sink).is_none()) {
+        let mut threads = ACTIVE_THREADS
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        if threads.0 == 0 {
+            threads.1 = log::max_level();
+            log::set_max_level(log::LevelFilter::Trace);
+        }
+        threads.0 += 1;
+    }
+}
+
+/// Routes the interpreter logs of the current thread into a sink until dropped.
+///
+/// Raises the maximum log level while capturing and restores the previous level once
+/// dropped, including when the traced execution unwinds.
+pub(crate) struct Capture(());
+
+impl Capture {
+    /// Route the interpreter logs of the current thread into `sink`.
+    pub fn new(sink: Rc<RefCell<dyn InterpreterLogSink>>) -> Self {
+        activate(sink);
+        Self(())
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        deactivate();
+    }
+}
+
+/// Stop routing the interpreter logs of the current thread.
+fn deactivate() {
+    if ACTIVE_SINK.with(|active| active.borrow_mut().take().is_some()) {
+        let mut threads = ACTIVE_THREADS
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        threads.0 -= 1;
+        if threads.0 == 0 {
+            log::set_max_level(threads.1);
+        }
+    }
+}
+
+fn is_active() -> bool {
+    ACTIVE_SINK.with(|active| active.borrow().is_some())
+}
+
+/// A line of the PolkaVM interpreter debug log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum InterpreterLog<'a> {
+    /// The instruction at `program_counter` was compiled into `handler`.
+    Compiled {
+        handler: u32,
+        program_counter: u32,
+        instruction: &'a str,
+    },
+    /// The compiled `handler` was executed.
+    Executed { handler: u32 },
+    /// The `register` was read or written by the last executed instruction.
+    Register { register: &'a str, value: u64 },
+    /// The memory at `address` was loaded or stored by the last executed instruction.
+    Memory { address: u32, size: u8, value: u64 },
+    /// Any other log line.
+    Other,
+}
+
+impl<'a> InterpreterLog<'a> {
+    /// Parse a log line of the interpreter.
+    ///
+    /// Gas charging and stepping handlers are not instructions and yield [`InterpreterLog::Other`].
+    pub(crate) fn parse(line: &'a str) -> Self {
+        let is_pseudo = |rest: &str| rest == "step" || rest.starts_with("charge_gas");
+
+        if let Some(line) = line.strip_prefix("  [") {
+            let Some((handler, rest)) = line.split_once("]: ") else {
+                return Self::Other;
+            };
+            let Some((program_counter, instruction)) = rest.split_once(": ") else {
+                return Self::Other;
+            };
+            return match (handler.parse(), program_counter.parse()) {
+                (Ok(handler), Ok(program_counter)) if !is_pseudo(instruction) => Self::Compiled {
+                    handler,
+                    program_counter,
+                    instruction,
+                },
+                _ => Self::Other,
+            };
+        }
+
+        if let Some(line) = line.strip_prefix('[') {
+            let Some((handler, rest)) = line.split_once("]: ") else {
+                return Self::Other;
+            };
+            return match handler.parse() {
+                Ok(handler) if !is_pseudo(rest) => Self::Executed { handler },
+                _ => Self::Other,
+            };
+        }
+
+        if let Some(line) = line
+            .strip_prefix("  get: ")
+            .or_else(|| line.strip_prefix("  set: "))
+        {
+            let Some((register, value)) = line.split_once(" = ") else {
+                return Self::Other;
+            };
+            return match parse_hex(value) {
+                Some(value) => Self::Register { register, value },
+                None => Self::Other,
+            };
+        }
+
+        if let Some(line) = line.strip_prefix("  ") {
+            // Stores: `u32 [0x20000] = a0 = 0x1` or `u32 [0x20000] = 0x1`
+            // Loads: `a0 = u32 [0x20000] = 0x1`
+            let access = match line.split_once(" = ") {
+                Some((_, access)) if access.contains(" [0x") => access,
+                _ => line,
+            };
+            let Some((kind, rest)) = access.split_once(" [0x") else {
+                return Self::Other;
+            };
+            let Some((address, _)) = rest.split_once(']') else {
+                return Self::Other;
+            };
+            let Some(value) = rest.rsplit(" = ").next().and_then(parse_hex) else {
+                return Self::Other;
+            };
+            let size = match kind {
+                "u8" | "i8" => 1,
+                "u16" | "i16" => 2,
+                "u32" | "i32" => 4,
+                "u64" => 8,
+                _ => return Self::Other,
+            };
+            return match u32::from_str_radix(address, 16) {
+                Ok(address) => Self::Memory {
+                    address,
+                    size,
+                    value,
+                },
+                Err(_) => Self::Other,
+            };
+        }
+
+        Self::Other
+    }
+}
+
+fn parse_hex(value: &str) -> Option<u64> {
+    u64::from_str_radix(value.strip_prefix("0x")?, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+
+    use polkadot_sdk::*;
+    use polkadot_sdk::{
+        pallet_revive::tracing::Tracing,
+        sp_core::{H160, U256},
+    };
+
+    use super::{is_active, Capture, InterpreterLog, InterpreterLogSink};
+    use crate::*;
+
+    /// The interpreter logs parsed while capturing.
+    #[derive(Default)]
+    struct Parsed {
+        compiled: BTreeSet<u32>,
+        executed: BTreeSet<u32>,
+        registers: usize,
+        memory: usize,
+    }
+
+    impl InterpreterLogSink for Parsed {
+        fn interpreter_log(&mut self, log: InterpreterLog) {
+            match log {
+                InterpreterLog::Compiled { handler, .. } => {
+                    self.compiled.insert(handler);
+                }
+                InterpreterLog::Executed { handler } => {
+                    self.executed.insert(handler);
+                }
+                InterpreterLog::Register { .. } => self.registers += 1,
+                InterpreterLog::Memory { .. } => self.memory += 1,
+                InterpreterLog::Other => {}
+            }
+        }
+    }
+
+    /// Captures the interpreter logs from the first traced call on.
+    #[derive(Default)]
+    struct Tracer {
+        parsed: Rc<RefCell<Parsed>>,
+        capture: Option<Capture>,
+    }
+
+    impl Tracing for Tracer {
+        fn enter_child_span(
+            &mut self,
+            _from: H160,
+            _to: H160,
+            _delegate_call: Option<H160>,
+            _is_read_only: bool,
+            _value: U256,
+            _input: &[u8],
+            _gas_limit: u64,
+        ) {
+            if self.capture.is_none() {
+                self.capture = Some(Capture::new(self.parsed.clone()));
+            }
+        }
+    }
+
+    /// Fails when the interpreter the pallet executes contracts with changes its log format.
+    #[test]
+    fn parse_pallet_interpreter_logs() {
+        let mut tracer = Tracer::default();
+        Specs {
+            actions: vec![SpecsAction::instantiate(
+                Code::Bytes(include_bytes!("../fixtures/Baseline.pvm").to_vec()),
+                vec![],
+            )],
+            ..Default::default()
+        }
+        .run_with_tracer(&mut tracer);
+        tracer.capture = None;
+        assert!(!is_active());
+
+        let parsed = tracer.parsed.borrow();
+        assert!(!parsed.compiled.is_empty());
+        assert!(!parsed.executed.is_empty());
+        assert!(!parsed.executed.is_disjoint(&parsed.compiled));
+        assert!(parsed.registers > 0);
+        assert!(parsed.memory > 0);
+    }
+
+    /// The log lines of the `polkavm` 0.33 interpreter.
+    #[test]
+    fn parse_instructions() {
+        assert_eq!(
+            InterpreterLog::parse("  [12]: 34: a0 = a1 + a2"),
+            InterpreterLog::Compiled {
+                handler: 12,
+                program_counter: 34,
+                instruction: "a0 = a1 + a2"
+            }
+        );
+        assert_eq!(
+            InterpreterLog::parse("  [12]: 34: charge_gas"),
+            InterpreterLog::Other
+        );
+        assert_eq!(
+            InterpreterLog::parse("[12]: a0 = a1 + a2"),
+            InterpreterLog::Executed { handler: 12 }
+        );
+        assert_eq!(
+            InterpreterLog::parse("[11]: charge_gas: 3 (100 -> 97)"),
+            InterpreterLog::Other
+        );
+        assert_eq!(
+            InterpreterLog::parse("Compiling block:"),
+            InterpreterLog::Other
+        );
+    }
+
+    #[test]
+    fn parse_registers_and_memory() {
+        assert_eq!(
+            InterpreterLog::parse("  set: sp = 0xfffdfff0"),
+            InterpreterLog::Register {
+                register: "sp",
+                value: 0xfffdfff0
+            }
+        );
+        assert_eq!(
+            InterpreterLog::parse("  u64 [0xfffdfff8] = ra = 0x18"),
+            InterpreterLog::Memory {
+                address: 0xfffdfff8,
+                size: 8,
+                value: 0x18
+            }
+        );
+        assert_eq!(
+            InterpreterLog::parse("  u8 [0x20000] = 0x1"),
+            InterpreterLog::Memory {
+                address: 0x20000,
+                size: 1,
+                value: 0x1
+            }
+        );
+        assert_eq!(
+            InterpreterLog::parse("  a0 = i32 [0x20004] = 0xffffffffffffffff"),
+            InterpreterLog::Memory {
+                address: 0x20004,
+                size: 4,
+                value: 0xffffffffffffffff
+            }
+        );
+    }
+}
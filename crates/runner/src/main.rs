@@ -3,7 +3,11 @@ use std::path::PathBuf;
 
 use clap::Parser;
 
-use revive_runner::{Code, OptionalHex, Specs, SpecsAction::*, TestAddress, VerifyCallExpectation};
+use revive_runner::{
+    check, CallAbi, CallResult, Code, Debugger, Fuzzer, InvariantTest, OptionalHex, ProfileMetric,
+    Profiler, Recorder, Specs, SpecsAction::*, TestAddress, VerifyCallExpectation, WeightReport,
+    DEFAULT_INSTRUCTION_LIMIT,
+};
 
 /// The action which is expected to fail.
 ///
@@ -16,18 +20,48 @@ enum VerifyActionFailure {
     Call,
 }
 
+/// The output format of the host function weight report.
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum WeightReportFormat {
+    /// A human-readable table
+    Table,
+    /// JSON
+    Json,
+}
+
 /// Execute revive PolkaVM contracts locally.
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true)]
 struct Arguments {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The hex encoded calldata for the contract call.
     #[arg(short, long)]
     calldata: Option<String>,
 
+    /// The signature of the called function, e.g. `transfer(address,uint256)`.
+    /// The calldata is encoded from the function arguments given by `--args`.
+    #[arg(long, conflicts_with = "calldata")]
+    call: Option<String>,
+
+    /// The arguments of the called function.
+    #[arg(long, num_args = 1.., requires = "call")]
+    args: Vec<String>,
+
     /// The hex encoded calldata for the contract deployment.
     #[arg(short, long)]
     deploy_calldata: Option<String>,
 
+    /// The signature of the constructor, e.g. `constructor(uint256)`.
+    /// The deploy calldata is encoded from the arguments given by `--deploy-args`.
+    #[arg(long, conflicts_with = "deploy_calldata")]
+    constructor: Option<String>,
+
+    /// The arguments of the constructor.
+    #[arg(long, num_args = 1.., requires = "constructor")]
+    deploy_args: Vec<String>,
+
     /// The hex encoded contract code blob to instantiate and execute.
     #[arg(short, long)]
     blob: Option<String>,
@@ -55,13 +89,116 @@ struct Arguments {
     /// The expected amount of consumed gas by the contract execution.
     #[arg(long)]
     verify_call_gas_consumed: Option<u128>,
+
+    /// Write an instruction-level profile of the deploy and call transactions in the
+    /// folded-stack format to the given file.
+    #[arg(long)]
+    profile: Option<PathBuf>,
+
+    /// The sample value used for the profile.
+    #[arg(long, value_enum, default_value_t = ProfileMetric::Instructions)]
+    profile_metric: ProfileMetric,
+
+    /// Print the weight consumed per host function by the deploy and call transactions.
+    #[arg(long, value_enum, conflicts_with = "profile")]
+    weight_report: Option<WeightReportFormat>,
+
+    /// Record the deploy and call transactions and start a post-mortem step debugger.
+    #[arg(long, conflicts_with_all = ["profile", "weight_report"])]
+    debug: bool,
+
+    /// The maximum number of instructions recorded for the debugger.
+    #[arg(long, default_value_t = DEFAULT_INSTRUCTION_LIMIT, requires = "debug")]
+    debug_instruction_limit: usize,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Call a Solidity contract with random ABI encoded arguments and compare the
+    /// results against the EVM.
+    Fuzz(FuzzArguments),
+    /// Run an invariant test, calling the target contracts with random arguments from
+    /// random actors and verifying the invariants after every call.
+    Invariant(InvariantArguments),
+    /// Find the smallest heap and stack sizes at which a Solidity contract behaves the same
+    /// as with the default memory configuration for the given calls.
+    TuneMemory(TuneMemoryArguments),
+}
+
+#[derive(clap::Args)]
+struct FuzzArguments {
+    /// The Solidity source file.
+    file: PathBuf,
+
+    /// The name of the contract to fuzz.
+    #[arg(long)]
+    contract: String,
+
+    /// The number of call sequences to run.
+    #[arg(long, default_value_t = 256)]
+    runs: usize,
+
+    /// The number of calls in each sequence.
+    #[arg(long, default_value_t = 8)]
+    depth: usize,
+
+    /// The seed of the random generator, defaults to the current time.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// The directory to save failing call sequences to as `runner.json` specs.
+    #[arg(long, default_value = ".")]
+    output: PathBuf,
+}
+
+#[derive(clap::Args)]
+struct InvariantArguments {
+    /// The invariant test JSON file.
+    file: PathBuf,
+
+    /// Override the seed of the random generator.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// The file to save the shrunk call sequence violating an invariant to as `runner.json` spec.
+    #[arg(long, default_value = "invariant-failure.json")]
+    output: PathBuf,
+}
+
+#[derive(clap::Args)]
+struct TuneMemoryArguments {
+    /// The Solidity source file.
+    file: PathBuf,
+
+    /// The name of the contract to tune.
+    #[arg(long)]
+    contract: String,
+
+    /// The hex encoded calldata for the contract deployment.
+    #[arg(short, long)]
+    deploy_calldata: Option<String>,
+
+    /// The hex encoded calldata for the contract calls, executed in order.
+    #[arg(short, long, num_args = 1..)]
+    calldata: Vec<String>,
+
+    /// The LLVM optimization parameter -O[0 | 1 | 2 | 3 | s | z] the contract is compiled with.
+    #[arg(short = 'O', long, default_value = "z")]
+    optimization: char,
 }
 
 fn main() -> anyhow::Result<()> {
-    env_logger::init();
+    revive_runner::init_logger()?;
 
     let arguments = Arguments::parse();
 
+    match arguments.command {
+        Some(Command::Fuzz(arguments)) => return fuzz(arguments),
+        Some(Command::Invariant(arguments)) => return invariant(arguments),
+        Some(Command::TuneMemory(arguments)) => return tune_memory(arguments),
+        None => {}
+    }
+
     let code = match (arguments.blob, arguments.file) {
         (Some(blob), None) => hex::decode(blob)
             .map_err(|error| anyhow::anyhow!("expected hex encoded PVM blob: {error}"))?,
@@ -93,11 +230,15 @@ fn main() -> anyhow::Result<()> {
             code: Code::Bytes(code),
             data: deploy_calldata,
             salt: OptionalHex::default(),
+            constructor: arguments.constructor,
+            args: arguments.deploy_args,
         },
         VerifyCall(VerifyCallExpectation {
             gas_consumed: None,
             output: OptionalHex::default(),
             success: !verify_failure.contains(&VerifyActionFailure::Instantiate),
+            values: None,
+            revert_reason: None,
         }),
         Call {
             origin,
@@ -106,20 +247,210 @@ fn main() -> anyhow::Result<()> {
             gas_limit: None,
             storage_deposit_limit: None,
             data: calldata,
+            call: arguments.call,
+            args: arguments.args,
         },
         VerifyCall(VerifyCallExpectation {
             gas_consumed: arguments.verify_call_gas_consumed,
             output: OptionalHex::default(),
             success: !verify_failure.contains(&VerifyActionFailure::Call),
+            values: None,
+            revert_reason: None,
         }),
     ];
 
-    Specs {
+    let specs = Specs {
         actions,
         differential: false,
         ..Default::default()
+    };
+
+    if arguments.debug {
+        let mut recorder = Recorder::with_limit(arguments.debug_instruction_limit);
+        // Failing expectations are reported but should not prevent debugging.
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            specs.run_decoded(Some(&mut recorder))
+        })) {
+            Ok(results) => print_results(&results, &mut std::io::stderr())?,
+            Err(_) => eprintln!("the contract execution did not meet the expectations"),
+        }
+        // Taking the recording also ends the log capture of a call that panicked.
+        let recording = recorder.recording();
+        Debugger::new(recording).run(std::io::stdin().lock(), std::io::stdout())?;
+        return Ok(());
+    }
+
+    let results = match (arguments.profile, arguments.weight_report) {
+        (Some(path), _) => {
+            let mut profiler = Profiler::default();
+            let results = specs.run_decoded(Some(&mut profiler));
+            let folded = profiler.profile().folded(arguments.profile_metric);
+            std::fs::write(&path, folded).map_err(|error| {
+                anyhow::anyhow!("unable to write profile {}: {error}", path.display())
+            })?;
+            results
+        }
+        (None, Some(format)) => {
+            let mut report = WeightReport::default();
+            let results = specs.run_decoded(Some(&mut report));
+            match format {
+                WeightReportFormat::Table => print!("{}", report.table()),
+                WeightReportFormat::Json => println!("{}", report.json()),
+            }
+            // The report owns the standard output.
+            return print_results(&results, &mut std::io::stderr());
+        }
+        (None, None) => specs.run_decoded(None),
+    };
+
+    print_results(&results, &mut std::io::stdout())
+}
+
+/// Print the decoded return values or revert reasons of the deploy and call transactions.
+///
+/// Unexpected failures are reported with their revert reason by the [`VerifyCall`] actions.
+fn print_results(
+    results: &[(CallResult, CallAbi)],
+    output: &mut impl std::io::Write,
+) -> anyhow::Result<()> {
+    for ((result, abi), name) in results.iter().zip(["deploy", "call"]) {
+        writeln!(output, "{name} {}", abi.describe(result))?;
+    }
+    Ok(())
+}
+
+fn fuzz(arguments: FuzzArguments) -> anyhow::Result<()> {
+    let code = solidity_code(arguments.file, arguments.contract.clone())?;
+    let abi = code.abi().expect("the ABI of Solidity contracts is known");
+    let seed = arguments.seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("the system time should be after the Unix epoch")
+            .as_nanos() as u64
+    });
+    println!("fuzzing {} with seed {seed}", arguments.contract);
+
+    // Divergences are reported below, the panic messages would only add noise.
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let mut fuzzer = Fuzzer::new(code, &abi, seed);
+    let result = (0..arguments.runs).try_fold(0, |failures, run| {
+        let specs = fuzzer.sequence(arguments.depth);
+        let Err(reason) = check(specs.clone()) else {
+            return Ok(failures);
+        };
+
+        let path = arguments
+            .output
+            .join(format!("{}-{seed}-{run}.json", arguments.contract));
+        let json = serde_json::to_string_pretty(&specs)?;
+        std::fs::write(&path, json)
+            .map_err(|error| anyhow::anyhow!("unable to write {}: {error}", path.display()))?;
+        println!("run {run} diverged: {reason}");
+        println!("  saved to {}", path.display());
+        anyhow::Ok(failures + 1)
+    });
+    std::panic::set_hook(hook);
+
+    let failures = result?;
+    if failures > 0 {
+        anyhow::bail!("{failures} of {} runs diverged", arguments.runs);
+    }
+    println!("{} runs passed", arguments.runs);
+
+    Ok(())
+}
+
+fn invariant(arguments: InvariantArguments) -> anyhow::Result<()> {
+    let json = std::fs::read_to_string(&arguments.file)
+        .map_err(|error| anyhow::anyhow!("unable to read {}: {error}", arguments.file.display()))?;
+    let mut test: InvariantTest = serde_json::from_str(&json)
+        .map_err(|error| anyhow::anyhow!("invalid invariant test JSON: {error}"))?;
+    if let Some(seed) = arguments.seed {
+        test.seed = seed;
+    }
+    let runs = test.runs;
+
+    // Violations are reported below, the panic messages would only add noise.
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = test.run();
+    std::panic::set_hook(hook);
+
+    let Err(failure) = result else {
+        println!("{runs} runs passed");
+        return Ok(());
+    };
+    let json = serde_json::to_string_pretty(&failure.specs)?;
+    std::fs::write(&arguments.output, json).map_err(|error| {
+        anyhow::anyhow!("unable to write {}: {error}", arguments.output.display())
+    })?;
+    println!(
+        "the shrunk call sequence was saved to {}",
+        arguments.output.display()
+    );
+    anyhow::bail!("invariant violated: {}", failure.reason)
+}
+
+#[cfg(feature = "solidity")]
+fn tune_memory(arguments: TuneMemoryArguments) -> anyhow::Result<()> {
+    let source_code = std::fs::read_to_string(&arguments.file)
+        .map_err(|error| anyhow::anyhow!("unable to read {}: {error}", arguments.file.display()))?;
+    let deploy_calldata = match arguments.deploy_calldata {
+        Some(calldata) => hex::decode(calldata)
+            .map_err(|error| anyhow::anyhow!("expected hex encoded calldata: {error}"))?,
+        None => vec![],
+    };
+    let calldata = arguments
+        .calldata
+        .iter()
+        .map(hex::decode)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| anyhow::anyhow!("expected hex encoded calldata: {error}"))?;
+
+    let optimizer_settings =
+        revive_llvm_context::OptimizerSettings::try_from_cli(arguments.optimization)?;
+
+    let tuning = revive_runner::MemoryTuner::new(
+        arguments.contract,
+        source_code,
+        optimizer_settings,
+        deploy_calldata,
+        calldata,
+    )
+    .tune()?;
+    match tuning.auto {
+        Ok((auto, true)) => eprintln!(
+            "the automatic memory configuration {} behaves the same for the given calls",
+            serde_json::to_string(&auto)?
+        ),
+        Ok((auto, false)) => eprintln!(
+            "warning: the automatic memory configuration {} behaves differently for the given calls",
+            serde_json::to_string(&auto)?
+        ),
+        Err(error) => eprintln!("the automatic memory configuration was not checked: {error}"),
     }
-    .run();
 
+    println!("{}", serde_json::to_string_pretty(&tuning.tuned)?);
     Ok(())
 }
+
+#[cfg(not(feature = "solidity"))]
+fn tune_memory(_arguments: TuneMemoryArguments) -> anyhow::Result<()> {
+    anyhow::bail!("memory tuning requires revive-runner to be built with the 'solidity' feature")
+}
+
+#[cfg(feature = "solidity")]
+fn solidity_code(path: PathBuf, contract: String) -> anyhow::Result<Code> {
+    Ok(Code::Solidity {
+        path: Some(path),
+        solc_optimizer: None,
+        contract,
+        libraries: Default::default(),
+    })
+}
+
+#[cfg(not(feature = "solidity"))]
+fn solidity_code(_path: PathBuf, _contract: String) -> anyhow::Result<Code> {
+    anyhow::bail!("fuzzing requires revive-runner to be built with the 'solidity' feature")
+}
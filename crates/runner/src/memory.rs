@@ -0,0 +1,210 @@
+//! Memory configuration tuning by profiling.
+//!
+//! The [`MemoryTuner`] finds the smallest heap and stack sizes at which a Solidity
+//! contract behaves the same as with the default memory configuration for a set of
+//! test calls. Unlike the static estimates of `polkavm.memoryConfig: "auto"`, the
+//! result only holds for the code paths the test calls exercise. The tuner also checks
+//! the automatic memory configuration against the same calls.
+//!
+//! The contract is compiled again for every candidate size. The heap size is searched
+//! first, using the default stack size, followed by the stack size, using the found heap
+//! size. Both searches assume that contracts keep working with more memory; the found
+//! configuration is verified and an error is returned if the assumption doesn't hold.
+
+use std::panic::PanicHookInfo;
+
+use revive_llvm_context::OptimizerSettings;
+use revive_solc_json_interface::{
+    PolkaVMDefaultHeapMemorySize, PolkaVMDefaultStackMemorySize,
+    SolcStandardJsonInputSettingsPolkaVMMemory,
+};
+
+use crate::{fuzz::panic_message, *};
+
+/// The granularity of the tuned memory sizes in bytes.
+pub const TUNING_GRANULARITY: u32 = 4 * 1024;
+
+/// The outcome of the deploy and call transactions: whether they reverted and the output,
+/// or the panic message if the execution trapped.
+type Outcome = Result<Vec<(bool, Vec<u8>)>, String>;
+
+/// The tuned memory configuration of a contract.
+#[derive(Clone, Debug)]
+pub struct MemoryTuning {
+    /// The smallest memory configuration with the same outcome as the default one
+    pub tuned: SolcStandardJsonInputSettingsPolkaVMMemory,
+    /// The automatic memory configuration and whether it has the same outcome as the
+    /// default one, or why it is unavailable
+    pub auto: Result<(SolcStandardJsonInputSettingsPolkaVMMemory, bool), String>,
+}
+
+/// Tunes the memory configuration of a Solidity contract with test calls.
+pub struct MemoryTuner {
+    contract: String,
+    source_code: String,
+    optimizer_settings: OptimizerSettings,
+    deploy_calldata: Vec<u8>,
+    calldata: Vec<Vec<u8>>,
+}
+
+impl MemoryTuner {
+    /// Create a tuner for the `contract` in `source_code`, compiled with the
+    /// `optimizer_settings`, deployed with `deploy_calldata` and called with every `calldata`.
+    pub fn new(
+        contract: String,
+        source_code: String,
+        optimizer_settings: OptimizerSettings,
+        deploy_calldata: Vec<u8>,
+        calldata: Vec<Vec<u8>>,
+    ) -> Self {
+        Self {
+            contract,
+            source_code,
+            optimizer_settings,
+            deploy_calldata,
+            calldata,
+        }
+    }
+
+    /// Returns the smallest memory configuration with the same outcome as the default one,
+    /// and checks the automatic memory configuration.
+    ///
+    /// Candidate sizes which are too small trap; the panic hook is silenced while tuning
+    /// and restored afterwards.
+    pub fn tune(&self) -> anyhow::Result<MemoryTuning> {
+        let _hook = SilentPanicHook::install();
+
+        let baseline = self.outcome(SolcStandardJsonInputSettingsPolkaVMMemory::default())?;
+        match &baseline {
+            Ok(outcome) if outcome.first().is_some_and(|(reverted, _)| !reverted) => {}
+            Ok(_) => anyhow::bail!(
+                "the contract can't be deployed with the default memory configuration"
+            ),
+            Err(message) => anyhow::bail!(
+                "the contract panicked with the default memory configuration: {message}"
+            ),
+        }
+
+        let heap_size = self.search(PolkaVMDefaultHeapMemorySize, &baseline, |size| {
+            SolcStandardJsonInputSettingsPolkaVMMemory::new(
+                Some(size),
+                Some(PolkaVMDefaultStackMemorySize),
+            )
+        })?;
+        let stack_size = self.search(PolkaVMDefaultStackMemorySize, &baseline, |size| {
+            SolcStandardJsonInputSettingsPolkaVMMemory::new(Some(heap_size), Some(size))
+        })?;
+        let tuned =
+            SolcStandardJsonInputSettingsPolkaVMMemory::new(Some(heap_size), Some(stack_size));
+        if let Some(difference) = difference(&baseline, &self.outcome(tuned)?) {
+            anyhow::bail!(
+                "the contract doesn't behave monotonically in the memory size: \
+                 the tuned configuration {} {difference}",
+                serde_json::to_string(&tuned)?,
+            );
+        }
+
+        let auto = resolc::test_utils::compile_blob_with_auto_memory_config(
+            &self.contract,
+            &self.source_code,
+            self.optimizer_settings.to_owned(),
+        )
+        .map(|(blob, auto)| (auto, self.run(blob) == baseline))
+        .map_err(|error| error.to_string());
+
+        Ok(MemoryTuning { tuned, auto })
+    }
+
+    /// Binary searches the smallest size up to `maximum` whose memory configuration,
+    /// created by `memory_config`, has the `baseline` outcome.
+    fn search(
+        &self,
+        maximum: u32,
+        baseline: &Outcome,
+        memory_config: impl Fn(u32) -> SolcStandardJsonInputSettingsPolkaVMMemory,
+    ) -> anyhow::Result<u32> {
+        let (mut low, mut high) = (1, maximum.div_ceil(TUNING_GRANULARITY));
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.outcome(memory_config(middle * TUNING_GRANULARITY))? == *baseline {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+        Ok(high * TUNING_GRANULARITY)
+    }
+
+    /// Compiles the contract with the `memory_config` and returns the outcome of the deploy
+    /// and call transactions.
+    fn outcome(
+        &self,
+        memory_config: SolcStandardJsonInputSettingsPolkaVMMemory,
+    ) -> anyhow::Result<Outcome> {
+        let blob = resolc::test_utils::compile_blob_with_memory_config(
+            &self.contract,
+            &self.source_code,
+            self.optimizer_settings.to_owned(),
+            memory_config,
+        )?;
+        Ok(self.run(blob))
+    }
+
+    /// Returns the outcome of the deploy and call transactions of the `blob`.
+    fn run(&self, blob: Vec<u8>) -> Outcome {
+        let mut actions = vec![SpecsAction::instantiate(
+            Code::Bytes(blob),
+            self.deploy_calldata.clone(),
+        )];
+        actions.extend(
+            self.calldata
+                .iter()
+                .map(|data| SpecsAction::call(TestAddress::Instantiated(0), data.clone())),
+        );
+
+        let specs = Specs {
+            actions,
+            differential: false,
+            ..Default::default()
+        };
+        // Calls to a contract which failed to deploy panic.
+        let results = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| specs.run()))
+            .map_err(panic_message)?;
+        Ok(results
+            .iter()
+            .map(|result| (result.did_revert(), result.output()))
+            .collect())
+    }
+}
+
+/// Describes how the `outcome` differs from the `baseline`, if it does.
+fn difference(baseline: &Outcome, outcome: &Outcome) -> Option<String> {
+    match outcome {
+        _ if outcome == baseline => None,
+        Ok(_) => Some("changes the outcome of the calls".to_string()),
+        Err(message) => Some(format!("panics: {message}")),
+    }
+}
+
+/// Silences the panic hook until dropped, restoring the previous hook.
+struct SilentPanicHook {
+    previous: Option<Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static>>,
+}
+
+impl SilentPanicHook {
+    fn install() -> Self {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        Self {
+            previous: Some(previous),
+        }
+    }
+}
+
+impl Drop for SilentPanicHook {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            std::panic::set_hook(previous);
+        }
+    }
+}
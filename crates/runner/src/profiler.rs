@@ -0,0 +1,464 @@
+//! Instruction-level profiler for PVM contracts.
+//!
+//! The [`Profiler`] attributes executed PolkaVM instructions and consumed `ref_time`
+//! to a stack made of the contract frames, the function symbols found in the blob
+//! debug information and the called host functions. The result can be written in
+//! the folded-stack format consumed by flamegraph tools (`inferno`, `flamegraph.pl`).
+//!
+//! Executed instructions are observed through the debug logs of the PolkaVM interpreter.
+//! This requires the [`crate::init_logger`] logger, which is installed when the first
+//! externalities are built unless another logger was set before; otherwise only the host
+//! function and interpreter fuel weights are recorded.
+//! Function symbols are only available for blobs with debug information (`resolc -g`).
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+};
+
+use pallet_revive::{
+    tracing::{FrameTraceInfo, Tracing},
+    ExecReturnValue,
+};
+use polkadot_sdk::*;
+use polkadot_sdk::{
+    sp_core::{H160, U256},
+    sp_runtime::DispatchError,
+};
+
+use crate::ecalls::{Ecalls, Exited};
+use crate::logger::{self, InterpreterLog, InterpreterLogSink};
+use crate::symbols::{CodeTracker, Symbolizer};
+use crate::*;
+
+/// The stack frame name for executed code that could not be attributed to an instruction.
+const UNATTRIBUTED_FRAME: &str = "[interpreter]";
+
+/// The stack frame name for code without debug information.
+const UNKNOWN_FRAME: &str = "[unknown]";
+
+/// The metric used as the sample value of the folded stacks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProfileMetric {
+    /// The number of executed PolkaVM instructions.
+    #[default]
+    Instructions,
+    /// The consumed `ref_time` weight.
+    RefTime,
+}
+
+/// The cost attributed to a stack.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sample {
+    /// The number of executed PolkaVM instructions.
+    pub instructions: u64,
+    /// The consumed `ref_time` weight.
+    pub ref_time: u64,
+}
+
+impl Sample {
+    fn value(&self, metric: ProfileMetric) -> u64 {
+        match metric {
+            ProfileMetric::Instructions => self.instructions,
+            ProfileMetric::RefTime => self.ref_time,
+        }
+    }
+}
+
+/// A symbolized profile.
+#[derive(Clone, Debug, Default)]
+pub struct Profile {
+    /// The cost per stack, outermost frame first.
+    pub samples: BTreeMap<Vec<String>, Sample>,
+}
+
+impl Profile {
+    /// Render the profile in the folded-stack format.
+    ///
+    /// Stacks with a zero value for the given `metric` are omitted.
+    pub fn folded(&self, metric: ProfileMetric) -> String {
+        let mut folded = String::new();
+        for (stack, sample) in &self.samples {
+            let value = sample.value(metric);
+            if value == 0 {
+                continue;
+            }
+            let frames = stack
+                .iter()
+                .map(|frame| frame.replace(';', ":"))
+                .collect::<Vec<_>>();
+            folded.push_str(&format!("{} {value}\n", frames.join(";")));
+        }
+        folded
+    }
+
+    /// The total cost of all stacks.
+    pub fn total(&self) -> Sample {
+        self.samples
+            .values()
+            .fold(Sample::default(), |total, sample| Sample {
+                instructions: total.instructions + sample.instructions,
+                ref_time: total.ref_time + sample.ref_time,
+            })
+    }
+}
+
+/// A raw, not yet symbolized stack frame.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Node {
+    /// A contract call frame executing the code of the given address.
+    Contract(H160),
+    /// The instruction at the given program counter of the enclosing contract.
+    Instruction(u32),
+    /// Executed code that could not be attributed to an instruction.
+    Unattributed,
+    /// A host function call.
+    HostFunction(&'static str),
+}
+
+/// A contract call frame in progress.
+#[derive(Default)]
+struct Frame {
+    /// The stack leading to this frame, including the frame itself.
+    stack: Vec<Node>,
+    /// The mapping of the interpreter compiled handler indices to program counters.
+    compiled: HashMap<u32, u32>,
+    /// The instructions executed since the last interpreter fuel synchronization.
+    pending: BTreeMap<u32, u64>,
+    /// The program counter of the last executed instruction.
+    last_program_counter: Option<u32>,
+    /// The host function calls in progress.
+    ecalls: Ecalls,
+}
+
+impl Frame {
+    /// The stack of the current location inside this frame.
+    fn current_stack(&self) -> Vec<Node> {
+        let mut stack = self.stack.clone();
+        if let Some(program_counter) = self.last_program_counter {
+            stack.push(Node::Instruction(program_counter));
+        }
+        if let Some(name) = self.ecalls.current() {
+            stack.push(Node::HostFunction(name));
+        }
+        stack
+    }
+}
+
+/// The profiler state shared between the tracer and the logger.
+#[derive(Default)]
+struct State {
+    frames: Vec<Frame>,
+    samples: BTreeMap<Vec<Node>, Sample>,
+    code: CodeTracker,
+    /// The interpreter log capture while a traced call is executing.
+    capture: Option<logger::Capture>,
+}
+
+impl InterpreterLogSink for State {
+    fn interpreter_log(&mut self, log: InterpreterLog) {
+        let Some(frame) = self.frames.last_mut() else {
+            return;
+        };
+
+        match log {
+            InterpreterLog::Compiled {
+                handler,
+                program_counter,
+                ..
+            } => {
+                frame.compiled.insert(handler, program_counter);
+            }
+            InterpreterLog::Executed { handler } => {
+                if let Some(program_counter) = frame.compiled.get(&handler).copied() {
+                    *frame.pending.entry(program_counter).or_default() += 1;
+                    frame.last_program_counter = Some(program_counter);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl State {
+    fn record(&mut self, stack: Vec<Node>, instructions: u64, ref_time: u64) {
+        let sample = self.samples.entry(stack).or_default();
+        sample.instructions += instructions;
+        sample.ref_time += ref_time;
+    }
+
+    /// Attribute the instructions executed since the last fuel synchronization of the
+    /// top frame, distributing `ref_time` proportionally to the instruction counts.
+    fn flush_pending(&mut self, ref_time: u64) {
+        let Some(frame) = self.frames.last_mut() else {
+            return;
+        };
+        let pending = std::mem::take(&mut frame.pending);
+        let stack = frame.stack.clone();
+
+        let instructions = pending.values().sum::<u64>();
+        if instructions == 0 {
+            if ref_time > 0 {
+                self.record([stack, vec![Node::Unattributed]].concat(), 0, ref_time);
+            }
+            return;
+        }
+
+        let mut remaining = ref_time;
+        let last = pending.len() - 1;
+        for (index, (program_counter, count)) in pending.into_iter().enumerate() {
+            let share = if index == last {
+                remaining
+            } else {
+                (ref_time as u128 * count as u128 / instructions as u128) as u64
+            };
+            remaining -= share;
+            let mut stack = stack.clone();
+            stack.push(Node::Instruction(program_counter));
+            self.record(stack, count, share);
+        }
+    }
+
+    fn enter_frame(&mut self, address: H160) {
+        let mut stack = self
+            .frames
+            .last()
+            .map(Frame::current_stack)
+            .unwrap_or_default();
+        stack.push(Node::Contract(address));
+        self.code.enter_frame(address);
+
+        self.frames.push(Frame {
+            stack,
+            ..Default::default()
+        });
+    }
+
+    fn exit_frame(&mut self, weight: Weight) {
+        self.flush_pending(0);
+        self.frames.pop();
+        if let Some(frame) = self.frames.last_mut() {
+            frame.ecalls.nested(weight);
+        }
+    }
+
+    fn symbolize(&self, stack: &[Node], cache: &mut Symbolizer) -> Vec<String> {
+        let mut symbolized = Vec::with_capacity(stack.len());
+        let mut address = None;
+        for node in stack {
+            match node {
+                Node::Contract(contract) => {
+                    address = Some(*contract);
+                    symbolized.push(format!("{contract:?}"));
+                }
+                Node::Instruction(program_counter) => {
+                    let functions = address
+                        .and_then(|address| self.code.get(&address))
+                        .map(|code| cache.symbol(code, *program_counter).functions)
+                        .unwrap_or_default();
+                    if functions.is_empty() {
+                        symbolized.push(UNKNOWN_FRAME.to_string());
+                    }
+                    symbolized.extend(functions);
+                }
+                Node::Unattributed => symbolized.push(UNATTRIBUTED_FRAME.to_string()),
+                Node::HostFunction(name) => symbolized.push(format!("host:{name}")),
+            }
+        }
+        symbolized
+    }
+}
+
+/// A [`Tracing`] implementation collecting an instruction-level profile.
+///
+/// The profiler is activated while a traced call is executing. It receives the
+/// interpreter logs only if the [`crate::init_logger`] logger is installed.
+#[derive(Clone, Default)]
+pub struct Profiler {
+    state: Rc<RefCell<State>>,
+}
+
+impl Profiler {
+    /// Symbolize the collected samples.
+    ///
+    /// Ends the interpreter log capture of a traced call that did not finish (e.g. panicked).
+    pub fn profile(&self) -> Profile {
+        self.state.borrow_mut().capture = None;
+        let state = self.state.borrow();
+        let mut symbolizer = Symbolizer::default();
+        let mut profile = Profile::default();
+        for (stack, sample) in &state.samples {
+            let entry = profile
+                .samples
+                .entry(state.symbolize(stack, &mut symbolizer))
+                .or_default();
+            entry.instructions += sample.instructions;
+            entry.ref_time += sample.ref_time;
+        }
+        profile
+    }
+}
+
+impl Tracing for Profiler {
+    fn instantiate_code(&mut self, code: &pallet_revive::Code, _salt: Option<&[u8; 32]>) {
+        self.state.borrow_mut().code.instantiate_code(code);
+    }
+
+    fn enter_child_span(
+        &mut self,
+        _from: H160,
+        to: H160,
+        _delegate_call: Option<H160>,
+        _is_read_only: bool,
+        _value: U256,
+        _input: &[u8],
+        _gas_limit: u64,
+    ) {
+        if self.state.borrow().frames.is_empty() {
+            let capture = logger::Capture::new(self.state.clone());
+            self.state.borrow_mut().capture = Some(capture);
+        }
+        self.state.borrow_mut().enter_frame(to);
+    }
+
+    fn exit_child_span(&mut self, _output: &ExecReturnValue, _gas_used: u64, weight: Weight) {
+        let mut state = self.state.borrow_mut();
+        state.exit_frame(weight);
+        if state.frames.is_empty() {
+            state.capture = None;
+        }
+    }
+
+    fn exit_child_span_with_error(
+        &mut self,
+        _error: DispatchError,
+        _gas_used: u64,
+        weight: Weight,
+    ) {
+        self.exit_child_span(&Default::default(), 0, weight);
+    }
+
+    fn enter_ecall(&mut self, name: &'static str, _args: &[u64], trace_info: &dyn FrameTraceInfo) {
+        if let Some(frame) = self.state.borrow_mut().frames.last_mut() {
+            frame.ecalls.enter(name, trace_info.weight_consumed());
+        }
+    }
+
+    fn exit_step(&mut self, trace_info: &dyn FrameTraceInfo, _returned: Option<u64>) {
+        let mut state = self.state.borrow_mut();
+        let Some(frame) = state.frames.last_mut() else {
+            return;
+        };
+        match frame.ecalls.exit(trace_info.weight_consumed()) {
+            Some(Exited::Fuel(weight)) => state.flush_pending(weight.ref_time()),
+            Some(Exited::HostFunction { name, weight }) => {
+                let mut stack = frame.current_stack();
+                stack.push(Node::HostFunction(name));
+                state.record(stack, 0, weight.ref_time());
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fold_stacks() {
+        let mut profile = Profile::default();
+        profile.samples.insert(
+            vec!["0xaa".into(), "fun_transfer;1".into()],
+            Sample {
+                instructions: 10,
+                ref_time: 0,
+            },
+        );
+        profile.samples.insert(
+            vec!["0xaa".into(), "host:seal_return".into()],
+            Sample {
+                instructions: 0,
+                ref_time: 7,
+            },
+        );
+
+        assert_eq!(
+            profile.folded(ProfileMetric::Instructions),
+            "0xaa;fun_transfer:1 10\n"
+        );
+        assert_eq!(
+            profile.folded(ProfileMetric::RefTime),
+            "0xaa;host:seal_return 7\n"
+        );
+        assert_eq!(
+            profile.total(),
+            Sample {
+                instructions: 10,
+                ref_time: 7
+            }
+        );
+    }
+
+    /// Profile the instantiation of `code` followed by a call with `data`, if any.
+    fn profile(code: Code, data: Option<Vec<u8>>) -> Profile {
+        let mut profiler = Profiler::default();
+        let mut actions = vec![SpecsAction::instantiate(code, vec![])];
+        actions.extend(data.map(|data| SpecsAction::call(TestAddress::Instantiated(0), data)));
+        Specs {
+            actions,
+            ..Default::default()
+        }
+        .run_with_tracer(&mut profiler);
+        profiler.profile()
+    }
+
+    #[test]
+    fn profile_stripped_fixture() {
+        let profile = profile(
+            Code::Bytes(include_bytes!("../fixtures/Baseline.pvm").to_vec()),
+            None,
+        );
+
+        // The interpreter logs were captured and attributed to instructions.
+        let folded = profile.folded(ProfileMetric::Instructions);
+        assert!(profile.total().instructions > 0, "{folded}");
+        assert!(folded.contains(UNKNOWN_FRAME), "{folded}");
+        assert!(profile
+            .folded(ProfileMetric::RefTime)
+            .contains("host:seal_return"));
+    }
+
+    #[cfg(feature = "resolc")]
+    #[test]
+    fn profile_solidity_fixture() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../integration/contracts/Fibonacci.sol");
+        // fib3(6)
+        let data =
+            hex::decode("6b83dd2e0000000000000000000000000000000000000000000000000000000000000006")
+                .unwrap();
+        let profile = profile(
+            Code::Solidity {
+                path: Some(path),
+                solc_optimizer: None,
+                contract: "FibonacciRecursive".to_string(),
+                libraries: Default::default(),
+            },
+            Some(data),
+        );
+
+        let folded = profile.folded(ProfileMetric::Instructions);
+        let frames = folded
+            .lines()
+            .flat_map(|line| line.rsplit_once(' ').unwrap().0.split(';'))
+            .collect::<std::collections::BTreeSet<_>>();
+        assert!(frames.contains("__entry"), "{folded}");
+        // The recursive internal function can not be inlined.
+        assert!(
+            frames.iter().any(|frame| frame.starts_with("fun_f_")),
+            "{folded}"
+        );
+    }
+}
@@ -1,9 +1,16 @@
-use std::{str::FromStr, time::Instant};
-
-use polkadot_sdk::pallet_revive::{ExecConfig, Pallet, TransactionLimits};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Instant,
+};
+
+use codec::{Decode, Encode};
+use polkadot_sdk::pallet_revive::{tracing::Tracing, ExecConfig, Pallet, TransactionLimits};
+use polkadot_sdk::sp_state_machine::InMemoryBackend;
 use serde::{Deserialize, Serialize};
 
-use crate::*;
+use crate::{abi::Abis, *};
 use alloy_primitives::keccak256;
 #[cfg(feature = "resolc")]
 use alloy_primitives::Address;
@@ -34,6 +41,13 @@ pub enum SpecsAction {
         data: Vec<u8>,
         #[serde(default, with = "hex::serde")]
         salt: OptionalHex<[u8; 32]>,
+        /// The constructor signature, e.g. `constructor(uint256)`, used to encode `args`
+        /// Defaults to the constructor in the contract ABI
+        #[serde(default)]
+        constructor: Option<String>,
+        /// The constructor arguments, encoded in place of `data`
+        #[serde(default)]
+        args: Vec<String>,
     },
     /// Upload contract code without calling the constructor
     Upload {
@@ -57,6 +71,13 @@ pub enum SpecsAction {
         storage_deposit_limit: Option<Balance>,
         #[serde(default, with = "hex::serde")]
         data: Vec<u8>,
+        /// The function signature, e.g. `transfer(address,uint256) returns (bool)`, or
+        /// the function name if the contract ABI is known, used to encode `args`
+        #[serde(default)]
+        call: Option<String>,
+        /// The function arguments, encoded in place of `data`
+        #[serde(default)]
+        args: Vec<String>,
     },
     /// Verify the result of the last call, omitting this will simply ensure the last call was successful
     VerifyCall(VerifyCallExpectation),
@@ -73,6 +94,104 @@ pub enum SpecsAction {
         #[serde(default, with = "hex::serde")]
         expected: [u8; 32],
     },
+    /// Save the current state under `name`, overwriting any previous snapshot of that name
+    Snapshot { name: String },
+    /// Restore the state saved under `name`
+    /// The results of the calls made after the snapshot are kept, so `Instantiated(n)` indices stay valid
+    Revert { name: String },
+    /// Write the full state to the file at `path`
+    DumpState { path: PathBuf },
+    /// Replace the state with the state dumped to the file at `path`
+    LoadState { path: PathBuf },
+    /// Finalize the current block and start `count` new blocks, each `seconds` after its parent
+    AdvanceBlocks {
+        #[serde(default = "one")]
+        count: u64,
+        #[serde(default)]
+        seconds: u64,
+    },
+    /// Set the timestamp of the current block in seconds since the Unix epoch
+    SetTimestamp { seconds: u64 },
+}
+
+fn one() -> u64 {
+    1
+}
+
+impl SpecsAction {
+    /// Instantiate `code` as Alice with the constructor calldata `data`, without value and limits
+    pub fn instantiate(code: Code, data: Vec<u8>) -> Self {
+        Self::Instantiate {
+            origin: TestAddress::Alice,
+            value: 0,
+            gas_limit: None,
+            storage_deposit_limit: None,
+            code,
+            data,
+            salt: OptionalHex::default(),
+            constructor: None,
+            args: vec![],
+        }
+    }
+
+    /// Call `dest` as Alice with the calldata `data`, without value and limits
+    pub fn call(dest: TestAddress, data: Vec<u8>) -> Self {
+        Self::Call {
+            origin: TestAddress::Alice,
+            dest,
+            value: 0,
+            gas_limit: None,
+            storage_deposit_limit: None,
+            data,
+            call: None,
+            args: vec![],
+        }
+    }
+
+    /// Set the value transferred by an `Instantiate` or `Call`, other actions fail
+    pub fn with_value(mut self, amount: Balance) -> Result<Self, String> {
+        match &mut self {
+            Self::Instantiate { value, .. } | Self::Call { value, .. } => *value = amount,
+            action => return Err(format!("{action:?} does not transfer a value")),
+        }
+        Ok(self)
+    }
+
+    /// Set the gas limit of an `Instantiate` or `Call`, other actions fail
+    pub fn with_gas_limit(mut self, limit: Weight) -> Result<Self, String> {
+        match &mut self {
+            Self::Instantiate { gas_limit, .. } | Self::Call { gas_limit, .. } => {
+                *gas_limit = Some(limit)
+            }
+            action => return Err(format!("{action:?} has no gas limit")),
+        }
+        Ok(self)
+    }
+
+    /// Set the storage deposit limit of an `Instantiate` or `Call`, other actions fail
+    pub fn with_storage_deposit_limit(mut self, limit: Balance) -> Result<Self, String> {
+        match &mut self {
+            Self::Instantiate {
+                storage_deposit_limit,
+                ..
+            }
+            | Self::Call {
+                storage_deposit_limit,
+                ..
+            } => *storage_deposit_limit = Some(limit),
+            action => return Err(format!("{action:?} has no storage deposit limit")),
+        }
+        Ok(self)
+    }
+
+    /// Set the salt of an `Instantiate`, other actions fail
+    pub fn with_salt(mut self, bytes: [u8; 32]) -> Result<Self, String> {
+        match &mut self {
+            Self::Instantiate { salt, .. } => *salt = OptionalHex::from(bytes),
+            action => return Err(format!("{action:?} has no salt")),
+        }
+        Ok(self)
+    }
 }
 
 #[cfg(feature = "solidity")]
@@ -94,6 +213,8 @@ impl SpecsAction {
                 gas_consumed: None,
                 success: log.output.run_success(),
                 output: log.output.output.to_vec().into(),
+                values: None,
+                revert_reason: None,
             }),
             Self::VerifyBalance {
                 origin: account_pvm.clone(),
@@ -268,7 +389,11 @@ impl Specs {
                 #[cfg(not(feature = "resolc"))]
                 Code::Bytes(_) => panic!("{NO_SOLIDITY_FRONTEND}"),
                 #[cfg(feature = "resolc")]
-                Code::Solidity { path, .. } if path.is_none() => *path = Some(contract_path.into()),
+                Code::Solidity { path, .. } | Code::SolidityLinked { path, .. }
+                    if path.is_none() =>
+                {
+                    *path = Some(contract_path.into())
+                }
                 _ => continue,
             }
         }
@@ -277,6 +402,34 @@ impl Specs {
     /// Run a contract test
     /// The test takes a [`Specs`] and executes the actions in order
     pub fn run(self) -> Vec<CallResult> {
+        self.run_traced(None)
+    }
+
+    /// Run a contract test like [`Specs::run`], tracing all contract calls with `tracer`
+    pub fn run_with_tracer(self, tracer: &mut (dyn Tracing + 'static)) -> Vec<CallResult> {
+        self.run_traced(Some(tracer))
+    }
+
+    /// Run a contract test like [`Specs::run`], returning each result together with
+    /// the [`CallAbi`] to decode its output or revert reason
+    pub fn run_decoded(
+        self,
+        tracer: Option<&mut (dyn Tracing + 'static)>,
+    ) -> Vec<(CallResult, CallAbi)> {
+        let executor = self.derive().run_on_pallet(tracer);
+        executor
+            .results
+            .into_iter()
+            .zip(executor.call_abis)
+            .collect()
+    }
+
+    fn run_traced(self, tracer: Option<&mut (dyn Tracing + 'static)>) -> Vec<CallResult> {
+        self.derive().run_on_pallet(tracer).results
+    }
+
+    /// Derive the expectations from the EVM if this is a differential test
+    fn derive(self) -> Self {
         if self.differential {
             #[cfg(not(feature = "solidity"))]
             panic!("{NO_SOLIDITY_FRONTEND}");
@@ -285,7 +438,6 @@ impl Specs {
         } else {
             self
         }
-        .run_on_pallet()
     }
 
     #[cfg(feature = "solidity")]
@@ -297,6 +449,8 @@ impl Specs {
 
         let mut evm = Evm::default();
         let mut deployed_accounts = vec![];
+        let mut snapshots = BTreeMap::new();
+        let mut abis = Abis::default();
 
         for action in self.actions {
             derived_specs.actions.push(action.clone());
@@ -310,8 +464,19 @@ impl Specs {
                     code,
                     data,
                     salt,
+                    constructor,
+                    args,
                     ..
                 } => {
+                    let (data, _) = abis
+                        .instantiate(
+                            deployed_accounts.len(),
+                            &code,
+                            constructor.as_deref(),
+                            &args,
+                            data,
+                        )
+                        .unwrap_or_else(|error| panic!("{error}"));
                     assert!(
                         salt.0.is_none(),
                         "salt is not supported in differential mode"
@@ -388,12 +553,19 @@ impl Specs {
                     value,
                     gas_limit,
                     data,
+                    call,
+                    args,
                     ..
                 } => {
-                    assert_eq!(
-                        origin,
-                        TestAddress::default(),
-                        "configuring the origin is not supported in differential mode"
+                    let (data, _) = abis
+                        .call(&dest, call.as_deref(), &args, data)
+                        .unwrap_or_else(|error| panic!("{error}"));
+                    assert!(
+                        matches!(
+                            origin,
+                            TestAddress::Alice | TestAddress::Bob | TestAddress::Charlie
+                        ),
+                        "only the Alice, Bob and Charlie origins are supported in differential mode"
                     );
                     let TestAddress::Instantiated(n) = dest else {
                         panic!("the differential runner requires TestAccountId::Instantiated(n) as dest");
@@ -421,6 +593,21 @@ impl Specs {
                     evm = Evm::from_genesis(log.state_dump.into());
                 }
                 Upload { .. } => continue,
+                Snapshot { name } => {
+                    snapshots.insert(name, evm.clone());
+                }
+                Revert { name } => {
+                    evm = snapshots
+                        .get(&name)
+                        .unwrap_or_else(|| panic!("no snapshot named '{name}'"))
+                        .clone();
+                }
+                DumpState { .. } | LoadState { .. } => {
+                    panic!("dumping and loading the state is not supported in differential mode")
+                }
+                AdvanceBlocks { .. } | SetTimestamp { .. } => {
+                    panic!("changing the block environment is not supported in differential mode")
+                }
                 other => derived_specs.actions.push(other),
             }
         }
@@ -428,116 +615,15 @@ impl Specs {
         derived_specs
     }
 
-    fn run_on_pallet(self) -> Vec<CallResult> {
-        let mut results = vec![];
-
-        ExtBuilder::default()
-            .balance_genesis_config(self.balances.clone())
-            .build()
-            .execute_with(|| {
-                use specs::SpecsAction::*;
-
-                for action in self.actions() {
-                    match action {
-                        Instantiate {
-                            origin,
-                            value,
-                            gas_limit,
-                            storage_deposit_limit,
-                            code,
-                            data,
-                            salt,
-                        } => {
-                            let code: pallet_revive::Code = code.into();
-                            let code_hash = match code.clone() {
-                                pallet_revive::Code::Existing(code_hash) => code_hash,
-                                pallet_revive::Code::Upload(bytes) => {
-                                    H256::from_slice(keccak256(&bytes).as_slice())
-                                }
-                            };
-                            let origin = RuntimeOrigin::signed(origin.to_account_id(&results));
-                            let time_start = Instant::now();
-                            let result = Contracts::bare_instantiate(
-                                origin,
-                                value.into(),
-                                TransactionLimits::WeightAndDeposit {
-                                    weight_limit: gas_limit.unwrap_or(GAS_LIMIT),
-                                    deposit_limit: storage_deposit_limit.unwrap_or(DEPOSIT_LIMIT),
-                                },
-                                code,
-                                data,
-                                salt.0,
-                                &ExecConfig::new_substrate_tx(),
-                            );
-                            results.push(CallResult::Instantiate {
-                                result,
-                                wall_time: time_start.elapsed(),
-                                code_hash,
-                            })
-                        }
-                        Upload {
-                            origin,
-                            code,
-                            storage_deposit_limit,
-                        } => Contracts::upload_code(
-                            RuntimeOrigin::signed(origin.to_account_id(&results)),
-                            match pallet_revive::Code::from(code) {
-                                pallet_revive::Code::Existing(_) => continue,
-                                pallet_revive::Code::Upload(bytes) => bytes,
-                            },
-                            storage_deposit_limit.unwrap_or(DEPOSIT_LIMIT),
-                        )
-                        .unwrap_or_else(|error| panic!("code upload failed: {error:?}")),
-                        Call {
-                            origin,
-                            dest,
-                            value,
-                            gas_limit,
-                            storage_deposit_limit,
-                            data,
-                        } => {
-                            let time_start = Instant::now();
-                            let result = Contracts::bare_call(
-                                RuntimeOrigin::signed(origin.to_account_id(&results)),
-                                dest.to_eth_addr(&results),
-                                value.into(),
-                                TransactionLimits::WeightAndDeposit {
-                                    weight_limit: gas_limit.unwrap_or(GAS_LIMIT),
-                                    deposit_limit: storage_deposit_limit.unwrap_or(DEPOSIT_LIMIT),
-                                },
-                                data,
-                                &ExecConfig::new_substrate_tx(),
-                            );
-                            results.push(CallResult::Exec {
-                                result,
-                                wall_time: time_start.elapsed(),
-                            });
-                        }
-                        VerifyCall(expectation) => {
-                            expectation.verify(results.last().expect("No call to verify"));
-                        }
-                        VerifyBalance { origin, expected } => {
-                            assert_eq!(
-                                Pallet::<Runtime>::evm_balance(&origin.to_eth_addr(&results)),
-                                expected.into()
-                            );
-                        }
-                        VerifyStorage {
-                            contract,
-                            key,
-                            expected,
-                        } => {
-                            let address = contract.to_eth_addr(&results);
-                            let value = Contracts::get_storage(address, key)
-                                .unwrap_or_else(|error| panic!("at {address}: {error:?}"))
-                                .unwrap_or_else(|| vec![0; 32]);
-                            assert_eq!(value, expected, "at {address} key 0x{}", hex::encode(key));
-                        }
-                    }
-                }
-            });
-
-        results
+    fn run_on_pallet<'a>(
+        self,
+        tracer: Option<&'a mut (dyn Tracing + 'static)>,
+    ) -> PalletExecutor<'a> {
+        let mut executor = PalletExecutor::new(self.balances.clone(), tracer);
+        for action in self.actions() {
+            executor.execute(action);
+        }
+        executor
     }
 
     pub fn from_comment(contract_name: &str, path: &str) -> Vec<Self> {
@@ -578,3 +664,325 @@ impl Specs {
         specs
     }
 }
+
+/// Executes [`SpecsAction`]s on pallet-revive one at a time.
+pub(crate) struct PalletExecutor<'a> {
+    ext: sp_io::TestExternalities,
+    /// The results of the instantiations and calls so far
+    pub results: Vec<CallResult>,
+    /// The saved states with the number of results at the time of the snapshot
+    snapshots: BTreeMap<String, (InMemoryBackend<sp_core::Blake2Hasher>, usize)>,
+    pub abis: Abis,
+    /// The ABIs decoding the [`PalletExecutor::results`]
+    pub call_abis: Vec<CallAbi>,
+    tracer: Option<&'a mut (dyn Tracing + 'static)>,
+}
+
+impl<'a> PalletExecutor<'a> {
+    pub fn new(
+        balances: Vec<(H160, Balance)>,
+        tracer: Option<&'a mut (dyn Tracing + 'static)>,
+    ) -> Self {
+        Self {
+            ext: ExtBuilder::default()
+                .balance_genesis_config(balances)
+                .build(),
+            results: vec![],
+            snapshots: BTreeMap::new(),
+            abis: Abis::default(),
+            call_abis: vec![],
+            tracer,
+        }
+    }
+
+    /// Restore the state saved under `name`, returning the number of results at the time
+    /// of the snapshot.
+    fn revert(&mut self, name: &str) -> usize {
+        let (backend, results) = self
+            .snapshots
+            .get(name)
+            .unwrap_or_else(|| panic!("no snapshot named '{name}'"));
+        self.ext.backend = backend.clone();
+        self.ext.reset_overlay();
+        *results
+    }
+
+    /// Restore the state saved under `name` and discard the results of the calls made
+    /// after the snapshot.
+    ///
+    /// Unlike [`SpecsAction::Revert`] this invalidates the `Instantiated(n)` indices of
+    /// contracts instantiated after the snapshot.
+    pub fn rewind(&mut self, name: &str) {
+        let results = self.revert(name);
+        self.results.truncate(results);
+        self.call_abis.truncate(results);
+    }
+
+    /// Execute the `action`, panicking if it is a verification that fails.
+    pub fn execute(&mut self, action: SpecsAction) {
+        use specs::SpecsAction::*;
+
+        match action {
+            Snapshot { name } => {
+                self.snapshots
+                    .insert(name, (self.ext.as_backend(), self.results.len()));
+            }
+            Revert { name } => {
+                self.revert(&name);
+            }
+            DumpState { path } => dump_state(&mut self.ext, &path),
+            LoadState { path } => load_state(&mut self.ext, &path),
+            action => self.ext.execute_with(|| match action {
+                Instantiate {
+                    origin,
+                    value,
+                    gas_limit,
+                    storage_deposit_limit,
+                    code,
+                    data,
+                    salt,
+                    constructor,
+                    args,
+                } => {
+                    let (data, abi) = self
+                        .abis
+                        .instantiate(
+                            self.results.len(),
+                            &code,
+                            constructor.as_deref(),
+                            &args,
+                            data,
+                        )
+                        .unwrap_or_else(|error| panic!("{error}"));
+                    let code: pallet_revive::Code = link(code, &self.results).into();
+                    let code_hash = match code.clone() {
+                        pallet_revive::Code::Existing(code_hash) => code_hash,
+                        pallet_revive::Code::Upload(bytes) => {
+                            H256::from_slice(keccak256(&bytes).as_slice())
+                        }
+                    };
+                    let origin = RuntimeOrigin::signed(origin.to_account_id(&self.results));
+                    let time_start = Instant::now();
+                    let result = with_tracer(&mut self.tracer, || {
+                        Contracts::bare_instantiate(
+                            origin,
+                            value.into(),
+                            TransactionLimits::WeightAndDeposit {
+                                weight_limit: gas_limit.unwrap_or(GAS_LIMIT),
+                                deposit_limit: storage_deposit_limit.unwrap_or(DEPOSIT_LIMIT),
+                            },
+                            code,
+                            data,
+                            salt.0,
+                            &ExecConfig::new_substrate_tx(),
+                        )
+                    });
+                    self.call_abis.push(abi);
+                    self.results.push(CallResult::Instantiate {
+                        result,
+                        wall_time: time_start.elapsed(),
+                        code_hash,
+                    })
+                }
+                Upload {
+                    origin,
+                    code,
+                    storage_deposit_limit,
+                } => Contracts::upload_code(
+                    RuntimeOrigin::signed(origin.to_account_id(&self.results)),
+                    match pallet_revive::Code::from(link(code, &self.results)) {
+                        pallet_revive::Code::Existing(_) => return,
+                        pallet_revive::Code::Upload(bytes) => bytes,
+                    },
+                    storage_deposit_limit.unwrap_or(DEPOSIT_LIMIT),
+                )
+                .unwrap_or_else(|error| panic!("code upload failed: {error:?}")),
+                Call {
+                    origin,
+                    dest,
+                    value,
+                    gas_limit,
+                    storage_deposit_limit,
+                    data,
+                    call,
+                    args,
+                } => {
+                    let (data, abi) = self
+                        .abis
+                        .call(&dest, call.as_deref(), &args, data)
+                        .unwrap_or_else(|error| panic!("{error}"));
+                    let time_start = Instant::now();
+                    let origin = RuntimeOrigin::signed(origin.to_account_id(&self.results));
+                    let dest = dest.to_eth_addr(&self.results);
+                    let result = with_tracer(&mut self.tracer, || {
+                        Contracts::bare_call(
+                            origin,
+                            dest,
+                            value.into(),
+                            TransactionLimits::WeightAndDeposit {
+                                weight_limit: gas_limit.unwrap_or(GAS_LIMIT),
+                                deposit_limit: storage_deposit_limit.unwrap_or(DEPOSIT_LIMIT),
+                            },
+                            data,
+                            &ExecConfig::new_substrate_tx(),
+                        )
+                    });
+                    self.call_abis.push(abi);
+                    self.results.push(CallResult::Exec {
+                        result,
+                        wall_time: time_start.elapsed(),
+                    });
+                }
+                VerifyCall(expectation) => {
+                    expectation.verify(
+                        self.results.last().expect("No call to verify"),
+                        self.call_abis.last().expect("No call to verify"),
+                    );
+                }
+                VerifyBalance { origin, expected } => {
+                    assert_eq!(
+                        Pallet::<Runtime>::evm_balance(&origin.to_eth_addr(&self.results)),
+                        expected.into()
+                    );
+                }
+                VerifyStorage {
+                    contract,
+                    key,
+                    expected,
+                } => {
+                    let address = contract.to_eth_addr(&self.results);
+                    let value = Contracts::get_storage(address, key)
+                        .unwrap_or_else(|error| panic!("at {address}: {error:?}"))
+                        .unwrap_or_else(|| vec![0; 32]);
+                    assert_eq!(value, expected, "at {address} key 0x{}", hex::encode(key));
+                }
+                AdvanceBlocks { count, seconds } => {
+                    for _ in 0..count {
+                        advance_block(seconds);
+                    }
+                }
+                SetTimestamp { seconds } => Timestamp::set_timestamp(milliseconds(seconds)),
+                Snapshot { .. } | Revert { .. } | DumpState { .. } | LoadState { .. } => {
+                    unreachable!()
+                }
+            }),
+        }
+    }
+}
+
+/// Link the deploy time libraries of a [`Code::SolidityLinked`] against the accounts
+/// they were instantiated at, according to the `results` so far.
+#[cfg(feature = "solidity")]
+fn link(code: Code, results: &[CallResult]) -> Code {
+    let (path, solc_optimizer, contract, libraries) = match code {
+        Code::SolidityLinked {
+            path,
+            solc_optimizer,
+            contract,
+            libraries,
+        } => (path, solc_optimizer, contract, libraries),
+        code => return code,
+    };
+    let Some(path) = path else {
+        panic!("Solidity source of contract '{contract}' missing path");
+    };
+    let Ok(source_code) = std::fs::read_to_string(&path) else {
+        panic!("Failed to read source code from {}", path.display());
+    };
+
+    let object = compile_object(&contract, &source_code, solc_optimizer.unwrap_or(true));
+    if object.starts_with(b"PVM") {
+        return Code::Bytes(object);
+    }
+
+    let libraries = libraries
+        .iter()
+        .flat_map(|(file, libraries)| {
+            // The test utilities compile the source at `path` under a fixed file name.
+            let file = if Path::new(file) == path {
+                SOLIDITY_FILE_NAME
+            } else {
+                file
+            };
+            libraries.iter().map(move |(name, account)| {
+                let address = account.to_eth_addr(results);
+                format!("{file}:{name}=0x{}", hex::encode(address))
+            })
+        })
+        .collect::<Vec<_>>();
+    let object_path = format!("{SOLIDITY_FILE_NAME}:{contract}");
+    let output = resolc::linker::Output::try_from(
+        &BTreeMap::from([(object_path.clone(), object)]),
+        &libraries,
+    )
+    .unwrap_or_else(|error| panic!("linking contract '{contract}' failed: {error}"));
+    match output.linked.get(&object_path) {
+        Some(blob) => Code::Bytes(blob.clone()),
+        None => panic!("contract '{contract}' has unresolved libraries after linking"),
+    }
+}
+
+#[cfg(not(feature = "solidity"))]
+fn link(code: Code, _results: &[CallResult]) -> Code {
+    code
+}
+
+/// Execute `f`, tracing it with `tracer` if provided.
+fn with_tracer<R>(tracer: &mut Option<&mut (dyn Tracing + 'static)>, f: impl FnOnce() -> R) -> R {
+    match tracer {
+        Some(tracer) => pallet_revive::tracing::trace(&mut **tracer, f),
+        None => f(),
+    }
+}
+
+/// Finalize the current block and initialize its successor `seconds` later.
+fn advance_block(seconds: u64) {
+    let number = System::block_number();
+    Contracts::on_finalize(number);
+    let parent = System::finalize();
+
+    System::reset_events();
+    System::initialize(&(number + 1), &parent.hash(), &Default::default());
+    Contracts::on_initialize(number + 1);
+    let now = pallet_timestamp::Now::<Runtime>::get();
+    let timestamp = now
+        .checked_add(milliseconds(seconds))
+        .unwrap_or_else(|| panic!("advancing the timestamp {now}ms by {seconds}s overflows"));
+    Timestamp::set_timestamp(timestamp);
+}
+
+/// The block timestamp in milliseconds of `seconds`.
+fn milliseconds(seconds: u64) -> u64 {
+    seconds
+        .checked_mul(1000)
+        .unwrap_or_else(|| panic!("the timestamp of {seconds}s overflows in milliseconds"))
+}
+
+/// The raw trie nodes with their reference counts, and the state root.
+type RawState = (Vec<(Vec<u8>, (Vec<u8>, i32))>, H256);
+
+/// Write the state of `ext` to the file at `path`.
+fn dump_state(ext: &mut sp_io::TestExternalities, path: &Path) {
+    let mut backend = ext.as_backend();
+    let root = *backend.root();
+    let nodes = backend
+        .backend_storage_mut()
+        .drain()
+        .into_iter()
+        .filter(|(_, (_, references))| *references > 0)
+        .collect::<Vec<_>>();
+    std::fs::write(path, (nodes, root).encode())
+        .unwrap_or_else(|error| panic!("failed to dump the state to {}: {error}", path.display()));
+}
+
+/// Replace the state of `ext` with the state dumped to the file at `path`.
+fn load_state(ext: &mut sp_io::TestExternalities, path: &Path) {
+    let encoded = std::fs::read(path).unwrap_or_else(|error| {
+        panic!("failed to read the state from {}: {error}", path.display())
+    });
+    let (nodes, root) = RawState::decode(&mut &encoded[..])
+        .unwrap_or_else(|error| panic!("invalid state dump {}: {error}", path.display()));
+    ext.backend =
+        sp_io::TestExternalities::from_raw_snapshot(nodes, root, ext.state_version).backend;
+    ext.reset_overlay();
+}
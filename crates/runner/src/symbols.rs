@@ -0,0 +1,109 @@
+//! Symbolization of PVM program counters using the blob debug information.
+
+use std::collections::{BTreeMap, HashMap};
+
+use polkadot_sdk::{
+    frame_support::{storage::storage_prefix, traits::PalletInfoAccess},
+    sp_core::H160,
+    *,
+};
+use polkavm_common::program::{ProgramBlob, ProgramCounter};
+
+use crate::runtime::Contracts;
+
+/// Tracks the code executed by the traced contract frames.
+#[derive(Default)]
+pub(crate) struct CodeTracker {
+    /// The code of the next instantiation.
+    pending: Option<Vec<u8>>,
+    /// The code per contract address.
+    code: BTreeMap<H160, Vec<u8>>,
+}
+
+impl CodeTracker {
+    /// Record the code of the next instantiation; the contract is not stored yet.
+    pub fn instantiate_code(&mut self, code: &pallet_revive::Code) {
+        self.pending = match code {
+            pallet_revive::Code::Upload(code) => Some(code.clone()),
+            pallet_revive::Code::Existing(code_hash) => {
+                let mut key =
+                    storage_prefix(Contracts::name().as_bytes(), b"PristineCode").to_vec();
+                key.extend_from_slice(code_hash.as_bytes());
+                frame_support::storage::unhashed::get::<Vec<u8>>(&key)
+            }
+        };
+    }
+
+    /// Record the code of the contract frame executing at `address`.
+    pub fn enter_frame(&mut self, address: H160) {
+        let code = Contracts::code(&address);
+        let code = if code.is_empty() {
+            self.pending.take().unwrap_or_default()
+        } else {
+            code
+        };
+        if !code.is_empty() {
+            self.code.insert(address, code);
+        }
+    }
+
+    /// The code executing at `address`.
+    pub fn get(&self, address: &H160) -> Option<&[u8]> {
+        self.code.get(address).map(Vec::as_slice)
+    }
+}
+
+/// The debug information at a program counter.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Symbol {
+    /// The inlined function frames, outermost first.
+    pub functions: Vec<String>,
+    /// The source file and line of the innermost frame.
+    pub location: Option<(String, u32)>,
+}
+
+/// Resolves program counters into symbols, caching the parsed blobs.
+#[derive(Default)]
+pub(crate) struct Symbolizer {
+    blobs: HashMap<Vec<u8>, Option<ProgramBlob>>,
+}
+
+impl Symbolizer {
+    /// The symbol at `program_counter` of `code`.
+    ///
+    /// Returns an empty symbol if the blob has no debug information.
+    pub fn symbol(&mut self, code: &[u8], program_counter: u32) -> Symbol {
+        let blob = self
+            .blobs
+            .entry(code.to_vec())
+            .or_insert_with(|| ProgramBlob::parse(code.to_vec().into()).ok());
+        let Some(blob) = blob.as_ref() else {
+            return Symbol::default();
+        };
+
+        let program_counter = ProgramCounter(program_counter);
+        let Ok(Some(mut line_program)) = blob.get_debug_line_program_at(program_counter) else {
+            return Symbol::default();
+        };
+        while let Ok(Some(region)) = line_program.run() {
+            if !region.instruction_range().contains(&program_counter) {
+                continue;
+            }
+
+            let mut symbol = Symbol::default();
+            for frame in region.frames() {
+                if let Ok(name) = frame.full_name() {
+                    symbol.functions.push(name.to_string());
+                }
+                if let (Ok(Some(path)), Some(line)) = (frame.path(), frame.line()) {
+                    symbol.location = Some((path.to_string(), line));
+                }
+            }
+            if !symbol.functions.is_empty() {
+                return symbol;
+            }
+        }
+
+        Symbol::default()
+    }
+}
@@ -0,0 +1,208 @@
+//! Weight breakdown per host function.
+//!
+//! The [`WeightReport`] attributes the `ref_time` and `proof_size` weight consumed
+//! by contract calls to the runtime API imports (host functions) they call.
+//! Weight consumed by the PolkaVM interpreter between host function calls is
+//! reported separately under [`PVM_FUEL_NAME`].
+
+use std::collections::BTreeMap;
+
+use pallet_revive::{
+    tracing::{FrameTraceInfo, Tracing, PVM_FUEL_NAME},
+    ExecReturnValue,
+};
+use polkadot_sdk::{
+    sp_core::{H160, U256},
+    sp_runtime::DispatchError,
+};
+use serde::Serialize;
+
+use crate::ecalls::{Ecalls, Exited};
+use crate::*;
+
+/// The weight attributed to a single host function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct HostFunctionWeight {
+    /// The number of calls.
+    pub calls: u64,
+    /// The consumed `ref_time` weight.
+    pub ref_time: u64,
+    /// The consumed `proof_size` weight.
+    pub proof_size: u64,
+}
+
+/// The weight consumed by the PolkaVM interpreter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct InterpreterWeight {
+    /// The consumed `ref_time` weight.
+    pub ref_time: u64,
+    /// The consumed `proof_size` weight.
+    pub proof_size: u64,
+}
+
+/// A [`Tracing`] implementation collecting the weight consumed per host function.
+///
+/// Weight consumed by nested contract calls is attributed to the host functions of
+/// the nested frames, not to the calling host function (e.g. `call_evm`).
+#[derive(Default)]
+pub struct WeightReport {
+    /// The weight per host function name.
+    pub host_functions: BTreeMap<&'static str, HostFunctionWeight>,
+    /// The weight consumed by the interpreter between host function calls.
+    pub interpreter: InterpreterWeight,
+    frames: Vec<Ecalls>,
+}
+
+impl WeightReport {
+    /// The total weight of all host functions and the interpreter.
+    ///
+    /// The interpreter fuel synchronizations are not counted as calls.
+    pub fn total(&self) -> HostFunctionWeight {
+        self.host_functions.values().fold(
+            HostFunctionWeight {
+                calls: 0,
+                ref_time: self.interpreter.ref_time,
+                proof_size: self.interpreter.proof_size,
+            },
+            |total, weight| HostFunctionWeight {
+                calls: total.calls + weight.calls,
+                ref_time: total.ref_time + weight.ref_time,
+                proof_size: total.proof_size + weight.proof_size,
+            },
+        )
+    }
+
+    /// Render the report as a human-readable table, sorted by descending `ref_time`.
+    pub fn table(&self) -> String {
+        let total = self.total();
+        let percentage = |value: u64| match total.ref_time {
+            0 => 0.0,
+            total => value as f64 * 100.0 / total as f64,
+        };
+
+        let mut rows = self.host_functions.iter().collect::<Vec<_>>();
+        rows.sort_by_key(|(_, weight)| std::cmp::Reverse(weight.ref_time));
+
+        let width = rows
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or_default()
+            .max("host function".len());
+        let mut table = format!(
+            "{:<width$} {:>8} {:>16} {:>7} {:>12}\n",
+            "host function", "calls", "ref_time", "%", "proof_size"
+        );
+        let mut row = |name: &str, calls: String, ref_time: u64, proof_size: u64| {
+            table.push_str(&format!(
+                "{:<width$} {:>8} {:>16} {:>6.2}% {:>12}\n",
+                name,
+                calls,
+                ref_time,
+                percentage(ref_time),
+                proof_size
+            ));
+        };
+        for (name, weight) in rows {
+            row(
+                name,
+                weight.calls.to_string(),
+                weight.ref_time,
+                weight.proof_size,
+            );
+        }
+        row(
+            PVM_FUEL_NAME,
+            "-".to_string(),
+            self.interpreter.ref_time,
+            self.interpreter.proof_size,
+        );
+        row(
+            "total",
+            total.calls.to_string(),
+            total.ref_time,
+            total.proof_size,
+        );
+        table
+    }
+
+    /// Render the report as JSON.
+    pub fn json(&self) -> String {
+        #[derive(Serialize)]
+        struct Report<'a> {
+            host_functions: &'a BTreeMap<&'static str, HostFunctionWeight>,
+            #[serde(rename = "pvm_fuel")]
+            interpreter: &'a InterpreterWeight,
+        }
+
+        serde_json::to_string_pretty(&Report {
+            host_functions: &self.host_functions,
+            interpreter: &self.interpreter,
+        })
+        .expect("the report should serialize")
+    }
+
+    fn exit_frame(&mut self, weight_consumed: Weight) {
+        self.frames.pop();
+        if let Some(frame) = self.frames.last_mut() {
+            frame.nested(weight_consumed);
+        }
+    }
+}
+
+impl Tracing for WeightReport {
+    fn enter_child_span(
+        &mut self,
+        _from: H160,
+        _to: H160,
+        _delegate_call: Option<H160>,
+        _is_read_only: bool,
+        _value: U256,
+        _input: &[u8],
+        _gas_limit: u64,
+    ) {
+        self.frames.push(Ecalls::default());
+    }
+
+    fn exit_child_span(&mut self, _output: &ExecReturnValue, _gas_used: u64, weight: Weight) {
+        self.exit_frame(weight);
+    }
+
+    fn exit_child_span_with_error(
+        &mut self,
+        _error: DispatchError,
+        _gas_used: u64,
+        weight: Weight,
+    ) {
+        self.exit_frame(weight);
+    }
+
+    fn enter_ecall(&mut self, name: &'static str, _args: &[u64], trace_info: &dyn FrameTraceInfo) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.enter(name, trace_info.weight_consumed());
+        }
+    }
+
+    fn exit_step(&mut self, trace_info: &dyn FrameTraceInfo, _returned: Option<u64>) {
+        let Some(exited) = self
+            .frames
+            .last_mut()
+            .and_then(|frame| frame.exit(trace_info.weight_consumed()))
+        else {
+            return;
+        };
+
+        match exited {
+            Exited::Fuel(weight) => {
+                self.interpreter.ref_time += weight.ref_time();
+                self.interpreter.proof_size += weight.proof_size();
+            }
+            Exited::HostFunction { name, weight } => {
+                let entry = self.host_functions.entry(name).or_default();
+                entry.calls += 1;
+                entry.ref_time += weight.ref_time();
+                entry.proof_size += weight.proof_size();
+            }
+        }
+    }
+}
@@ -27,12 +27,30 @@ pub use self::standard_json::input::settings::Settings as SolcStandardJsonInputS
 pub use self::standard_json::input::source::Source as SolcStandardJsonInputSource;
 pub use self::standard_json::input::Input as SolcStandardJsonInput;
 pub use self::standard_json::output::contract::evm::bytecode::Bytecode as SolcStandardJsonOutputContractEVMBytecode;
+pub use self::standard_json::output::contract::evm::gas_estimates::CodeDeposit as SolcStandardJsonOutputContractEVMGasEstimatesCodeDeposit;
+pub use self::standard_json::output::contract::evm::gas_estimates::Creation as SolcStandardJsonOutputContractEVMGasEstimatesCreation;
+pub use self::standard_json::output::contract::evm::gas_estimates::GasEstimates as SolcStandardJsonOutputContractEVMGasEstimates;
+pub use self::standard_json::output::contract::evm::gas_estimates::Weight as SolcStandardJsonOutputContractEVMWeight;
+pub use self::standard_json::output::contract::evm::gas_estimates::WeightEstimate as SolcStandardJsonOutputContractEVMWeightEstimate;
 pub use self::standard_json::output::contract::evm::EVM as SolcStandardJsonOutputContractEVM;
+pub use self::standard_json::output::contract::polkavm::annotated_assembly::AnnotatedInstruction as SolcStandardJsonOutputContractPolkaVMAnnotatedInstruction;
+pub use self::standard_json::output::contract::polkavm::annotated_assembly::YulLocation as SolcStandardJsonOutputContractPolkaVMYulLocation;
+#[cfg(feature = "resolc")]
+pub use self::standard_json::output::contract::polkavm::immutable_layout::Declaration as SolcStandardJsonOutputContractPolkaVMImmutableDeclaration;
+pub use self::standard_json::output::contract::polkavm::immutable_layout::Immutable as SolcStandardJsonOutputContractPolkaVMImmutable;
+pub use self::standard_json::output::contract::polkavm::immutable_layout::ImmutableLayout as SolcStandardJsonOutputContractPolkaVMImmutableLayout;
+pub use self::standard_json::output::contract::polkavm::PolkaVM as SolcStandardJsonOutputContractPolkaVM;
 pub use self::standard_json::output::contract::Contract as SolcStandardJsonOutputContract;
 #[cfg(feature = "resolc")]
 pub use self::standard_json::output::error::error_handler::ErrorHandler as SolcStandardJsonOutputErrorHandler;
 pub use self::standard_json::output::error::mapped_location::MappedLocation as SolcStandardJsonOutputErrorMappedLocation;
 pub use self::standard_json::output::error::Error as SolcStandardJsonOutputError;
+#[cfg(feature = "resolc")]
+pub use self::standard_json::output::lint::Lint as ResolcLint;
+#[cfg(feature = "resolc")]
+pub use self::standard_json::output::lint::Registry as ResolcLintRegistry;
+#[cfg(feature = "resolc")]
+pub use self::standard_json::output::lint::Severity as ResolcLintSeverity;
 pub use self::standard_json::output::Output as SolcStandardJsonOutput;
 
 pub mod combined_json;
@@ -9,6 +9,8 @@ pub mod selection;
 #[cfg(feature = "resolc")]
 pub mod warning;
 
+#[cfg(feature = "resolc")]
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 
 use serde::Deserialize;
@@ -21,6 +23,8 @@ use self::polkavm::PolkaVM;
 use self::selection::Selection;
 #[cfg(feature = "resolc")]
 use self::warning::Warning;
+#[cfg(feature = "resolc")]
+use crate::standard_json::output::lint::Severity as LintSeverity;
 
 /// The `solc --standard-json` input settings.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -59,6 +63,11 @@ pub struct Settings {
     #[serde(default, skip_serializing)]
     pub suppressed_warnings: Vec<self::warning::Warning>,
 
+    /// The lint severities by lint ID.
+    #[cfg(feature = "resolc")]
+    #[serde(default, skip_serializing)]
+    pub lints: BTreeMap<String, LintSeverity>,
+
     /// The extra LLVM arguments.
     #[cfg(feature = "resolc")]
     #[serde(default, alias = "LLVMOptions", skip_serializing)]
@@ -95,6 +104,7 @@ impl Settings {
             via_ir: Some(true),
             polkavm,
             suppressed_warnings,
+            lints: BTreeMap::new(),
             llvm_arguments,
             detect_missing_libraries,
         }
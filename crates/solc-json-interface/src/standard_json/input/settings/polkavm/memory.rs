@@ -5,9 +5,15 @@ use serde::{Deserialize, Serialize};
 pub const DEFAULT_HEAP_SIZE: u32 = 128 * 1024;
 pub const DEFAULT_STACK_SIZE: u32 = 128 * 1024;
 
+/// The keyword selecting the automatic memory configuration.
+pub const AUTO_KEYWORD: &str = "auto";
+
 /// The PolkaVM memory configuration.
+///
+/// Besides the object form, the standard JSON input accepts the `"auto"` keyword,
+/// which is a shorthand for `{ "auto": true }`.
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", try_from = "MemoryConfigSetting")]
 pub struct MemoryConfig {
     /// The emulated EVM linear heap memory size in bytes.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -15,6 +21,9 @@ pub struct MemoryConfig {
     /// The PVM stack size in bytes.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stack_size: Option<u32>,
+    /// Size the memory not given explicitly per contract from the static analysis.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto: bool,
 }
 
 impl MemoryConfig {
@@ -23,6 +32,26 @@ impl MemoryConfig {
         Self {
             heap_size,
             stack_size,
+            auto: false,
+        }
+    }
+
+    /// A shorthand constructor for the automatic memory configuration.
+    pub fn auto() -> Self {
+        Self {
+            heap_size: None,
+            stack_size: None,
+            auto: true,
+        }
+    }
+
+    /// Returns the configuration with the default size for every size not given explicitly
+    /// and the automatic sizing disabled.
+    pub fn with_default_sizes(self) -> Self {
+        Self {
+            heap_size: self.heap_size.or(Some(DEFAULT_HEAP_SIZE)),
+            stack_size: self.stack_size.or(Some(DEFAULT_STACK_SIZE)),
+            auto: false,
         }
     }
 }
@@ -32,6 +61,45 @@ impl Default for MemoryConfig {
         Self {
             heap_size: Some(DEFAULT_HEAP_SIZE),
             stack_size: Some(DEFAULT_STACK_SIZE),
+            auto: false,
+        }
+    }
+}
+
+/// The memory configuration as given in the standard JSON input.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MemoryConfigSetting {
+    /// The `"auto"` keyword.
+    Keyword(String),
+    /// The object form.
+    #[serde(rename_all = "camelCase")]
+    Object {
+        heap_size: Option<u32>,
+        stack_size: Option<u32>,
+        #[serde(default)]
+        auto: bool,
+    },
+}
+
+impl TryFrom<MemoryConfigSetting> for MemoryConfig {
+    type Error = String;
+
+    fn try_from(setting: MemoryConfigSetting) -> Result<Self, Self::Error> {
+        match setting {
+            MemoryConfigSetting::Keyword(keyword) if keyword == AUTO_KEYWORD => Ok(Self::auto()),
+            MemoryConfigSetting::Keyword(keyword) => Err(format!(
+                "Invalid memory configuration `{keyword}`, expected `{AUTO_KEYWORD}` or an object."
+            )),
+            MemoryConfigSetting::Object {
+                heap_size,
+                stack_size,
+                auto,
+            } => Ok(Self {
+                heap_size,
+                stack_size,
+                auto,
+            }),
         }
     }
 }
@@ -20,6 +20,9 @@ pub struct PolkaVM {
     /// Route Yul lowering through the experimental newyork IR pipeline. Off by default.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub newyork: Option<bool>,
+    /// Refuse options which make the build depend on the host. Off by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deterministic: Option<bool>,
 }
 
 impl PolkaVM {
@@ -28,6 +31,7 @@ impl PolkaVM {
             memory_config,
             debug_information: Some(debug_information),
             newyork: None,
+            deterministic: None,
         }
     }
 }
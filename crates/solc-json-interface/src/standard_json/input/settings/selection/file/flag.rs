@@ -24,6 +24,9 @@ pub enum Flag {
     /// The storage layout.
     #[serde(rename = "storageLayout")]
     StorageLayout,
+    /// The transient storage layout.
+    #[serde(rename = "transientStorageLayout")]
+    TransientStorageLayout,
     /// The AST JSON.
     #[serde(rename = "ast")]
     AST,
@@ -43,9 +46,30 @@ pub enum Flag {
     /// The assembly code
     #[serde(rename = "evm.assembly")]
     Assembly,
+    /// The PolkaVM weight estimates.
+    #[serde(rename = "evm.gasEstimates")]
+    GasEstimates,
     /// The Ir
     #[serde(rename = "ir")]
     Ir,
+    /// The PolkaVM immutable data layout.
+    #[serde(rename = "polkavm.immutableLayout")]
+    ImmutableLayout,
+    /// The optimized newyork IR.
+    #[serde(rename = "polkavm.newyorkIr")]
+    NewYorkIr,
+    /// The unoptimized LLVM IR.
+    #[serde(rename = "polkavm.llvmIr")]
+    LLVMIr,
+    /// The optimized LLVM IR.
+    #[serde(rename = "polkavm.llvmIrOptimized")]
+    LLVMIrOptimized,
+    /// The PolkaVM assembly code.
+    #[serde(rename = "polkavm.assembly")]
+    PolkaVMAssembly,
+    /// The annotated PolkaVM assembly JSON.
+    #[serde(rename = "polkavm.annotatedAssembly")]
+    AnnotatedAssembly,
 }
 
 impl Flag {
@@ -58,6 +82,7 @@ impl Flag {
             Self::Userdoc,
             Self::MethodIdentifiers,
             Self::StorageLayout,
+            Self::TransientStorageLayout,
             Self::AST,
             Self::Yul,
             Self::EVM,
@@ -65,7 +90,14 @@ impl Flag {
             Self::EVMBC,
             Self::EVMDBC,
             Self::Assembly,
+            Self::GasEstimates,
             Self::Ir,
+            Self::ImmutableLayout,
+            Self::NewYorkIr,
+            Self::LLVMIr,
+            Self::LLVMIrOptimized,
+            Self::PolkaVMAssembly,
+            Self::AnnotatedAssembly,
         ]
     }
 
@@ -77,6 +109,7 @@ impl Flag {
             Self::EVMBC,
             Self::EVMDBC,
             Self::Assembly,
+            Self::GasEstimates,
         ]
     }
 
@@ -97,7 +130,27 @@ impl Flag {
     pub fn is_evm_codegen(&self) -> bool {
         matches!(
             self,
-            Flag::EVMBC | Flag::EVMDBC | Flag::EVMLA | Flag::EVM | Flag::Assembly
+            Flag::EVMBC
+                | Flag::EVMDBC
+                | Flag::EVMLA
+                | Flag::EVM
+                | Flag::Assembly
+                | Flag::GasEstimates
+        )
+    }
+
+    /// Whether this selection flag is specific for the resolc PolkaVM outputs.
+    ///
+    /// These flags are unknown to `solc` and must never be passed to it.
+    pub fn is_polkavm(&self) -> bool {
+        matches!(
+            self,
+            Flag::ImmutableLayout
+                | Flag::NewYorkIr
+                | Flag::LLVMIr
+                | Flag::LLVMIrOptimized
+                | Flag::PolkaVMAssembly
+                | Flag::AnnotatedAssembly
         )
     }
 }
@@ -52,6 +52,7 @@ impl File {
         Self {
             per_file: HashSet::from_iter([SelectionFlag::AST]),
             per_contract: HashSet::from_iter([
+                SelectionFlag::ABI,
                 SelectionFlag::EVMBC,
                 SelectionFlag::EVMDBC,
                 SelectionFlag::MethodIdentifiers,
@@ -125,6 +126,13 @@ impl File {
             SelectionFlag::EVMBC,
             SelectionFlag::EVMDBC,
             SelectionFlag::Assembly,
+            SelectionFlag::GasEstimates,
+            SelectionFlag::ImmutableLayout,
+            SelectionFlag::NewYorkIr,
+            SelectionFlag::LLVMIr,
+            SelectionFlag::LLVMIrOptimized,
+            SelectionFlag::PolkaVMAssembly,
+            SelectionFlag::AnnotatedAssembly,
         ])
     }
 
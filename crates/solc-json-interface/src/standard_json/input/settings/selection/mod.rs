@@ -60,8 +60,10 @@ impl PerFileSelection {
     /// Removes unneeded selections.
     pub fn retain(&mut self) {
         for file in self.files.values_mut() {
-            file.per_contract.retain(|flag| !flag.is_evm_codegen());
-            file.per_file.retain(|flag| !flag.is_evm_codegen());
+            file.per_contract
+                .retain(|flag| !flag.is_evm_codegen() && !flag.is_polkavm());
+            file.per_file
+                .retain(|flag| !flag.is_evm_codegen() && !flag.is_polkavm());
         }
     }
 }
@@ -153,10 +155,24 @@ impl Selection {
             .unwrap_or(self.all.contains(flag))
     }
 
+    /// Checks whether any of the `flags` is requested for any file.
+    pub fn contains_any(&self, flags: &[Flag]) -> bool {
+        self.all.contains_any(flags)
+            || self
+                .files
+                .files
+                .values()
+                .any(|file| file.contains_any(flags))
+    }
+
     /// Removes unneeded selections.
     pub fn retain(&mut self) {
-        self.all.per_file.retain(|flag| !flag.is_evm_codegen());
-        self.all.per_contract.retain(|flag| !flag.is_evm_codegen());
+        self.all
+            .per_file
+            .retain(|flag| !flag.is_evm_codegen() && !flag.is_polkavm());
+        self.all
+            .per_contract
+            .retain(|flag| !flag.is_evm_codegen() && !flag.is_polkavm());
         self.files.retain();
     }
 }
@@ -3,16 +3,11 @@
 //! The revive compiler adds warnings only applicable when compilng
 //! to the revive stack on Polkadot to the output.
 
-use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use serde::Deserialize;
 use serde::Serialize;
 
-use crate::standard_json::output::error::source_location::SourceLocation;
-use crate::SolcStandardJsonInputSource;
-use crate::SolcStandardJsonOutputError;
-
 // The `resolc` custom compiler warning.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Warning {
@@ -32,7 +27,7 @@ impl Warning {
     }
 
     /// The displayed warning messages.
-    pub fn as_message(&self) -> &'static str {
+    pub const fn as_message(&self) -> &'static str {
         match self {
             Self::SendAndTransfer => {
                 r#"
@@ -57,17 +52,12 @@ to rely on tx.origin, but use msg.sender instead.
         }
     }
 
-    pub fn as_error(
-        &self,
-        node: Option<&str>,
-        id_paths: &BTreeMap<usize, &String>,
-        sources: &BTreeMap<String, SolcStandardJsonInputSource>,
-    ) -> SolcStandardJsonOutputError {
-        SolcStandardJsonOutputError::new_warning(
-            self.as_message(),
-            node.and_then(|node| SourceLocation::try_from_ast(node, id_paths)),
-            Some(sources),
-        )
+    /// The ID of the lint emitting the warning.
+    pub fn lint_id(&self) -> &'static str {
+        match self {
+            Self::SendAndTransfer => "send-transfer",
+            Self::TxOrigin => "tx-origin",
+        }
     }
 }
 
@@ -0,0 +1,145 @@
+//! The `solc --standard-json` output contract EVM gas estimates.
+//!
+//! PolkaVM contracts are charged weight instead of gas, so the estimates are
+//! `ref_time` and `proof_size` upper bounds.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+/// The `solc --standard-json` output contract EVM gas estimates.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GasEstimates {
+    /// The contract deployment estimates.
+    pub creation: Creation,
+    /// The external function estimates by signature.
+    #[serde(default)]
+    pub external: BTreeMap<String, WeightEstimate>,
+}
+
+/// The contract deployment estimates.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Creation {
+    /// The storage the code deposit is charged for.
+    pub code_deposit: CodeDeposit,
+    /// The constructor execution weight.
+    pub execution_cost: WeightEstimate,
+    /// The weight of the deployment transaction, including the code upload.
+    pub total_cost: WeightEstimate,
+}
+
+/// The storage the code deposit is charged for.
+///
+/// The deposit per byte and per item are runtime specific.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeDeposit {
+    /// The number of bytes.
+    pub bytes: u64,
+    /// The number of storage items.
+    pub items: u64,
+}
+
+/// The weight.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Weight {
+    /// The computation time in picoseconds.
+    pub ref_time: u64,
+    /// The storage proof size in bytes.
+    pub proof_size: u64,
+}
+
+impl Weight {
+    /// A shortcut constructor.
+    pub const fn new(ref_time: u64, proof_size: u64) -> Self {
+        Self {
+            ref_time,
+            proof_size,
+        }
+    }
+
+    /// Adds the `other` weight.
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self {
+            ref_time: self.ref_time.saturating_add(other.ref_time),
+            proof_size: self.proof_size.saturating_add(other.proof_size),
+        }
+    }
+
+    /// Multiplies the weight by `factor`.
+    pub fn saturating_mul(self, factor: u64) -> Self {
+        Self {
+            ref_time: self.ref_time.saturating_mul(factor),
+            proof_size: self.proof_size.saturating_mul(factor),
+        }
+    }
+}
+
+/// The weight estimate, written as `"infinite"` if it can't be bounded statically.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WeightEstimate {
+    /// The weight upper bound.
+    Bounded(Weight),
+    /// The weight depends on the input, storage or called contracts.
+    #[default]
+    Infinite,
+}
+
+impl WeightEstimate {
+    /// The `Infinite` representation.
+    pub const INFINITE: &'static str = "infinite";
+
+    /// Adds the `weight` to a bounded estimate.
+    pub fn saturating_add(self, weight: Weight) -> Self {
+        match self {
+            Self::Bounded(bound) => Self::Bounded(bound.saturating_add(weight)),
+            Self::Infinite => Self::Infinite,
+        }
+    }
+}
+
+impl From<Option<Weight>> for WeightEstimate {
+    fn from(weight: Option<Weight>) -> Self {
+        weight.map_or(Self::Infinite, Self::Bounded)
+    }
+}
+
+impl Serialize for WeightEstimate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Bounded(weight) => weight.serialize(serializer),
+            Self::Infinite => serializer.serialize_str(Self::INFINITE),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WeightEstimate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Representation {
+            Bounded(Weight),
+            Infinite(String),
+        }
+
+        match Representation::deserialize(deserializer)? {
+            Representation::Bounded(weight) => Ok(Self::Bounded(weight)),
+            Representation::Infinite(value) if value == Self::INFINITE => Ok(Self::Infinite),
+            Representation::Infinite(value) => Err(serde::de::Error::custom(format!(
+                "invalid weight estimate `{value}`"
+            ))),
+        }
+    }
+}
@@ -7,8 +7,10 @@ use serde::Serialize;
 
 use self::bytecode::Bytecode;
 use self::bytecode::DeployedBytecode;
+use self::gas_estimates::GasEstimates;
 
 pub mod bytecode;
+pub mod gas_estimates;
 
 /// The `solc --standard-json` output contract EVM data.
 /// It is replaced by PolkaVM data after compiling.
@@ -30,6 +32,9 @@ pub struct EVM {
     /// The contract function signatures.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub method_identifiers: BTreeMap<String, String>,
+    /// The PolkaVM weight estimates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_estimates: Option<GasEstimates>,
 }
 
 impl EVM {
@@ -1,6 +1,7 @@
 //! The `solc --standard-json` output contract.
 
 pub mod evm;
+pub mod polkavm;
 
 #[cfg(feature = "resolc")]
 use std::collections::BTreeMap;
@@ -14,6 +15,7 @@ use serde::Serialize;
 use crate::SolcStandardJsonInputSettingsSelectionFileFlag;
 
 use self::evm::EVM;
+use self::polkavm::PolkaVM;
 
 /// The `solc --standard-json` output contract.
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -34,12 +36,15 @@ pub struct Contract {
     /// The contract storage layout.
     #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
     pub storage_layout: serde_json::Value,
-    /// The contract storage layout.
+    /// The contract transient storage layout.
     #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
     pub transient_storage_layout: serde_json::Value,
     /// Contract's bytecode and related objects
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub evm: Option<EVM>,
+    /// The PolkaVM specific outputs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub polkavm: Option<PolkaVM>,
     /// The contract IR code.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ir: Option<String>,
@@ -79,6 +84,7 @@ impl Contract {
             && self.userdoc.is_null()
             && self.ir_optimized.is_empty()
             && self.evm.is_none()
+            && self.polkavm.is_none()
             && self.hash.is_none()
             && self.factory_dependencies_unlinked.is_empty()
             && self.factory_dependencies.is_empty()
@@ -104,6 +110,39 @@ impl Contract {
             SolcStandardJsonInputSettingsSelectionFileFlag::StorageLayout => {
                 self.storage_layout = Default::default();
             }
+            SolcStandardJsonInputSettingsSelectionFileFlag::TransientStorageLayout => {
+                self.transient_storage_layout = Default::default();
+            }
+            SolcStandardJsonInputSettingsSelectionFileFlag::ImmutableLayout => {
+                if let Some(polkavm) = self.polkavm.as_mut() {
+                    polkavm.immutable_layout = Default::default();
+                }
+            }
+            SolcStandardJsonInputSettingsSelectionFileFlag::NewYorkIr => {
+                if let Some(polkavm) = self.polkavm.as_mut() {
+                    polkavm.newyork_ir = Default::default();
+                }
+            }
+            SolcStandardJsonInputSettingsSelectionFileFlag::LLVMIr => {
+                if let Some(polkavm) = self.polkavm.as_mut() {
+                    polkavm.llvm_ir = Default::default();
+                }
+            }
+            SolcStandardJsonInputSettingsSelectionFileFlag::LLVMIrOptimized => {
+                if let Some(polkavm) = self.polkavm.as_mut() {
+                    polkavm.llvm_ir_optimized = Default::default();
+                }
+            }
+            SolcStandardJsonInputSettingsSelectionFileFlag::PolkaVMAssembly => {
+                if let Some(polkavm) = self.polkavm.as_mut() {
+                    polkavm.assembly = Default::default();
+                }
+            }
+            SolcStandardJsonInputSettingsSelectionFileFlag::AnnotatedAssembly => {
+                if let Some(polkavm) = self.polkavm.as_mut() {
+                    polkavm.annotated_assembly = Default::default();
+                }
+            }
             SolcStandardJsonInputSettingsSelectionFileFlag::Yul => {
                 self.ir_optimized = Default::default();
             }
@@ -128,6 +167,11 @@ impl Contract {
                     evm.assembly_text = Default::default();
                 }
             }
+            SolcStandardJsonInputSettingsSelectionFileFlag::GasEstimates => {
+                if let Some(evm) = self.evm.as_mut() {
+                    evm.gas_estimates = Default::default();
+                }
+            }
             SolcStandardJsonInputSettingsSelectionFileFlag::MethodIdentifiers => {
                 if let Some(evm) = self.evm.as_mut() {
                     evm.method_identifiers = Default::default();
@@ -139,5 +183,9 @@ impl Contract {
                 // as they have no contract field mappings.
             }
         }
+
+        if self.polkavm.as_ref().is_some_and(PolkaVM::is_empty) {
+            self.polkavm = None;
+        }
     }
 }
@@ -0,0 +1,38 @@
+//! The `solc --standard-json` output contract annotated PolkaVM assembly.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::standard_json::output::error::source_location::SourceLocation;
+
+/// The PolkaVM instruction annotated with the code it was generated from.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotatedInstruction {
+    /// The program counter.
+    pub offset: u32,
+    /// The instruction assembly.
+    pub instruction: String,
+    /// The innermost function the instruction belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// The newyork pass the outlined helper function was produced for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pass: Option<String>,
+    /// The Yul code location.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yul_location: Option<YulLocation>,
+    /// The Solidity source code range.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_location: Option<SourceLocation>,
+}
+
+/// The Yul code location.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct YulLocation {
+    /// The line number, starting from 1.
+    pub line: u32,
+    /// The column number, starting from 1.
+    pub column: u32,
+}
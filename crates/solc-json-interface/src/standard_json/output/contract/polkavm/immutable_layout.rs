@@ -0,0 +1,178 @@
+//! The `solc --standard-json` output contract immutable layout.
+//!
+//! PolkaVM contracts keep their immutable values in the immutable data of the
+//! contract instance rather than in the code. The layout tells where to find
+//! each value in the `get_immutable_data` output.
+
+#[cfg(feature = "resolc")]
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+#[cfg(feature = "resolc")]
+use crate::standard_json::output::source::Source;
+
+/// The `solc --standard-json` output contract immutable layout.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImmutableLayout {
+    /// The immutable values, ordered by offset.
+    pub immutables: Vec<Immutable>,
+    /// The immutable data size in bytes.
+    pub size: u64,
+}
+
+/// The immutable value location in the immutable data.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Immutable {
+    /// The variable declaration AST node ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ast_id: Option<usize>,
+    /// The declaring contract full path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract: Option<String>,
+    /// The variable name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The Yul immutable identifier.
+    pub key: String,
+    /// The offset in the immutable data in bytes.
+    pub offset: u64,
+    /// The value size in bytes.
+    pub size: u64,
+    /// The variable type.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+}
+
+#[cfg(feature = "resolc")]
+impl ImmutableLayout {
+    /// Initializes the layout from the Yul identifier to offset mapping.
+    ///
+    /// The identifiers generated by `solc` are the variable declaration AST node IDs,
+    /// which are resolved using the `declarations`. Other identifiers, like the library
+    /// deploy address, are left unresolved.
+    pub fn new(
+        offsets: &BTreeMap<String, usize>,
+        declarations: &BTreeMap<usize, Declaration>,
+    ) -> Self {
+        let mut immutables = offsets
+            .iter()
+            .map(|(key, offset)| {
+                let ast_id = key.parse::<usize>().ok();
+                let declaration = ast_id.and_then(|ast_id| declarations.get(&ast_id));
+                Immutable {
+                    ast_id,
+                    contract: declaration.map(|declaration| declaration.contract.to_owned()),
+                    label: declaration.map(|declaration| declaration.label.to_owned()),
+                    key: key.to_owned(),
+                    offset: *offset as u64,
+                    size: revive_common::BYTE_LENGTH_WORD as u64,
+                    r#type: declaration.map(|declaration| declaration.r#type.to_owned()),
+                }
+            })
+            .collect::<Vec<Immutable>>();
+        immutables.sort_by_key(|immutable| immutable.offset);
+
+        Self {
+            size: (immutables.len() * revive_common::BYTE_LENGTH_WORD) as u64,
+            immutables,
+        }
+    }
+}
+
+/// The immutable variable declaration found in the AST.
+#[cfg(feature = "resolc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Declaration {
+    /// The declaring contract full path.
+    pub contract: String,
+    /// The variable name.
+    pub label: String,
+    /// The variable type.
+    pub r#type: String,
+}
+
+#[cfg(feature = "resolc")]
+impl Declaration {
+    /// Collects the immutable variable declarations by AST node ID from the `sources` ASTs.
+    pub fn collect(sources: &BTreeMap<String, Source>) -> BTreeMap<usize, Self> {
+        let mut declarations = BTreeMap::new();
+        for (path, source) in sources.iter() {
+            let mut contracts = BTreeMap::new();
+            let mut variables = Vec::new();
+            Self::visit(&source.ast, &mut contracts, &mut variables);
+
+            for (id, scope, label, r#type) in variables {
+                let Some(contract) = contracts.get(&scope) else {
+                    continue;
+                };
+                declarations.insert(
+                    id,
+                    Self {
+                        contract: format!("{path}:{contract}"),
+                        label,
+                        r#type,
+                    },
+                );
+            }
+        }
+        declarations
+    }
+
+    /// Collects the contract names and immutable variables of the `ast` by node ID.
+    fn visit(
+        ast: &serde_json::Value,
+        contracts: &mut BTreeMap<usize, String>,
+        variables: &mut Vec<(usize, usize, String, String)>,
+    ) {
+        match ast {
+            serde_json::Value::Array(array) => {
+                for element in array.iter() {
+                    Self::visit(element, contracts, variables);
+                }
+            }
+            serde_json::Value::Object(object) => {
+                let id = object
+                    .get("id")
+                    .and_then(serde_json::Value::as_u64)
+                    .map(|id| id as usize);
+                let name = object.get("name").and_then(serde_json::Value::as_str);
+                match (
+                    object.get("nodeType").and_then(serde_json::Value::as_str),
+                    id,
+                    name,
+                ) {
+                    (Some("ContractDefinition"), Some(id), Some(name)) => {
+                        contracts.insert(id, name.to_owned());
+                    }
+                    (Some("VariableDeclaration"), Some(id), Some(name))
+                        if object.get("mutability").and_then(serde_json::Value::as_str)
+                            == Some("immutable") =>
+                    {
+                        let scope = object.get("scope").and_then(serde_json::Value::as_u64);
+                        let r#type = object
+                            .get("typeDescriptions")
+                            .and_then(|descriptions| descriptions.get("typeString"))
+                            .and_then(serde_json::Value::as_str);
+                        if let (Some(scope), Some(r#type)) = (scope, r#type) {
+                            variables.push((
+                                id,
+                                scope as usize,
+                                name.to_owned(),
+                                r#type.to_owned(),
+                            ));
+                        }
+                    }
+                    _ => {}
+                }
+                for value in object.values() {
+                    Self::visit(value, contracts, variables);
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! The `solc --standard-json` output contract PolkaVM data.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use self::annotated_assembly::AnnotatedInstruction;
+use self::immutable_layout::ImmutableLayout;
+
+pub mod annotated_assembly;
+pub mod immutable_layout;
+
+/// The `solc --standard-json` output contract PolkaVM data.
+/// Contains the outputs without an EVM equivalent.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PolkaVM {
+    /// The layout of the immutable values in the contract immutable data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub immutable_layout: Option<ImmutableLayout>,
+    /// The optimized newyork IR.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub newyork_ir: Option<String>,
+    /// The unoptimized LLVM IR.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llvm_ir: Option<String>,
+    /// The optimized LLVM IR.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llvm_ir_optimized: Option<String>,
+    /// The PolkaVM assembly code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assembly: Option<String>,
+    /// The PolkaVM assembly code annotated with the code it was generated from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotated_assembly: Option<Vec<AnnotatedInstruction>>,
+}
+
+impl PolkaVM {
+    /// Checks if all fields are unset.
+    pub fn is_empty(&self) -> bool {
+        self.immutable_layout.is_none()
+            && self.newyork_ir.is_none()
+            && self.llvm_ir.is_none()
+            && self.llvm_ir_optimized.is_none()
+            && self.assembly.is_none()
+            && self.annotated_assembly.is_none()
+    }
+}
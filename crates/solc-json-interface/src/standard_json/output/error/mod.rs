@@ -2,6 +2,9 @@
 
 use std::collections::BTreeMap;
 
+use revive_common::CodedError;
+use revive_common::ErrorCode;
+use revive_common::LocatedError;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -49,7 +52,6 @@ impl Error {
         S: std::fmt::Display,
     {
         let message = message.to_string();
-
         let message_trimmed = message.trim();
         let mut formatted_message = if message_trimmed.starts_with(r#type) {
             message_trimmed.to_owned()
@@ -80,6 +82,21 @@ impl Error {
         }
     }
 
+    /// Sets the resolc `error_code`, pointing to its explanation in the formatted message.
+    pub fn with_code(mut self, error_code: ErrorCode) -> Self {
+        if self.error_code.as_deref() != Some(error_code.code()) {
+            self.formatted_message.push_str(
+                format!(
+                    "For more information about this error, try `resolc --explain {}`.\n",
+                    error_code.code()
+                )
+                .as_str(),
+            );
+            self.error_code = Some(error_code.code().to_owned());
+        }
+        self
+    }
+
     /// A shortcut constructor.
     pub fn new_error<S>(
         message: S,
@@ -92,6 +109,26 @@ impl Error {
         Self::new("Error", message, source_location, sources)
     }
 
+    /// Converts the compiler `error` into an error message, with the resolc error code
+    /// carried by the `error`.
+    ///
+    /// The source location the `error` is located at replaces the `source_location`.
+    pub fn from_error(
+        error: anyhow::Error,
+        source_location: Option<SourceLocation>,
+        sources: Option<&BTreeMap<String, SolcStandardJsonInputSource>>,
+    ) -> Self {
+        let error_code = CodedError::code_of(&error);
+        let source_location = LocatedError::<SourceLocation>::location_of(&error)
+            .cloned()
+            .or(source_location);
+        let message = Self::new_error(error, source_location, sources);
+        match error_code {
+            Some(error_code) => message.with_code(error_code),
+            None => message,
+        }
+    }
+
     /// A shortcut constructor.
     pub fn new_warning<S>(
         message: S,
@@ -164,6 +201,7 @@ Please consider changing the functionality relying on reading runtime code to a
             node.and_then(|node| SourceLocation::try_from_ast(node, id_paths)),
             Some(sources),
         )
+        .with_code(ErrorCode::RuntimeCode)
     }
 
     /// Appends the contract path to the message..
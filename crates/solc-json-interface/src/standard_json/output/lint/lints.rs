@@ -0,0 +1,430 @@
+//! The built-in `resolc` lints.
+
+use revive_common::ErrorCode;
+
+use crate::ResolcWarning;
+
+use super::Lint;
+use super::Severity;
+
+/// The AST node object.
+type Node = serde_json::Map<String, serde_json::Value>;
+
+/// The built-in lints.
+pub const ALL: [Lint; 10] = [
+    SEND_TRANSFER,
+    TX_ORIGIN,
+    RUNTIME_CODE,
+    GASLEFT,
+    CALL_GAS_STIPEND,
+    SELFDESTRUCT,
+    EXTCODESIZE,
+    BLOCKHASH,
+    CREATE_INLINE_BYTECODE,
+    THIS_CODE,
+];
+
+/// The `<address payable>`'s `send` and `transfer` methods usage.
+pub const SEND_TRANSFER: Lint = Lint {
+    id: "send-transfer",
+    default_severity: Severity::Warning,
+    configurable: true,
+    message: ResolcWarning::SendAndTransfer.as_message(),
+    error_code: None,
+    check: check_send_transfer,
+};
+
+/// The `tx.origin` value usage.
+pub const TX_ORIGIN: Lint = Lint {
+    id: "tx-origin",
+    default_severity: Severity::Warning,
+    configurable: true,
+    message: ResolcWarning::TxOrigin.as_message(),
+    error_code: None,
+    check: check_tx_origin,
+};
+
+/// The `type(T).runtimeCode` usage, which is not supported.
+pub const RUNTIME_CODE: Lint = Lint {
+    id: "runtime-code",
+    default_severity: Severity::Error,
+    configurable: false,
+    message: r#"
+Deploy and runtime code are merged in PVM, accessing `type(T).runtimeCode` is not possible.
+Please consider changing the functionality relying on reading runtime code to a different approach.
+"#,
+    error_code: Some(ErrorCode::RuntimeCode),
+    check: check_runtime_code,
+};
+
+/// The arithmetic and comparisons on `gasleft()` and Yul `gas()`.
+///
+/// Forwarding the remaining gas, like in `call(gas(), ...)`, is not a finding.
+pub const GASLEFT: Lint = Lint {
+    id: "gasleft",
+    default_severity: Severity::Warning,
+    configurable: true,
+    message: r#"
+You are computing with the remaining gas. PolkaVM meters execution in weight and storage deposits,
+hence the returned value is not comparable to the remaining gas on the EVM. Avoid gas amounts
+tuned for the EVM and basing the control flow on the remaining gas.
+"#,
+    error_code: None,
+    check: check_gasleft,
+};
+
+/// The calls with an explicit gas limit.
+pub const CALL_GAS_STIPEND: Lint = Lint {
+    id: "call-gas-stipend",
+    default_severity: Severity::Warning,
+    configurable: true,
+    message: r#"
+You are limiting the gas supplied to a call. Gas limits of calls are translated to weight limits
+on PolkaVM, hence limits tuned for the EVM, like the 2300 gas stipend, may be too low or too high.
+"#,
+    error_code: None,
+    check: check_call_gas_stipend,
+};
+
+/// The `selfdestruct` usage.
+pub const SELFDESTRUCT: Lint = Lint {
+    id: "selfdestruct",
+    default_severity: Severity::Warning,
+    configurable: true,
+    message: r#"
+You are using 'selfdestruct'. Like on the EVM after the Cancun upgrade, the contract code and
+storage are only removed if the contract was created in the same transaction.
+"#,
+    error_code: None,
+    check: check_selfdestruct,
+};
+
+/// The code size checks of other accounts.
+pub const EXTCODESIZE: Lint = Lint {
+    id: "extcodesize",
+    default_severity: Severity::Warning,
+    configurable: true,
+    message: r#"
+You are checking the code size of an account, which is often used to tell externally owned
+accounts from contracts. Contracts under construction have no code and Polkadot comes with native
+account abstraction support, hence the check is not reliable.
+"#,
+    error_code: None,
+    check: check_extcodesize,
+};
+
+/// The `blockhash` calls for blocks statically known to be outside of the last 256 blocks,
+/// like the current block.
+pub const BLOCKHASH: Lint = Lint {
+    id: "blockhash",
+    default_severity: Severity::Warning,
+    configurable: true,
+    message: r#"
+You are requesting the hash of a block outside of the last 256 blocks, which is zero.
+"#,
+    error_code: None,
+    check: check_blockhash,
+};
+
+/// The Yul `create` and `create2` usage with inline bytecode.
+///
+/// Bytecode written inline, like the EIP-1167 minimal proxy, has a literal size. Creating
+/// contracts from `type(C).creationCode`, which has a dynamic size, is not a finding.
+pub const CREATE_INLINE_BYTECODE: Lint = Lint {
+    id: "create-inline-bytecode",
+    default_severity: Severity::Warning,
+    configurable: true,
+    message: r#"
+You are creating a contract from bytecode written in inline assembly. PolkaVM deploys contracts
+by the hash of code uploaded beforehand, hence creating contracts from EVM bytecode is not possible.
+Use 'new C()' or create the contract from 'type(C).creationCode'.
+"#,
+    error_code: None,
+    check: check_create_inline_bytecode,
+};
+
+/// The `address(this).code` usage.
+pub const THIS_CODE: Lint = Lint {
+    id: "this-code",
+    default_severity: Severity::Warning,
+    configurable: true,
+    message: r#"
+You are reading 'address(this).code'. Deploy and runtime code are merged in PVM, hence the
+value is the PVM blob and not EVM bytecode.
+"#,
+    error_code: None,
+    check: check_this_code,
+};
+
+/// Returns the `nodeType` of the `node`.
+fn node_type(node: &Node) -> Option<&str> {
+    node.get("nodeType")?.as_str()
+}
+
+/// Returns the `field` of the `node` as a node object.
+fn child<'a>(node: &'a Node, field: &str) -> Option<&'a Node> {
+    node.get(field)?.as_object()
+}
+
+/// Returns the name of the called Solidity identifier, if the `node` is a function call.
+fn called_identifier(node: &Node) -> Option<&str> {
+    (node_type(node)? == "FunctionCall").then_some(())?;
+    let expression = child(node, "expression")?;
+    (node_type(expression)? == "Identifier").then_some(())?;
+    expression.get("name")?.as_str()
+}
+
+/// Returns the name of the called Yul function, if the `node` is a Yul function call.
+fn called_yul_function(node: &Node) -> Option<&str> {
+    (node_type(node)? == "YulFunctionCall").then_some(())?;
+    child(node, "functionName")?.get("name")?.as_str()
+}
+
+/// Whether the `node` is a call to the Solidity `gasleft()` or the Yul `gas()`.
+fn is_gasleft(node: &Node) -> bool {
+    called_identifier(node) == Some("gasleft") || called_yul_function(node) == Some("gas")
+}
+
+/// Returns the `node` without enclosing parentheses.
+fn unparenthesized(mut node: &Node) -> Option<&Node> {
+    while node_type(node)? == "TupleExpression" {
+        let [component] = node.get("components")?.as_array()?.as_slice() else {
+            return None;
+        };
+        node = component.as_object()?;
+    }
+    Some(node)
+}
+
+/// Returns the value of the number literal `node`, Solidity or Yul.
+fn number_literal(node: &Node) -> Option<u64> {
+    match node_type(node)? {
+        "Literal" => {
+            (node.get("kind")?.as_str()? == "number").then_some(())?;
+            node.get("subdenomination")
+                .is_none_or(serde_json::Value::is_null)
+                .then_some(())?;
+        }
+        "YulLiteral" => (node.get("kind")?.as_str()? == "number").then_some(())?,
+        _ => return None,
+    }
+    let value = node.get("value")?.as_str()?.replace('_', "");
+    match value.strip_prefix("0x") {
+        Some(hexadecimal) => u64::from_str_radix(hexadecimal, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// Whether the `node` is the Solidity `block.number` or the Yul `number()`.
+fn is_block_number(node: &Node) -> bool {
+    if called_yul_function(node) == Some("number") {
+        return true;
+    }
+
+    (|| {
+        (node_type(node)? == "MemberAccess").then_some(())?;
+        (node.get("memberName")?.as_str()? == "number").then_some(())?;
+        let expression = child(node, "expression")?;
+        (node_type(expression)? == "Identifier").then_some(())?;
+        (expression.get("name")?.as_str()? == "block").then_some(())
+    })()
+    .is_some()
+}
+
+/// Returns the operator and the operands of the Solidity binary operation or the
+/// Yul function call with two arguments `node`.
+fn binary_operation(node: &Node) -> Option<(&str, &Node, &Node)> {
+    if node_type(node)? == "BinaryOperation" {
+        return Some((
+            node.get("operator")?.as_str()?,
+            unparenthesized(child(node, "leftExpression")?)?,
+            unparenthesized(child(node, "rightExpression")?)?,
+        ));
+    }
+
+    let name = called_yul_function(node)?;
+    let [left, right] = node.get("arguments")?.as_array()?.as_slice() else {
+        return None;
+    };
+    Some((name, left.as_object()?, right.as_object()?))
+}
+
+/// Whether the `node` is `address(this)`.
+fn is_address_this(node: &Node) -> bool {
+    (|| {
+        (node_type(node)? == "FunctionCall").then_some(())?;
+        (node.get("kind")?.as_str()? == "typeConversion").then_some(())?;
+        let arguments = node.get("arguments")?.as_array()?;
+        let [argument] = arguments.as_slice() else {
+            return None;
+        };
+        let argument = argument.as_object()?;
+        (node_type(argument)? == "Identifier").then_some(())?;
+        (argument.get("name")?.as_str()? == "this").then_some(())
+    })()
+    .is_some()
+}
+
+fn check_send_transfer(node: &Node) -> bool {
+    (|| {
+        (node_type(node)? == "FunctionCall").then_some(())?;
+
+        let expression = child(node, "expression")?;
+        (node_type(expression)? == "MemberAccess").then_some(())?;
+        let member_name = expression.get("memberName")?.as_str()?;
+        ["send", "transfer"].contains(&member_name).then_some(())?;
+
+        let expression = child(expression, "expression")?;
+        let type_descriptions = child(expression, "typeDescriptions")?;
+        (type_descriptions.get("typeIdentifier")?.as_str()? == "t_address_payable").then_some(())
+    })()
+    .is_some()
+}
+
+fn check_tx_origin(node: &Node) -> bool {
+    (|| {
+        (node_type(node)? == "MemberAccess").then_some(())?;
+        (node.get("memberName")?.as_str()? == "origin").then_some(())?;
+
+        let expression = child(node, "expression")?;
+        (node_type(expression)? == "Identifier").then_some(())?;
+        (expression.get("name")?.as_str()? == "tx").then_some(())
+    })()
+    .is_some()
+}
+
+fn check_runtime_code(node: &Node) -> bool {
+    (|| {
+        (node_type(node)? == "MemberAccess").then_some(())?;
+        (node.get("memberName")?.as_str()? == "runtimeCode").then_some(())?;
+
+        let expression = child(node, "expression")?;
+        let type_descriptions = child(expression, "typeDescriptions")?;
+        type_descriptions
+            .get("typeIdentifier")?
+            .as_str()?
+            .starts_with("t_magic_meta_type")
+            .then_some(())
+    })()
+    .is_some()
+}
+
+fn check_gasleft(node: &Node) -> bool {
+    if node_type(node) == Some("Assignment") {
+        return node.get("operator").and_then(serde_json::Value::as_str) != Some("=")
+            && child(node, "rightHandSide")
+                .and_then(unparenthesized)
+                .is_some_and(is_gasleft);
+    }
+
+    binary_operation(node).is_some_and(|(operator, left, right)| {
+        let arithmetic = matches!(
+            operator,
+            "+" | "-"
+                | "*"
+                | "/"
+                | "%"
+                | "<"
+                | ">"
+                | "<="
+                | ">="
+                | "=="
+                | "!="
+                | "add"
+                | "sub"
+                | "mul"
+                | "div"
+                | "mod"
+                | "lt"
+                | "gt"
+                | "eq"
+        );
+        arithmetic && (is_gasleft(left) || is_gasleft(right))
+    })
+}
+
+fn check_call_gas_stipend(node: &Node) -> bool {
+    if node_type(node) == Some("FunctionCallOptions") {
+        return node
+            .get("names")
+            .and_then(serde_json::Value::as_array)
+            .is_some_and(|names| names.iter().any(|name| name.as_str() == Some("gas")));
+    }
+
+    (|| {
+        let name = called_yul_function(node)?;
+        ["call", "callcode", "delegatecall", "staticcall"]
+            .contains(&name)
+            .then_some(())?;
+        let gas = node.get("arguments")?.as_array()?.first()?.as_object()?;
+        (called_yul_function(gas) != Some("gas")).then_some(())
+    })()
+    .is_some()
+}
+
+fn check_selfdestruct(node: &Node) -> bool {
+    matches!(called_identifier(node), Some("selfdestruct" | "suicide"))
+        || called_yul_function(node) == Some("selfdestruct")
+}
+
+fn check_extcodesize(node: &Node) -> bool {
+    if called_yul_function(node) == Some("extcodesize") {
+        return true;
+    }
+
+    (|| {
+        (node_type(node)? == "MemberAccess").then_some(())?;
+        (node.get("memberName")?.as_str()? == "length").then_some(())?;
+
+        let code = child(node, "expression")?;
+        (node_type(code)? == "MemberAccess").then_some(())?;
+        (code.get("memberName")?.as_str()? == "code").then_some(())?;
+
+        let address = child(code, "expression")?;
+        (!is_address_this(address)).then_some(())
+    })()
+    .is_some()
+}
+
+fn check_blockhash(node: &Node) -> bool {
+    (|| {
+        (called_identifier(node) == Some("blockhash")
+            || called_yul_function(node) == Some("blockhash"))
+        .then_some(())?;
+        let [block] = node.get("arguments")?.as_array()?.as_slice() else {
+            return None;
+        };
+        let block = unparenthesized(block.as_object()?)?;
+        if is_block_number(block) {
+            return Some(());
+        }
+
+        let (operator, left, right) = binary_operation(block)?;
+        is_block_number(left).then_some(())?;
+        let distance = number_literal(right)?;
+        match operator {
+            "-" | "sub" => (distance == 0 || distance > 256).then_some(()),
+            "+" | "add" => Some(()),
+            _ => None,
+        }
+    })()
+    .is_some()
+}
+
+fn check_create_inline_bytecode(node: &Node) -> bool {
+    (|| {
+        matches!(called_yul_function(node)?, "create" | "create2").then_some(())?;
+        let size = node.get("arguments")?.as_array()?.get(2)?.as_object()?;
+        number_literal(size).map(|_| ())
+    })()
+    .is_some()
+}
+
+fn check_this_code(node: &Node) -> bool {
+    (|| {
+        (node_type(node)? == "MemberAccess").then_some(())?;
+        (node.get("memberName")?.as_str()? == "code").then_some(())?;
+        is_address_this(child(node, "expression")?).then_some(())
+    })()
+    .is_some()
+}
@@ -0,0 +1,312 @@
+//! The `resolc` PolkaVM compatibility lints.
+//!
+//! Lints check the `solc` AST for code behaving differently on PolkaVM than on
+//! the EVM. Every lint has a stable ID, which is used to configure its severity
+//! in the `settings.lints` standard JSON input field and to disable it with a
+//! `// resolc-disable-next-line <id>` comment on the line before the code.
+//! The directive must start the comment, it is ignored in string literals and
+//! block comments.
+
+pub mod lints;
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use revive_common::ErrorCode;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::standard_json::output::error::source_location::SourceLocation;
+use crate::SolcStandardJsonInputSource;
+use crate::SolcStandardJsonOutputError;
+
+/// The directive in a line comment disabling lints for the next line.
+pub const DISABLE_NEXT_LINE_DIRECTIVE: &str = "resolc-disable-next-line";
+
+/// The comment disabling lints for the next line.
+pub const DISABLE_NEXT_LINE_COMMENT: &str = "// resolc-disable-next-line";
+
+/// The lint severity.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The lint is disabled.
+    Off,
+    /// The lint emits warnings.
+    Warning,
+    /// The lint emits errors.
+    Error,
+}
+
+/// A lint over the `solc` AST.
+#[derive(Debug, Clone, Copy)]
+pub struct Lint {
+    /// The stable lint ID.
+    pub id: &'static str,
+    /// The severity unless configured otherwise.
+    pub default_severity: Severity,
+    /// Whether the severity can be configured and the lint disabled.
+    ///
+    /// Lints detecting unsupported code are not configurable.
+    pub configurable: bool,
+    /// The message emitted for findings.
+    pub message: &'static str,
+    /// The resolc error code of the findings.
+    pub error_code: Option<ErrorCode>,
+    /// Checks whether the AST `node` is a finding.
+    pub check: fn(&serde_json::Map<String, serde_json::Value>) -> bool,
+}
+
+impl Lint {
+    /// Returns the severity of this lint with the configured `severities`.
+    pub fn severity(&self, severities: &BTreeMap<String, Severity>) -> Severity {
+        match severities.get(self.id) {
+            Some(severity) if self.configurable => *severity,
+            _ => self.default_severity,
+        }
+    }
+}
+
+/// The lint registry.
+#[derive(Debug, Clone)]
+pub struct Registry {
+    /// The registered lints.
+    lints: Vec<Lint>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Self { lints: Vec::new() };
+        for lint in lints::ALL {
+            registry.register(lint);
+        }
+        registry
+    }
+}
+
+impl Registry {
+    /// Registers the `lint`.
+    ///
+    /// Panics if a lint with the same ID is already registered.
+    pub fn register(&mut self, lint: Lint) {
+        assert!(
+            self.get(lint.id).is_none(),
+            "The lint `{}` is already registered",
+            lint.id
+        );
+        self.lints.push(lint);
+    }
+
+    /// Returns the lint with the `id`.
+    pub fn get(&self, id: &str) -> Option<&Lint> {
+        self.lints.iter().find(|lint| lint.id == id)
+    }
+
+    /// Returns the registered lints.
+    pub fn lints(&self) -> &[Lint] {
+        self.lints.as_slice()
+    }
+
+    /// Returns warnings for configured `severities` of unknown or not configurable lints.
+    pub fn check_severities(
+        &self,
+        severities: &BTreeMap<String, Severity>,
+    ) -> Vec<SolcStandardJsonOutputError> {
+        severities
+            .keys()
+            .filter_map(|id| match self.get(id) {
+                None => Some(format!("Unknown lint `{id}` in the lint settings.")),
+                Some(lint) if !lint.configurable => Some(format!(
+                    "The severity of the lint `{id}` can't be configured."
+                )),
+                Some(_) => None,
+            })
+            .map(|message| SolcStandardJsonOutputError::new_warning(message, None, None))
+            .collect()
+    }
+
+    /// Checks the source `ast` with the lint `severities`.
+    pub fn check(
+        &self,
+        ast: &serde_json::Value,
+        id_paths: &BTreeMap<usize, &String>,
+        sources: &BTreeMap<String, SolcStandardJsonInputSource>,
+        severities: &BTreeMap<String, Severity>,
+    ) -> Vec<SolcStandardJsonOutputError> {
+        let lints = self
+            .lints
+            .iter()
+            .filter_map(|lint| match lint.severity(severities) {
+                Severity::Off => None,
+                severity => Some((lint, severity)),
+            })
+            .collect::<Vec<_>>();
+
+        let mut messages = Vec::new();
+        let mut disabled_lines = BTreeMap::new();
+        Self::visit(ast, &mut |node| {
+            for (lint, severity) in lints.iter() {
+                if !(lint.check)(node) {
+                    continue;
+                }
+
+                let location = node
+                    .get("src")
+                    .and_then(serde_json::Value::as_str)
+                    .and_then(|src| SourceLocation::try_from_ast(src, id_paths));
+                if lint.configurable {
+                    if let Some(location) = location.as_ref() {
+                        let disabled = disabled_lines
+                            .entry(location.file.to_owned())
+                            .or_insert_with(|| {
+                                sources
+                                    .get(location.file.as_str())
+                                    .and_then(SolcStandardJsonInputSource::content)
+                                    .map(DisabledLines::new)
+                                    .unwrap_or_default()
+                            });
+                        if disabled.is_disabled(lint.id, location.start) {
+                            continue;
+                        }
+                    }
+                }
+
+                let mut message = lint.message;
+                if *severity == Severity::Error {
+                    message = message
+                        .trim_start()
+                        .strip_prefix("Warning:")
+                        .unwrap_or(message);
+                }
+                let message = if lint.configurable {
+                    format!(
+                        "{}\nDisable with `{DISABLE_NEXT_LINE_COMMENT} {}`.\n",
+                        message.trim_end(),
+                        lint.id
+                    )
+                } else {
+                    message.to_owned()
+                };
+                let finding = match severity {
+                    Severity::Error => {
+                        SolcStandardJsonOutputError::new_error(message, location, Some(sources))
+                    }
+                    _ => SolcStandardJsonOutputError::new_warning(message, location, Some(sources)),
+                };
+                messages.push(match lint.error_code {
+                    Some(error_code) => finding.with_code(error_code),
+                    None => finding,
+                });
+            }
+        });
+        messages
+    }
+
+    /// Calls `visitor` for every AST node object in `ast`.
+    fn visit(
+        ast: &serde_json::Value,
+        visitor: &mut impl FnMut(&serde_json::Map<String, serde_json::Value>),
+    ) {
+        match ast {
+            serde_json::Value::Array(array) => {
+                for element in array.iter() {
+                    Self::visit(element, visitor);
+                }
+            }
+            serde_json::Value::Object(object) => {
+                if object.contains_key("nodeType") {
+                    visitor(object);
+                }
+                for value in object.values() {
+                    Self::visit(value, visitor);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The lines with lints disabled by comments.
+#[derive(Debug, Default)]
+struct DisabledLines {
+    /// The byte offsets of the line starts.
+    line_offsets: Vec<usize>,
+    /// The lint IDs disabled by line. Empty sets disable all lints.
+    lines: BTreeMap<usize, BTreeSet<String>>,
+}
+
+impl DisabledLines {
+    /// Parses the disable comments in the source `content`.
+    ///
+    /// Only `//` comments starting with the directive count, not the directive in
+    /// string literals or other comments.
+    fn new(content: &str) -> Self {
+        let mut line_offsets = vec![0];
+        line_offsets.extend(content.match_indices('\n').map(|(offset, _)| offset + 1));
+
+        let mut lines = BTreeMap::new();
+        let mut characters = content.char_indices().peekable();
+        while let Some((offset, character)) = characters.next() {
+            match character {
+                '"' | '\'' => {
+                    while let Some((_, next)) = characters.next() {
+                        match next {
+                            '\\' => {
+                                characters.next();
+                            }
+                            '\n' => break,
+                            next if next == character => break,
+                            _ => {}
+                        }
+                    }
+                }
+                '/' if characters.next_if(|(_, next)| *next == '*').is_some() => {
+                    let mut previous = ' ';
+                    for (_, next) in characters.by_ref() {
+                        if previous == '*' && next == '/' {
+                            break;
+                        }
+                        previous = next;
+                    }
+                }
+                '/' if characters.next_if(|(_, next)| *next == '/').is_some() => {
+                    let end = content[offset..]
+                        .find('\n')
+                        .map_or(content.len(), |length| offset + length);
+                    while characters.next_if(|(next, _)| *next < end).is_some() {}
+
+                    let comment = content[offset + 2..end].trim();
+                    let Some(ids) = comment.strip_prefix(DISABLE_NEXT_LINE_DIRECTIVE) else {
+                        continue;
+                    };
+                    if ids.starts_with(|character: char| !character.is_whitespace()) {
+                        continue;
+                    }
+                    let ids = ids
+                        .split(|character: char| character == ',' || character.is_whitespace())
+                        .filter(|id| !id.is_empty())
+                        .map(str::to_owned)
+                        .collect();
+                    let line = line_offsets.partition_point(|start| *start <= offset);
+                    lines.insert(line, ids);
+                }
+                _ => {}
+            }
+        }
+        Self {
+            line_offsets,
+            lines,
+        }
+    }
+
+    /// Whether the lint `id` is disabled at the byte `offset`.
+    fn is_disabled(&self, id: &str, offset: isize) -> bool {
+        let Ok(offset) = usize::try_from(offset) else {
+            return false;
+        };
+        let line = self.line_offsets.partition_point(|start| *start <= offset) - 1;
+        self.lines
+            .get(&line)
+            .is_some_and(|ids| ids.is_empty() || ids.contains(id))
+    }
+}
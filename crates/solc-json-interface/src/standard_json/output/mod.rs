@@ -9,6 +9,10 @@ use serde::Serialize;
 use crate::standard_json::input::settings::warning::Warning;
 use crate::standard_json::output::error::error_handler::ErrorHandler;
 #[cfg(feature = "resolc")]
+use crate::standard_json::output::lint::Registry as LintRegistry;
+#[cfg(feature = "resolc")]
+use crate::standard_json::output::lint::Severity as LintSeverity;
+#[cfg(feature = "resolc")]
 use crate::SolcStandardJsonInputSettingsSelection;
 #[cfg(feature = "resolc")]
 use crate::SolcStandardJsonInputSource;
@@ -21,6 +25,8 @@ use self::source::Source;
 
 pub mod contract;
 pub mod error;
+#[cfg(feature = "resolc")]
+pub mod lint;
 pub mod source;
 
 /// The `solc --standard-json` output.
@@ -108,6 +114,14 @@ impl Output {
         mut self,
         selection_to_prune: SolcStandardJsonInputSettingsSelection,
     ) -> ! {
+        self.prune(selection_to_prune);
+
+        serde_json::to_writer(std::io::stdout(), &self).expect("Stdout writing error");
+        std::process::exit(revive_common::EXIT_CODE_SUCCESS);
+    }
+
+    /// Removes the selected fields and empty contracts from the output JSON.
+    pub fn prune(&mut self, selection_to_prune: SolcStandardJsonInputSettingsSelection) {
         for (path, source) in self.sources.iter_mut() {
             if selection_to_prune.contains(
                 path,
@@ -131,9 +145,6 @@ impl Output {
             contracts.retain(|_, contract| !contract.is_empty());
             !contracts.is_empty()
         });
-
-        serde_json::to_writer(std::io::stdout(), &self).expect("Stdout writing error");
-        std::process::exit(revive_common::EXIT_CODE_SUCCESS);
     }
 
     /// Traverses the AST and returns the list of additional errors and warnings.
@@ -141,6 +152,7 @@ impl Output {
         &mut self,
         sources: &BTreeMap<String, SolcStandardJsonInputSource>,
         suppressed_warnings: &[Warning],
+        lints: &BTreeMap<String, LintSeverity>,
     ) -> anyhow::Result<()> {
         let id_paths: BTreeMap<usize, &String> = self
             .sources
@@ -148,6 +160,13 @@ impl Output {
             .map(|(path, source)| (source.id, path))
             .collect();
 
+        let registry = LintRegistry::default();
+        let mut severities = lints.to_owned();
+        for warning in suppressed_warnings.iter() {
+            severities.insert(warning.lint_id().to_owned(), LintSeverity::Off);
+        }
+        self.errors.extend(registry.check_severities(lints));
+
         #[cfg(feature = "parallel")]
         let iter = self.sources.par_iter();
         #[cfg(not(feature = "parallel"))]
@@ -155,7 +174,7 @@ impl Output {
 
         let messages: Vec<SolcStandardJsonOutputError> = iter
             .flat_map(|(_path, source)| {
-                Source::get_messages(&source.ast, &id_paths, sources, suppressed_warnings)
+                registry.check(&source.ast, &id_paths, sources, &severities)
             })
             .collect();
         self.errors.extend(messages);
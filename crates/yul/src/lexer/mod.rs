@@ -3,6 +3,8 @@
 #[cfg(test)]
 mod tests;
 
+use crate::source_map::SourceMap;
+
 use self::error::Error;
 use self::token::lexeme::comment::Comment;
 use self::token::lexeme::identifier::Identifier;
@@ -26,6 +28,8 @@ pub struct Lexer {
     location: Location,
     /// The peeked lexeme, waiting to be fetched.
     peeked: Option<Token>,
+    /// The Solidity source map collected from the comments.
+    source_map: SourceMap,
 }
 
 impl Lexer {
@@ -38,6 +42,7 @@ impl Lexer {
             offset: 0,
             location: Location::default(),
             peeked: None,
+            source_map: SourceMap::default(),
         }
     }
 
@@ -72,9 +77,11 @@ impl Lexer {
             }
 
             if let Some(token) = Comment::parse(input) {
+                let comment = input.get(..token.length as usize).unwrap_or(input);
                 self.offset += token.length;
                 self.location
                     .shift_down(token.location.line, token.location.column);
+                self.source_map.scan_comment(comment, self.location);
                 continue;
             }
 
@@ -123,6 +130,11 @@ impl Lexer {
         Ok(Token::new(self.location, Lexeme::EndOfFile, 0))
     }
 
+    /// Returns the Solidity source map of the lexed code.
+    pub fn into_source_map(self) -> SourceMap {
+        self.source_map
+    }
+
     /// Peeks the next lexeme without advancing the iterator.
     pub fn peek(&mut self) -> Result<Token, Error> {
         match self.peeked {
@@ -51,6 +51,18 @@ impl PartialEq for Location {
     }
 }
 
+impl PartialOrd for Location {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Location {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.line, self.column).cmp(&(other.line, other.column))
+    }
+}
+
 impl Hash for Location {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.line.hash(state);
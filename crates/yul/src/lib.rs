@@ -3,4 +3,5 @@
 pub mod error;
 pub mod lexer;
 pub mod parser;
+pub mod source_map;
 pub mod visitor;
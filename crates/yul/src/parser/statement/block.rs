@@ -19,6 +19,7 @@ use crate::parser::error::Error as ParserError;
 use crate::parser::statement::assignment::Assignment;
 use crate::parser::statement::expression::Expression;
 use crate::parser::statement::Statement;
+use crate::source_map::LocatedError;
 use crate::visitor::AstNode;
 use crate::visitor::AstVisitor;
 
@@ -154,7 +155,10 @@ impl PolkaVMWriteLLVM for Block {
         }
 
         for function in functions.into_iter() {
-            function.into_llvm(context)?;
+            let location = function.location;
+            function
+                .into_llvm(context)
+                .map_err(|error| LocatedError::locate(error, location))?;
         }
 
         context.set_current_function(
@@ -189,18 +193,15 @@ impl PolkaVMWriteLLVM for Block {
                 break;
             }
 
-            match statement {
-                Statement::Block(block) => {
-                    block.into_llvm(context)?;
-                }
-                Statement::Expression(expression) => {
-                    expression.into_llvm(context)?;
-                }
-                Statement::VariableDeclaration(statement) => statement.into_llvm(context)?,
-                Statement::Assignment(statement) => statement.into_llvm(context)?,
-                Statement::IfConditional(statement) => statement.into_llvm(context)?,
-                Statement::Switch(statement) => statement.into_llvm(context)?,
-                Statement::ForLoop(statement) => statement.into_llvm(context)?,
+            let location = statement.location();
+            let result = match statement {
+                Statement::Block(block) => block.into_llvm(context),
+                Statement::Expression(expression) => expression.into_llvm(context).map(|_| ()),
+                Statement::VariableDeclaration(statement) => statement.into_llvm(context),
+                Statement::Assignment(statement) => statement.into_llvm(context),
+                Statement::IfConditional(statement) => statement.into_llvm(context),
+                Statement::Switch(statement) => statement.into_llvm(context),
+                Statement::ForLoop(statement) => statement.into_llvm(context),
                 Statement::Continue(_location) => {
                     context.build_unconditional_branch(context.r#loop().continue_block);
                     break;
@@ -220,7 +221,8 @@ impl PolkaVMWriteLLVM for Block {
                     statement.location(),
                     statement
                 ),
-            }
+            };
+            result.map_err(|error| LocatedError::locate(error, location))?;
         }
 
         context.pop_debug_scope();
@@ -6,6 +6,8 @@ pub mod verbatim;
 use std::collections::BTreeSet;
 
 use inkwell::values::BasicValue;
+use revive_common::CodedError;
+use revive_common::ErrorCode;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -602,10 +604,13 @@ impl FunctionCall {
                     .code_type()
                     .ok_or_else(|| anyhow::anyhow!("The contract code part type is undefined"))?
                 {
-                    anyhow::bail!(
-                        "{} The `CODECOPY` instruction is not supported in the runtime code",
-                        location,
-                    );
+                    return Err(CodedError::new(
+                        ErrorCode::RuntimeCodeCopy,
+                        format!(
+                            "{location} The `CODECOPY` instruction is not supported in the runtime code"
+                        ),
+                    )
+                    .into());
                 }
 
                 let arguments = self.pop_arguments_llvm::<3>(context)?;
@@ -939,10 +944,11 @@ impl FunctionCall {
             }
             Name::BlobHash => {
                 let _arguments = self.pop_arguments_llvm::<1>(context)?;
-                anyhow::bail!(
-                    "{} The `BLOBHASH` instruction is not supported in revive",
-                    location
-                );
+                Err(CodedError::new(
+                    ErrorCode::Blob,
+                    format!("{location} The `BLOBHASH` instruction is not supported in revive"),
+                )
+                .into())
             }
             Name::Difficulty | Name::Prevrandao => {
                 revive_llvm_context::polkavm_evm_contract_context::difficulty(context).map(Some)
@@ -953,12 +959,11 @@ impl FunctionCall {
             Name::BaseFee => {
                 revive_llvm_context::polkavm_evm_contract_context::basefee(context).map(Some)
             }
-            Name::BlobBaseFee => {
-                anyhow::bail!(
-                    "{} The `BLOBBASEFEE` instruction is not supported in revive",
-                    location
-                );
-            }
+            Name::BlobBaseFee => Err(CodedError::new(
+                ErrorCode::Blob,
+                format!("{location} The `BLOBBASEFEE` instruction is not supported in revive"),
+            )
+            .into()),
             Name::MSize => revive_llvm_context::polkavm_evm_memory::msize(context).map(Some),
 
             Name::Verbatim {
@@ -968,15 +973,24 @@ impl FunctionCall {
 
             Name::CallCode => {
                 let _arguments = self.pop_arguments_llvm::<7>(context)?;
-                anyhow::bail!("{} The `CALLCODE` instruction is not supported", location)
+                Err(CodedError::new(
+                    ErrorCode::CallCode,
+                    format!("{location} The `CALLCODE` instruction is not supported"),
+                )
+                .into())
             }
-            Name::Pc => anyhow::bail!("{} The `PC` instruction is not supported", location),
+            Name::Pc => Err(CodedError::new(
+                ErrorCode::Pc,
+                format!("{location} The `PC` instruction is not supported"),
+            )
+            .into()),
             Name::ExtCodeCopy => {
                 let _arguments = self.pop_arguments_llvm::<4>(context)?;
-                anyhow::bail!(
-                    "{} The `EXTCODECOPY` instruction is not supported",
-                    location
+                Err(CodedError::new(
+                    ErrorCode::ExtCodeCopy,
+                    format!("{location} The `EXTCODECOPY` instruction is not supported"),
                 )
+                .into())
             }
         }
     }
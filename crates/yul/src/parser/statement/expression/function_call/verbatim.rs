@@ -1,5 +1,7 @@
 //! Translates the verbatim simulations.
 
+use revive_common::CodedError;
+use revive_common::ErrorCode;
 use revive_llvm_context::PolkaVMContext;
 
 use crate::parser::statement::expression::function_call::FunctionCall;
@@ -12,10 +14,14 @@ pub fn verbatim<'ctx>(
     output_size: usize,
 ) -> anyhow::Result<Option<inkwell::values::BasicValueEnum<'ctx>>> {
     if output_size > 1 {
-        anyhow::bail!(
-            "{} Verbatim instructions with multiple return values are not supported",
-            call.location
-        );
+        return Err(CodedError::new(
+            ErrorCode::Verbatim,
+            format!(
+                "{} Verbatim instructions with multiple return values are not supported",
+                call.location
+            ),
+        )
+        .into());
     }
 
     let mut arguments = call.pop_arguments::<1>(context)?;
@@ -24,9 +30,12 @@ pub fn verbatim<'ctx>(
         .take()
         .ok_or_else(|| anyhow::anyhow!("{} Verbatim literal is missing", call.location))?;
 
-    anyhow::bail!(
-        "{} Found unknown internal function `{}`",
-        call.location,
-        identifier
+    Err(CodedError::new(
+        ErrorCode::Verbatim,
+        format!(
+            "{} Found unknown internal function `{identifier}`",
+            call.location
+        ),
     )
+    .into())
 }
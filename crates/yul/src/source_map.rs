@@ -0,0 +1,176 @@
+//! The Solidity source map of Yul code.
+//!
+//! `solc` annotates the Yul IR with the Solidity code it was generated from:
+//! `/// @use-src 0:"contract.sol"` comments list the source files of an object and
+//! `/** @src 0:120:180 */` comments set the source file index and the byte range of
+//! the code following them. The lexer collects the annotations into a [`SourceMap`],
+//! which maps Yul locations back to Solidity source code.
+//!
+//! Errors carry the Yul location of the failing statement as a [`LocatedError`], which
+//! is converted into the Solidity source code range of the error.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::lexer::token::location::Location;
+
+/// The Solidity source code range of an `@src` annotation.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SourceAnnotation {
+    /// The source file path.
+    pub file: String,
+    /// The start byte offset.
+    pub start: isize,
+    /// The end byte offset.
+    pub end: isize,
+}
+
+/// The Solidity source map of Yul code.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    /// The source file paths by index, from the latest `@use-src` annotation.
+    #[serde(skip)]
+    files: BTreeMap<isize, String>,
+    /// The annotations ordered by the Yul location they start applying at.
+    /// Annotations of unknown code, like `@src -1:-1:-1`, are `None`.
+    annotations: Vec<(Location, Option<SourceAnnotation>)>,
+}
+
+impl SourceMap {
+    /// The source files annotation.
+    pub const USE_SOURCE: &'static str = "@use-src";
+    /// The source code range annotation.
+    pub const SOURCE: &'static str = "@src";
+
+    /// Records the annotations in the `comment` ending at the Yul `location`.
+    pub fn scan_comment(&mut self, comment: &str, location: Location) {
+        if let Some(position) = comment.find(Self::USE_SOURCE) {
+            self.files = Self::parse_files(&comment[position + Self::USE_SOURCE.len()..]);
+        }
+        if let Some(position) = comment.rfind(Self::SOURCE) {
+            let annotation = self.parse_range(&comment[position + Self::SOURCE.len()..]);
+            self.annotations.push((location, annotation));
+        }
+    }
+
+    /// Returns the Solidity source code range of the Yul code at `location`.
+    pub fn resolve(&self, location: Location) -> Option<&SourceAnnotation> {
+        let index = self
+            .annotations
+            .partition_point(|(start, _)| *start <= location);
+        self.annotations
+            .get(index.checked_sub(1)?)
+            .and_then(|(_, annotation)| annotation.as_ref())
+    }
+
+    /// Parses the `0:"a.sol", 1:"b.sol"` file list of an `@use-src` annotation.
+    fn parse_files(mut input: &str) -> BTreeMap<isize, String> {
+        let mut files = BTreeMap::new();
+        loop {
+            input = input.trim_start_matches(|character: char| {
+                character == ',' || character == ' ' || character == '\t'
+            });
+            let Some((index, rest)) = input.split_once(":\"") else {
+                break;
+            };
+            let Ok(index) = index.parse::<isize>() else {
+                break;
+            };
+            let mut end = None;
+            let mut escaped = false;
+            for (position, character) in rest.char_indices() {
+                match character {
+                    '"' if !escaped => {
+                        end = Some(position);
+                        break;
+                    }
+                    '\\' => escaped = !escaped,
+                    _ => escaped = false,
+                }
+            }
+            let Some(end) = end else {
+                break;
+            };
+            files.insert(index, rest[..end].replace("\\\"", "\""));
+            input = &rest[end + 1..];
+        }
+        files
+    }
+
+    /// Parses the `0:120:180` range of an `@src` annotation.
+    fn parse_range(&self, input: &str) -> Option<SourceAnnotation> {
+        let range = input.split_whitespace().next()?;
+        let mut parts = range.split(':').map(str::parse::<isize>);
+        let (Some(Ok(index)), Some(Ok(start)), Some(Ok(end))) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return None;
+        };
+        if start < 0 || end < start {
+            return None;
+        }
+        Some(SourceAnnotation {
+            file: self.files.get(&index)?.to_owned(),
+            start,
+            end,
+        })
+    }
+}
+
+/// An error located at the Yul statement it occurs in.
+pub type LocatedError = revive_common::LocatedError<Location>;
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::token::location::Location;
+    use crate::lexer::Lexer;
+    use crate::parser::statement::object::Object;
+
+    use super::SourceAnnotation;
+
+    #[test]
+    fn resolve() {
+        let input = r#"
+/// @use-src 0:"contracts/a.sol", 1:"b \"quoted\".sol"
+object "Test" {
+    code {
+        /// @src 0:10:20  "contract Test {..."
+        mstore(64, 128)
+        sstore(/** @src 1:5:7  "42" */ 42, /** @src -1:-1:-1 */ 0)
+    }
+}
+"#;
+        let mut lexer = Lexer::new(input.to_owned());
+        Object::parse(&mut lexer, None).expect("Yul parsing");
+        let source_map = lexer.into_source_map();
+
+        assert_eq!(source_map.resolve(Location::new(4, 5)), None);
+        assert_eq!(
+            source_map.resolve(Location::new(6, 9)),
+            Some(&SourceAnnotation {
+                file: "contracts/a.sol".to_owned(),
+                start: 10,
+                end: 20,
+            })
+        );
+        assert_eq!(
+            source_map.resolve(Location::new(7, 9)),
+            Some(&SourceAnnotation {
+                file: "contracts/a.sol".to_owned(),
+                start: 10,
+                end: 20,
+            })
+        );
+        assert_eq!(
+            source_map.resolve(Location::new(7, 40)),
+            Some(&SourceAnnotation {
+                file: "b \"quoted\".sol".to_owned(),
+                start: 5,
+                end: 7,
+            })
+        );
+        assert_eq!(source_map.resolve(Location::new(7, 70)), None);
+    }
+}
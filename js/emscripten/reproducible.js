@@ -0,0 +1,37 @@
+// Compiles the reproducible build inputs of the resolc tests with the wasm build,
+// writing the standard JSON outputs by input name. The native `resolc` tests compare
+// their hashes with the recorded ones (see `RESOLC_REPRODUCIBLE_OUTPUTS`).
+//
+// Usage: node reproducible.js <resolc.js> <soljson.js> <inputs.json> <outputs.json>
+const fs = require("fs");
+const path = require("path");
+
+const [resolcPath, soljsonPath, inputsPath, outputsPath] =
+  process.argv.slice(2);
+if (!outputsPath) {
+  console.error(
+    "Usage: node reproducible.js <resolc.js> <soljson.js> <inputs.json> <outputs.json>",
+  );
+  process.exit(1);
+}
+
+const createRevive = require(path.resolve(resolcPath));
+const soljson = require(path.resolve(soljsonPath));
+const inputs = JSON.parse(fs.readFileSync(inputsPath, "utf-8"));
+
+const outputs = {};
+for (const [name, input] of Object.entries(inputs)) {
+  const compiler = createRevive();
+  compiler.soljson = soljson;
+  compiler.writeToStdin(JSON.stringify(input));
+  compiler.callMain(["--standard-json"]);
+
+  const stderr = compiler.readFromStderr();
+  if (stderr) {
+    console.error(`Compiling ${name} failed: ${stderr}`);
+    process.exit(1);
+  }
+  outputs[name] = JSON.parse(compiler.readFromStdout());
+}
+
+fs.writeFileSync(outputsPath, JSON.stringify(outputs));